@@ -24,6 +24,18 @@ pub struct TileId {
 	pub tileset_id: TilesetId,
 }
 
+/// A precomputed key for resolving a tile without looking up its name
+///
+/// This is obtained once with [`Tileset::intern`] and then used with [`Tileset::index_of`],
+/// which avoids hashing the tile's name on every lookup. Since a key only stores the tile's
+/// IDs, it stays valid across rebuilds of its tileset (such as hot-reloads) as long as the tile
+/// keeps its group ID.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct TileKey {
+	group_id: TileGroupId,
+	tileset_id: TilesetId,
+}
+
 /// This struct is used to identify a tile when the particular [`Tileset`] is already known or unneeded
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
 pub struct PartialTileId {
@@ -88,6 +100,32 @@ impl TileId {
 	}
 }
 
+impl TileKey {
+	/// Create a new key for the given tile
+	pub(crate) const fn new(group_id: TileGroupId, tileset_id: TilesetId) -> Self {
+		Self {
+			group_id,
+			tileset_id,
+		}
+	}
+
+	/// The group ID of the tile
+	pub fn group_id(&self) -> TileGroupId {
+		self.group_id
+	}
+
+	/// The ID of the tileset containing the tile
+	pub fn tileset_id(&self) -> TilesetId {
+		self.tileset_id
+	}
+}
+
+impl From<TileKey> for TileId {
+	fn from(key: TileKey) -> Self {
+		Self::new(key.group_id, key.tileset_id)
+	}
+}
+
 impl PartialTileId {
 	pub const fn new(group_id: TileGroupId) -> Self {
 		Self {
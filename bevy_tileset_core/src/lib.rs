@@ -1,6 +1,8 @@
 pub mod debug;
 mod ids;
 mod plugin;
+#[cfg(test)]
+mod test_utils;
 mod tileset;
 
 #[cfg(feature = "auto-tile")]
@@ -9,7 +11,10 @@ pub mod coords;
 
 /// A collection of commonly used modules (import via `bevy_tileset_core::prelude::*`)
 pub mod prelude {
-	pub use super::ids::{PartialTileId, TileGroupId, TileId, TilesetId};
-	pub use super::plugin::TilesetPlugin;
+	pub use super::ids::{PartialTileId, TileGroupId, TileId, TileKey, TilesetId};
+	pub use super::plugin::{
+		TilesetCompressedFormats, TilesetLoadLimits, TilesetPlugin, TilesetReloaded,
+		TilesetSubsets,
+	};
 	pub use super::tileset::*;
 }
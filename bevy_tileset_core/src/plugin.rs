@@ -1,36 +1,437 @@
+use crate::prelude::TileGroupId;
+#[cfg(feature = "lazy")]
+use crate::tileset::LazyTilesetAsset;
 use crate::tileset::{Tileset, TilesetAssetLoader, TilesetMap};
+use bevy::asset::HandleId;
 use bevy::prelude::*;
+use bevy::render::texture::CompressedImageFormats;
+use std::collections::{BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
 
 /// Plugin for setting up tilesets
 #[derive(Default)]
-pub struct TilesetPlugin {}
+pub struct TilesetPlugin {
+	/// The compressed texture formats the tileset loader should support (if overridden)
+	compressed_formats: Option<CompressedImageFormats>,
+	/// Whether tilesets sharing the ID of an already loaded tileset should be rejected
+	strict_ids: bool,
+	/// The limits enforced by the tileset loader (if any)
+	load_limits: Option<TilesetLoadLimits>,
+}
+
+impl TilesetPlugin {
+	/// Override the compressed texture formats supported when loading tile textures
+	///
+	/// By default, these are derived from the `RenderDevice` (or all formats when running
+	/// headless). Overriding them is useful for building assets on a machine whose GPU
+	/// differs from the target's.
+	///
+	/// # Arguments
+	///
+	/// * `formats`: The supported compressed formats
+	///
+	/// returns: TilesetPlugin
+	pub fn with_compressed_formats(mut self, formats: CompressedImageFormats) -> Self {
+		self.compressed_formats = Some(formats);
+		self
+	}
+
+	/// Reject any tileset that shares its [`TilesetId`] with an already loaded tileset
+	///
+	/// Every tileset should have a unique ID, since tiles reference their tileset by it. By
+	/// default, a duplicate ID logs a warning (naming both tilesets) and the newer tileset takes
+	/// over the ID. In strict mode, an error is logged instead and the newer tileset can't be
+	/// found by its ID or name.
+	///
+	/// # Arguments
+	///
+	/// * `strict_ids`: Whether duplicate IDs should be rejected
+	///
+	/// returns: TilesetPlugin
+	pub fn with_strict_ids(mut self, strict_ids: bool) -> Self {
+		self.strict_ids = strict_ids;
+		self
+	}
+
+	/// Limit the size of the tilesets the tileset loader accepts
+	///
+	/// This is useful when loading untrusted tilesets (such as user-generated content), so that
+	/// a malicious or corrupt tileset fails to load instead of exhausting memory.
+	///
+	/// # Arguments
+	///
+	/// * `limits`: The limits to enforce
+	///
+	/// returns: TilesetPlugin
+	pub fn with_load_limits(mut self, limits: TilesetLoadLimits) -> Self {
+		self.load_limits = Some(limits);
+		self
+	}
+}
+
+/// A resource that overrides the compressed texture formats supported by the tileset loader
+///
+/// This must be inserted before the [`TilesetPlugin`] is added (which is done automatically
+/// when using [`TilesetPlugin::with_compressed_formats`]).
+#[derive(Resource, Debug, Copy, Clone)]
+pub struct TilesetCompressedFormats(pub CompressedImageFormats);
+
+/// A resource limiting the size of the tilesets the tileset loader accepts
+///
+/// Each limit is checked before the memory it guards is allocated, failing the load with a
+/// specific [`TilesetError`](crate::prelude::TilesetError) when exceeded. By default, nothing
+/// is limited.
+///
+/// This must be inserted before the [`TilesetPlugin`] is added (which is done automatically
+/// when using [`TilesetPlugin::with_load_limits`]).
+#[derive(Resource, Debug, Default, Copy, Clone, Eq, PartialEq)]
+pub struct TilesetLoadLimits {
+	/// The maximum number of tiles a tileset may declare (after expanding ranged entries)
+	///
+	/// This is checked before any tile definition or image is read.
+	pub max_tiles: Option<usize>,
+	/// The maximum size of a single image (in bytes), both encoded and decoded
+	///
+	/// The decoded size is derived from the image's header before it's decoded, where possible.
+	pub max_image_bytes: Option<usize>,
+	/// The maximum number of pixels across every image packed into the atlas
+	///
+	/// This is checked before the atlas is packed.
+	pub max_atlas_pixels: Option<u64>,
+}
 
 impl Plugin for TilesetPlugin {
 	fn build(&self, app: &mut App) {
+		if let Some(formats) = self.compressed_formats {
+			app.insert_resource(TilesetCompressedFormats(formats));
+		}
+		if let Some(limits) = self.load_limits {
+			app.insert_resource(limits);
+		}
+
+		#[cfg(feature = "lazy")]
+		app.add_asset::<LazyTilesetAsset>();
 		app.add_asset::<Tileset>()
+			.init_resource::<TilesetSubsets>()
 			.init_asset_loader::<TilesetAssetLoader>()
-			.init_resource::<TilesetMap>()
-			.add_systems(Update, tileset_event_sys);
+			.insert_resource(TilesetMap::new(self.strict_ids))
+			.add_event::<TilesetReloaded>()
+			.add_systems(Update, (tileset_event_sys, tileset_image_event_sys));
 	}
 }
 
+/// A resource that restricts which tiles are loaded from a tileset, mapped by the tileset's path
+///
+/// Only the tiles with the given group IDs are read, decoded, and packed, producing a smaller
+/// atlas whose indices are dense over the loaded tiles. Every loaded tile keeps its original
+/// group ID. Tilesets without a subset load every tile.
+///
+/// Since asset loaders can't be given settings for a specific load, this resource is shared
+/// with the tileset loader instead. A subset must be set before its tileset is loaded, and
+/// changing it only takes effect once the tileset is reloaded. Cloning this resource shares the
+/// same subsets.
+///
+/// # Examples
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_tileset_core::prelude::*;
+///
+/// fn load_forest(subsets: Res<TilesetSubsets>, asset_server: Res<AssetServer>) {
+/// 	subsets.set("tilesets/forest.ron", [0, 3, 7]);
+/// 	let handle: Handle<Tileset> = asset_server.load("tilesets/forest.ron");
+/// 	// Store handle...
+/// }
+/// ```
+#[derive(Resource, Debug, Clone, Default)]
+pub struct TilesetSubsets(Arc<RwLock<HashMap<PathBuf, BTreeSet<TileGroupId>>>>);
+
+impl TilesetSubsets {
+	/// Restrict the tileset at the given path to the tiles with the given group IDs
+	///
+	/// # Arguments
+	///
+	/// * `path`: The path to the tileset (relative to the asset root)
+	/// * `group_ids`: The group IDs of the tiles to load
+	///
+	/// returns: ()
+	pub fn set<TPath: Into<PathBuf>, TIds: IntoIterator<Item = TileGroupId>>(
+		&self,
+		path: TPath,
+		group_ids: TIds,
+	) {
+		if let Ok(mut subsets) = self.0.write() {
+			subsets.insert(path.into(), group_ids.into_iter().collect());
+		}
+	}
+
+	/// Removes the subset of the tileset at the given path, so that it loads every tile again
+	///
+	/// # Arguments
+	///
+	/// * `path`: The path to the tileset (relative to the asset root)
+	///
+	/// returns: Option<BTreeSet<TileGroupId>>
+	pub fn remove<TPath: AsRef<Path>>(&self, path: TPath) -> Option<BTreeSet<TileGroupId>> {
+		self.0.write().ok()?.remove(path.as_ref())
+	}
+
+	/// Gets the subset of the tileset at the given path (if any)
+	///
+	/// # Arguments
+	///
+	/// * `path`: The path to the tileset (relative to the asset root)
+	///
+	/// returns: Option<BTreeSet<TileGroupId>>
+	pub fn get<TPath: AsRef<Path>>(&self, path: TPath) -> Option<BTreeSet<TileGroupId>> {
+		self.0.read().ok()?.get(path.as_ref()).cloned()
+	}
+}
+
+/// An event sent when a loaded [`Tileset`] is modified (e.g. hot-reloaded)
+///
+/// Tilemaps that were already spawned using the old tileset keep their old texture indices,
+/// so this can be used to rebuild them. The tileset's `TextureAtlas` and texture are reloaded
+/// along with the tileset itself, so their handles remain the same.
+#[derive(Event, Debug, Clone, PartialEq, Eq)]
+pub struct TilesetReloaded(pub Handle<Tileset>);
+
 /// System that registers/deregisters tilesets as they are loaded and unloaded
 fn tileset_event_sys(
 	mut event_reader: EventReader<AssetEvent<Tileset>>,
 	mut map: ResMut<TilesetMap>,
+	mut reloaded: EventWriter<TilesetReloaded>,
 	tilesets: Res<Assets<Tileset>>,
+	asset_server: Res<AssetServer>,
 ) {
 	for event in event_reader.iter() {
 		match event {
 			AssetEvent::<Tileset>::Created { handle } => {
 				if let Some(tileset) = tilesets.get(handle) {
-					map.register_tileset(tileset, &handle);
+					register_tileset(&mut map, tileset, handle, &asset_server);
 				}
 			},
+			AssetEvent::<Tileset>::Modified { handle } => {
+				// The tileset's name or ID may have changed, so re-register it from scratch
+				map.deregister_tileset(&handle);
+				if let Some(tileset) = tilesets.get(handle) {
+					register_tileset(&mut map, tileset, handle, &asset_server);
+				}
+				reloaded.send(TilesetReloaded(handle.clone_weak()));
+			},
 			AssetEvent::<Tileset>::Removed { handle } => {
 				map.deregister_tileset(&handle);
 			},
-			_ => {},
 		}
 	}
 }
+
+/// The tilesets built from each source image, mapped by the ID of the image's handle
+#[derive(Default)]
+struct SourceImageMap(HashMap<HandleId, Vec<Handle<Tileset>>>);
+
+impl SourceImageMap {
+	/// Maps every source image of the given tileset (and its pages) to the tileset
+	fn insert(&mut self, handle: &Handle<Tileset>, tileset: &Tileset) {
+		let pages = (0..tileset.page_count()).filter_map(|index| tileset.page(index));
+		for image in pages.flat_map(Tileset::source_image_handles) {
+			self.0
+				.entry(image.id())
+				.or_default()
+				.push(handle.clone_weak());
+		}
+	}
+
+	/// Removes the given tileset from every source image
+	fn remove(&mut self, handle: &Handle<Tileset>) {
+		self.0.retain(|_, tilesets| {
+			tilesets.retain(|tileset| tileset != handle);
+			!tilesets.is_empty()
+		});
+	}
+}
+
+/// System that copies modified source images into the atlas of every tileset built from them
+///
+/// This gives live texture iteration, since an image reloaded on its own (without its tileset)
+/// still shows up in the atlas. Images that can't be copied in place only log a warning.
+///
+/// Only images that a tileset was built from are handled, so the modifications this system
+/// makes to the atlas textures themselves are ignored. Tilesets loaded by the asset loader read
+/// their images directly rather than through the `Image` assets, so they never receive these
+/// events. Instead, they're reloaded as a whole whenever one of their images changes (as long
+/// as the `AssetServer` is watching for changes).
+fn tileset_image_event_sys(
+	mut event_reader: EventReader<AssetEvent<Image>>,
+	mut tileset_events: EventReader<AssetEvent<Tileset>>,
+	mut sources: Local<SourceImageMap>,
+	tilesets: Res<Assets<Tileset>>,
+	atlases: Res<Assets<TextureAtlas>>,
+	mut images: ResMut<Assets<Image>>,
+) {
+	for event in tileset_events.iter() {
+		match event {
+			AssetEvent::<Tileset>::Created { handle }
+			| AssetEvent::<Tileset>::Modified { handle } => {
+				sources.remove(handle);
+				if let Some(tileset) = tilesets.get(handle) {
+					sources.insert(handle, tileset);
+				}
+			},
+			AssetEvent::<Tileset>::Removed { handle } => sources.remove(handle),
+		}
+	}
+
+	for event in event_reader.iter() {
+		let AssetEvent::<Image>::Modified { handle } = event else {
+			continue;
+		};
+		let Some(owners) = sources.0.get(&handle.id()) else {
+			continue;
+		};
+		for tileset in owners.iter().filter_map(|owner| tilesets.get(owner)) {
+			match tileset.update_source_image(handle, &atlases, &mut images) {
+				Ok(true) => {},
+				Ok(false) => warn!(
+					"tileset {:?} must be reloaded to show the modified image",
+					tileset.name()
+				),
+				Err(err) => error!(
+					"could not update the atlas of tileset {:?}: {}",
+					tileset.name(),
+					err
+				),
+			}
+		}
+	}
+}
+
+/// Registers the given tileset, checking that its ID isn't already taken by another tileset
+fn register_tileset(
+	map: &mut TilesetMap,
+	tileset: &Tileset,
+	handle: &Handle<Tileset>,
+	asset_server: &AssetServer,
+) {
+	let source = asset_server
+		.get_handle_path(handle)
+		.map(|path| path.path().display().to_string())
+		.unwrap_or_else(|| tileset.name().to_string());
+	if let Err(err) = map.check_unique_id(tileset, handle, &source) {
+		if map.strict_ids() {
+			error!("{} (skipping {:?})", err, source);
+			return;
+		}
+		warn!("{} ({:?} replaces the other)", err, source);
+	}
+	map.register_tileset(tileset, handle, source);
+}
+
+#[cfg(test)]
+mod tests {
+	use bevy::asset::AssetPlugin;
+	use bevy::ecs::system::SystemState;
+	use bevy_tileset_tiles::prelude::{TileHandle, TileTransform};
+
+	use super::*;
+	use crate::prelude::{TilesetBuilder, TilesetError, Tilesets};
+	use crate::test_utils::{solid_image, TestTextureStore};
+	use bevy_tile_atlas::TextureStore;
+
+	#[test]
+	fn should_detect_duplicate_tileset_ids() {
+		let mut app = App::new();
+		app.add_plugins((MinimalPlugins, AssetPlugin::default()))
+			.add_asset::<Image>()
+			.add_asset::<TextureAtlas>()
+			.add_plugins(TilesetPlugin::default().with_strict_ids(true));
+
+		let mut store = TestTextureStore::default();
+		let mut handles = Vec::new();
+		for name in ["Forest", "Desert"] {
+			let mut builder = TilesetBuilder::default();
+			let texture = store.add(solid_image(16, 16, [255; 4]));
+			builder
+				.add_tile(TileHandle::new_standard("Grass", texture), 0, &store)
+				.unwrap();
+			let raw = builder.build(name, 7, &mut store).unwrap();
+			let tileset = raw.into_asset(&mut app.world.resource_mut::<Assets<TextureAtlas>>());
+			handles.push(app.world.resource_mut::<Assets<Tileset>>().add(tileset));
+		}
+		app.update();
+		app.update();
+
+		let mut state = SystemState::<Tilesets>::new(&mut app.world);
+		let tilesets = state.get(&app.world);
+		assert_eq!(Some("Forest"), tilesets.get_by_id(&7).map(Tileset::name));
+		assert!(!tilesets.contains_name("Desert"));
+
+		let map = app.world.resource::<TilesetMap>();
+		let desert = tilesets.get(&handles[1]).unwrap();
+		assert!(matches!(
+			map.check_unique_id(desert, &handles[1], "Desert"),
+			Err(TilesetError::DuplicateTilesetId { id: 7, first, second })
+				if first == "Forest" && second == "Desert"
+		));
+		assert!(map.check_unique_id(desert, &handles[0], "Forest").is_ok());
+	}
+
+	#[test]
+	fn should_update_atlas_when_source_image_changes() {
+		let mut app = App::new();
+		app.add_plugins((MinimalPlugins, AssetPlugin::default()))
+			.add_asset::<Image>()
+			.add_asset::<TextureAtlas>()
+			.add_plugins(TilesetPlugin::default());
+
+		let mut images = app.world.resource_mut::<Assets<Image>>();
+		let grass = images.add(solid_image(16, 16, [255; 4]));
+		let flipped = images.add(solid_image(16, 16, [255; 4]));
+		let mut builder = TilesetBuilder::default();
+		builder
+			.add_tile(TileHandle::new_standard("Grass", grass.clone()), 0, &*images)
+			.unwrap();
+		let mut tile = TileHandle::new_standard("Flipped", flipped.clone());
+		tile.transform = Some(TileTransform::FlipX);
+		builder.add_tile(tile, 1, &*images).unwrap();
+		let raw = builder.build("Live", 0, &mut *images).unwrap();
+		let rects = raw.atlas().textures.clone();
+		// Another tileset built from the same image is updated as well
+		let mut builder = TilesetBuilder::default();
+		builder
+			.add_tile(TileHandle::new_standard("Grass", grass.clone()), 0, &*images)
+			.unwrap();
+		let other = builder.build("Other", 1, &mut *images).unwrap();
+
+		let tileset = raw.into_asset(&mut app.world.resource_mut::<Assets<TextureAtlas>>());
+		let texture = tileset.texture().clone();
+		let grass_index = tileset.get_base_tile_index("Grass").unwrap();
+		let flipped_index = tileset.get_base_tile_index("Flipped").unwrap();
+		assert_eq!(vec![grass_index], tileset.get_source_image_indices(&grass));
+		app.world.resource_mut::<Assets<Tileset>>().add(tileset);
+		let other = other.into_asset(&mut app.world.resource_mut::<Assets<TextureAtlas>>());
+		let other_texture = other.texture().clone();
+		app.world.resource_mut::<Assets<Tileset>>().add(other);
+		app.update();
+
+		let mut images = app.world.resource_mut::<Assets<Image>>();
+		*images.get_mut(&grass).unwrap() = solid_image(16, 16, [0, 255, 0, 255]);
+		*images.get_mut(&flipped).unwrap() = solid_image(16, 16, [0, 0, 255, 255]);
+		app.update();
+		app.update();
+
+		let images = app.world.resource::<Assets<Image>>();
+		let atlas_texture = images.get(&texture).unwrap();
+		let pixel_at = |index: usize| {
+			let rect = rects[index];
+			let width = atlas_texture.texture_descriptor.size.width as usize;
+			let start = (rect.min.y as usize * width + rect.min.x as usize) * 4;
+			atlas_texture.data[start..start + 4].to_vec()
+		};
+		assert_eq!(vec![0, 255, 0, 255], pixel_at(grass_index));
+		// Processed textures can't be copied as-is
+		assert_eq!(vec![255; 4], pixel_at(flipped_index));
+		assert_eq!([0, 255, 0, 255], images.get(&other_texture).unwrap().data[..4]);
+	}
+}
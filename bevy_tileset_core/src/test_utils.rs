@@ -0,0 +1,92 @@
+//! Helpers shared between tests
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use bevy::asset::{
+	AssetIo, AssetIoError, BoxedFuture, ChangeWatcher, FileType, HandleId, Metadata,
+};
+use bevy::prelude::{Handle, Image};
+use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+use bevy_tile_atlas::TextureStore;
+
+/// A 1x1 RGBA PNG
+pub(crate) const PIXEL_PNG: &[u8] = &[
+	0x89, 0x50, 0x4E, 0x47, 0x0D, 0x0A, 0x1A, 0x0A, 0x00, 0x00, 0x00, 0x0D, 0x49, 0x48, 0x44,
+	0x52, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x01, 0x08, 0x06, 0x00, 0x00, 0x00, 0x1F,
+	0x15, 0xC4, 0x89, 0x00, 0x00, 0x00, 0x0D, 0x49, 0x44, 0x41, 0x54, 0x78, 0xDA, 0x63, 0x64,
+	0xF8, 0xCF, 0x50, 0x0F, 0x00, 0x03, 0x86, 0x01, 0x80, 0x5A, 0x34, 0x7D, 0x6B, 0x00, 0x00,
+	0x00, 0x00, 0x49, 0x45, 0x4E, 0x44, 0xAE, 0x42, 0x60, 0x82,
+];
+
+/// A simple in-memory [`TextureStore`]
+#[derive(Default)]
+pub(crate) struct TestTextureStore {
+	pub images: HashMap<HandleId, Image>,
+}
+
+impl TextureStore for TestTextureStore {
+	fn add(&mut self, asset: Image) -> Handle<Image> {
+		let id = HandleId::random::<Image>();
+		self.images.insert(id, asset);
+		Handle::weak(id)
+	}
+
+	fn get<H: Into<HandleId>>(&self, handle: H) -> Option<&Image> {
+		self.images.get(&handle.into())
+	}
+}
+
+/// Creates an RGBA image filled with a single color
+pub(crate) fn solid_image(width: u32, height: u32, color: [u8; 4]) -> Image {
+	Image::new_fill(
+		Extent3d {
+			width,
+			height,
+			depth_or_array_layers: 1,
+		},
+		TextureDimension::D2,
+		&color,
+		TextureFormat::Rgba8UnormSrgb,
+	)
+}
+
+/// An `AssetIo` serving files from memory, like one reading from a packed archive would
+pub(crate) struct MemoryAssetIo(pub HashMap<PathBuf, Vec<u8>>);
+
+impl AssetIo for MemoryAssetIo {
+	fn load_path<'a>(&'a self, path: &'a Path) -> BoxedFuture<'a, Result<Vec<u8>, AssetIoError>> {
+		let result = self
+			.0
+			.get(path)
+			.cloned()
+			.ok_or_else(|| AssetIoError::NotFound(path.to_path_buf()));
+		Box::pin(async move { result })
+	}
+
+	fn read_directory(
+		&self,
+		path: &Path,
+	) -> Result<Box<dyn Iterator<Item = PathBuf>>, AssetIoError> {
+		Err(AssetIoError::NotFound(path.to_path_buf()))
+	}
+
+	fn get_metadata(&self, path: &Path) -> Result<Metadata, AssetIoError> {
+		self.0
+			.get(path)
+			.map(|_| Metadata::new(FileType::File))
+			.ok_or_else(|| AssetIoError::NotFound(path.to_path_buf()))
+	}
+
+	fn watch_path_for_changes(
+		&self,
+		_to_watch: &Path,
+		_to_reload: Option<PathBuf>,
+	) -> Result<(), AssetIoError> {
+		Ok(())
+	}
+
+	fn watch_for_changes(&self, _configuration: &ChangeWatcher) -> Result<(), AssetIoError> {
+		Ok(())
+	}
+}
@@ -0,0 +1,414 @@
+//! A component for driving the animation of a tile entity
+
+use bevy::prelude::Component;
+use bevy_tileset_tiles::prelude::AnimatedTileData;
+
+use crate::prelude::Tileset;
+
+/// A component tracking which animation (and frame) of an animated tile an entity is showing
+///
+/// For [`MultiAnimated`](bevy_tileset_tiles::prelude::TileType::MultiAnimated) tiles, this
+/// selects one of the named tracks (e.g. `"idle"` or `"walk"`). Without a track (or for regular
+/// animated tiles), the tile's default animation is used.
+///
+/// Variant and auto tiles have an animation for each of their animated variants, so the variant
+/// to play must be selected with [`Self::set_variant`] (e.g. using the index chosen by an auto
+/// tile rule).
+///
+/// This doesn't advance on its own. An animation system should call [`Self::advance`] whenever
+/// the next frame is due and apply the returned atlas index to the entity's sprite. Each
+/// animation begins on its start frame and plays in its defined direction.
+#[derive(Debug, Clone, Eq, PartialEq, Component)]
+pub struct TileAnimationState {
+	/// The name of the animated tile
+	tile: String,
+	/// The name of the current animation track (if any)
+	track: Option<String>,
+	/// An atlas index within the animation of the selected variant (if any)
+	variant: Option<usize>,
+	/// The number of frames the current animation has advanced since it started
+	elapsed_frames: usize,
+}
+
+impl TileAnimationState {
+	/// Create a new [`TileAnimationState`] starting at the start frame of the given tile's
+	/// default animation
+	///
+	/// # Arguments
+	///
+	/// * `tile`: The name of the animated tile
+	///
+	/// returns: TileAnimationState
+	///
+	pub fn new<TName: Into<String>>(tile: TName) -> Self {
+		Self {
+			tile: tile.into(),
+			track: None,
+			variant: None,
+			elapsed_frames: 0,
+		}
+	}
+
+	/// The name of the animated tile
+	pub fn tile(&self) -> &str {
+		&self.tile
+	}
+
+	/// The name of the current animation track
+	///
+	/// Returns `None` if the tile's default animation is being used.
+	pub fn track(&self) -> Option<&str> {
+		self.track.as_deref()
+	}
+
+	/// The number of frames the current animation has advanced since it (last) started
+	pub fn elapsed_frames(&self) -> usize {
+		self.elapsed_frames
+	}
+
+	/// Switch to the animation track with the given name
+	///
+	/// The animation restarts from its start frame, unless the given track is already playing.
+	///
+	/// # Arguments
+	///
+	/// * `track`: The name of the animation track (e.g. `"walk"`)
+	///
+	pub fn set_animation_state<TName: Into<String>>(&mut self, track: TName) {
+		let track = track.into();
+		if self.track.as_ref() != Some(&track) {
+			self.track = Some(track);
+			self.elapsed_frames = 0;
+		}
+	}
+
+	/// Switch back to the tile's default animation, restarting from its start frame
+	pub fn clear_animation_state(&mut self) {
+		if self.track.take().is_some() {
+			self.elapsed_frames = 0;
+		}
+	}
+
+	/// Switch to the animation of the variant containing the given atlas index
+	///
+	/// This is needed for variant and auto tiles, which can have a different animation for each
+	/// of their variants. The animation restarts from its start frame, unless the given index
+	/// belongs to the variant that's already playing. This also clears the current track.
+	///
+	/// # Arguments
+	///
+	/// * `tileset`: The tileset containing the tile
+	/// * `index`: The atlas index of any frame of the variant (e.g. from a [`TileIndex`])
+	///
+	/// [`TileIndex`]: crate::prelude::TileIndex
+	pub fn set_variant(&mut self, tileset: &Tileset, index: usize) {
+		let current = self.animation(tileset).map(AnimatedTileData::start);
+		self.track = None;
+		self.variant = Some(index);
+		if self.animation(tileset).map(AnimatedTileData::start) != current {
+			self.elapsed_frames = 0;
+		}
+	}
+
+	/// Get the atlas index of the current frame within the given tileset
+	///
+	/// # Arguments
+	///
+	/// * `tileset`: The tileset containing the tile
+	///
+	/// returns: Option<usize>
+	///
+	/// Returns `None` if the tile doesn't exist or doesn't have the current track.
+	pub fn atlas_index(&self, tileset: &Tileset) -> Option<usize> {
+		let anim = self.animation(tileset)?;
+		anim.frame_index(anim.playback_frame(self.elapsed_frames))
+	}
+
+	/// Move on to the next frame of the current animation (looping back around once it ends)
+	///
+	/// # Arguments
+	///
+	/// * `tileset`: The tileset containing the tile
+	///
+	/// returns: Option<usize>
+	///
+	/// Returns the atlas index of the new frame, or `None` if the current animation doesn't
+	/// exist (in which case the frame is left unchanged).
+	pub fn advance(&mut self, tileset: &Tileset) -> Option<usize> {
+		let anim = self.animation(tileset)?;
+		self.elapsed_frames = (self.elapsed_frames + 1) % anim.frame_count().max(1);
+		anim.frame_index(anim.playback_frame(self.elapsed_frames))
+	}
+
+	/// Get the data of the current animation
+	fn animation<'a>(&self, tileset: &'a Tileset) -> Option<&'a AnimatedTileData> {
+		match (&self.track, self.variant) {
+			(Some(track), _) => tileset.get_animation_track(&self.tile, track),
+			(None, Some(index)) => tileset.get_tile_data(&self.tile)?.tile().animation_at(&index),
+			(None, None) => tileset.get_animation(&self.tile),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::collections::BTreeMap;
+
+	use bevy::asset::{AddAsset, AssetPlugin, Assets};
+	use bevy::prelude::{App, Image, MinimalPlugins, TextureAtlas};
+	use bevy_tile_atlas::TextureStore;
+	use bevy_tileset_tiles::prelude::{AnimatedTileHandle, TileHandle};
+
+	use super::*;
+	use crate::prelude::TilesetBuilder;
+	use crate::test_utils::{solid_image, TestTextureStore};
+
+	#[test]
+	fn should_switch_animation_tracks() {
+		let mut app = App::new();
+		app.add_plugins((MinimalPlugins, AssetPlugin::default()))
+			.add_asset::<Image>()
+			.add_asset::<TextureAtlas>();
+
+		let mut store = TestTextureStore::default();
+		let mut track = |count: usize| AnimatedTileHandle {
+			speed: 1.0,
+			frames: (0..count)
+				.map(|_| store.add(solid_image(16, 16, [255; 4])))
+				.collect(),
+			regions: Vec::new(),
+			..Default::default()
+		};
+		let tracks = BTreeMap::from([
+			(String::from("idle"), track(2)),
+			(String::from("walk"), track(3)),
+		]);
+		let mut builder = TilesetBuilder::default();
+		builder
+			.add_tile(TileHandle::new_multi_animated("Hero", tracks), 0, &store)
+			.unwrap();
+		let raw = builder.build("Characters", 0, &mut store).unwrap();
+		let tileset = raw.into_asset(&mut app.world.resource_mut::<Assets<TextureAtlas>>());
+
+		assert_eq!(Some(vec!["idle", "walk"]), tileset.animation_tracks("Hero"));
+		assert_eq!(Some(4), tileset.animation_track_frame_index("Hero", "walk", 2));
+
+		// Without a track, the first track ("idle") is used
+		let mut state = TileAnimationState::new("Hero");
+		assert_eq!(Some(0), state.atlas_index(&tileset));
+		assert_eq!(Some(1), state.advance(&tileset));
+		assert_eq!(Some(0), state.advance(&tileset));
+
+		state.advance(&tileset);
+		state.set_animation_state("walk");
+		assert_eq!(Some("walk"), state.track());
+		assert_eq!(0, state.elapsed_frames());
+		assert_eq!(Some(2), state.atlas_index(&tileset));
+		assert_eq!(Some(3), state.advance(&tileset));
+		assert_eq!(Some(4), state.advance(&tileset));
+		assert_eq!(Some(2), state.advance(&tileset));
+
+		// Setting the current track again doesn't restart it
+		state.advance(&tileset);
+		state.set_animation_state("walk");
+		assert_eq!(1, state.elapsed_frames());
+
+		state.set_animation_state("idle");
+		assert_eq!(Some(0), state.atlas_index(&tileset));
+
+		state.set_animation_state("attack");
+		assert_eq!(None, state.atlas_index(&tileset));
+		assert_eq!(None, state.advance(&tileset));
+	}
+
+	#[test]
+	fn should_play_from_start_frame_in_reverse() {
+		let mut app = App::new();
+		app.add_plugins((MinimalPlugins, AssetPlugin::default()))
+			.add_asset::<Image>()
+			.add_asset::<TextureAtlas>();
+
+		let mut store = TestTextureStore::default();
+		let anim = AnimatedTileHandle {
+			speed: 1.0,
+			frames: (0..3)
+				.map(|_| store.add(solid_image(16, 16, [255; 4])))
+				.collect(),
+			regions: Vec::new(),
+			reversed: true,
+			start_frame: Some(1),
+		};
+		let mut builder = TilesetBuilder::default();
+		builder
+			.add_tile(TileHandle::new_animated("Torch", anim), 0, &store)
+			.unwrap();
+		let raw = builder.build("Props", 0, &mut store).unwrap();
+		let tileset = raw.into_asset(&mut app.world.resource_mut::<Assets<TextureAtlas>>());
+
+		let mut state = TileAnimationState::new("Torch");
+		assert_eq!(Some(1), state.atlas_index(&tileset));
+		assert_eq!(Some(0), state.advance(&tileset));
+		assert_eq!(Some(2), state.advance(&tileset));
+		assert_eq!(Some(1), state.advance(&tileset));
+
+		// Without a start frame, reversed animations start on their last frame
+		let mut store = TestTextureStore::default();
+		let anim = AnimatedTileHandle {
+			frames: (0..3)
+				.map(|_| store.add(solid_image(16, 16, [255; 4])))
+				.collect(),
+			reversed: true,
+			..Default::default()
+		};
+		let mut builder = TilesetBuilder::default();
+		builder
+			.add_tile(TileHandle::new_animated("Candle", anim), 0, &store)
+			.unwrap();
+		let raw = builder.build("Props", 0, &mut store).unwrap();
+		let tileset = raw.into_asset(&mut app.world.resource_mut::<Assets<TextureAtlas>>());
+
+		let mut state = TileAnimationState::new("Candle");
+		assert_eq!(Some(2), state.atlas_index(&tileset));
+		assert_eq!(Some(1), state.advance(&tileset));
+		assert_eq!(Some(0), state.advance(&tileset));
+		assert_eq!(Some(2), state.advance(&tileset));
+	}
+
+	#[cfg(feature = "auto-tile")]
+	#[test]
+	fn should_animate_auto_tile_variants() {
+		use bevy::math::IVec2;
+		use bevy_tileset_tiles::prelude::{
+			AutoTileHandle, AutoTileRule, SimpleTileHandle, VariantTileHandle,
+		};
+
+		use crate::auto::{AutoTile, AutoTileId, AutoTilemap, AutoTiler};
+		use crate::coords::TileCoords;
+		use crate::prelude::TileIndex;
+
+		#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq)]
+		struct Coords(IVec2);
+		impl TileCoords for Coords {
+			fn pos(&self) -> IVec2 {
+				self.0
+			}
+		}
+
+		#[derive(Debug, Copy, Clone)]
+		struct Tile(IVec2, AutoTileId);
+		impl AutoTile for Tile {
+			type Coords = Coords;
+			fn coords(&self) -> Coords {
+				Coords(self.0)
+			}
+			fn auto_id(&self) -> AutoTileId {
+				self.1
+			}
+			fn can_match(&self, other: &Self) -> bool {
+				self.1 == other.1
+			}
+		}
+
+		struct Tilemap(Vec<Tile>);
+		impl AutoTilemap for Tilemap {
+			type Tile = Tile;
+			fn make_coords(&self, pos: IVec2, _: &Coords) -> Coords {
+				Coords(pos)
+			}
+			fn get_tile_at(&self, coords: &Coords) -> Option<Tile> {
+				self.0.iter().find(|tile| tile.0 == coords.0).copied()
+			}
+			fn len(&self) -> usize {
+				self.0.len()
+			}
+		}
+
+		let mut app = App::new();
+		app.add_plugins((MinimalPlugins, AssetPlugin::default()))
+			.add_asset::<Image>()
+			.add_asset::<TextureAtlas>();
+
+		let mut store = TestTextureStore::default();
+		let mut image = || store.add(solid_image(16, 16, [255; 4]));
+		let corner = AutoTileHandle {
+			rule: AutoTileRule {
+				north: Some(false),
+				west: Some(false),
+				east: Some(true),
+				south: Some(true),
+				..Default::default()
+			},
+			variants: vec![VariantTileHandle {
+				weight: 1.0,
+				transform: None,
+				tile: SimpleTileHandle::Animated(AnimatedTileHandle {
+					speed: 1.0,
+					frames: vec![image(), image(), image()],
+					regions: Vec::new(),
+					..Default::default()
+				}),
+			}],
+		};
+		let fallback = AutoTileHandle {
+			rule: AutoTileRule::default(),
+			variants: vec![VariantTileHandle {
+				weight: 1.0,
+				transform: None,
+				tile: SimpleTileHandle::Standard(image()),
+			}],
+		};
+		let mut builder = TilesetBuilder::default();
+		builder
+			.add_tile(TileHandle::new_auto("Wall", vec![corner, fallback]), 0, &store)
+			.unwrap();
+		let raw = builder.build("Walls", 0, &mut store).unwrap();
+		let tileset = raw.into_asset(&mut app.world.resource_mut::<Assets<TextureAtlas>>());
+
+		// A corner at the origin, with walls to its east and south
+		let id = AutoTileId {
+			group_id: 0,
+			tileset_id: 0,
+		};
+		let tiles = [IVec2::ZERO, IVec2::X, IVec2::NEG_Y].map(|pos| Tile(pos, id));
+		let mut tilemap = Tilemap(tiles.to_vec());
+		let mut tiler = AutoTiler::new(&mut tilemap);
+		for tile in tiles {
+			tiler.add_tile(tile, true);
+		}
+		let requests = tiler.finish();
+		let request = requests
+			.iter()
+			.find(|request| request.tile.0 == IVec2::ZERO)
+			.unwrap();
+
+		let index = tileset.get_auto_index("Wall", request.rule).unwrap();
+		let TileIndex::Animated(start, end, ..) = index else {
+			panic!("expected an animated index, found {:?}", index);
+		};
+		assert_eq!(2, end - start);
+
+		// The animation can't be found without selecting the variant
+		let mut state = TileAnimationState::new("Wall");
+		assert_eq!(None, state.atlas_index(&tileset));
+
+		state.set_variant(&tileset, start);
+		assert_eq!(Some(start), state.atlas_index(&tileset));
+		assert_eq!(Some(start + 1), state.advance(&tileset));
+		assert_eq!(Some(end), state.advance(&tileset));
+		assert_eq!(Some(start), state.advance(&tileset));
+
+		// Re-selecting the playing variant (by any of its frames) doesn't restart it
+		state.advance(&tileset);
+		state.set_variant(&tileset, end);
+		assert_eq!(1, state.elapsed_frames());
+		for frame in start..=end {
+			assert!(tileset.is_auto_variant("Wall", &frame, &request.rule));
+		}
+
+		// The other walls use the (non-animated) fallback
+		for request in requests.iter().filter(|request| request.tile.0 != IVec2::ZERO) {
+			let index = tileset.get_auto_index("Wall", request.rule).unwrap();
+			assert!(matches!(index, TileIndex::Standard(..)));
+		}
+	}
+}
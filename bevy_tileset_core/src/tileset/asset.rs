@@ -1,57 +1,400 @@
-use std::collections::{BTreeMap, HashMap};
-use std::path::{Path, PathBuf};
-use std::sync::{Arc, RwLock};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::ops::RangeInclusive;
+use std::path::{Component, Path, PathBuf};
+use std::sync::{Arc, Mutex, RwLock};
 
+#[cfg(feature = "lazy")]
+use bevy::asset::AssetServer;
 use bevy::asset::{
-	Asset, AssetLoader, AssetPath, BoxedFuture, Handle, HandleId, LoadContext, LoadedAsset,
+	AssetIoError, AssetLoader, AssetPath, BoxedFuture, Handle, HandleId, LoadContext, LoadedAsset,
 };
-use bevy::prelude::{FromWorld, World};
+use bevy::log::{debug, info_span, warn};
+use bevy::prelude::{FromWorld, UVec2, Vec2, World};
 use bevy::render::renderer::RenderDevice;
 use bevy::render::texture::{CompressedImageFormats, Image, ImageType};
+use bevy::utils::tracing::{field, Instrument, Span};
 use bevy::utils::Uuid;
 use bevy_tile_atlas::TextureStore;
-use bevy_tileset_tiles::prelude::{TileDef, TileHandle};
+use bevy_tileset_tiles::prelude::{
+	SamplerPreset, TileCollision, TileDef, TileDefType, TileHandle, TileHandleType,
+	TileTransform, DEFAULT_PIVOT,
+};
+use futures::StreamExt;
+use ron::error::SpannedError;
 use serde::{Deserialize, Serialize};
 
-use crate::prelude::{TileGroupId, Tileset, TilesetBuilder, TilesetError, TilesetId};
+#[cfg(feature = "lazy")]
+use crate::prelude::LazyTilesetAsset;
+use crate::prelude::{
+	RawTileset, TileGroupId, Tileset, TilesetBuilder, TilesetCompressedFormats, TilesetError,
+	TilesetId, TilesetLoadLimits, TilesetSubsets,
+};
+use crate::tileset::bake::{BakedTileset, BAKED_TILESET_EXTENSION};
 use crate::tileset::load::{load_tile_handles, TextureLoader};
+use crate::tileset::{header, pixels, source};
 
+/// The asset loader for tileset definitions (and baked tilesets)
+///
+/// Loading is instrumented with `tracing` spans for each of its phases (reading tile
+/// definitions, decoding images, and packing the atlas). These can be viewed in a trace viewer
+/// by enabling Bevy's `trace` feature.
+///
+/// Every file (the tileset, its tile definitions, and their textures) is read through the
+/// `LoadContext`, so tilesets can be loaded from any registered `AssetIo` (such as a packed or
+/// encrypted archive) rather than just the filesystem.
+///
+/// Only a subset of a tileset's tiles can be loaded by registering it in the [`TilesetSubsets`]
+/// resource. The size of loaded tilesets can be limited with the [`TilesetLoadLimits`]
+/// resource.
 pub struct TilesetAssetLoader {
 	supported_compressed_formats: CompressedImageFormats,
+	subsets: TilesetSubsets,
+	limits: TilesetLoadLimits,
+	/// The server whose `AssetIo` lazy tilesets decode their textures through
+	#[cfg(feature = "lazy")]
+	asset_server: AssetServer,
 }
 
-#[derive(Default, Deserialize, Serialize)]
+#[derive(Default, Clone, Deserialize, Serialize)]
 pub struct TilesetDef {
-	/// The optional name of the tileset (defaults to a random UUID string)
+	/// The optional name of the tileset
+	///
+	/// Defaults to the stem of the tileset's file name (e.g. `"forest"` for `forest.ron`), or a
+	/// random UUID string if the file name isn't available
 	pub name: Option<String>,
 	/// The ID of the tileset
 	pub id: TilesetId,
 	/// The tiles in this tileset as a mapping of their group ID to the relative path to
 	/// their definition file
+	///
+	/// Sequential tiles can be defined in bulk by keying an entry with a range of group IDs
+	/// (e.g. `"1..4"` or `"1..=3"`) and including a `{}` placeholder in its path. The entry is
+	/// expanded into one tile per group ID, with the placeholder replaced by that group ID.
+	#[serde(deserialize_with = "deserialize_tiles")]
 	pub tiles: BTreeMap<TileGroupId, String>,
+	/// Whether the generated atlas should be padded to power-of-two dimensions
+	///
+	/// Default: `false`
+	#[serde(default)]
+	pub power_of_two: bool,
+	/// The RGBA color of every atlas pixel not covered by a tile (such as padding)
+	///
+	/// Opaque tilesets can set this to an opaque color so that linear filtering doesn't blend in
+	/// stray transparent pixels at tile edges.
+	///
+	/// Default: `[0, 0, 0, 0]` (transparent)
+	#[serde(default)]
+	pub gutter_color: [u8; 4],
+	/// The number of columns to lay the atlas out in (as a row-major grid)
+	///
+	/// This is useful for external tools that index tiles by their row and column.
+	///
+	/// Default: `None`
+	#[serde(default)]
+	pub grid_columns: Option<u32>,
+	/// The sampler used by the atlas texture
+	///
+	/// Default: [`SamplerPreset::Default`]
+	#[serde(default)]
+	pub sampler: SamplerPreset,
+	/// The size every tile in this tileset is expected to be (in pixels)
+	///
+	/// If `None`, the size is inferred from the first loaded tile
+	#[serde(default)]
+	pub tile_size: Option<Vec2>,
+	/// How tiles whose texture could not be read or decoded should be handled
+	///
+	/// Default: [`MissingTexturePolicy::Fail`]
+	#[serde(default)]
+	pub missing_texture_policy: MissingTexturePolicy,
+	/// Whether every texture should be checked against the `tile_size` before decoding any of them
+	///
+	/// Only the header of each texture is read for this check, so a mismatched texture is caught
+	/// without waiting for every other texture to be decoded. This has no effect if `tile_size`
+	/// is `None`.
+	///
+	/// Default: `false`
+	#[serde(default)]
+	pub preflight_size_check: bool,
+	/// Whether tiles that fail to load should be skipped rather than failing the entire tileset
+	///
+	/// This covers tiles whose definition can't be read or parsed, as well as tiles with a
+	/// texture that can't be read, can't be decoded, or doesn't match the `tile_size`, and tiles
+	/// that can't be added to the atlas (such as an animation with an invalid `start_frame`). A
+	/// warning describing each skipped tile is logged and stored on the tileset (see
+	/// [`Tileset::load_warnings`]). Textures replaced according to the
+	/// `missing_texture_policy` are never skipped.
+	///
+	/// Default: `false`
+	#[serde(default)]
+	pub skip_invalid_tiles: bool,
+	/// Whether the raw RON source of each tile definition should be kept on the tileset
+	///
+	/// This allows editors to present (and re-save) the original text of a tile definition,
+	/// including its comments and field order, rather than a reserialized version. Only the
+	/// tile's own file is kept, not any of its bases. See [`Tileset::tile_source`].
+	///
+	/// Default: `false`
+	#[serde(default)]
+	pub retain_tile_sources: bool,
+	/// The directory (relative to the asset root) that texture paths in tile definitions
+	/// are resolved against
+	///
+	/// Texture paths starting with a `/` bypass this root and are resolved from the asset root.
+	///
+	/// If `None`, texture paths are resolved from the asset root.
+	#[serde(default)]
+	pub texture_root: Option<PathBuf>,
+	/// The name of the asset source that textures are read from
+	///
+	/// Texture paths are prefixed with this name (such as `remote://textures/grass.png`), which
+	/// a [`MultiSourceAssetIo`](crate::prelude::MultiSourceAssetIo) routes to the `AssetIo`
+	/// registered under it. This allows textures to be served from a different location than
+	/// the definition files (such as a CDN). The `texture_root` is resolved within this source.
+	///
+	/// A tile can read its texture from a specific source by prefixing its texture path with
+	/// the name of that source (such as `mods://grass.png`), which bypasses the `texture_root`.
+	///
+	/// If `None`, textures are read from the same source as the definition files.
+	#[serde(default)]
+	pub texture_source: Option<String>,
+	/// The group IDs of tiles mapped by the name of the category they belong to
+	///
+	/// This is purely metadata (useful for organizing tiles in a palette UI) and doesn't
+	/// affect how the tileset is built. A tile may belong to multiple categories.
+	#[serde(default)]
+	pub categories: BTreeMap<String, Vec<TileGroupId>>,
+	/// The number of atlas textures to generate, each half the size of the previous one
+	///
+	/// This includes the full-resolution atlas, so a value of `1` (or `0`) only generates that.
+	///
+	/// Default: `1`
+	#[serde(default = "default_lod_levels")]
+	pub lod_levels: u32,
+	/// Whether every tile texture should have its color channels premultiplied by its alpha
+	///
+	/// This prevents blended tile edges from showing dark fringes when rendered with
+	/// premultiplied alpha blending.
+	///
+	/// Default: `false`
+	#[serde(default)]
+	pub premultiply_alpha: bool,
+	/// Whether every tile texture should be trimmed to the bounds of its non-transparent pixels
+	///
+	/// See [`TilesetBuilder::trim`] for details.
+	///
+	/// Default: `false`
+	#[serde(default)]
+	pub trim: bool,
+	/// The group ID of the tile to use in place of unknown tile names (e.g. an "unknown" tile)
+	///
+	/// See [`Tileset::get_tile_index_or_default`]. This tile is always loaded, even when only
+	/// a subset of the tileset is requested.
+	///
+	/// Default: `None`
+	#[serde(default)]
+	pub default_tile: Option<TileGroupId>,
+	/// Whether the loader should defer decoding the tiles' textures until they're requested
+	///
+	/// Instead of a [`Tileset`], loading a lazy tileset produces a
+	/// [`LazyTilesetAsset`](crate::prelude::LazyTilesetAsset), which only reads the tile
+	/// definitions up front. This requires the `lazy` feature (otherwise the tileset is loaded
+	/// eagerly, with a warning).
+	///
+	/// Default: `false`
+	#[serde(default)]
+	pub lazy: bool,
+}
+
+/// Gets the default number of LOD levels
+///
+/// Used for deserialization
+#[inline]
+fn default_lod_levels() -> u32 {
+	1
+}
+
+/// Defines how a texture that could not be read or decoded should be handled
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Deserialize, Serialize)]
+pub enum MissingTexturePolicy {
+	/// Fail to load the tileset
+	#[default]
+	Fail,
+	/// Substitute the texture with a generated magenta checkerboard
+	///
+	/// The placeholder is sized to the tileset's `tile_size` (or the size of the other
+	/// loaded tiles) and packed like any other texture, so indices stay stable.
+	Placeholder,
+}
+
+/// The size of a placeholder texture when no tile size could be determined
+const DEFAULT_PLACEHOLDER_SIZE: f32 = 16.0;
+
+/// A source for the files that make up a tileset
+///
+/// This allows the logic for loading a tileset to be shared between the [`TilesetAssetLoader`]
+/// and loading a tileset without an `AssetServer` (see [`RawTileset::from_ron_bytes`]).
+pub(crate) trait TilesetSource {
+	/// The path to the tileset definition file
+	fn path(&self) -> &Path;
+	/// Reads the bytes of the file at the given path
+	fn read_bytes<'a>(&'a self, path: &'a Path) -> BoxedFuture<'a, Result<Vec<u8>, TilesetError>>;
+	/// Gets the handle to use for the texture at the given path
+	fn texture_handle(&self, path: AssetPath<'static>) -> Handle<Image>;
+}
+
+impl<'y> TilesetSource for LoadContext<'y> {
+	fn path(&self) -> &Path {
+		LoadContext::path(self)
+	}
+
+	fn read_bytes<'a>(&'a self, path: &'a Path) -> BoxedFuture<'a, Result<Vec<u8>, TilesetError>> {
+		Box::pin(async move {
+			self.read_asset_bytes(path)
+				.await
+				.map_err(TilesetError::from)
+		})
+	}
+
+	fn texture_handle(&self, path: AssetPath<'static>) -> Handle<Image> {
+		self.get_handle(path)
+	}
+}
+
+/// A [`TilesetSource`] that reads files using a closure
+pub(crate) struct ResolverSource<TResolver: Fn(&Path) -> Option<Vec<u8>>> {
+	/// The path to the tileset definition file
+	pub path: PathBuf,
+	/// The closure used to read the bytes of a file by its path
+	pub resolver: TResolver,
+}
+
+impl<TResolver: Fn(&Path) -> Option<Vec<u8>>> TilesetSource for ResolverSource<TResolver> {
+	fn path(&self) -> &Path {
+		&self.path
+	}
+
+	fn read_bytes<'a>(&'a self, path: &'a Path) -> BoxedFuture<'a, Result<Vec<u8>, TilesetError>> {
+		let result = (self.resolver)(path).ok_or_else(|| {
+			TilesetError::AssetIoError(AssetIoError::NotFound(path.to_path_buf()))
+		});
+		Box::pin(async move { result })
+	}
+
+	fn texture_handle(&self, path: AssetPath<'static>) -> Handle<Image> {
+		Handle::weak(HandleId::from(path))
+	}
+}
+
+/// A [`TilesetSource`] that records the path of every file read through it
+struct TrackedSource<'x, TSource: TilesetSource> {
+	source: &'x TSource,
+	/// The paths of every file read so far
+	paths: Mutex<BTreeSet<PathBuf>>,
+}
+
+impl<'x, TSource: TilesetSource> TrackedSource<'x, TSource> {
+	/// Wraps the given source, starting with the path of its tileset definition (if it has one)
+	fn new(source: &'x TSource) -> Self {
+		let path = Some(source.path().to_path_buf()).filter(|path| !path.as_os_str().is_empty());
+		Self {
+			source,
+			paths: Mutex::new(path.into_iter().collect()),
+		}
+	}
+
+	/// Gets the paths of every file read so far (in sorted order)
+	fn paths(&self) -> Vec<PathBuf> {
+		self.paths
+			.lock()
+			.map(|paths| paths.iter().cloned().collect())
+			.unwrap_or_default()
+	}
+}
+
+impl<'x, TSource: TilesetSource> TilesetSource for TrackedSource<'x, TSource> {
+	fn path(&self) -> &Path {
+		self.source.path()
+	}
+
+	fn read_bytes<'a>(&'a self, path: &'a Path) -> BoxedFuture<'a, Result<Vec<u8>, TilesetError>> {
+		if let Ok(mut paths) = self.paths.lock() {
+			paths.insert(path.to_path_buf());
+		}
+		self.source.read_bytes(path)
+	}
+
+	fn texture_handle(&self, path: AssetPath<'static>) -> Handle<Image> {
+		self.source.texture_handle(path)
+	}
+}
+
+/// A [`TextureStore`] that serves the textures loaded from a [`ResolverSource`], while storing
+/// added textures (i.e. the atlas texture) in the given store
+pub(crate) struct ResolvedTextureStore<'x, TStore: TextureStore> {
+	pub store: &'x mut TStore,
+	pub images: HashMap<HandleId, Image>,
+}
+
+impl<'x, TStore: TextureStore> ResolvedTextureStore<'x, TStore> {
+	/// Adds a copy of the atlas texture of each region page of the given tileset to the given
+	/// store, since those textures were only read through the resolver
+	pub fn store_region_atlases(&mut self, tileset: &mut RawTileset) {
+		for page in &mut tileset.pages {
+			if let Some(texture) = self.images.get(&page.atlas.texture.id()) {
+				page.atlas.texture = self.store.add(texture.clone());
+			}
+		}
+	}
+}
+
+impl<'x, TStore: TextureStore> TextureStore for ResolvedTextureStore<'x, TStore> {
+	fn add(&mut self, asset: Image) -> Handle<Image> {
+		self.store.add(asset)
+	}
+
+	fn get<H: Into<HandleId>>(&self, handle: H) -> Option<&Image> {
+		let id = handle.into();
+		self.images.get(&id).or_else(|| self.store.get(id))
+	}
 }
 
 /// A struct that mimics a Bevy `AssetServer`
 ///
 /// Instead of loading an image right away, it tracks the paths to the images to be loaded
 /// later (so we don't need to await on _every_ image).
-struct TilesetTextureLoader<'x, 'y> {
-	supported_compressed_formats: CompressedImageFormats,
-	load_context: &'x mut LoadContext<'y>,
+struct TilesetTextureLoader<'x, TSource: TilesetSource> {
+	source: &'x TSource,
 	/// The images that need to be loaded
 	bytes: Arc<RwLock<HashMap<HandleId, PathBuf>>>,
+	/// The directory texture paths are resolved against (if any)
+	texture_root: Option<PathBuf>,
+	/// The asset source textures are read from (if any)
+	texture_source: Option<String>,
 }
 
 /// A struct that mimics a Bevy `Assets<Texture>` resource by allowing get/add operations
 struct TilesetTextureStore<'x, 'y> {
 	load_context: &'x mut LoadContext<'y>,
 	images: HashMap<HandleId, Image>,
+	/// The ID of the tileset being loaded
+	tileset_id: TilesetId,
+	/// The number of textures added to this store so far
+	added: usize,
 }
 
-impl<'x, 'y> TextureLoader for TilesetTextureLoader<'x, 'y> {
-	fn load_texture<'a, T: Asset, P: Into<AssetPath<'a>>>(&self, path: P) -> Handle<Image> {
-		let asset_path = path.into().clone();
-		let handle: Handle<Image> = self.load_context.get_handle(asset_path.clone());
+impl<'x, TSource: TilesetSource> TextureLoader for TilesetTextureLoader<'x, TSource> {
+	fn load_texture<'a, P: Into<AssetPath<'a>>>(&self, path: P) -> Handle<Image> {
+		let asset_path = path.into();
+		let asset_path = AssetPath::new(
+			resolve_texture_path(
+				self.texture_root.as_deref(),
+				self.texture_source.as_deref(),
+				asset_path.path(),
+			),
+			asset_path.label().map(String::from),
+		);
+		let handle = self.source.texture_handle(asset_path.clone());
 		let path = asset_path.path().to_path_buf();
 
 		if let Ok(mut images) = self.bytes.try_write() {
@@ -61,26 +404,165 @@ impl<'x, 'y> TextureLoader for TilesetTextureLoader<'x, 'y> {
 	}
 }
 
-impl<'x, 'y> TilesetTextureLoader<'x, 'y> {
+/// Resolves a texture path from a tile definition against the given texture root and source
+///
+/// Paths starting with a `/` bypass the root and are resolved from the asset root. Paths
+/// prefixed with a source name (`name://`) bypass both and are resolved from that source's root.
+pub(crate) fn resolve_texture_path(
+	texture_root: Option<&Path>,
+	texture_source: Option<&str>,
+	path: &Path,
+) -> PathBuf {
+	if let Some((name, path)) = source::split_source(path) {
+		return source::with_source(name, path);
+	}
+	let path = match (texture_root, path.strip_prefix("/")) {
+		(_, Ok(path)) => path.to_path_buf(),
+		(Some(root), Err(..)) => root.join(path),
+		(None, Err(..)) => path.to_path_buf(),
+	};
+	match texture_source {
+		Some(name) => source::with_source(name, &path),
+		None => path,
+	}
+}
+
+impl<'x, TSource: TilesetSource> TilesetTextureLoader<'x, TSource> {
 	/// Load the images and collect them into a HashMap
-	fn collect_images(self) -> BoxedFuture<'x, Result<HashMap<HandleId, Image>, TilesetError>> {
-		let images = self.bytes.read().unwrap().clone();
-		Box::pin(async move {
-			let image_map = futures::future::join_all(images.into_iter().map(|(id, path)| {
-				load_image(
-					&self.load_context,
-					id,
-					path,
-					self.supported_compressed_formats,
-				)
-			}))
-			.await
+	///
+	/// Images that fail to load are handled according to the given [`MissingTexturePolicy`].
+	///
+	/// If `expected_sizes` is given, every image is checked against its expected size (using
+	/// only its header) before any of them are decoded.
+	///
+	/// If `skip_invalid` is true, images that fail to load (or don't match their expected size)
+	/// are returned as failures mapped by their handle ID instead of returning an error.
+	///
+	/// Exceeding the given limits always returns an error, before the offending images are
+	/// decoded where possible. When any image limit is set, the images are read one at a time
+	/// (in order of their paths), so that an offending image fails the load before the rest
+	/// are read.
+	async fn collect_images(
+		self,
+		formats: CompressedImageFormats,
+		policy: MissingTexturePolicy,
+		tile_size: Option<Vec2>,
+		expected_sizes: Option<HashMap<HandleId, (String, Vec2)>>,
+		skip_invalid: bool,
+		limits: &TilesetLoadLimits,
+	) -> Result<(HashMap<HandleId, Image>, HashMap<HandleId, String>), TilesetError> {
+		let mut images = self.bytes.read().unwrap().clone().into_iter().collect::<Vec<_>>();
+		images.sort_unstable_by(|(.., a), (.., b)| a.cmp(b));
+		let concurrency = if limits.limits_images() {
+			1
+		} else {
+			images.len().max(1)
+		};
+		let source = self.source;
+		let mut pending = futures::stream::iter(images.into_iter().map(|(id, path)| async move {
+			let bytes = read_image(source, &path).await;
+			(id, path, bytes)
+		}))
+		.buffered(concurrency);
+
+		let check_size = |id: &HandleId, found: Vec2| -> Result<(), TilesetError> {
+			match expected_sizes.as_ref().and_then(|sizes| sizes.get(id)) {
+				Some((tile, expected)) if found != *expected => Err(TilesetError::InvalidTileSize {
+					tile: tile.clone(),
+					expected: *expected,
+					found,
+				}),
+				_ => Ok(()),
+			}
+		};
+
+		let mut reads = Vec::new();
+		let mut failures = HashMap::new();
+		let mut atlas_pixels = 0;
+		while let Some((id, path, bytes)) = pending.next().await {
+			if let Ok(bytes) = &bytes {
+				let found = limits.check_encoded_image(&path, bytes)?;
+				if let Some(found) = found {
+					let pixels = found.x as u64 * found.y as u64;
+					atlas_pixels = pixels.saturating_add(atlas_pixels);
+					limits.check_atlas_pixels(atlas_pixels)?;
+				}
+				if let Some(found) = found.map(|found| found.as_vec2()) {
+					match check_size(&id, found) {
+						Err(err) if skip_invalid => {
+							failures.insert(id, err.to_string());
+						},
+						result => result?,
+					}
+				}
+			}
+			reads.push((id, path, bytes));
+		}
+
+		let results = reads
 			.into_iter()
-			.filter_map(|x| x.ok())
-			.collect();
+			.filter(|(id, ..)| !failures.contains_key(id))
+			.map(|(id, path, bytes)| {
+				let image = bytes.and_then(|bytes| decode_image(&path, &bytes, formats));
+				// Not every format's header can be read, so decoded images are checked as well
+				if let Ok(image) = &image {
+					limits.check_image_bytes(&path, image.data.len() as u64)?;
+				}
+				let image = match image {
+					Ok(image) if skip_invalid => check_size(&id, image.size()).map(|_| image),
+					image => image,
+				};
+				Ok((id, image))
+			})
+			.collect::<Result<Vec<_>, TilesetError>>()?;
 
-			Ok(image_map)
-		})
+		let atlas_pixels = results
+			.iter()
+			.filter_map(|(_, result)| result.as_ref().ok())
+			.map(|image| image.size().x as u64 * image.size().y as u64)
+			.sum::<u64>();
+		limits.check_atlas_pixels(atlas_pixels)?;
+
+		let total_bytes = results
+			.iter()
+			.filter_map(|(_, result)| result.as_ref().ok())
+			.map(|image| image.data.len())
+			.sum::<usize>();
+		Span::current().record("bytes", total_bytes);
+
+		let mut image_map = HashMap::with_capacity(results.len());
+		let mut missing = Vec::new();
+		for (id, result) in results {
+			match result {
+				Ok(image) => {
+					image_map.insert(id, image);
+				},
+				// Escaping the asset root is never a recoverable error
+				Err(err @ TilesetError::PathEscape(..)) => return Err(err),
+				Err(err @ TilesetError::InvalidTileSize { .. }) => {
+					failures.insert(id, err.to_string());
+				},
+				Err(err) => match policy {
+					MissingTexturePolicy::Fail if skip_invalid => {
+						failures.insert(id, err.to_string());
+					},
+					MissingTexturePolicy::Fail => return Err(err),
+					MissingTexturePolicy::Placeholder => missing.push(id),
+				},
+			}
+		}
+
+		if !missing.is_empty() {
+			let size = tile_size
+				.or_else(|| image_map.values().next().map(|image| image.size()))
+				.unwrap_or(Vec2::splat(DEFAULT_PLACEHOLDER_SIZE));
+			let placeholder = pixels::checkerboard(size.x as u32, size.y as u32);
+			for id in missing {
+				image_map.insert(id, placeholder.clone());
+			}
+		}
+
+		Ok((image_map, failures))
 	}
 }
 
@@ -88,12 +570,8 @@ impl<'x, 'y> TextureStore for TilesetTextureStore<'x, 'y> {
 	fn add(&mut self, asset: Image) -> Handle<Image> {
 		//! This should only really be called once: When creating the tile texture atlas
 		//! since we'll need to track that asset as well.
-		let prefix = self
-			.load_context
-			.path()
-			.to_str()
-			.unwrap_or("UNKNOWN_TILESET");
-		let label = format!("Tileset__[{:?}]__{:?}", prefix, Uuid::new_v4().to_string());
+		let label = atlas_label(self.load_context.path(), self.tileset_id, self.added);
+		self.added += 1;
 		self.load_context
 			.set_labeled_asset(&label, LoadedAsset::new(asset))
 	}
@@ -103,15 +581,40 @@ impl<'x, 'y> TextureStore for TilesetTextureStore<'x, 'y> {
 	}
 }
 
+/// Creates the label for a texture added while loading a tileset
+///
+/// This is derived entirely from the tileset itself (rather than being random) so that loading
+/// the same tileset always produces the same labels.
+fn atlas_label(path: &Path, tileset_id: TilesetId, index: usize) -> String {
+	let prefix = path.to_str().unwrap_or("UNKNOWN_TILESET");
+	format!("Tileset__[{:?}]__{}__{}", prefix, tileset_id, index)
+}
+
 impl FromWorld for TilesetAssetLoader {
 	fn from_world(world: &mut World) -> Self {
-		let supported_compressed_formats = match world.get_resource::<RenderDevice>() {
-			Some(render_device) => CompressedImageFormats::from_features(render_device.features()),
-
-			None => CompressedImageFormats::all(),
+		let supported_compressed_formats = match (
+			world.get_resource::<TilesetCompressedFormats>(),
+			world.get_resource::<RenderDevice>(),
+		) {
+			(Some(TilesetCompressedFormats(formats)), ..) => *formats,
+			(None, Some(render_device)) => {
+				CompressedImageFormats::from_features(render_device.features())
+			},
+			(None, None) => CompressedImageFormats::all(),
 		};
+		let subsets = world
+			.get_resource_or_insert_with(TilesetSubsets::default)
+			.clone();
+		let limits = world
+			.get_resource::<TilesetLoadLimits>()
+			.copied()
+			.unwrap_or_default();
 		Self {
 			supported_compressed_formats,
+			subsets,
+			limits,
+			#[cfg(feature = "lazy")]
+			asset_server: world.resource::<AssetServer>().clone(),
 		}
 	}
 }
@@ -122,126 +625,1596 @@ impl AssetLoader for TilesetAssetLoader {
 		bytes: &'a [u8],
 		load_context: &'a mut LoadContext,
 	) -> BoxedFuture<'a, anyhow::Result<(), anyhow::Error>> {
-		Box::pin(async move {
-			let config = ron::de::from_bytes::<TilesetDef>(bytes)?;
-
-			// === Load Handles === //
-			let loader = TilesetTextureLoader {
-				supported_compressed_formats: self.supported_compressed_formats,
-				bytes: Arc::new(RwLock::new(HashMap::new())),
-				load_context,
-			};
-
-			let tile_handles = get_tile_handles(&loader, &config.tiles).await?;
-
-			// === Build Tiles === //
-			let images = loader.collect_images().await?;
-			let mut store = TilesetTextureStore {
-				load_context,
-				images,
-			};
-
-			let mut builder = TilesetBuilder::default();
-			for (group_id, tile_handle) in tile_handles {
-				builder.add_tile(tile_handle, group_id, &store)?;
-			}
-
-			// === Create Raw Tileset === //
-			let name = config
-				.name
-				.unwrap_or_else(|| Uuid::new_v4().hyphenated().to_string());
-			let raw_tileset = builder.build(name, config.id, &mut store)?;
-
-			// === Finalize Tileset === //
-			let texture = raw_tileset.atlas().texture.clone();
-			let atlas_asset = LoadedAsset::new(raw_tileset.atlas);
-			let atlas = load_context.set_labeled_asset("atlas", atlas_asset);
-			let tileset = Tileset {
-				id: raw_tileset.id,
-				name: raw_tileset.name,
-				tiles: raw_tileset.tiles,
-				size: raw_tileset.size,
-				tile_size: raw_tileset.tile_size,
-				tile_ids: raw_tileset.tile_ids,
-				tile_names: raw_tileset.tile_names,
-				tile_handles: raw_tileset.tile_handles,
-				tile_indices: raw_tileset.tile_indices,
-				atlas,
-				texture,
-			};
-
-			load_context.set_default_asset(LoadedAsset::new(tileset));
-
-			Ok(())
-		})
+		let span = info_span!("load_tileset", path = ?load_context.path());
+		Box::pin(
+			async move {
+				if is_baked(load_context.path()) {
+					return Ok(load_baked(bytes, load_context)?);
+				}
+
+				// === Load Tiles === //
+				let subset = self.subsets.get(load_context.path());
+				let formats = self.supported_compressed_formats;
+				if parse_tileset_def(bytes, load_context.path())?.lazy {
+					#[cfg(feature = "lazy")]
+					{
+						let lazy = LazyTilesetAsset::load(
+							bytes,
+							load_context,
+							self.asset_server.clone(),
+							formats,
+							subset.as_ref(),
+							&self.limits,
+						)
+						.await?;
+						load_context.set_default_asset(LoadedAsset::new(lazy));
+						return Ok(());
+					}
+					#[cfg(not(feature = "lazy"))]
+					warn!(
+						"loading lazy tileset {:?} eagerly (the `lazy` feature is disabled)",
+						load_context.path()
+					);
+				}
+				let LoadedTiles {
+					config,
+					tile_handles,
+					images,
+					region_atlases,
+					report,
+				} = load_tiles(
+					bytes,
+					&*load_context,
+					formats,
+					subset.as_ref(),
+					&self.limits,
+				)
+				.await?;
+
+				// === Create Raw Tileset === //
+				let mut store = TilesetTextureStore {
+					load_context,
+					images,
+					tileset_id: config.id,
+					added: 0,
+				};
+				let raw_tileset = build_tiles(config, tile_handles, report, &mut store)?;
+
+				// === Finalize Tileset === //
+				let tileset = finalize_tileset(raw_tileset, String::from("atlas"), load_context);
+
+				debug!(
+					"loaded tileset {:?} ({} textures, {}x{} atlas, ~{} bytes)",
+					tileset.name(),
+					tileset.tile_count(),
+					tileset.atlas_dimensions().x,
+					tileset.atlas_dimensions().y,
+					tileset.atlas_byte_size()
+				);
+
+				// Region tiles use their atlas texture as-is, so it needs to be loaded as well
+				let dependencies = region_atlases.into_iter().map(AssetPath::from).collect();
+				load_context
+					.set_default_asset(LoadedAsset::new(tileset).with_dependencies(dependencies));
+
+				Ok(())
+			}
+			.instrument(span),
+		)
 	}
 
 	fn extensions(&self) -> &[&str] {
-		&["ron"]
+		&["ron", BAKED_TILESET_EXTENSION]
 	}
 }
 
-/// Get a `Vec` of ([`TileGroupId`], [`TileHandle`]) tuples
-async fn get_tile_handles<'x, 'y>(
-	loader: &'x TilesetTextureLoader<'x, 'y>,
+/// Moves the atlas of the given tileset (and of each of its pages) into the load context
+///
+/// The main atlas is labeled with `label`, while each page's atlas is labeled `atlas_page_{n}`
+/// (starting at `1`).
+fn finalize_tileset(raw: RawTileset, label: String, load_context: &mut LoadContext) -> Tileset {
+	let texture = raw.atlas().texture.clone();
+	let atlas = load_context.set_labeled_asset(&label, LoadedAsset::new(raw.atlas));
+	let pages = raw
+		.pages
+		.into_iter()
+		.enumerate()
+		.map(|(index, page)| {
+			let label = format!("atlas_page_{}", index + 1);
+			finalize_tileset(page, label, load_context)
+		})
+		.collect();
+	Tileset {
+		id: raw.id,
+		name: raw.name,
+		tiles: raw.tiles,
+		size: raw.size,
+		tile_size: raw.tile_size,
+		tile_ids: raw.tile_ids,
+		tile_names: raw.tile_names,
+		tile_handles: raw.tile_handles,
+		group_handles: raw.group_handles,
+		tile_indices: raw.tile_indices,
+		tile_colors: raw.tile_colors,
+		tile_sizes: raw.tile_sizes,
+		categories: raw.categories,
+		tile_trims: raw.tile_trims,
+		processed_indices: raw.processed_indices,
+		lod_textures: raw.lod_textures,
+		load_warnings: raw.load_warnings,
+		tile_sources: raw.tile_sources,
+		dependency_paths: raw.dependency_paths,
+		default_tile: raw.default_tile,
+		grid_columns: raw.grid_columns,
+		atlas,
+		texture,
+		pages,
+	}
+}
+
+/// Checks whether the file at the given path is a baked tileset
+fn is_baked(path: &Path) -> bool {
+	path.to_str()
+		.map(|path| path.ends_with(&format!(".{}", BAKED_TILESET_EXTENSION)))
+		.unwrap_or_default()
+}
+
+/// Load a baked tileset, skipping the build phase entirely
+fn load_baked(bytes: &[u8], load_context: &mut LoadContext) -> Result<(), TilesetError> {
+	let baked = BakedTileset::from_bytes(bytes)?;
+	let mut textures = Vec::new();
+	let mut atlases = Vec::new();
+	let mut tileset = baked.into_tileset(
+		|label, image| {
+			textures.push((label.to_string(), image));
+			load_context.get_handle(AssetPath::new_ref(load_context.path(), Some(label)))
+		},
+		|label, atlas| {
+			atlases.push((label.to_string(), atlas));
+			load_context.get_handle(AssetPath::new_ref(load_context.path(), Some(label)))
+		},
+	)?;
+
+	for (label, texture) in textures {
+		load_context.set_labeled_asset(&label, LoadedAsset::new(texture));
+	}
+	for (label, atlas) in atlases {
+		load_context.set_labeled_asset(&label, LoadedAsset::new(atlas));
+	}
+	tileset.dependency_paths = vec![load_context.path().to_path_buf()];
+	load_context.set_default_asset(LoadedAsset::new(tileset));
+	Ok(())
+}
+
+/// The parsed definition, tile handles, and decoded images of a tileset, ready to be built
+pub(crate) struct LoadedTiles {
+	pub config: TilesetDef,
+	pub tile_handles: Vec<(TileGroupId, TileHandle)>,
+	pub images: HashMap<HandleId, Image>,
+	/// The paths of the atlas textures referenced by region tiles
+	pub region_atlases: Vec<PathBuf>,
+	pub report: LoadReport,
+}
+
+/// The details recorded while loading the tiles of a tileset, which are stored on the tileset
+#[derive(Default, Clone)]
+pub(crate) struct LoadReport {
+	/// The warnings for any tiles that were skipped
+	pub warnings: Vec<String>,
+	/// The raw RON source of each tile definition mapped by its group ID (if retained)
+	pub sources: HashMap<TileGroupId, String>,
+	/// The paths of every file read while loading (including the tileset definition itself)
+	pub dependencies: Vec<PathBuf>,
+}
+
+/// The parsed definition and tile handles of a tileset, before any of its images are read
+pub(crate) struct PendingTiles {
+	pub config: TilesetDef,
+	pub tile_handles: Vec<(TileGroupId, TileHandle)>,
+	/// The path of every texture used by the tiles, mapped by the ID of its handle
+	pub texture_paths: HashMap<HandleId, PathBuf>,
+	pub report: LoadReport,
+}
+
+/// Parses the given tileset definition, without reading any of its tile definitions
+///
+/// If the definition doesn't specify a name, the tileset is named after its file.
+pub(crate) fn parse_tileset_def(bytes: &[u8], path: &Path) -> Result<TilesetDef, TilesetError> {
+	let mut config = match ron_options().from_bytes::<TilesetDef>(bytes) {
+		Ok(config) => config,
+		Err(err) => {
+			// Out-of-range IDs are otherwise only reported as a generic invalid value
+			check_id_ranges(bytes, path)?;
+			return Err(TilesetError::InvalidTilesetDef(err, path.to_path_buf()));
+		},
+	};
+	if config.name.is_none() {
+		config.name = default_tileset_name(path);
+	}
+	Ok(config)
+}
+
+/// Creates the handles of the given tile definitions, without reading any of their images
+///
+/// Texture paths are resolved against the given texture root. The handles are returned along
+/// with the path of every texture they use, mapped by the ID of its handle.
+pub(crate) fn create_tile_handles<TSource: TilesetSource>(
+	tile_defs: Vec<TileDef>,
+	source: &TSource,
+	texture_root: Option<PathBuf>,
+	texture_source: Option<String>,
+) -> (Vec<TileHandle>, HashMap<HandleId, PathBuf>) {
+	let loader = TilesetTextureLoader {
+		bytes: Arc::new(RwLock::new(HashMap::new())),
+		source,
+		texture_root,
+		texture_source,
+	};
+	let handles = load_tile_handles(tile_defs, &loader);
+	let texture_paths = loader.bytes.read().unwrap().clone();
+	(handles, texture_paths)
+}
+
+/// Parses the given tileset definition and reads all of its tile definitions
+///
+/// None of the tiles' images are read. If a `subset` is given, only the tiles with those group
+/// IDs are read.
+pub(crate) async fn read_tile_defs<TSource: TilesetSource>(
+	bytes: &[u8],
+	source: &TSource,
+	subset: Option<&BTreeSet<TileGroupId>>,
+	limits: &TilesetLoadLimits,
+) -> Result<PendingTiles, TilesetError> {
+	// Ranged entries are counted before they're expanded, so a huge range is never allocated
+	if let Some(count) = limits.max_tiles.and_then(|_| count_tiles(bytes)) {
+		limits.check_tiles(count)?;
+	}
+	let mut config = parse_tileset_def(bytes, source.path())?;
+	check_duplicate_group_ids(bytes, source.path())?;
+	if let Some(subset) = subset {
+		let default_tile = config.default_tile;
+		config
+			.tiles
+			.retain(|group_id, _| subset.contains(group_id) || Some(*group_id) == default_tile);
+		for group_ids in config.categories.values_mut() {
+			group_ids.retain(|group_id| subset.contains(group_id));
+		}
+	}
+
+	let skip_invalid = config.skip_invalid_tiles;
+	let retain_sources = config.retain_tile_sources;
+	let (tile_defs, report) = get_tile_defs(source, &config.tiles, skip_invalid, retain_sources)
+		.instrument(info_span!("read_tile_defs", tiles = config.tiles.len()))
+		.await?;
+	let (group_ids, tile_defs): (Vec<_>, Vec<_>) = tile_defs.into_iter().unzip();
+	let texture_root = config.texture_root.clone();
+	let texture_source = config.texture_source.clone();
+	let (handles, texture_paths) =
+		create_tile_handles(tile_defs, source, texture_root, texture_source);
+	let tile_handles = group_ids.into_iter().zip(handles).collect();
+
+	Ok(PendingTiles {
+		config,
+		tile_handles,
+		texture_paths,
+		report,
+	})
+}
+
+/// Parses the given tileset definition and loads all of its tiles and textures
+///
+/// If a `subset` is given, only the tiles with those group IDs are loaded.
+pub(crate) async fn load_tiles<TSource: TilesetSource>(
+	bytes: &[u8],
+	source: &TSource,
+	supported_compressed_formats: CompressedImageFormats,
+	subset: Option<&BTreeSet<TileGroupId>>,
+	limits: &TilesetLoadLimits,
+) -> Result<LoadedTiles, TilesetError> {
+	let source = &TrackedSource::new(source);
+
+	// === Load Handles === //
+	let PendingTiles {
+		config,
+		mut tile_handles,
+		texture_paths,
+		mut report,
+	} = read_tile_defs(bytes, source, subset, limits).await?;
+	let skip_invalid = config.skip_invalid_tiles;
+	let region_paths = region_atlas_ids(&tile_handles)
+		.filter_map(|id| Some((id, texture_paths.get(&id)?.clone())))
+		.collect::<HashMap<_, _>>();
+
+	// === Load Images === //
+	let loader = TilesetTextureLoader {
+		bytes: Arc::new(RwLock::new(texture_paths)),
+		source,
+		texture_root: config.texture_root.clone(),
+		texture_source: config.texture_source.clone(),
+	};
+	let image_count = loader.bytes.read().unwrap().len();
+	let expected_sizes = config
+		.tile_size
+		.filter(|_| config.preflight_size_check || skip_invalid)
+		.map(|tile_size| {
+			header::expected_sizes(tile_handles.iter().map(|(_, tile)| tile), tile_size)
+		});
+	let (images, failures) = loader
+		.collect_images(
+			supported_compressed_formats,
+			config.missing_texture_policy,
+			config.tile_size,
+			expected_sizes,
+			skip_invalid,
+			limits,
+		)
+		.instrument(info_span!(
+			"decode_images",
+			images = image_count,
+			bytes = field::Empty
+		))
+		.await?;
+
+	if !failures.is_empty() {
+		tile_handles.retain(|(group_id, tile)| {
+			let failure = tile.iter_handles().find_map(|handle| failures.get(&handle.id()));
+			match failure {
+				Some(err) => {
+					skip_tile(&mut report.warnings, *group_id, &tile.name, err);
+					false
+				},
+				None => true,
+			}
+		});
+	}
+
+	let region_atlases = region_atlas_ids(&tile_handles)
+		.filter_map(|id| region_paths.get(&id).cloned())
+		.collect::<BTreeSet<_>>()
+		.into_iter()
+		.collect();
+	report.dependencies = source.paths();
+	Ok(LoadedTiles {
+		config,
+		tile_handles,
+		images,
+		region_atlases,
+		report,
+	})
+}
+
+/// Gets the handle ID of the atlas texture of every region tile
+fn region_atlas_ids(
+	tile_handles: &[(TileGroupId, TileHandle)],
+) -> impl Iterator<Item = HandleId> + '_ {
+	tile_handles.iter().filter_map(|(.., tile)| match &tile.tile {
+		TileHandleType::AtlasRegion(handle, ..) => Some(handle.id()),
+		_ => None,
+	})
+}
+
+/// The tiles of a tileset definition, in the order they were written (including duplicates)
+///
+/// Since [`TilesetDef::tiles`] is a map, any tiles sharing a group ID would otherwise be
+/// silently collapsed into one.
+#[derive(Deserialize)]
+struct TilesetDefEntries {
+	tiles: TileEntries,
+}
+
+/// The raw entries of a tileset definition's `tiles` map
+struct TileEntries(Vec<(TileGroupId, String)>);
+
+/// The number of tiles in a tileset definition, counted without expanding ranged entries
+#[derive(Deserialize)]
+struct TilesetDefTileCount {
+	tiles: TileCount,
+}
+
+/// The number of tiles declared by a tileset definition's `tiles` map
+struct TileCount(u64);
+
+impl<'de> Deserialize<'de> for TileCount {
+	fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		struct CountVisitor;
+
+		impl<'de> serde::de::Visitor<'de> for CountVisitor {
+			type Value = TileCount;
+
+			fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+				formatter.write_str("a map of group IDs to tile definition paths")
+			}
+
+			fn visit_map<A: serde::de::MapAccess<'de>>(
+				self,
+				mut map: A,
+			) -> Result<Self::Value, A::Error> {
+				let mut count = 0u64;
+				while let Some((key, ..)) = map.next_entry::<TileKey, serde::de::IgnoredAny>()? {
+					count += match key {
+						TileKey::Single(..) => 1,
+						TileKey::Range(range) => (*range.end() - *range.start()) as u64 + 1,
+					};
+				}
+				Ok(TileCount(count))
+			}
+		}
+
+		deserializer.deserialize_map(CountVisitor)
+	}
+}
+
+/// Counts the tiles declared by the given tileset definition (without expanding ranged entries)
+///
+/// Returns `None` if the definition can't be parsed, leaving the error to be reported when
+/// parsing the full definition.
+fn count_tiles(bytes: &[u8]) -> Option<u64> {
+	ron_options()
+		.from_bytes::<TilesetDefTileCount>(bytes)
+		.ok()
+		.map(|def| def.tiles.0)
+}
+
+impl TilesetLoadLimits {
+	/// Errors if the given number of tiles exceeds `max_tiles`
+	pub(crate) fn check_tiles(&self, count: u64) -> Result<(), TilesetError> {
+		match self.max_tiles {
+			Some(max) if count > max as u64 => Err(TilesetError::TooManyTiles { count, max }),
+			_ => Ok(()),
+		}
+	}
+
+	/// Errors if the given size of the image at the given path exceeds `max_image_bytes`
+	pub(crate) fn check_image_bytes(&self, path: &Path, bytes: u64) -> Result<(), TilesetError> {
+		match self.max_image_bytes {
+			Some(max) if bytes > max as u64 => Err(TilesetError::ImageTooLarge {
+				path: path.to_path_buf(),
+				bytes,
+				max,
+			}),
+			_ => Ok(()),
+		}
+	}
+
+	/// Errors if the encoded image at the given path exceeds `max_image_bytes`, either by itself
+	/// or once decoded (as estimated from its header)
+	///
+	/// Returns the dimensions read from the image's header (if they could be read).
+	pub(crate) fn check_encoded_image(
+		&self,
+		path: &Path,
+		bytes: &[u8],
+	) -> Result<Option<UVec2>, TilesetError> {
+		self.check_image_bytes(path, bytes.len() as u64)?;
+		let found = header::image_dimensions(bytes);
+		if let Some((found, pixel_size)) = found.zip(header::decoded_pixel_size(bytes)) {
+			let pixels = found.x as u64 * found.y as u64;
+			self.check_image_bytes(path, pixels.saturating_mul(pixel_size))?;
+		}
+		Ok(found)
+	}
+
+	/// Checks whether any limit applies to the images of a tileset
+	fn limits_images(&self) -> bool {
+		self.max_image_bytes.is_some() || self.max_atlas_pixels.is_some()
+	}
+
+	/// Errors if the given number of atlas pixels exceeds `max_atlas_pixels`
+	fn check_atlas_pixels(&self, pixels: u64) -> Result<(), TilesetError> {
+		match self.max_atlas_pixels {
+			Some(max) if pixels > max => Err(TilesetError::AtlasTooLarge { pixels, max }),
+			_ => Ok(()),
+		}
+	}
+}
+
+impl<'de> Deserialize<'de> for TileEntries {
+	fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		struct EntriesVisitor;
+
+		impl<'de> serde::de::Visitor<'de> for EntriesVisitor {
+			type Value = TileEntries;
+
+			fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+				formatter.write_str("a map of group IDs to tile definition paths")
+			}
+
+			fn visit_map<A: serde::de::MapAccess<'de>>(
+				self,
+				mut map: A,
+			) -> Result<Self::Value, A::Error> {
+				let mut entries = Vec::with_capacity(map.size_hint().unwrap_or_default());
+				while let Some((key, path)) = map.next_entry::<TileKey, String>()? {
+					key.expand(&path, &mut entries)?;
+				}
+				Ok(TileEntries(entries))
+			}
+		}
+
+		deserializer.deserialize_map(EntriesVisitor)
+	}
+}
+
+/// The IDs of a tileset definition, deserialized without checking whether they fit their types
+#[derive(Deserialize)]
+struct TilesetDefIds {
+	#[serde(default)]
+	id: Option<UncheckedIds>,
+	#[serde(default)]
+	tiles: UncheckedTileKeys,
+	#[serde(default)]
+	categories: BTreeMap<String, Vec<UncheckedIds>>,
+	#[serde(default)]
+	default_tile: Option<UncheckedIds>,
+}
+
+/// The IDs contained within a single value (such as the bounds of a ranged tile key)
+struct UncheckedIds(Vec<i128>);
+
+/// The IDs contained within the keys of a tileset definition's `tiles` map
+#[derive(Default)]
+struct UncheckedTileKeys(Vec<i128>);
+
+impl<'de> Deserialize<'de> for UncheckedIds {
+	fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		struct IdsVisitor;
+
+		impl<'de> serde::de::Visitor<'de> for IdsVisitor {
+			type Value = UncheckedIds;
+
+			fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+				formatter.write_str("an ID or a range of IDs")
+			}
+
+			fn visit_u64<E: serde::de::Error>(self, value: u64) -> Result<Self::Value, E> {
+				Ok(UncheckedIds(vec![value as i128]))
+			}
+
+			fn visit_i64<E: serde::de::Error>(self, value: i64) -> Result<Self::Value, E> {
+				Ok(UncheckedIds(vec![value as i128]))
+			}
+
+			fn visit_str<E: serde::de::Error>(self, value: &str) -> Result<Self::Value, E> {
+				let bounds = value
+					.split("..")
+					.map(|bound| bound.trim_start_matches('=').trim().parse::<i128>())
+					.filter_map(Result::ok)
+					.collect();
+				Ok(UncheckedIds(bounds))
+			}
+		}
+
+		deserializer.deserialize_any(IdsVisitor)
+	}
+}
+
+impl<'de> Deserialize<'de> for UncheckedTileKeys {
+	fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		struct KeysVisitor;
+
+		impl<'de> serde::de::Visitor<'de> for KeysVisitor {
+			type Value = UncheckedTileKeys;
+
+			fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+				formatter.write_str("a map of group IDs to tile definition paths")
+			}
+
+			fn visit_map<A: serde::de::MapAccess<'de>>(
+				self,
+				mut map: A,
+			) -> Result<Self::Value, A::Error> {
+				let mut ids = Vec::new();
+				while let Some((key, ..)) =
+					map.next_entry::<UncheckedIds, serde::de::IgnoredAny>()?
+				{
+					ids.extend(key.0);
+				}
+				Ok(UncheckedTileKeys(ids))
+			}
+		}
+
+		deserializer.deserialize_map(KeysVisitor)
+	}
+}
+
+/// Errors if the given tileset definition contains an ID that doesn't fit its type (such as a
+/// negative group ID or a tileset ID above `255`)
+pub(crate) fn check_id_ranges(bytes: &[u8], path: &Path) -> Result<(), TilesetError> {
+	let def = match ron_options().from_bytes::<TilesetDefIds>(bytes) {
+		Ok(def) => def,
+		Err(..) => return Ok(()),
+	};
+
+	let tileset_id: fn(i128) -> bool = |id| TilesetId::try_from(id).is_ok();
+	let group_id: fn(i128) -> bool = |id| TileGroupId::try_from(id).is_ok();
+	let unwrap = |ids: Option<UncheckedIds>| ids.map(|ids| ids.0).unwrap_or_default();
+	let mut fields = vec![
+		(String::from("id"), unwrap(def.id), tileset_id),
+		(String::from("tiles"), def.tiles.0, group_id),
+		(String::from("default_tile"), unwrap(def.default_tile), group_id),
+	];
+	for (category, ids) in def.categories {
+		let ids = ids.into_iter().flat_map(|ids| ids.0).collect();
+		fields.push((format!("categories[{:?}]", category), ids, group_id));
+	}
+
+	for (field, ids, fits) in fields {
+		if let Some(value) = ids.into_iter().find(|id| !fits(*id)) {
+			return Err(TilesetError::InvalidId {
+				field,
+				value,
+				path: path.to_path_buf(),
+			});
+		}
+	}
+	Ok(())
+}
+
+/// Deserializes the `tiles` map of a [`TilesetDef`], expanding any ranges of group IDs
+fn deserialize_tiles<'de, D: serde::Deserializer<'de>>(
+	deserializer: D,
+) -> Result<BTreeMap<TileGroupId, String>, D::Error> {
+	let entries = TileEntries::deserialize(deserializer)?;
+	Ok(entries.0.into_iter().collect())
+}
+
+/// The placeholder in the path of a ranged tile entry that is replaced by each group ID
+const GROUP_ID_PLACEHOLDER: &str = "{}";
+
+/// A key in a tileset definition's `tiles` map
+///
+/// This is either a single group ID or a string range of group IDs (e.g. `"1..4"` or `"1..=3"`).
+enum TileKey {
+	Single(TileGroupId),
+	Range(RangeInclusive<TileGroupId>),
+}
+
+impl TileKey {
+	/// Expands this key, paired with the given path, into its individual entries
+	fn expand<E: serde::de::Error>(
+		self,
+		path: &str,
+		entries: &mut Vec<(TileGroupId, String)>,
+	) -> Result<(), E> {
+		match self {
+			Self::Single(group_id) => entries.push((group_id, path.to_string())),
+			Self::Range(range) => {
+				if !path.contains(GROUP_ID_PLACEHOLDER) {
+					return Err(E::custom(format!(
+						"expected the path of a ranged tile entry to contain a `{}` placeholder, \
+						 found {:?}",
+						GROUP_ID_PLACEHOLDER, path
+					)));
+				}
+				for group_id in range {
+					let tile_path = path.replace(GROUP_ID_PLACEHOLDER, &group_id.to_string());
+					entries.push((group_id, tile_path));
+				}
+			},
+		}
+		Ok(())
+	}
+}
+
+impl<'de> Deserialize<'de> for TileKey {
+	fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		struct KeyVisitor;
+
+		impl<'de> serde::de::Visitor<'de> for KeyVisitor {
+			type Value = TileKey;
+
+			fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+				formatter.write_str("a group ID or a range of group IDs (e.g. \"1..4\")")
+			}
+
+			fn visit_u64<E: serde::de::Error>(self, value: u64) -> Result<Self::Value, E> {
+				TileGroupId::try_from(value)
+					.map(TileKey::Single)
+					.map_err(|_| E::invalid_value(serde::de::Unexpected::Unsigned(value), &self))
+			}
+
+			fn visit_i64<E: serde::de::Error>(self, value: i64) -> Result<Self::Value, E> {
+				TileGroupId::try_from(value)
+					.map(TileKey::Single)
+					.map_err(|_| E::invalid_value(serde::de::Unexpected::Signed(value), &self))
+			}
+
+			fn visit_str<E: serde::de::Error>(self, value: &str) -> Result<Self::Value, E> {
+				let invalid = || E::invalid_value(serde::de::Unexpected::Str(value), &self);
+				let (start, end, inclusive) = match value.split_once("..=") {
+					Some((start, end)) => (start, end, true),
+					None => match value.split_once("..") {
+						Some((start, end)) => (start, end, false),
+						None => return Err(invalid()),
+					},
+				};
+
+				let start = start.trim().parse::<TileGroupId>().map_err(|_| invalid())?;
+				let end = end.trim().parse::<TileGroupId>().map_err(|_| invalid())?;
+				let end = if inclusive {
+					end
+				} else {
+					end.checked_sub(1).ok_or_else(invalid)?
+				};
+				if start > end {
+					return Err(invalid());
+				}
+				Ok(TileKey::Range(start..=end))
+			}
+		}
+
+		deserializer.deserialize_any(KeyVisitor)
+	}
+}
+
+/// Errors if the given tileset definition assigns the same group ID to multiple tiles
+fn check_duplicate_group_ids(bytes: &[u8], path: &Path) -> Result<(), TilesetError> {
+	match duplicate_group_ids(bytes).into_iter().next() {
+		Some(group_id) => Err(TilesetError::DuplicateGroupId {
+			group_id,
+			path: path.to_path_buf(),
+		}),
+		None => Ok(()),
+	}
+}
+
+/// Gets every group ID the given tileset definition assigns to multiple tiles
+///
+/// Each duplicated group ID is only returned once.
+pub(crate) fn duplicate_group_ids(bytes: &[u8]) -> Vec<TileGroupId> {
+	// The definition is expected to have already been parsed successfully
+	let def = match ron_options().from_bytes::<TilesetDefEntries>(bytes) {
+		Ok(def) => def,
+		Err(..) => return Vec::new(),
+	};
+
+	let mut seen = HashSet::with_capacity(def.tiles.0.len());
+	let mut duplicates = Vec::new();
+	for (group_id, _) in def.tiles.0 {
+		if !seen.insert(group_id) && !duplicates.contains(&group_id) {
+			duplicates.push(group_id);
+		}
+	}
+	duplicates
+}
+
+/// Gets the name to use for a tileset that doesn't define one, derived from its file name
+fn default_tileset_name(path: &Path) -> Option<String> {
+	path.file_stem()
+		.and_then(|stem| stem.to_str())
+		.map(String::from)
+}
+
+/// Records a warning for a tile that was skipped while loading a tileset
+fn skip_tile(warnings: &mut Vec<String>, group_id: TileGroupId, tile: &str, err: &str) {
+	let warning = format!("skipped tile {} ({}): {}", group_id, tile, err);
+	warn!("{}", warning);
+	warnings.push(warning);
+}
+
+/// Builds the given tiles into a [`RawTileset`] according to the tileset definition
+///
+/// The given [`LoadReport`] (such as the warnings for any tiles skipped while loading) is stored
+/// on the tileset.
+pub(crate) fn build_tiles<TStore: TextureStore>(
+	config: TilesetDef,
+	tile_handles: Vec<(TileGroupId, TileHandle)>,
+	report: LoadReport,
+	store: &mut TStore,
+) -> Result<RawTileset, TilesetError> {
+	let mut builder = TilesetBuilder::with_capacity(tile_handles.len());
+	builder
+		.power_of_two(config.power_of_two)
+		.gutter_color(config.gutter_color)
+		.grid_columns(config.grid_columns)
+		.sampler(config.sampler.sampler())
+		.tile_size(config.tile_size)
+		.lod_levels(config.lod_levels)
+		.premultiply_alpha(config.premultiply_alpha)
+		.trim(config.trim)
+		.default_tile(config.default_tile);
+	let mut warnings = report.warnings;
+	for (group_id, tile_handle) in tile_handles {
+		let name = tile_handle.name.clone();
+		match builder.add_tile(tile_handle, group_id, store) {
+			Ok(..) => {},
+			Err(err) if config.skip_invalid_tiles => {
+				skip_tile(&mut warnings, group_id, &name, &err.to_string())
+			},
+			Err(err) => return Err(err),
+		}
+	}
+	for (category, group_ids) in config.categories {
+		for group_id in group_ids {
+			builder.add_to_category(category.clone(), group_id);
+		}
+	}
+
+	let name = config
+		.name
+		.unwrap_or_else(|| Uuid::new_v4().hyphenated().to_string());
+	let mut tileset = builder.build(name, config.id, store)?;
+	let mut sources = report.sources;
+	sources.retain(|group_id, _| {
+		tileset.tiles.contains_key(group_id)
+			|| tileset.pages.iter().any(|page| page.tiles.contains_key(group_id))
+	});
+	tileset.load_warnings = warnings;
+	tileset.tile_sources = sources;
+	tileset.dependency_paths = report.dependencies;
+	Ok(tileset)
+}
+
+/// Get a `Vec` of ([`TileGroupId`], [`TileDef`]) tuples
+///
+/// If `skip_invalid` is true, tiles whose definition fails to load are skipped, with a warning
+/// for each being reported alongside the definitions. If `retain_sources` is true, the raw
+/// source of each definition is reported as well.
+async fn get_tile_defs<TSource: TilesetSource>(
+	source: &TSource,
 	tile_paths: &BTreeMap<TileGroupId, String>,
-) -> Result<Vec<(TileGroupId, TileHandle)>, TilesetError> {
-	let tile_defs = futures::future::join_all(
+	skip_invalid: bool,
+	retain_sources: bool,
+) -> Result<(Vec<(TileGroupId, TileDef)>, LoadReport), TilesetError> {
+	let results = futures::future::join_all(
 		tile_paths
 			.iter()
-			.map(|(.., tile_path)| load_tile(&loader.load_context, tile_path)),
+			.map(|(.., tile_path)| load_tile_with_source(source, tile_path, retain_sources)),
 	)
-	.await
-	.into_iter()
-	.filter_map(|tile_def| tile_def.ok())
-	.collect::<Vec<_>>();
+	.await;
 
-	let handles = load_tile_handles(tile_defs, loader);
+	let mut tile_defs = Vec::with_capacity(results.len());
+	let mut report = LoadReport::default();
+	for ((group_id, tile_path), result) in tile_paths.iter().zip(results) {
+		match result {
+			Ok((tile_def, tile_source)) => {
+				tile_defs.push((*group_id, tile_def));
+				if let Some(tile_source) = tile_source {
+					report.sources.insert(*group_id, tile_source);
+				}
+			},
+			// Escaping the asset root is never a recoverable error
+			Err(err @ TilesetError::PathEscape(..)) => return Err(err),
+			Err(err) if skip_invalid => {
+				skip_tile(&mut report.warnings, *group_id, tile_path, &err.to_string());
+			},
+			Err(err) => return Err(err),
+		}
+	}
 
-	Ok(tile_paths
-		.iter()
-		.map(|(id, ..)| *id)
-		.zip(handles.into_iter().map(|handle| handle))
-		.collect())
+	Ok((tile_defs, report))
 }
 
 /// Load the tile definition at the given path and return its corresponding [TileDef]
 ///
-/// The path is always relative to the tileset's configuration file path
-async fn load_tile(context: &LoadContext<'_>, path: &str) -> Result<TileDef, TilesetError> {
-	let path = if let Some(parent) = context.path().parent() {
-		parent.join(path)
+/// The path is always relative to the tileset's configuration file path.
+///
+/// Tile definitions may import another definition using the `base` field, which is resolved
+/// relative to the importing file. Any field not defined by the importing file is taken from
+/// its base (with conflicts resolving in favor of the importing file).
+pub(crate) async fn load_tile<TSource: TilesetSource>(
+	source: &TSource,
+	path: &str,
+) -> Result<TileDef, TilesetError> {
+	let (def, ..) = load_tile_with_source(source, path, false).await?;
+	Ok(def)
+}
+
+/// Load the tile definition at the given path (see [`load_tile`])
+///
+/// If `retain_source` is true, the raw source of the tile's own file is returned as well.
+async fn load_tile_with_source<TSource: TilesetSource>(
+	source: &TSource,
+	path: &str,
+	retain_source: bool,
+) -> Result<(TileDef, Option<String>), TilesetError> {
+	let root = resolve_relative(source.path(), path)?;
+
+	let mut path = root.clone();
+	let mut visited = Vec::new();
+	let mut fragments = Vec::new();
+	let mut root_source = None;
+	loop {
+		if visited.contains(&path) {
+			return Err(TilesetError::CircularTileImport(root));
+		}
+
+		let bytes = source.read_bytes(&path).await?;
+		if retain_source && visited.is_empty() {
+			root_source = String::from_utf8(bytes.clone()).ok();
+		}
+		let fragment = ron_options()
+			.from_bytes::<TileDefFragment>(&bytes)
+			.map_err(|err| map_definition_error(&bytes, &path, err))?;
+
+		let base = fragment
+			.base
+			.as_ref()
+			.map(|base| resolve_relative(&path, base))
+			.transpose()?;
+		visited.push(path);
+		fragments.push(fragment);
+
+		match base {
+			Some(base) => path = base,
+			None => break,
+		}
+	}
+
+	let def = fragments
+		.into_iter()
+		.rev()
+		.reduce(|base, fragment| fragment.merge(base))
+		.unwrap_or_default()
+		.into_def(root)?;
+	Ok((def, root_source))
+}
+
+/// Resolves the given path relative to the directory containing `file`
+///
+/// Errors if the resolved path escapes the asset root (see [`checked_path`]).
+fn resolve_relative(file: &Path, path: &str) -> Result<PathBuf, TilesetError> {
+	if let Some(parent) = file.parent() {
+		checked_path(&parent.join(path))
 	} else {
-		Path::new(path).to_path_buf()
+		checked_path(Path::new(path))
+	}
+}
+
+/// Lexically normalizes the given path, erroring if it escapes the asset root
+///
+/// Since tilesets may come from untrusted sources (such as mods), paths are not allowed to be
+/// absolute or to use `..` to reach outside of the asset root.
+pub(crate) fn checked_path(path: &Path) -> Result<PathBuf, TilesetError> {
+	let mut normalized = PathBuf::new();
+	for component in path.components() {
+		match component {
+			Component::Normal(part) => normalized.push(part),
+			Component::CurDir => {},
+			Component::ParentDir => {
+				if !normalized.pop() {
+					return Err(TilesetError::PathEscape(path.to_path_buf()));
+				}
+			},
+			Component::RootDir | Component::Prefix(..) => {
+				return Err(TilesetError::PathEscape(path.to_path_buf()));
+			},
+		}
+	}
+	Ok(normalized)
+}
+
+/// Defines [`TileDefFragment`] from the fields of [`TileDef`]
+///
+/// Every field of `TileDef` is listed exactly once as one of:
+///
+/// * `required`: Fields that must be given by the fragment or one of its bases
+/// * `optional`: Fields that are themselves an `Option` (which a fragment can override with
+///   `None`)
+/// * `defaulted`: Fields that fall back to the given default
+///
+/// Since `TileDef` is built with an exhaustive struct literal, adding a field to it without
+/// listing it here fails to compile.
+macro_rules! tile_def_fragment {
+	(
+		required { $($required: ident: $required_ty: ty),* $(,)? }
+		optional { $($optional: ident: $optional_ty: ty),* $(,)? }
+		defaulted { $($defaulted: ident: $defaulted_ty: ty = $default: expr),* $(,)? }
+	) => {
+		/// A possibly incomplete [`TileDef`], which may import its missing fields from a base
+		/// definition
+		///
+		/// Each field is `None` when it's left out, so it can be filled in by the base.
+		#[derive(Default, Deserialize)]
+		struct TileDefFragment {
+			/// The path to the base definition, relative to this file
+			#[serde(default, deserialize_with = "deserialize_some")]
+			base: Option<String>,
+			$(
+				#[serde(default, deserialize_with = "deserialize_some")]
+				$required: Option<$required_ty>,
+			)*
+			$(
+				#[serde(default, deserialize_with = "deserialize_some")]
+				$optional: Option<Option<$optional_ty>>,
+			)*
+			$(
+				#[serde(default, deserialize_with = "deserialize_some")]
+				$defaulted: Option<$defaulted_ty>,
+			)*
+		}
+
+		impl TileDefFragment {
+			/// Fills in any fields missing from this fragment with the ones from `base`
+			fn merge(self, base: Self) -> Self {
+				Self {
+					base: None,
+					$($required: self.$required.or(base.$required),)*
+					$($optional: self.$optional.or(base.$optional),)*
+					$($defaulted: self.$defaulted.or(base.$defaulted),)*
+				}
+			}
+
+			/// Converts this fragment into a full [`TileDef`], erroring if any required field is
+			/// missing
+			fn into_def(self, path: PathBuf) -> Result<TileDef, TilesetError> {
+				let missing = |field| TilesetError::MissingTileField {
+					field,
+					path: path.clone(),
+				};
+				Ok(TileDef {
+					$($required: self.$required.ok_or_else(|| missing(stringify!($required)))?,)*
+					$($optional: self.$optional.flatten(),)*
+					$($defaulted: self.$defaulted.unwrap_or_else(|| $default),)*
+				})
+			}
+		}
 	};
-	let bytes = context
-		.read_asset_bytes(&path)
-		.await
-		.map_err(|err| TilesetError::AssetIoError(err))?;
-	let def = ron::de::from_bytes::<TileDef>(&bytes)
-		.map_err(|err| TilesetError::InvalidDefinition(err))?;
-	Ok(def)
+}
+
+tile_def_fragment! {
+	required {
+		name: String,
+		tile: TileDefType,
+	}
+	optional {
+		transform: TileTransform,
+		color_key: [u8; 4],
+		sampler: SamplerPreset,
+	}
+	defaulted {
+		collision: TileCollision = TileCollision::default(),
+		tags: Vec<String> = Vec::new(),
+		z_offset: f32 = 0.0,
+		layer: i32 = 0,
+		pivot: Vec2 = DEFAULT_PIVOT,
+		aliases: Vec<String> = Vec::new(),
+	}
+}
+
+/// Deserializes a value as `Some`, allowing optional fields to be written like required ones
+/// (without needing `Some(...)` or the `implicit_some` extension)
+fn deserialize_some<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+	D: serde::Deserializer<'de>,
+	T: Deserialize<'de>,
+{
+	T::deserialize(deserializer).map(Some)
+}
+
+/// Gets the options used to parse every tileset and tile definition
+///
+/// The `implicit_some` extension is enabled by default so optional fields (such as a tileset's
+/// `name` or a tile's `transform`) can be written without wrapping them in `Some(...)`.
+pub(crate) fn ron_options() -> ron::Options {
+	ron::Options::default().with_default_extension(ron::extensions::Extensions::IMPLICIT_SOME)
+}
+
+/// Maps an error from parsing a tile definition into a [`TilesetError`]
+///
+/// If the definition uses a tile type whose cargo feature is disabled, a
+/// [`TilesetError::FeatureDisabled`] error is returned so users know which feature to enable
+/// (rather than getting a confusing deserialization error).
+fn map_definition_error(bytes: &[u8], path: &Path, err: SpannedError) -> TilesetError {
+	if let ron::Error::NoSuchEnumVariant { ref found, .. } = err.code {
+		if let Some(feature) = disabled_feature(found) {
+			/// A minimal tile definition used to retrieve the tile's name
+			#[derive(Deserialize)]
+			struct NamedTile {
+				name: String,
+			}
+
+			let tile = ron_options()
+				.from_bytes::<NamedTile>(bytes)
+				.map(|tile| tile.name)
+				.unwrap_or_else(|_| path.display().to_string());
+			return TilesetError::FeatureDisabled { feature, tile };
+		}
+	}
+
+	TilesetError::InvalidDefinition(err, path.to_path_buf())
+}
+
+/// Gets the name of the disabled cargo feature required by the given tile type (if any)
+#[allow(unused_variables)]
+fn disabled_feature(tile_type: &str) -> Option<&'static str> {
+	#[cfg(not(feature = "variants"))]
+	if tile_type == "Variant" {
+		return Some("variants");
+	}
+	#[cfg(not(feature = "auto-tile"))]
+	if tile_type == "Auto" {
+		return Some("auto-tile");
+	}
+	None
 }
 
 /// Load an image at the given path
-async fn load_image(
-	context: &LoadContext<'_>,
-	id: HandleId,
+///
+/// Errors if the image exceeds the `max_image_bytes` of the given limits, which is checked
+/// before the image is decoded where possible.
+pub(crate) async fn load_image<TSource: TilesetSource>(
+	source: &TSource,
 	path: PathBuf,
 	supported_compressed_formats: CompressedImageFormats,
-) -> Result<(HandleId, Image), TilesetError> {
-	let bytes = context
-		.read_asset_bytes(path.clone())
-		.await
-		.map_err(|err| TilesetError::AssetIoError(err))?;
-	let path = path.as_path();
-	let ext = path.extension().unwrap().to_str().unwrap();
-	let img = Image::from_buffer(
-		&bytes,
-		ImageType::Extension(ext),
-		supported_compressed_formats,
-		true,
-	)
-	.map_err(|err| TilesetError::ImageError(err))?;
-	Ok((id, img))
+	limits: &TilesetLoadLimits,
+) -> Result<Image, TilesetError> {
+	let bytes = read_image(source, &path).await?;
+	limits.check_encoded_image(&path, &bytes)?;
+	let image = decode_image(&path, &bytes, supported_compressed_formats)?;
+	limits.check_image_bytes(&path, image.data.len() as u64)?;
+	Ok(image)
+}
+
+/// Read the (still encoded) bytes of the image at the given path
+async fn read_image<TSource: TilesetSource>(
+	source: &TSource,
+	path: &Path,
+) -> Result<Vec<u8>, TilesetError> {
+	let path = checked_path(path)?;
+	source.read_bytes(&path).await
+}
+
+/// Decode the given bytes of the image at the given path
+fn decode_image(
+	path: &Path,
+	bytes: &[u8],
+	supported_compressed_formats: CompressedImageFormats,
+) -> Result<Image, TilesetError> {
+	let image_type = image_type(path, bytes)
+		.ok_or_else(|| TilesetError::UnknownImageFormat(path.to_path_buf()))?;
+	let img = Image::from_buffer(bytes, image_type, supported_compressed_formats, true)
+		.map_err(TilesetError::ImageError)?;
+	pixels::into_rgba8(img)
+}
+
+/// Determines the format of an image from its extension, falling back to its contents
+///
+/// Files loaded from a custom `AssetIo` (such as a packed archive) aren't guaranteed to keep
+/// their extension, so the first few bytes are checked for a known signature instead.
+fn image_type<'a>(path: &'a Path, bytes: &[u8]) -> Option<ImageType<'a>> {
+	if let Some(ext) = path.extension().and_then(|ext| ext.to_str()) {
+		return Some(ImageType::Extension(ext));
+	}
+
+	const SIGNATURES: &[(&[u8], &str)] = &[
+		(&[0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A], "image/png"),
+		(&[0xFF, 0xD8, 0xFF], "image/jpeg"),
+		(b"BM", "image/bmp"),
+		(b"DDS ", "image/vnd-ms.dds"),
+		(&[0xAB, b'K', b'T', b'X', b' ', b'2', b'0', 0xBB], "image/ktx2"),
+	];
+	SIGNATURES
+		.iter()
+		.find(|(signature, _)| bytes.starts_with(signature))
+		.map(|(_, mime_type)| ImageType::MimeType(mime_type))
+}
+
+#[cfg(test)]
+mod tests {
+	use bevy::asset::{AddAsset, AssetPlugin, AssetServer, Assets, LoadState};
+	use bevy::prelude::{App, MinimalPlugins, TextureAtlas, UVec2};
+
+	use super::*;
+	use crate::prelude::{MultiSourceAssetIo, TilesetPlugin};
+	use crate::test_utils::{MemoryAssetIo, PIXEL_PNG};
+
+	#[test]
+	fn should_create_deterministic_atlas_labels() {
+		let path = Path::new("tilesets/my_tileset.ron");
+		assert_eq!(atlas_label(path, 123, 0), atlas_label(path, 123, 0));
+		assert_ne!(atlas_label(path, 123, 0), atlas_label(path, 123, 1));
+		assert_ne!(atlas_label(path, 123, 0), atlas_label(path, 456, 0));
+	}
+
+	#[test]
+	fn should_resolve_texture_paths() {
+		let root = Path::new("textures");
+		assert_eq!(
+			PathBuf::from("textures/tiles/dirt.png"),
+			resolve_texture_path(Some(root), None, Path::new("tiles/dirt.png"))
+		);
+		assert_eq!(
+			PathBuf::from("shared/dirt.png"),
+			resolve_texture_path(Some(root), None, Path::new("/shared/dirt.png"))
+		);
+		assert_eq!(
+			PathBuf::from("tiles/dirt.png"),
+			resolve_texture_path(None, None, Path::new("tiles/dirt.png"))
+		);
+		assert_eq!(
+			PathBuf::from("remote://textures/tiles/dirt.png"),
+			resolve_texture_path(Some(root), Some("remote"), Path::new("tiles/dirt.png"))
+		);
+		assert_eq!(
+			PathBuf::from("mods://dirt.png"),
+			resolve_texture_path(Some(root), Some("remote"), Path::new("mods://dirt.png"))
+		);
+	}
+
+	#[test]
+	fn should_merge_tile_fragments() {
+		let base = ron::de::from_str::<TileDefFragment>(
+			r#"(name: "Base", tile: Standard("base.png"), transform: Some(FlipX))"#,
+		)
+		.unwrap();
+		let fragment = ron::de::from_str::<TileDefFragment>(
+			r#"(base: "base.ron", tile: Standard("override.png"))"#,
+		)
+		.unwrap();
+		assert_eq!(Some(String::from("base.ron")), fragment.base);
+
+		let def = fragment.merge(base).into_def(PathBuf::from("tile.ron")).unwrap();
+		assert_eq!("Base", def.name);
+		assert!(matches!(def.tile, TileDefType::Standard(ref path) if path == "override.png"));
+		assert_eq!(Some(TileTransform::FlipX), def.transform);
+
+		// Optional fields can be cleared by a fragment rather than always being inherited
+		let base = ron::de::from_str::<TileDefFragment>(
+			r#"(name: "Base", tile: Standard("base.png"), transform: Some(FlipX), layer: 2)"#,
+		)
+		.unwrap();
+		let fragment = r#"(base: "base.ron", transform: None)"#;
+		let fragment = ron::de::from_str::<TileDefFragment>(fragment).unwrap();
+		let def = fragment.merge(base).into_def(PathBuf::from("tile.ron")).unwrap();
+		assert_eq!(None, def.transform);
+		assert_eq!(2, def.layer);
+	}
+
+	#[test]
+	fn should_error_on_missing_tile_field() {
+		let fragment = ron::de::from_str::<TileDefFragment>(r#"(name: "Incomplete")"#).unwrap();
+		let result = fragment.into_def(PathBuf::from("tile.ron"));
+		assert!(matches!(
+			result,
+			Err(TilesetError::MissingTileField { field: "tile", .. })
+		));
+	}
+
+	#[test]
+	fn should_detect_image_type() {
+		let png = [0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A, 0x00];
+		assert!(matches!(
+			image_type(Path::new("tiles/grass.png"), &[]),
+			Some(ImageType::Extension("png"))
+		));
+		assert!(matches!(
+			image_type(Path::new("archive/3f2a"), &png),
+			Some(ImageType::MimeType("image/png"))
+		));
+		assert!(image_type(Path::new("archive/3f2a"), b"unknown").is_none());
+	}
+
+	#[test]
+	fn should_error_on_duplicate_group_ids() {
+		let path = Path::new("tilesets/duplicates.ron");
+		let def = br#"(id: 0, tiles: {0: "grass.ron", 1: "dirt.ron", 0: "water.ron"})"#;
+		assert!(matches!(
+			check_duplicate_group_ids(def, path),
+			Err(TilesetError::DuplicateGroupId { group_id: 0, .. })
+		));
+
+		let def = br#"(id: 0, tiles: {0: "grass.ron", 1: "dirt.ron"})"#;
+		assert!(check_duplicate_group_ids(def, path).is_ok());
+	}
+
+	#[test]
+	fn should_error_on_out_of_range_ids() {
+		let path = Path::new("tilesets/ids.ron");
+		let check = |def: &str| {
+			assert!(ron_options().from_str::<TilesetDef>(def).is_err());
+			match check_id_ranges(def.as_bytes(), path) {
+				Err(TilesetError::InvalidId { field, value, .. }) => Some((field, value)),
+				_ => None,
+			}
+		};
+
+		let invalid = |field: &str, value: i128| Some((String::from(field), value));
+		assert_eq!(invalid("id", 256), check(r#"(id: 256, tiles: {0: "grass.ron"})"#));
+		assert_eq!(invalid("tiles", -1), check(r#"(id: 0, tiles: {-1: "grass.ron"})"#));
+		assert_eq!(
+			invalid("tiles", 4_294_967_296),
+			check(r#"(id: 0, tiles: {"0..=4294967296": "grass_{}.ron"})"#)
+		);
+		assert_eq!(
+			invalid("categories[\"Ground\"]", 4_294_967_296),
+			check(r#"(id: 0, tiles: {0: "grass.ron"}, categories: {"Ground": [0, 4294967296]})"#)
+		);
+		assert_eq!(
+			invalid("default_tile", -3),
+			check(r#"(id: 0, tiles: {0: "grass.ron"}, default_tile: Some(-3))"#)
+		);
+
+		let def = br#"(id: 255, tiles: {0: "grass.ron", "1..4": "brick_{}.ron"})"#;
+		assert!(check_id_ranges(def, path).is_ok());
+	}
+
+	#[test]
+	fn should_expand_group_id_ranges() {
+		let config = ron_options()
+			.from_str::<TilesetDef>(r#"(id: 0, tiles: {0: "grass.ron", "1..4": "brick_{}.ron"})"#)
+			.unwrap();
+		let tiles = config.tiles.into_iter().collect::<Vec<_>>();
+		assert_eq!(
+			vec![
+				(0, String::from("grass.ron")),
+				(1, String::from("brick_1.ron")),
+				(2, String::from("brick_2.ron")),
+				(3, String::from("brick_3.ron")),
+			],
+			tiles
+		);
+
+		// Ranges can overlap other entries
+		let def = br#"(id: 0, tiles: {2: "grass.ron", "1..=3": "brick_{}.ron"})"#;
+		assert_eq!(vec![2], duplicate_group_ids(def));
+
+		// Ranges must be non-empty and have a placeholder
+		for def in [
+			r#"(id: 0, tiles: {"3..1": "brick_{}.ron"})"#,
+			r#"(id: 0, tiles: {"1..3": "brick.ron"})"#,
+		] {
+			assert!(ron_options().from_str::<TilesetDef>(def).is_err());
+		}
+	}
+
+	#[test]
+	fn should_name_tileset_after_file() {
+		assert_eq!(
+			Some(String::from("forest")),
+			default_tileset_name(Path::new("tilesets/forest.ron"))
+		);
+		assert_eq!(None, default_tileset_name(Path::new("")));
+	}
+
+	#[test]
+	fn should_parse_implicit_some() {
+		let config = ron_options()
+			.from_str::<TilesetDef>(
+				r#"(name: "Implicit", id: 0, tiles: {}, texture_root: "textures")"#,
+			)
+			.unwrap();
+		assert_eq!(Some(String::from("Implicit")), config.name);
+		assert_eq!(Some(PathBuf::from("textures")), config.texture_root);
+
+		let fragment = ron_options()
+			.from_str::<TileDefFragment>(
+				r#"(name: "Flipped", tile: Standard("tile.png"), transform: FlipX)"#,
+			)
+			.unwrap();
+		assert_eq!(Some(Some(TileTransform::FlipX)), fragment.transform);
+	}
+
+	#[test]
+	fn should_reject_escaping_paths() {
+		let file = Path::new("tilesets/my_tileset.ron");
+		assert_eq!(
+			PathBuf::from("tiles/dirt.ron"),
+			resolve_relative(file, "../tiles/./dirt.ron").unwrap()
+		);
+		assert!(matches!(
+			resolve_relative(file, "../../etc/passwd"),
+			Err(TilesetError::PathEscape(..))
+		));
+		assert!(matches!(
+			resolve_relative(file, "/etc/passwd"),
+			Err(TilesetError::PathEscape(..))
+		));
+		assert!(matches!(
+			checked_path(Path::new("textures/../../secret.png")),
+			Err(TilesetError::PathEscape(..))
+		));
+	}
+
+	#[test]
+	fn should_error_on_truncated_files() {
+		let source = ResolverSource {
+			path: PathBuf::from("tilesets/forest.ron"),
+			resolver: |path: &Path| match path.to_str()? {
+				"tilesets/dirt.ron" => Some(br#"(name: "Dirt", tile: Stand"#.to_vec()),
+				_ => None,
+			},
+		};
+		let load = |def: &[u8]| {
+			let formats = CompressedImageFormats::all();
+			let limits = TilesetLoadLimits::default();
+			futures::executor::block_on(load_tiles(def, &source, formats, None, &limits))
+		};
+
+		let result = load(br#"(id: 1, tiles: {0: "dirt.ron""#);
+		assert!(matches!(
+			result,
+			Err(TilesetError::InvalidTilesetDef(_, ref path)) if path == source.path()
+		));
+
+		let result = load(br#"(id: 1, tiles: {0: "dirt.ron"})"#);
+		let tile_path = Path::new("tilesets/dirt.ron");
+		assert!(matches!(
+			result,
+			Err(TilesetError::InvalidDefinition(_, ref path)) if path == tile_path
+		));
+
+		let result = load(br#"(id: 1, tiles: {0: "missing.ron"})"#);
+		assert!(matches!(
+			result,
+			Err(TilesetError::AssetIoError(AssetIoError::NotFound(..)))
+		));
+
+		assert!(matches!(
+			BakedTileset::from_bytes(&[1, 2, 3]),
+			Err(TilesetError::BakeError(..))
+		));
+	}
+
+	#[test]
+	fn should_enforce_load_limits() {
+		let reads = Mutex::new(Vec::new());
+		let tile = br#"(name: "Huge", tile: Standard("huge.png"))"#;
+		let source = ResolverSource {
+			path: PathBuf::from("tilesets/mod.ron"),
+			resolver: |path: &Path| {
+				reads.lock().unwrap().push(path.to_path_buf());
+				match path.to_str()? {
+					"tilesets/huge.ron" => Some(tile.to_vec()),
+					// Only the header of a 65536x65536 PNG, which is never decoded
+					"huge.png" => {
+						let mut bytes = vec![0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A];
+						bytes.extend([0, 0, 0, 13, b'I', b'H', b'D', b'R', 0, 1, 0, 0, 0, 1, 0, 0]);
+						Some(bytes)
+					},
+					_ => None,
+				}
+			},
+		};
+		let load = |def: &[u8], limits: TilesetLoadLimits| {
+			let formats = CompressedImageFormats::all();
+			futures::executor::block_on(load_tiles(def, &source, formats, None, &limits))
+		};
+
+		let limits = TilesetLoadLimits {
+			max_tiles: Some(10),
+			..Default::default()
+		};
+		let def = br#"(id: 1, tiles: {0: "huge.ron", "1..=4294967295": "{}.ron"})"#;
+		let result = load(def, limits);
+		assert!(matches!(
+			result,
+			Err(TilesetError::TooManyTiles {
+				count: 4294967296,
+				max: 10
+			})
+		));
+		assert!(reads.lock().unwrap().is_empty());
+
+		let limits = TilesetLoadLimits {
+			max_image_bytes: Some(1024 * 1024),
+			..Default::default()
+		};
+		let result = load(br#"(id: 1, tiles: {0: "huge.ron"})"#, limits);
+		assert!(matches!(
+			result,
+			Err(TilesetError::ImageTooLarge { ref path, max: 1048576, .. })
+				if path == Path::new("huge.png")
+		));
+
+		let limits = TilesetLoadLimits {
+			max_atlas_pixels: Some(4096),
+			..Default::default()
+		};
+		let result = load(br#"(id: 1, tiles: {0: "huge.ron"})"#, limits);
+		assert!(matches!(result, Err(TilesetError::AtlasTooLarge { max: 4096, .. })));
+	}
+
+	#[test]
+	fn should_load_through_custom_asset_io() {
+		// Apart from the tileset itself, packed files don't keep their extensions
+		let files = HashMap::from([
+			(
+				PathBuf::from("tilesets/packed.ron"),
+				br#"(id: 0, tiles: {0: "3f2a"})"#.to_vec(),
+			),
+			(
+				PathBuf::from("tilesets/3f2a"),
+				br#"(name: "Grass", tile: Standard("textures/9c1e"))"#.to_vec(),
+			),
+			(PathBuf::from("textures/9c1e"), PIXEL_PNG.to_vec()),
+		]);
+
+		let mut app = App::new();
+		app.insert_resource(AssetServer::new(MemoryAssetIo(files)))
+			.add_plugins((MinimalPlugins, AssetPlugin::default()))
+			.add_asset::<Image>()
+			.add_asset::<TextureAtlas>()
+			.add_plugins(TilesetPlugin::default());
+
+		let handle: Handle<Tileset> = app
+			.world
+			.resource::<AssetServer>()
+			.load("tilesets/packed.ron");
+		for _ in 0..100 {
+			app.update();
+			match app.world.resource::<AssetServer>().get_load_state(&handle) {
+				LoadState::NotLoaded | LoadState::Loading => {
+					std::thread::sleep(std::time::Duration::from_millis(10))
+				},
+				_ => break,
+			}
+		}
+		// Loaded assets are only added to their storage on the following update
+		app.update();
+
+		assert_eq!(
+			LoadState::Loaded,
+			app.world.resource::<AssetServer>().get_load_state(&handle)
+		);
+		let tilesets = app.world.resource::<Assets<Tileset>>();
+		let tileset = tilesets.get(&handle).unwrap();
+		assert_eq!(Some(&0), tileset.get_tile_group_id("Grass"));
+		assert_eq!(UVec2::ONE, tileset.tile_size_of("Grass").unwrap());
+		let images = app.world.resource::<Assets<Image>>();
+		assert!(images.get(tileset.texture()).is_some());
+	}
+
+	#[test]
+	fn should_load_textures_from_other_sources() {
+		let local = HashMap::from([
+			(
+				PathBuf::from("tilesets/sourced.ron"),
+				br#"(
+					id: 0,
+					tiles: {0: "grass.ron", 1: "dirt.ron"},
+					texture_root: "tiles",
+					texture_source: "remote",
+				)"#
+				.to_vec(),
+			),
+			(
+				PathBuf::from("tilesets/grass.ron"),
+				br#"(name: "Grass", tile: Standard("grass.png"))"#.to_vec(),
+			),
+			(
+				PathBuf::from("tilesets/dirt.ron"),
+				br#"(name: "Dirt", tile: Standard("mods://dirt.png"))"#.to_vec(),
+			),
+		]);
+		// Neither texture exists in the default source
+		let remote = HashMap::from([(PathBuf::from("tiles/grass.png"), PIXEL_PNG.to_vec())]);
+		let mods = HashMap::from([(PathBuf::from("dirt.png"), PIXEL_PNG.to_vec())]);
+		let asset_io = MultiSourceAssetIo::new(MemoryAssetIo(local))
+			.with_source("remote", MemoryAssetIo(remote))
+			.with_source("mods", MemoryAssetIo(mods));
+
+		let mut app = App::new();
+		app.insert_resource(AssetServer::new(asset_io))
+			.add_plugins((MinimalPlugins, AssetPlugin::default()))
+			.add_asset::<Image>()
+			.add_asset::<TextureAtlas>()
+			.add_plugins(TilesetPlugin::default());
+
+		let handle: Handle<Tileset> = app
+			.world
+			.resource::<AssetServer>()
+			.load("tilesets/sourced.ron");
+		for _ in 0..100 {
+			app.update();
+			match app.world.resource::<AssetServer>().get_load_state(&handle) {
+				LoadState::NotLoaded | LoadState::Loading => {
+					std::thread::sleep(std::time::Duration::from_millis(10))
+				},
+				_ => break,
+			}
+		}
+		// Loaded assets are only added to their storage on the following update
+		app.update();
+
+		assert_eq!(
+			LoadState::Loaded,
+			app.world.resource::<AssetServer>().get_load_state(&handle)
+		);
+		let tilesets = app.world.resource::<Assets<Tileset>>();
+		let tileset = tilesets.get(&handle).unwrap();
+		assert_eq!(Some(&0), tileset.get_tile_group_id("Grass"));
+		assert_eq!(Some(&1), tileset.get_tile_group_id("Dirt"));
+		assert!(tileset.load_warnings().is_empty());
+	}
 }
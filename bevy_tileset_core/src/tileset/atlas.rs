@@ -0,0 +1,211 @@
+//! Post-processing applied to the generated atlas texture
+
+use std::collections::HashMap;
+
+use bevy::asset::HandleId;
+use bevy::prelude::{Assets, Handle, Image};
+use bevy::render::texture::ImageSampler;
+use bevy_tile_atlas::TextureStore;
+
+use crate::prelude::{Tileset, TilesetError};
+use crate::tileset::pixels;
+
+/// Options used to post-process the atlas texture before it is stored
+#[derive(Debug, Copy, Clone, Default)]
+pub(crate) struct AtlasOptions {
+	/// Whether the atlas should be padded to power-of-two dimensions
+	pub power_of_two: bool,
+	/// The minimum width of the atlas (in pixels)
+	pub min_width: u32,
+	/// The color of every atlas pixel not covered by a tile
+	pub gutter_color: [u8; 4],
+}
+
+/// A [`TextureStore`] wrapper that applies the [`AtlasOptions`] to the atlas texture
+///
+/// The atlas builder adds its generated texture to the store it's given, so wrapping
+/// the user's store allows us to modify the texture before anyone else sees it.
+///
+/// It also serves any tile textures the [`TilesetBuilder`](crate::prelude::TilesetBuilder)
+/// processed itself (such as transformed tiles), which don't exist in the user's store.
+pub(crate) struct AtlasTextureStore<'a, TStore: TextureStore> {
+	pub store: &'a mut TStore,
+	pub options: AtlasOptions,
+	pub processed: &'a HashMap<HandleId, Image>,
+	/// The sampler to give the atlas texture
+	pub sampler: &'a ImageSampler,
+}
+
+impl AtlasOptions {
+	/// Gets the final dimensions of an atlas with the given content dimensions
+	///
+	/// Returns `None` if the final dimensions can't be represented.
+	pub fn final_size(&self, width: u32, height: u32) -> Option<(u32, u32)> {
+		let width = width.max(self.min_width);
+		if self.power_of_two {
+			Some((width.checked_next_power_of_two()?, height.checked_next_power_of_two()?))
+		} else {
+			Some((width, height))
+		}
+	}
+}
+
+impl<'a, TStore: TextureStore> TextureStore for AtlasTextureStore<'a, TStore> {
+	fn add(&mut self, asset: Image) -> Handle<Image> {
+		let (width, height) = pixels::dimensions(&asset);
+		// Oversized atlases are reported by the builder once the atlas is finished
+		let (final_width, final_height) = self
+			.options
+			.final_size(width, height)
+			.unwrap_or((width, height));
+		let mut asset = if (final_width, final_height) != (width, height) {
+			pixels::pad(&asset, final_width, final_height, [0; 4])
+		} else {
+			asset
+		};
+		asset.sampler_descriptor = self.sampler.clone();
+		self.store.add(asset)
+	}
+
+	fn get<H: Into<HandleId>>(&self, handle: H) -> Option<&Image> {
+		let id = handle.into();
+		self.processed.get(&id).or_else(|| self.store.get(id))
+	}
+}
+
+impl Tileset {
+	/// Replaces the sampler of the atlas texture (and its LOD textures) in place
+	///
+	/// This allows the filtering to be changed at runtime (e.g. when toggling a "smooth scaling"
+	/// option) without rebuilding the tileset. Any additional atlas pages keep their own sampler.
+	///
+	/// # Arguments
+	///
+	/// * `images`: The `Image` assets containing this tileset's atlas texture
+	/// * `sampler`: The new sampler
+	///
+	/// returns: Result<(), TilesetError>
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use bevy::prelude::*;
+	/// # use bevy::render::texture::ImageSampler;
+	/// # use bevy_tileset_core::prelude::*;
+	///
+	/// fn set_smooth_scaling(
+	/// 	tileset: &Tileset,
+	/// 	images: &mut Assets<Image>,
+	/// 	smooth: bool,
+	/// ) -> Result<(), TilesetError> {
+	/// 	let sampler = if smooth {
+	/// 		ImageSampler::linear()
+	/// 	} else {
+	/// 		ImageSampler::nearest()
+	/// 	};
+	/// 	tileset.set_atlas_sampler(images, sampler)
+	/// }
+	/// ```
+	pub fn set_atlas_sampler(
+		&self,
+		images: &mut Assets<Image>,
+		sampler: ImageSampler,
+	) -> Result<(), TilesetError> {
+		let textures = std::iter::once(&self.texture).chain(&self.lod_textures);
+		if !textures.clone().all(|texture| images.contains(texture)) {
+			return Err(TilesetError::InvalidData {
+				expected: String::from("A loaded atlas texture"),
+				found: String::from("Missing atlas texture"),
+			});
+		}
+
+		for texture in textures {
+			if let Some(image) = images.get_mut(texture) {
+				image.sampler_descriptor = sampler.clone();
+			}
+		}
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use bevy::asset::{AddAsset, AssetPlugin, Assets};
+	use bevy::prelude::{App, Image, MinimalPlugins, TextureAtlas};
+	use bevy::render::render_resource::FilterMode;
+	use bevy::render::texture::ImageSampler;
+	use bevy_tile_atlas::TextureStore;
+	use bevy_tileset_tiles::prelude::TileHandle;
+
+	use super::AtlasOptions;
+	use crate::prelude::TilesetBuilder;
+	use crate::test_utils::{solid_image, TestTextureStore};
+
+	#[test]
+	fn should_not_overflow_power_of_two() {
+		let options = AtlasOptions {
+			power_of_two: true,
+			..Default::default()
+		};
+		assert_eq!(Some((64, 32)), options.final_size(48, 17));
+		assert_eq!(None, options.final_size(u32::MAX / 2 + 2, 1));
+	}
+
+	#[test]
+	fn should_pad_to_min_width() {
+		let options = AtlasOptions {
+			min_width: 64,
+			..Default::default()
+		};
+		assert_eq!(Some((64, 16)), options.final_size(32, 16));
+		assert_eq!(Some((96, 16)), options.final_size(96, 16));
+	}
+
+	#[test]
+	fn should_set_atlas_sampler() {
+		let mut app = App::new();
+		app.add_plugins((MinimalPlugins, AssetPlugin::default()))
+			.add_asset::<Image>()
+			.add_asset::<TextureAtlas>();
+
+		let mut store = TestTextureStore::default();
+		let mut builder = TilesetBuilder::default();
+		builder.lod_levels(2);
+		let handle = store.add(solid_image(16, 16, [255; 4]));
+		builder
+			.add_tile(TileHandle::new_standard("Grass", handle), 0, &store)
+			.unwrap();
+		let raw = builder.build("Sampled", 0, &mut store).unwrap();
+		let tileset = raw.into_asset(&mut app.world.resource_mut::<Assets<TextureAtlas>>());
+
+		let mut images = app.world.resource_mut::<Assets<Image>>();
+		for (id, image) in store.images.drain() {
+			images.set_untracked(id, image);
+		}
+
+		let mag_filter = |images: &Assets<Image>, level: usize| {
+			let texture = images.get(tileset.atlas_for_lod(level).unwrap()).unwrap();
+			match &texture.sampler_descriptor {
+				ImageSampler::Descriptor(descriptor) => Some(descriptor.mag_filter),
+				ImageSampler::Default => None,
+			}
+		};
+
+		tileset
+			.set_atlas_sampler(&mut images, ImageSampler::nearest())
+			.unwrap();
+		assert_eq!(Some(FilterMode::Nearest), mag_filter(&images, 0));
+		assert_eq!(Some(FilterMode::Nearest), mag_filter(&images, 1));
+
+		tileset
+			.set_atlas_sampler(&mut images, ImageSampler::linear())
+			.unwrap();
+		assert_eq!(Some(FilterMode::Linear), mag_filter(&images, 0));
+		assert_eq!(Some(FilterMode::Linear), mag_filter(&images, 1));
+
+		images.remove(tileset.texture());
+		assert!(tileset
+			.set_atlas_sampler(&mut images, ImageSampler::nearest())
+			.is_err());
+	}
+}
@@ -0,0 +1,597 @@
+//! Support for baking fully built tilesets so they can be loaded without being rebuilt
+//!
+//! Loading a tileset from its RON definition requires reading every tile definition, decoding
+//! every texture, and packing them into an atlas. For large tilesets this can add up, so a build
+//! step can instead [bake](RawTileset::bake) the result into a `.tileset.bin` file, which the
+//! [`TilesetAssetLoader`](super::TilesetAssetLoader) loads directly.
+//!
+//! Baked files are not guaranteed to be compatible across crate versions or feature sets
+//! (e.g. baking with the `auto-tile` feature and loading without it), so they should be treated
+//! as build artifacts and regenerated alongside the game.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use bevy::prelude::{Assets, Handle, Image, Rect, TextureAtlas, UVec2, Vec2};
+use bevy::render::render_resource::{
+	AddressMode, CompareFunction, Extent3d, FilterMode, SamplerBorderColor, SamplerDescriptor,
+	TextureDimension, TextureFormat,
+};
+use bevy::render::texture::ImageSampler;
+use bevy_tile_atlas::TextureStore;
+use serde::{Deserialize, Serialize};
+
+use crate::prelude::{RawTileset, TileGroupId, TileId, TileTrim, Tileset, TilesetError, TilesetId};
+use crate::tileset::pixels;
+use bevy_tileset_tiles::prelude::TileData;
+
+/// The file extension used by baked tilesets
+pub const BAKED_TILESET_EXTENSION: &str = "tileset.bin";
+
+/// The current version of the baked format
+///
+/// This should be incremented whenever the layout of [`BakedTileset`] changes.
+const BAKED_VERSION: u32 = 14;
+
+/// A fully built tileset, including its packed atlas texture
+#[derive(Deserialize, Serialize)]
+pub(crate) struct BakedTileset {
+	version: u32,
+	id: TilesetId,
+	name: String,
+	tiles: HashMap<TileGroupId, TileData>,
+	size: Vec2,
+	tile_size: Vec2,
+	tile_ids: HashMap<String, TileGroupId>,
+	tile_names: HashMap<TileGroupId, String>,
+	tile_indices: HashMap<usize, TileId>,
+	tile_colors: HashMap<TileGroupId, [u8; 4]>,
+	tile_sizes: HashMap<TileGroupId, UVec2>,
+	categories: BTreeMap<String, Vec<TileGroupId>>,
+	tile_trims: HashMap<usize, TileTrim>,
+	default_tile: Option<TileGroupId>,
+	grid_columns: Option<u32>,
+	/// The texture rects of the atlas
+	rects: Vec<Rect>,
+	/// The atlas texture
+	texture: BakedTexture,
+	/// The LOD textures of the atlas
+	lod_textures: Vec<BakedTexture>,
+	/// The additional atlas pages
+	pages: Vec<BakedTileset>,
+}
+
+/// The raw pixel data of an atlas texture
+#[derive(Deserialize, Serialize)]
+struct BakedTexture {
+	width: u32,
+	height: u32,
+	format: BakedFormat,
+	sampler: BakedSampler,
+	data: Vec<u8>,
+}
+
+/// The texture formats an atlas texture can be baked in
+#[derive(Deserialize, Serialize, Debug, Clone, Copy)]
+enum BakedFormat {
+	Rgba8Unorm,
+	Rgba8UnormSrgb,
+	Bgra8Unorm,
+	Bgra8UnormSrgb,
+}
+
+impl TryFrom<TextureFormat> for BakedFormat {
+	type Error = TilesetError;
+
+	fn try_from(format: TextureFormat) -> Result<Self, Self::Error> {
+		match format {
+			TextureFormat::Rgba8Unorm => Ok(Self::Rgba8Unorm),
+			TextureFormat::Rgba8UnormSrgb => Ok(Self::Rgba8UnormSrgb),
+			TextureFormat::Bgra8Unorm => Ok(Self::Bgra8Unorm),
+			TextureFormat::Bgra8UnormSrgb => Ok(Self::Bgra8UnormSrgb),
+			format => Err(TilesetError::InvalidData {
+				expected: String::from("An 8-bit RGBA or BGRA atlas texture"),
+				found: format!("{:?}", format),
+			}),
+		}
+	}
+}
+
+impl From<BakedFormat> for TextureFormat {
+	fn from(format: BakedFormat) -> Self {
+		match format {
+			BakedFormat::Rgba8Unorm => Self::Rgba8Unorm,
+			BakedFormat::Rgba8UnormSrgb => Self::Rgba8UnormSrgb,
+			BakedFormat::Bgra8Unorm => Self::Bgra8Unorm,
+			BakedFormat::Bgra8UnormSrgb => Self::Bgra8UnormSrgb,
+		}
+	}
+}
+
+/// Defines a serializable copy of a `wgpu` enum, along with conversions to and from it
+macro_rules! baked_enum {
+	($name: ident, $remote: ident, [$($variant: ident),+ $(,)?]) => {
+		#[derive(Deserialize, Serialize, Debug, Clone, Copy)]
+		enum $name {
+			$($variant),+
+		}
+
+		impl From<$remote> for $name {
+			fn from(value: $remote) -> Self {
+				match value {
+					$($remote::$variant => Self::$variant),+
+				}
+			}
+		}
+
+		impl From<$name> for $remote {
+			fn from(value: $name) -> Self {
+				match value {
+					$($name::$variant => Self::$variant),+
+				}
+			}
+		}
+	};
+}
+
+baked_enum!(
+	BakedAddressMode,
+	AddressMode,
+	[ClampToEdge, Repeat, MirrorRepeat, ClampToBorder]
+);
+baked_enum!(BakedFilterMode, FilterMode, [Nearest, Linear]);
+baked_enum!(
+	BakedCompareFunction,
+	CompareFunction,
+	[Never, Less, Equal, LessEqual, Greater, NotEqual, GreaterEqual, Always]
+);
+baked_enum!(
+	BakedBorderColor,
+	SamplerBorderColor,
+	[TransparentBlack, OpaqueBlack, OpaqueWhite, Zero]
+);
+
+/// The sampler of an atlas texture
+///
+/// The label of a sampler descriptor isn't baked.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+enum BakedSampler {
+	Default,
+	Descriptor {
+		/// The address modes along the `u`, `v`, and `w` axes
+		address_modes: [BakedAddressMode; 3],
+		mag_filter: BakedFilterMode,
+		min_filter: BakedFilterMode,
+		mipmap_filter: BakedFilterMode,
+		lod_min_clamp: f32,
+		lod_max_clamp: f32,
+		compare: Option<BakedCompareFunction>,
+		anisotropy_clamp: u16,
+		border_color: Option<BakedBorderColor>,
+	},
+}
+
+impl From<&ImageSampler> for BakedSampler {
+	fn from(sampler: &ImageSampler) -> Self {
+		match sampler {
+			ImageSampler::Default => Self::Default,
+			ImageSampler::Descriptor(descriptor) => Self::Descriptor {
+				address_modes: [
+					descriptor.address_mode_u.into(),
+					descriptor.address_mode_v.into(),
+					descriptor.address_mode_w.into(),
+				],
+				mag_filter: descriptor.mag_filter.into(),
+				min_filter: descriptor.min_filter.into(),
+				mipmap_filter: descriptor.mipmap_filter.into(),
+				lod_min_clamp: descriptor.lod_min_clamp,
+				lod_max_clamp: descriptor.lod_max_clamp,
+				compare: descriptor.compare.map(Into::into),
+				anisotropy_clamp: descriptor.anisotropy_clamp,
+				border_color: descriptor.border_color.map(Into::into),
+			},
+		}
+	}
+}
+
+impl From<BakedSampler> for ImageSampler {
+	fn from(sampler: BakedSampler) -> Self {
+		match sampler {
+			BakedSampler::Default => Self::Default,
+			BakedSampler::Descriptor {
+				address_modes: [u, v, w],
+				mag_filter,
+				min_filter,
+				mipmap_filter,
+				lod_min_clamp,
+				lod_max_clamp,
+				compare,
+				anisotropy_clamp,
+				border_color,
+			} => Self::Descriptor(SamplerDescriptor {
+				label: None,
+				address_mode_u: u.into(),
+				address_mode_v: v.into(),
+				address_mode_w: w.into(),
+				mag_filter: mag_filter.into(),
+				min_filter: min_filter.into(),
+				mipmap_filter: mipmap_filter.into(),
+				lod_min_clamp,
+				lod_max_clamp,
+				compare: compare.map(Into::into),
+				anisotropy_clamp,
+				border_color: border_color.map(Into::into),
+			}),
+		}
+	}
+}
+
+impl BakedTexture {
+	fn new(image: &Image) -> Result<Self, TilesetError> {
+		let (width, height) = pixels::dimensions(image);
+		Ok(Self {
+			width,
+			height,
+			format: image.texture_descriptor.format.try_into()?,
+			sampler: (&image.sampler_descriptor).into(),
+			data: image.data.clone(),
+		})
+	}
+
+	fn into_image(self) -> Result<Image, TilesetError> {
+		let expected = self.width as usize * self.height as usize * pixels::PIXEL_SIZE;
+		if self.data.len() != expected {
+			return Err(TilesetError::InvalidData {
+				expected: format!("{} bytes of texture data", expected),
+				found: format!("{} bytes", self.data.len()),
+			});
+		}
+
+		let mut image = Image::new(
+			Extent3d {
+				width: self.width,
+				height: self.height,
+				depth_or_array_layers: 1,
+			},
+			TextureDimension::D2,
+			self.data,
+			self.format.into(),
+		);
+		image.sampler_descriptor = self.sampler.into();
+		Ok(image)
+	}
+}
+
+macro_rules! baked_from {
+	($tileset: ident, $atlas: expr, $texture: expr, $lod_textures: expr, $pages: expr) => {
+		BakedTileset {
+			version: BAKED_VERSION,
+			id: $tileset.id,
+			name: $tileset.name.clone(),
+			tiles: $tileset.tiles.clone(),
+			size: $tileset.size,
+			tile_size: $tileset.tile_size,
+			tile_ids: $tileset.tile_ids.clone(),
+			tile_names: $tileset.tile_names.clone(),
+			tile_indices: $tileset.tile_indices.clone(),
+			tile_colors: $tileset.tile_colors.clone(),
+			tile_sizes: $tileset.tile_sizes.clone(),
+			categories: $tileset.categories.clone(),
+			tile_trims: $tileset.tile_trims.clone(),
+			default_tile: $tileset.default_tile,
+			grid_columns: $tileset.grid_columns,
+			rects: $atlas.textures.clone(),
+			texture: BakedTexture::new($texture)?,
+			lod_textures: $lod_textures,
+			pages: $pages,
+		}
+	};
+}
+
+impl BakedTileset {
+	/// Deserializes a baked tileset from its bytes
+	pub(crate) fn from_bytes(bytes: &[u8]) -> Result<Self, TilesetError> {
+		let baked: Self = bincode::deserialize(bytes).map_err(TilesetError::BakeError)?;
+		if baked.version != BAKED_VERSION {
+			return Err(TilesetError::InvalidData {
+				expected: format!("baked tileset version {}", BAKED_VERSION),
+				found: format!("version {}", baked.version),
+			});
+		}
+		Ok(baked)
+	}
+
+	/// Serializes this baked tileset into its bytes
+	fn to_bytes(&self) -> Result<Vec<u8>, TilesetError> {
+		bincode::serialize(self).map_err(TilesetError::BakeError)
+	}
+
+	/// Converts this baked tileset into a [`Tileset`], using the given functions to store the
+	/// atlas textures and the atlases themselves (given the label to store them under)
+	///
+	/// Since the original tile textures are not baked, the resulting tileset does not contain
+	/// any tile handles.
+	pub(crate) fn into_tileset(
+		self,
+		mut add_texture: impl FnMut(&str, Image) -> Handle<Image>,
+		mut add_atlas: impl FnMut(&str, TextureAtlas) -> Handle<TextureAtlas>,
+	) -> Result<Tileset, TilesetError> {
+		self.into_page(0, &mut add_texture, &mut add_atlas)
+	}
+
+	/// Converts this baked tileset into the atlas page at the given index
+	///
+	/// The assets of page `0` are labeled `"texture"`, `"lod_{level}"`, and `"atlas"`, while
+	/// those of every other page are prefixed with `"page_{index}_"`.
+	fn into_page(
+		self,
+		index: usize,
+		add_texture: &mut dyn FnMut(&str, Image) -> Handle<Image>,
+		add_atlas: &mut dyn FnMut(&str, TextureAtlas) -> Handle<TextureAtlas>,
+	) -> Result<Tileset, TilesetError> {
+		let label = |name: &str| match index {
+			0 => name.to_string(),
+			index => format!("page_{}_{}", index, name),
+		};
+		let texture = add_texture(&label("texture"), self.texture.into_image()?);
+		let mut lod_textures = Vec::with_capacity(self.lod_textures.len());
+		for (level, lod) in self.lod_textures.into_iter().enumerate() {
+			let lod_label = label(&format!("lod_{}", level + 1));
+			lod_textures.push(add_texture(&lod_label, lod.into_image()?));
+		}
+		let mut atlas = TextureAtlas::new_empty(texture.clone(), self.size);
+		atlas.textures = self.rects;
+		let atlas = add_atlas(&label("atlas"), atlas);
+		let pages = self
+			.pages
+			.into_iter()
+			.enumerate()
+			.map(|(page, baked)| baked.into_page(page + 1, add_texture, add_atlas))
+			.collect::<Result<Vec<_>, TilesetError>>()?;
+
+		Ok(Tileset {
+			id: self.id,
+			name: self.name,
+			tiles: self.tiles,
+			size: self.size,
+			tile_size: self.tile_size,
+			tile_ids: self.tile_ids,
+			tile_names: self.tile_names,
+			tile_handles: HashMap::new(),
+			group_handles: HashMap::new(),
+			tile_indices: self.tile_indices,
+			tile_colors: self.tile_colors,
+			tile_sizes: self.tile_sizes,
+			categories: self.categories,
+			tile_trims: self.tile_trims,
+			processed_indices: HashSet::new(),
+			lod_textures,
+			load_warnings: Vec::new(),
+			tile_sources: HashMap::new(),
+			dependency_paths: Vec::new(),
+			default_tile: self.default_tile,
+			grid_columns: self.grid_columns,
+			atlas,
+			texture,
+			pages,
+		})
+	}
+}
+
+/// Bakes the given LOD textures
+fn bake_lod_textures<TStore: TextureStore>(
+	lod_textures: &[Handle<Image>],
+	texture_store: &TStore,
+) -> Result<Vec<BakedTexture>, TilesetError> {
+	lod_textures
+		.iter()
+		.map(|handle| {
+			texture_store
+				.get(handle)
+				.ok_or(TilesetError::ImageNotFound)
+				.and_then(BakedTexture::new)
+		})
+		.collect()
+}
+
+impl RawTileset {
+	/// Bakes this tileset (including its packed atlas texture) into bytes
+	///
+	/// The bytes can be saved to a `.tileset.bin` file, which will then be loaded without
+	/// needing to re-read the tile definitions or re-pack the atlas. Every
+	/// [atlas page](Self::page) is baked, along with the format and sampler of each texture.
+	///
+	/// # Arguments
+	///
+	/// * `texture_store`: The store containing the atlas textures
+	///
+	/// returns: Result<Vec<u8, Global>, TilesetError>
+	///
+	pub fn bake<TStore: TextureStore>(
+		&self,
+		texture_store: &TStore,
+	) -> Result<Vec<u8>, TilesetError> {
+		self.to_baked(texture_store)?.to_bytes()
+	}
+
+	/// Converts this tileset (and its pages) into a [`BakedTileset`]
+	fn to_baked<TStore: TextureStore>(
+		&self,
+		texture_store: &TStore,
+	) -> Result<BakedTileset, TilesetError> {
+		let texture = texture_store
+			.get(self.texture())
+			.ok_or(TilesetError::ImageNotFound)?;
+		let lod_textures = bake_lod_textures(&self.lod_textures, texture_store)?;
+		let pages = self
+			.pages
+			.iter()
+			.map(|page| page.to_baked(texture_store))
+			.collect::<Result<Vec<_>, TilesetError>>()?;
+		Ok(baked_from!(self, self.atlas(), texture, lod_textures, pages))
+	}
+}
+
+impl Tileset {
+	/// Bakes this tileset (including its packed atlas texture) into bytes
+	///
+	/// The bytes can be saved to a `.tileset.bin` file, which will then be loaded without
+	/// needing to re-read the tile definitions or re-pack the atlas. Every
+	/// [atlas page](Self::page) is baked, along with the format and sampler of each texture.
+	///
+	/// # Arguments
+	///
+	/// * `atlases`: The `TextureAtlas` assets containing this tileset's atlases
+	/// * `texture_store`: The store containing the atlas textures
+	///
+	/// returns: Result<Vec<u8, Global>, TilesetError>
+	///
+	pub fn bake<TStore: TextureStore>(
+		&self,
+		atlases: &Assets<TextureAtlas>,
+		texture_store: &TStore,
+	) -> Result<Vec<u8>, TilesetError> {
+		self.to_baked(atlases, texture_store)?.to_bytes()
+	}
+
+	/// Converts this tileset (and its pages) into a [`BakedTileset`]
+	fn to_baked<TStore: TextureStore>(
+		&self,
+		atlases: &Assets<TextureAtlas>,
+		texture_store: &TStore,
+	) -> Result<BakedTileset, TilesetError> {
+		let atlas = atlases.get(self.atlas()).ok_or(TilesetError::InvalidData {
+			expected: String::from("A loaded texture atlas"),
+			found: String::from("Missing texture atlas"),
+		})?;
+		let texture = texture_store
+			.get(self.texture())
+			.ok_or(TilesetError::ImageNotFound)?;
+		let lod_textures = bake_lod_textures(&self.lod_textures, texture_store)?;
+		let pages = self
+			.pages
+			.iter()
+			.map(|page| page.to_baked(atlases, texture_store))
+			.collect::<Result<Vec<_>, TilesetError>>()?;
+		Ok(baked_from!(self, atlas, texture, lod_textures, pages))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use bevy::asset::HandleId;
+	use bevy::prelude::{Handle, Image};
+	use bevy_tileset_tiles::prelude::TileHandle;
+
+	use super::*;
+	use crate::prelude::{SamplerPreset, TilesetBuilder};
+	use crate::test_utils::{solid_image, TestTextureStore};
+
+	#[test]
+	fn should_round_trip_baked_tileset() {
+		let mut store = TestTextureStore::default();
+		let mut builder = TilesetBuilder::default();
+		for group_id in 0..3 {
+			let handle = store.add(solid_image(16, 16, [group_id as u8 * 80; 4]));
+			let tile = TileHandle::new_standard(format!("Tile {}", group_id), handle);
+			builder.add_tile(tile, group_id, &store).unwrap();
+		}
+		let raw = builder.build("Baked", 3, &mut store).unwrap();
+
+		let bytes = raw.bake(&store).unwrap();
+		let mut texture = None;
+		let tileset = BakedTileset::from_bytes(&bytes)
+			.unwrap()
+			.into_tileset(
+				|_, image| {
+					texture = Some(image);
+					Handle::weak(HandleId::random::<Image>())
+				},
+				|_, _| Handle::weak(HandleId::random::<TextureAtlas>()),
+			)
+			.unwrap();
+
+		assert_eq!(raw.name(), tileset.name());
+		assert_eq!(raw.id(), tileset.id());
+		assert_eq!(raw.size(), tileset.size());
+		assert_eq!(raw.tile_indices, tileset.tile_indices);
+		assert_eq!(raw.tile_ids, tileset.tile_ids);
+		assert_eq!(
+			raw.get_base_tile_index("Tile 2"),
+			tileset.get_base_tile_index("Tile 2")
+		);
+		assert_eq!(
+			store.get(raw.texture()).map(|image| &image.data),
+			texture.as_ref().map(|image| &image.data)
+		);
+	}
+
+	#[test]
+	fn should_round_trip_pages_and_samplers() {
+		let mut store = TestTextureStore::default();
+		let mut builder = TilesetBuilder::default();
+		builder.sampler(SamplerPreset::Tiling.sampler());
+		let grass = store.add(solid_image(16, 16, [0, 255, 0, 255]));
+		builder
+			.add_tile(TileHandle::new_standard("Grass", grass), 0, &store)
+			.unwrap();
+		let glow = TileHandle {
+			sampler: Some(SamplerPreset::PixelArt),
+			..TileHandle::new_standard("Glow", store.add(solid_image(16, 16, [255; 4])))
+		};
+		builder.add_tile(glow, 1, &store).unwrap();
+		let raw = builder.build("Paged", 0, &mut store).unwrap();
+		assert_eq!(2, raw.page_count());
+
+		let bytes = raw.bake(&store).unwrap();
+		let mut textures = HashMap::new();
+		let mut atlases = Vec::new();
+		let tileset = BakedTileset::from_bytes(&bytes)
+			.unwrap()
+			.into_tileset(
+				|label, image| {
+					let handle = Handle::weak(HandleId::random::<Image>());
+					textures.insert(label.to_string(), (handle.clone_weak(), image));
+					handle
+				},
+				|label, _| {
+					atlases.push(label.to_string());
+					Handle::weak(HandleId::random::<TextureAtlas>())
+				},
+			)
+			.unwrap();
+
+		assert_eq!(vec!["atlas", "page_1_atlas"], atlases);
+		assert_eq!(2, tileset.page_count());
+		assert_eq!(
+			raw.get_paged_tile_index("Glow"),
+			tileset.get_paged_tile_index("Glow")
+		);
+		for (index, label) in [(0, "texture"), (1, "page_1_texture")] {
+			let (handle, image) = &textures[label];
+			let original = store.get(raw.page(index).unwrap().texture()).unwrap();
+			assert_eq!(handle, tileset.page(index).unwrap().texture());
+			assert_eq!(original.data, image.data);
+			assert_eq!(
+				original.texture_descriptor.format,
+				image.texture_descriptor.format
+			);
+		}
+
+		let sampler = |label: &str| match &textures[label].1.sampler_descriptor {
+			ImageSampler::Descriptor(descriptor) => Some((
+				descriptor.address_mode_u,
+				descriptor.mag_filter,
+				descriptor.anisotropy_clamp,
+			)),
+			ImageSampler::Default => None,
+		};
+		assert_eq!(
+			Some((AddressMode::Repeat, FilterMode::Linear, 16)),
+			sampler("texture")
+		);
+		assert_eq!(
+			Some((AddressMode::ClampToEdge, FilterMode::Nearest, 1)),
+			sampler("page_1_texture")
+		);
+	}
+}
@@ -1,9 +1,23 @@
 use crate::ids::PartialTileId;
 use crate::prelude::*;
-use bevy::prelude::{Handle, Image};
-use bevy_tile_atlas::{TextureStore, TileAtlasBuilder, TileAtlasBuilderError};
+use crate::tileset::atlas::{AtlasOptions, AtlasTextureStore};
+use crate::tileset::pixels;
+use bevy::asset::{AssetIo, HandleId};
+use bevy::log::{debug, info_span, warn};
+use bevy::prelude::{Handle, Image, Rect, TextureAtlas, UVec2, Vec2};
+use bevy::reflect::TypeUuid;
+use bevy::render::texture::ImageSampler;
+use bevy_tile_atlas::{TextureStore, TileAtlasBuilder};
 use bevy_tileset_tiles::prelude::*;
-use std::collections::HashMap;
+use std::borrow::Cow;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::hash::{Hash, Hasher};
+use std::path::Path;
+use std::sync::Arc;
+
+/// The atlas utilization below which a built tileset is reported as wasting texture memory
+const LOW_ATLAS_UTILIZATION: f32 = 0.5;
 
 /// A builder for constructing a [`Tileset`]
 #[derive(Default)]
@@ -12,22 +26,114 @@ pub struct TilesetBuilder {
 	tiles: HashMap<TileGroupId, TileData>,
 	/// The builder used to construct the final [`TextureAtlas`]
 	atlas_builder: TileAtlasBuilder,
+	/// The maximum number of columns given to the atlas builder
+	max_columns: Option<usize>,
+	/// The handle each texture was added to the atlas builder with, in order of their index
+	atlas_handles: Vec<Handle<Image>>,
 	/// The tile IDs mapped by their name
 	tile_ids: HashMap<String, TileGroupId>,
 	/// The tile names mapped by their ID
 	tile_names: HashMap<TileGroupId, String>,
 	/// The tile handles mapped by their index in the atlas
 	tile_handles: HashMap<usize, Handle<Image>>,
+	/// The (weak) tile handles mapped by their ID
+	group_handles: HashMap<TileGroupId, TileHandle>,
 	/// The tile IDs mapped by their index in the atlas
 	tile_indices: HashMap<usize, PartialTileId>,
 	/// The current tile group ID being processed
 	current_group: TileGroupId,
+	/// The name of the current tile being processed
+	current_name: String,
 	/// The current variant index being processed
 	#[cfg(feature = "variants")]
 	current_variant: Option<usize>,
 	/// The current auto tile index being processed
 	#[cfg(feature = "auto-tile")]
 	current_auto: Option<usize>,
+	/// The options used to post-process the generated atlas
+	atlas_options: AtlasOptions,
+	/// Whether a tileset without any tiles is allowed to be built
+	allow_empty: bool,
+	/// The authoritative size of every tile (if any)
+	tile_size: Option<Vec2>,
+	/// The transform of the current tile being processed
+	current_transform: Option<TileTransform>,
+	/// The color key of the current tile being processed
+	current_color_key: Option<[u8; 4]>,
+	/// The transform of the current variant being processed
+	#[cfg(feature = "variants")]
+	current_variant_transform: Option<TileTransform>,
+	/// Textures that were processed by this builder (e.g. transformed), mapped by the ID
+	/// of the handle they were added to the atlas with
+	processed: HashMap<HandleId, Image>,
+	/// The number of textures processed by this builder so far (including removed ones)
+	processed_count: usize,
+	/// The average color of each tile mapped by their ID
+	tile_colors: HashMap<TileGroupId, [u8; 4]>,
+	/// The size of each tile mapped by their ID
+	tile_sizes: HashMap<TileGroupId, UVec2>,
+	/// The tile group IDs mapped by the name of the category they belong to
+	categories: BTreeMap<String, Vec<TileGroupId>>,
+	/// The transparent borders trimmed from each tile mapped by their ID
+	tile_trims: HashMap<usize, TileTrim>,
+	/// The atlas indices whose texture was processed from its source image
+	processed_indices: HashSet<usize>,
+	/// Whether tile textures should be trimmed to their non-transparent pixels
+	trim: bool,
+	/// The trim of the current texture being processed (if it was trimmed)
+	current_trim: Option<TileTrim>,
+	/// Whether the current texture being processed is an animation frame
+	current_animated: bool,
+	/// The ID of the tile used in place of unknown tile names (if any)
+	default_tile: Option<TileGroupId>,
+	/// A user-defined function applied to every tile texture before it's added to the atlas
+	tile_processor: Option<TileProcessor>,
+	/// The number of atlas textures to generate (including the full-resolution one)
+	lod_levels: u32,
+	/// Whether tile textures should be converted to premultiplied alpha
+	premultiply_alpha: bool,
+	/// The number of columns the atlas must be laid out in (if any)
+	grid_columns: Option<u32>,
+	/// The sampler to give the atlas texture
+	sampler: ImageSampler,
+	/// The existing atlas texture that every texture of this builder is a region of (if any)
+	///
+	/// Such a builder never packs its textures, but references them within this texture instead.
+	region_atlas: Option<Handle<Image>>,
+	/// The region of the `region_atlas` covered by each texture, in order of their index
+	regions: Vec<Rect>,
+	/// The builders for the tiles that need their own atlas page, in the order they were created
+	pages: Vec<(AtlasPage, TilesetBuilder)>,
+}
+
+/// The tiles gathered into a separate atlas page
+#[derive(Debug, Clone, PartialEq)]
+enum AtlasPage {
+	/// The tiles that requested the given sampler
+	Sampler(SamplerPreset),
+	/// The region tiles of the given atlas texture
+	Region(Handle<Image>),
+}
+
+/// A tile removed from a builder while its textures are kept in the atlas
+struct DetachedTile {
+	data: TileData,
+	name: Option<String>,
+	handle: Option<TileHandle>,
+	color: Option<[u8; 4]>,
+	size: Option<UVec2>,
+}
+
+/// A function used to process a tile's texture (given the tile's name) before it's packed
+type TileProcessor = Arc<dyn Fn(&str, Image) -> Image + Send + Sync>;
+
+/// Checks whether the given samplers are the same
+fn is_same_sampler(a: &ImageSampler, b: &ImageSampler) -> bool {
+	match (a, b) {
+		(ImageSampler::Default, ImageSampler::Default) => true,
+		(ImageSampler::Descriptor(a), ImageSampler::Descriptor(b)) => a == b,
+		_ => false,
+	}
 }
 
 impl TilesetBuilder {
@@ -36,38 +142,491 @@ impl TilesetBuilder {
 		atlas_builder.max_columns(max_columns);
 		Self {
 			atlas_builder,
+			max_columns,
+			atlas_handles: Vec::new(),
 			tile_ids: Default::default(),
 			current_group: Default::default(),
+			current_name: Default::default(),
 			tile_indices: Default::default(),
 			tile_names: Default::default(),
 			tiles: Default::default(),
 			tile_handles: Default::default(),
+			group_handles: Default::default(),
 			#[cfg(feature = "variants")]
 			current_variant: None,
 			#[cfg(feature = "auto-tile")]
 			current_auto: None,
+			atlas_options: Default::default(),
+			allow_empty: false,
+			tile_size: None,
+			current_transform: None,
+			current_color_key: None,
+			#[cfg(feature = "variants")]
+			current_variant_transform: None,
+			processed: Default::default(),
+			processed_count: 0,
+			tile_colors: Default::default(),
+			tile_sizes: Default::default(),
+			categories: Default::default(),
+			tile_trims: Default::default(),
+			processed_indices: Default::default(),
+			trim: false,
+			current_trim: None,
+			current_animated: false,
+			default_tile: None,
+			tile_processor: None,
+			lod_levels: 1,
+			premultiply_alpha: false,
+			grid_columns: None,
+			sampler: ImageSampler::Default,
+			region_atlas: None,
+			regions: Vec::new(),
+			pages: Vec::new(),
+		}
+	}
+
+	/// Create a builder with space allocated for the given number of tiles
+	///
+	/// This avoids repeatedly growing the builder's internal storage when the number of tiles
+	/// is known ahead of time.
+	///
+	/// # Arguments
+	///
+	/// * `capacity`: The number of tiles expected to be added
+	///
+	/// returns: TilesetBuilder
+	///
+	pub fn with_capacity(capacity: usize) -> Self {
+		let mut builder = Self::default();
+		builder.reserve(capacity);
+		builder
+	}
+
+	/// Reserve space for at least the given number of additional tiles
+	///
+	/// Every tile takes up at least one texture in the atlas, so space for that many textures
+	/// is reserved as well.
+	///
+	/// # Arguments
+	///
+	/// * `additional`: The number of additional tiles expected to be added
+	///
+	/// returns: &mut TilesetBuilder
+	///
+	pub fn reserve(&mut self, additional: usize) -> &mut Self {
+		self.tiles.reserve(additional);
+		self.tile_ids.reserve(additional);
+		self.tile_names.reserve(additional);
+		self.group_handles.reserve(additional);
+		self.tile_colors.reserve(additional);
+		self.tile_sizes.reserve(additional);
+		self.tile_handles.reserve(additional);
+		self.tile_indices.reserve(additional);
+		self
+	}
+
+	/// Create a builder containing the given tiles
+	///
+	/// Each tile is assigned a sequential group ID (starting at `0`) in the order given.
+	///
+	/// # Arguments
+	///
+	/// * `tiles`: The tiles to add (in their intended order)
+	/// * `texture_store`: The store of textures
+	///
+	/// returns: Result<TilesetBuilder, TilesetError>
+	///
+	pub fn from_tile_handles<TTiles: IntoIterator<Item = TileHandle>, TStore: TextureStore>(
+		tiles: TTiles,
+		texture_store: &TStore,
+	) -> Result<Self, TilesetError> {
+		let tiles = tiles.into_iter();
+		let mut builder = Self::with_capacity(tiles.size_hint().0);
+		for (group_id, tile) in tiles.enumerate() {
+			builder.add_tile(tile, group_id as TileGroupId, texture_store)?;
 		}
+		Ok(builder)
+	}
+
+	/// Create a builder containing a standard tile for every image in the given directory
+	///
+	/// This allows a tileset to be created from a folder of images without any configuration.
+	/// Each tile is named after its file stem and assigned a sequential group ID, in order of
+	/// their file names. See [`load_directory_tile_handles`] for how the directory is read.
+	///
+	/// The textures are taken from the store right away, so they must already be available in
+	/// it (e.g. because `asset_loader` loads them synchronously, or they were loaded beforehand).
+	///
+	/// # Arguments
+	///
+	/// * `asset_io`: The `AssetIo` to read the directory from
+	/// * `dir`: The directory containing the tile images, relative to the asset root
+	/// * `asset_loader`: The `AssetServer` or other loader for loading the textures
+	/// * `texture_store`: The store of textures
+	///
+	/// returns: Result<TilesetBuilder, TilesetError>
+	///
+	pub fn from_directory<TDir: AsRef<Path>, TLoader: TextureLoader, TStore: TextureStore>(
+		asset_io: &dyn AssetIo,
+		dir: TDir,
+		asset_loader: &TLoader,
+		texture_store: &TStore,
+	) -> Result<Self, TilesetError> {
+		let tiles = load_directory_tile_handles(asset_io, dir, asset_loader)?;
+		Self::from_tile_handles(tiles, texture_store)
+	}
+
+	/// Set the size every tile is expected to be
+	///
+	/// When set, each added texture is validated against this size, erroring with
+	/// [`TilesetError::InvalidTileSize`] on a mismatch. When `None`, the tile size is
+	/// inferred from the first texture added.
+	///
+	/// Default: `None`
+	pub fn tile_size(&mut self, tile_size: Option<Vec2>) -> &mut Self {
+		self.tile_size = tile_size;
+		self
+	}
+
+	/// Set a function used to process every tile texture before it's added to the atlas
+	///
+	/// The function is given the name of the tile and its decoded texture (after any transform
+	/// or color key has been applied) and returns the texture to pack. This allows custom effects
+	/// (such as tints or outlines) to be applied at build time. The returned texture is still
+	/// validated against the [tile size](Self::tile_size).
+	///
+	/// Default: `None`
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use bevy_tileset_core::prelude::*;
+	///
+	/// let mut builder = TilesetBuilder::default();
+	/// builder.tile_processor(|_name, mut image| {
+	/// 	// Invert every color
+	/// 	for pixel in image.data.chunks_exact_mut(4) {
+	/// 		pixel[0] = 255 - pixel[0];
+	/// 		pixel[1] = 255 - pixel[1];
+	/// 		pixel[2] = 255 - pixel[2];
+	/// 	}
+	/// 	image
+	/// });
+	/// ```
+	pub fn tile_processor<F>(&mut self, processor: F) -> &mut Self
+	where
+		F: Fn(&str, Image) -> Image + Send + Sync + 'static,
+	{
+		self.tile_processor = Some(Arc::new(processor));
+		self
+	}
+
+	/// Set the number of atlas textures to generate, each half the size of the previous one
+	///
+	/// These can be used as level-of-detail (LOD) textures when rendering at a distance (see
+	/// [`Tileset::atlas_for_lod`]). Unlike mipmaps, each level is a separate texture that can be
+	/// switched out entirely. Since every level is scaled uniformly, the atlas rects remain valid
+	/// as normalized UVs at every level, as long as the atlas dimensions are divisible by two for
+	/// each level (see [`power_of_two`](Self::power_of_two)).
+	///
+	/// Each tile is downscaled separately, so tiles never bleed into each other or into the
+	/// padding around them (which is filled with the [gutter color](Self::gutter_color)).
+	///
+	/// This includes the full-resolution atlas, so values below `1` are treated as `1`. Levels
+	/// past the one where the smallest side of the atlas reaches a single pixel are not generated.
+	///
+	/// Default: `1`
+	pub fn lod_levels(&mut self, lod_levels: u32) -> &mut Self {
+		self.lod_levels = lod_levels;
+		self
+	}
+
+	/// Set whether every tile texture should have its color channels premultiplied by its alpha
+	///
+	/// This is done right before a texture is packed (after any
+	/// [tile processor](Self::tile_processor) has been applied), so that blended tile edges
+	/// don't show dark fringes when rendered with premultiplied alpha blending.
+	///
+	/// Default: `false`
+	pub fn premultiply_alpha(&mut self, premultiply_alpha: bool) -> &mut Self {
+		self.premultiply_alpha = premultiply_alpha;
+		self
+	}
+
+	/// Set whether every tile texture should be trimmed to the bounds of its non-transparent pixels
+	///
+	/// This is done right before a texture is packed (after any other processing), so that
+	/// transparent padding in the source art doesn't take up atlas space. The trimmed border of
+	/// each tile is recorded (see [`Tileset::tile_trim`]) so that it can still be positioned
+	/// correctly, such as with [`Tileset::tile_packed_pivot`]. Textures are still validated
+	/// against the [tile size](Self::tile_size) using their untrimmed size.
+	///
+	/// Animation frames are never trimmed, since every frame needs to be the same size. Keep in
+	/// mind that the atlas is laid out as a grid, so every packed texture still needs to end up
+	/// the same size (e.g. sprites exported with the same padding).
+	///
+	/// Default: `false`
+	pub fn trim(&mut self, trim: bool) -> &mut Self {
+		self.trim = trim;
+		self
+	}
+
+	/// Set the group ID of the tile used in place of unknown tile names
+	///
+	/// The tile must have been added to this builder (not one of its sampler pages) by the time
+	/// the tileset is built, otherwise building fails with [`TilesetError::MissingDefaultTile`].
+	/// See [`Tileset::get_tile_index_or_default`].
+	///
+	/// Default: `None`
+	pub fn default_tile(&mut self, group_id: Option<TileGroupId>) -> &mut Self {
+		self.default_tile = group_id;
+		self
+	}
+
+	/// Set whether a tileset without any tiles is allowed to be built
+	///
+	/// By default, building an empty tileset results in [`TilesetError::EmptyTileset`]
+	/// since it's almost always a mistake.
+	///
+	/// Default: `false`
+	pub fn allow_empty(&mut self, allow_empty: bool) -> &mut Self {
+		self.allow_empty = allow_empty;
+		self
+	}
+
+	/// Set whether the generated atlas should be padded to power-of-two dimensions
+	///
+	/// The extra space is filled with the [gutter color](Self::gutter_color) along the right and
+	/// bottom edges, so the existing tile indices are left untouched.
+	///
+	/// Default: `false`
+	pub fn power_of_two(&mut self, power_of_two: bool) -> &mut Self {
+		self.atlas_options.power_of_two = power_of_two;
+		self
+	}
+
+	/// Set the color of every atlas pixel not covered by a tile (such as padding)
+	///
+	/// Opaque tilesets can use an opaque color here so that linear filtering near tile edges
+	/// doesn't blend in stray transparent pixels.
+	///
+	/// Default: `[0, 0, 0, 0]` (transparent)
+	pub fn gutter_color(&mut self, color: [u8; 4]) -> &mut Self {
+		self.atlas_options.gutter_color = color;
+		self
+	}
+
+	/// Set the sampler used by the atlas texture (and any LOD textures)
+	///
+	/// This allows the filtering and address modes used when rendering the tileset to be
+	/// configured. See [`SamplerPreset`] for a few common configurations.
+	///
+	/// Default: `ImageSampler::Default`
+	pub fn sampler(&mut self, sampler: ImageSampler) -> &mut Self {
+		self.sampler = sampler;
+		self
+	}
+
+	/// Set the number of columns the atlas must be laid out in
+	///
+	/// Tiles are laid out in a row-major grid (in the order their textures were added), with
+	/// the atlas always being exactly this many tiles wide. This allows external tools to find a
+	/// tile by its row and column. Building errors with [`TilesetError::InvalidGridLayout`] if
+	/// the textures can't be laid out this way (such as when the number of columns is `0`).
+	///
+	/// This overrides the `max_columns` given to [`TilesetBuilder::new`].
+	///
+	/// Default: `None`
+	pub fn grid_columns(&mut self, columns: Option<u32>) -> &mut Self {
+		self.grid_columns = columns;
+		self.max_columns = columns
+			.filter(|columns| *columns > 0)
+			.map(|columns| columns as usize);
+		self.atlas_builder.max_columns(self.max_columns);
+		self
 	}
 
 	/// Build the raw tileset
 	///
+	/// Any tiles with a [sampler hint](TileDef::sampler) are built into their own atlas pages
+	/// (see [`RawTileset::page`]), as are the region tiles of each existing atlas texture (whose
+	/// page uses that texture as its atlas texture). If no tile is packed into this builder's own
+	/// atlas, the first of these pages becomes page `0` instead, so a tileset made up of the
+	/// regions of a single atlas texture only has the one page.
+	///
 	/// # Arguments
 	///
 	/// * `texture_store`: The store of textures
 	///
-	/// returns: Result<RawTileset, TextureAtlasBuilderError>
+	/// returns: Result<RawTileset, TilesetError>
 	///
 	pub fn build<TName: Into<String>, TStore: TextureStore>(
-		self,
+		mut self,
 		name: TName,
 		id: TilesetId,
 		texture_store: &mut TStore,
-	) -> Result<RawTileset, TileAtlasBuilderError> {
-		let tile_size = self.atlas_builder.get_tile_size().unwrap_or_default();
-		let atlas = self.atlas_builder.finish(texture_store)?;
+	) -> Result<RawTileset, TilesetError> {
+		let name = name.into();
+		let mut pages = Vec::new();
+		for (kind, mut page) in std::mem::take(&mut self.pages) {
+			if page.tiles.is_empty() {
+				continue;
+			}
+			// These only affect packing, so they could have changed since the page was created
+			match kind {
+				AtlasPage::Sampler(..) => {
+					page.atlas_options = self.atlas_options;
+					page.lod_levels = self.lod_levels;
+					page.grid_columns(self.grid_columns);
+				},
+				// The regions are already laid out within their atlas texture
+				AtlasPage::Region(..) => page.grid_columns = self.grid_columns,
+			}
+			pages.push(page.build(name.clone(), id, texture_store)?);
+		}
+
+		if self.tiles.is_empty() && pages.is_empty() && !self.allow_empty {
+			return Err(TilesetError::EmptyTileset);
+		}
+		if self.tiles.is_empty() && !pages.is_empty() {
+			// Without any tiles of its own, this tileset is replaced by its first page (such as
+			// when every tile is a region of the same atlas texture)
+			let mut tileset = pages.remove(0);
+			if let Some(group_id) = self.default_tile {
+				if !tileset.tiles.contains_key(&group_id) {
+					return Err(TilesetError::MissingDefaultTile(group_id));
+				}
+			}
+			tileset.default_tile = self.default_tile;
+			tileset.pages = pages;
+			return Ok(tileset);
+		}
+		if let Some(group_id) = self.default_tile {
+			if !self.tiles.contains_key(&group_id) {
+				return Err(TilesetError::MissingDefaultTile(group_id));
+			}
+		}
+
+		let _span = info_span!(
+			"pack_atlas",
+			tiles = self.tiles.len(),
+			textures = self.tile_indices.len()
+		)
+		.entered();
+
+		let tile_size = self
+			.tile_size
+			.or_else(|| self.atlas_builder.get_tile_size())
+			.or_else(|| self.regions.first().map(Rect::size))
+			.unwrap_or_default();
+		if let Some(columns) = self.grid_columns {
+			if columns == 0 {
+				return Err(TilesetError::InvalidGridLayout { index: 0, columns });
+			}
+			let width = columns as f32 * tile_size.x;
+			if width > u32::MAX as f32 {
+				return Err(TilesetError::AtlasSizeOverflow {
+					width: u32::MAX,
+					height: tile_size.y as u32,
+				});
+			}
+			if self.region_atlas.is_none() {
+				self.atlas_options.min_width = width as u32;
+			}
+		}
+		let mut atlas = match &self.region_atlas {
+			Some(texture) => {
+				let source = texture_store.get(texture).ok_or(TilesetError::ImageNotFound)?;
+				let size = source.size();
+				let converted = match pixels::to_rgba8(source)? {
+					Cow::Owned(converted) => Some(converted),
+					Cow::Borrowed(_) => None,
+				};
+				// The atlas is used as-is, unless it had to be converted to a supported format
+				let texture = match converted {
+					Some(converted) => texture_store.add(converted),
+					None => texture.clone(),
+				};
+				let mut atlas = TextureAtlas::new_empty(texture, size);
+				for rect in &self.regions {
+					atlas.add_texture(*rect);
+				}
+				atlas
+			},
+			None => {
+				let mut store = AtlasTextureStore {
+					store: texture_store,
+					options: self.atlas_options,
+					processed: &self.processed,
+					sampler: &self.sampler,
+				};
+				self.atlas_builder
+					.finish(&mut store)
+					.map_err(|err| TilesetError::AtlasError(err))?
+			},
+		};
+		let (content_width, content_height) = (atlas.size.x as u32, atlas.size.y as u32);
+		let (width, height) = self
+			.atlas_options
+			.final_size(content_width, content_height)
+			.ok_or(TilesetError::AtlasSizeOverflow {
+				width: content_width,
+				height: content_height,
+			})?;
+		atlas.size = Vec2::new(width as f32, height as f32);
 		let size = atlas.size;
-		Ok(RawTileset {
-			name: name.into(),
+
+		// The atlas is already transparent wherever there are no tiles
+		let gutter_color = self.atlas_options.gutter_color;
+		if gutter_color != [0; 4] {
+			let mut texture = texture_store
+				.get(&atlas.texture)
+				.ok_or(TilesetError::ImageNotFound)?
+				.clone();
+			pixels::fill_uncovered(&mut texture, &atlas.textures, gutter_color);
+			atlas.texture = texture_store.add(texture);
+		}
+
+		if let Some(columns) = self.grid_columns {
+			for (index, rect) in atlas.textures.iter().enumerate() {
+				let cell = UVec2::new(index as u32 % columns, index as u32 / columns);
+				if rect.min != cell.as_vec2() * tile_size {
+					return Err(TilesetError::InvalidGridLayout { index, columns });
+				}
+			}
+		}
+
+		// Stop once the smallest side of the atlas has been halved down to a single pixel
+		let max_lod_levels = (size.x.min(size.y) as u32).max(1).ilog2() + 1;
+		let mut lod_textures = Vec::new();
+		if self.lod_levels > 1 {
+			let mut lod = texture_store
+				.get(&atlas.texture)
+				.ok_or(TilesetError::ImageNotFound)?
+				.clone();
+			let mut regions = atlas.textures.clone();
+			for _ in 1..self.lod_levels.min(max_lod_levels) {
+				(lod, regions) = pixels::downscale_regions(&lod, &regions, gutter_color);
+				lod_textures.push(texture_store.add(lod.clone()));
+			}
+		}
+
+		// Make sure every registered index actually points to a tile within the atlas
+		for index in self.tile_indices.keys() {
+			let in_bounds = atlas
+				.textures
+				.get(*index)
+				.map(|rect| rect.min.cmpge(Vec2::ZERO).all() && rect.max.cmple(size).all())
+				.unwrap_or_default();
+			if !in_bounds {
+				return Err(TilesetError::TileOutOfBounds {
+					index: *index,
+					size,
+				});
+			}
+		}
+		let tileset = RawTileset {
+			name,
 			id,
 			tiles: self.tiles,
 			tile_ids: self.tile_ids,
@@ -78,10 +637,36 @@ impl TilesetBuilder {
 				.collect(),
 			tile_names: self.tile_names,
 			tile_handles: self.tile_handles,
+			group_handles: self.group_handles,
+			tile_colors: self.tile_colors,
+			tile_sizes: self.tile_sizes,
+			categories: self.categories,
+			tile_trims: self.tile_trims,
+			processed_indices: self.processed_indices,
+			lod_textures,
+			load_warnings: Vec::new(),
+			tile_sources: HashMap::new(),
+			dependency_paths: Vec::new(),
+			default_tile: self.default_tile,
+			grid_columns: self.grid_columns,
 			tile_size,
 			atlas,
 			size,
-		})
+			pages,
+		};
+
+		let utilization = tileset.atlas_utilization();
+		if utilization < LOW_ATLAS_UTILIZATION {
+			debug!(
+				"Tileset {:?} only uses {:.1}% of its {}x{} atlas, consider adjusting its packing or padding",
+				tileset.name(),
+				utilization * 100.0,
+				size.x,
+				size.y
+			);
+		}
+
+		Ok(tileset)
 	}
 
 	/// Add a tile to the tileset being built
@@ -94,6 +679,24 @@ impl TilesetBuilder {
 	///
 	/// returns: Result<Option<TileData>, TilesetError>
 	///
+	/// If the tile has a [sampler hint](TileHandle::sampler), it's added to the atlas page for
+	/// that sampler instead. Region tiles are added to the atlas page of their atlas texture,
+	/// which shares that texture (and its sampler) rather than copying their regions. Since their
+	/// pixels are used as-is, region tiles are never transformed, color keyed, trimmed, or
+	/// otherwise processed.
+	///
+	/// Adding a tile is atomic: if it fails, the builder is left exactly as it was (none of the
+	/// tile's textures or aliases are registered). An alias that's already taken by another tile
+	/// is skipped with a warning, as is any alias that a later tile's name takes over.
+	///
+	/// # Errors
+	///
+	/// Returns [`TilesetError::TileAlreadyExists`] if a tile with the given group ID has already
+	/// been added. To replace an existing tile, use [`add_or_replace_tile`](Self::add_or_replace_tile).
+	///
+	/// Returns [`TilesetError::DuplicateTileName`] if the tile's name is already used in a
+	/// different atlas page than the one the tile is added to.
+	///
 	/// # Examples
 	///
 	/// ```
@@ -114,69 +717,439 @@ impl TilesetBuilder {
 		group_id: TileGroupId,
 		texture_store: &TStore,
 	) -> Result<Option<TileData>, TilesetError> {
-		if self.tiles.contains_key(&group_id) {
+		let exists = |builder: &Self| builder.tiles.contains_key(&group_id);
+		if exists(self) || self.pages.iter().any(|(.., page)| exists(page)) {
 			return Err(TilesetError::TileAlreadyExists(group_id));
 		}
 
+		let page = match (&tile_handle.tile, tile_handle.sampler) {
+			(TileHandleType::AtlasRegion(handle, ..), ..) if self.region_atlas.is_none() => {
+				Some(AtlasPage::Region(handle.clone()))
+			},
+			// A hint matching this builder's own sampler doesn't need a separate page
+			(.., Some(preset)) if !is_same_sampler(&preset.sampler(), &self.sampler) => {
+				Some(AtlasPage::Sampler(preset))
+			},
+			_ => None,
+		};
+
+		// Tiles are found by name across every page, so a name can't be used by multiple pages
+		let index = page
+			.as_ref()
+			.map(|page| self.pages.iter().position(|(other, ..)| other == page));
+		let is_taken = |builder: &Self| builder.tile_ids.contains_key(&tile_handle.name);
+		let is_taken_elsewhere = (index.is_some() && is_taken(self))
+			|| self.pages.iter().enumerate().any(|(page_index, (.., page))| {
+				index != Some(Some(page_index)) && is_taken(page)
+			});
+		if is_taken_elsewhere {
+			return Err(TilesetError::DuplicateTileName(tile_handle.name));
+		}
+
+		if let Some(page) = page {
+			let index = match index.flatten() {
+				Some(index) => index,
+				None => {
+					let builder = self.new_page(&page);
+					self.pages.push((page, builder));
+					self.pages.len() - 1
+				},
+			};
+			let tile_handle = TileHandle {
+				sampler: None,
+				..tile_handle
+			};
+			return self.pages[index].1.add_tile(tile_handle, group_id, texture_store);
+		}
+
+		let texture_count = self.atlas_handles.len();
+		let result = self.insert_tile(tile_handle, group_id, texture_store);
+		if result.is_err() {
+			self.tile_colors.remove(&group_id);
+			self.tile_sizes.remove(&group_id);
+			self.truncate_textures(texture_count, texture_store)?;
+		}
+		result
+	}
+
+	/// Adds the given tile to this builder's atlas
+	///
+	/// Any textures added before an error occurs are left in the builder, so callers should use
+	/// [`add_tile`](Self::add_tile) instead.
+	fn insert_tile<TStore: TextureStore>(
+		&mut self,
+		tile_handle: TileHandle,
+		group_id: TileGroupId,
+		texture_store: &TStore,
+	) -> Result<Option<TileData>, TilesetError> {
 		let name = tile_handle.name.clone();
+		let weak_handle = tile_handle.clone_weak();
 
 		self.current_group = group_id;
+		self.current_name = name.clone();
+		self.current_transform = tile_handle.transform;
+		self.current_color_key = tile_handle.color_key;
 
-		let tile = TileData::new(
-			tile_handle.name,
-			self.get_tile_type(tile_handle.tile, texture_store)?,
-		);
+		let tile_type = self.get_tile_type(tile_handle.tile, texture_store);
+		self.current_transform = None;
+		self.current_color_key = None;
+		let tile = TileData::new(tile_handle.name, tile_type?)
+			.with_collision(tile_handle.collision)
+			.with_tags(tile_handle.tags)
+			.with_z_offset(tile_handle.z_offset)
+			.with_layer(tile_handle.layer)
+			.with_pivot(tile_handle.pivot)
+			.with_aliases(tile_handle.aliases);
 
+		if let Some(other) = self.tile_ids.get(&name).filter(|other| **other != group_id) {
+			if self.tile_names.get(other) != Some(&name) {
+				warn!("tile {:?} takes over the name of an alias of tile {}", name, other);
+			}
+		}
 		self.tile_ids.insert(name.clone(), group_id);
+		// Aliases are only registered once the tile is added, and never shadow another tile
+		for alias in tile.aliases() {
+			match self.tile_ids.get(alias) {
+				Some(other) if *other != group_id => {
+					warn!(
+						"skipping alias {:?} of tile {:?}, which is taken by tile {}",
+						alias, name, other
+					);
+				},
+				_ => {
+					self.tile_ids.insert(alias.clone(), group_id);
+				},
+			}
+		}
 		self.tile_names.insert(group_id, name);
+		self.group_handles.insert(group_id, weak_handle);
 		Ok(self.tiles.insert(group_id, tile))
 	}
 
-	fn get_tile_type<TStore: TextureStore>(
+	/// Add a tile to a category
+	///
+	/// Categories are purely metadata (useful for organizing tiles in a palette UI) and don't
+	/// affect how the tileset is built. A tile may belong to multiple categories, and the tile
+	/// does not need to be added to the builder first.
+	///
+	/// # Arguments
+	///
+	/// * `category`: The name of the category
+	/// * `group_id`: The group ID of the tile
+	///
+	/// returns: &mut TilesetBuilder
+	///
+	pub fn add_to_category<TName: Into<String>>(
 		&mut self,
-		tile: TileHandleType,
+		category: TName,
+		group_id: TileGroupId,
+	) -> &mut Self {
+		let group_ids = self.categories.entry(category.into()).or_default();
+		if !group_ids.contains(&group_id) {
+			group_ids.push(group_id);
+		}
+		self
+	}
+
+	/// Add a tile to the tileset being built, replacing any existing tile with the same group ID
+	///
+	/// The replaced tile's name is unregistered and the new tile takes over its group ID. The new
+	/// tile is added before the old one is removed, so if adding it fails, the old tile is kept
+	/// as it was. Once added, the old tile's textures are removed from the atlas, which shifts
+	/// the atlas indices of every texture added after them.
+	///
+	/// # Arguments
+	///
+	/// * `tile_handle`: The tile to add
+	/// * `group_id`: The group ID of the tile
+	/// * `texture_store`: The store of textures
+	///
+	/// returns: Result<Option<TileData>, TilesetError>
+	///
+	/// The returned `TileData` is the tile that was replaced (if any).
+	pub fn add_or_replace_tile<TStore: TextureStore>(
+		&mut self,
+		tile_handle: TileHandle,
+		group_id: TileGroupId,
 		texture_store: &TStore,
-	) -> Result<TileType, TilesetError> {
-		Ok(match tile {
-			TileHandleType::Standard(handle) => {
-				TileType::Standard(self.insert_handle(&handle, texture_store)?)
-			}
-			TileHandleType::Animated(anim) => {
-				TileType::Animated(self.create_animated(anim, texture_store)?)
-			}
-			#[cfg(feature = "variants")]
-			TileHandleType::Variant(variants) => {
-				TileType::Variant(self.create_variants(variants, texture_store)?)
+	) -> Result<Option<TileData>, TilesetError> {
+		let owner = if self.tiles.contains_key(&group_id) {
+			Some(None)
+		} else {
+			self.pages
+				.iter()
+				.position(|(.., page)| page.tiles.contains_key(&group_id))
+				.map(Some)
+		};
+		let Some(owner) = owner else {
+			return self.add_tile(tile_handle, group_id, texture_store);
+		};
+
+		// The old tile keeps its textures until the new tile is added
+		let texture_count = self.page_mut(owner).atlas_handles.len();
+		let detached = self.page_mut(owner).detach_tile(&group_id);
+		if let Err(err) = self.add_tile(tile_handle, group_id, texture_store) {
+			self.page_mut(owner).attach_tile(group_id, detached);
+			return Err(err);
+		}
+
+		let builder = self.page_mut(owner);
+		let old_indices = builder
+			.tile_indices
+			.iter()
+			.filter(|(index, id)| **index < texture_count && id.group_id == group_id)
+			.map(|(index, ..)| *index)
+			.collect::<HashSet<_>>();
+		builder.remove_textures(&old_indices, texture_store)?;
+		Ok(detached.map(|tile| tile.data))
+	}
+
+	/// Gets the builder of the given sampler page (or this builder if `None`)
+	fn page_mut(&mut self, page: Option<usize>) -> &mut Self {
+		match page {
+			Some(index) => &mut self.pages[index].1,
+			None => self,
+		}
+	}
+
+	/// Removes the tile with the given group ID from this builder (but not its pages), while
+	/// keeping its textures in the atlas
+	fn detach_tile(&mut self, group_id: &TileGroupId) -> Option<DetachedTile> {
+		let data = self.tiles.remove(group_id)?;
+		let name = self.tile_names.remove(group_id);
+		if let Some(name) = &name {
+			self.tile_ids.remove(name);
+		}
+		for alias in data.aliases() {
+			if self.tile_ids.get(alias) == Some(group_id) {
+				self.tile_ids.remove(alias);
 			}
-			#[cfg(feature = "auto-tile")]
-			TileHandleType::Auto(autos) => TileType::Auto(self.create_autos(autos, texture_store)?),
+		}
+		Some(DetachedTile {
+			data,
+			name,
+			handle: self.group_handles.remove(group_id),
+			color: self.tile_colors.remove(group_id),
+			size: self.tile_sizes.remove(group_id),
 		})
 	}
 
-	#[cfg(feature = "auto-tile")]
-	fn create_autos<TStore: TextureStore>(
+	/// Restores a tile removed by [`Self::detach_tile`]
+	fn attach_tile(&mut self, group_id: TileGroupId, tile: Option<DetachedTile>) {
+		let Some(tile) = tile else {
+			return;
+		};
+		for alias in tile.data.aliases() {
+			self.tile_ids.entry(alias.clone()).or_insert(group_id);
+		}
+		if let Some(name) = tile.name {
+			self.tile_ids.insert(name.clone(), group_id);
+			self.tile_names.insert(group_id, name);
+		}
+		if let Some(handle) = tile.handle {
+			self.group_handles.insert(group_id, handle);
+		}
+		if let Some(color) = tile.color {
+			self.tile_colors.insert(group_id, color);
+		}
+		if let Some(size) = tile.size {
+			self.tile_sizes.insert(group_id, size);
+		}
+		self.tiles.insert(group_id, tile.data);
+	}
+
+	/// Removes the textures at the given atlas indices, shifting down the index of every texture
+	/// after them
+	fn remove_textures<TStore: TextureStore>(
 		&mut self,
-		autos: Vec<AutoTileHandle>,
+		removed: &HashSet<usize>,
 		texture_store: &TStore,
-	) -> Result<Vec<AutoTileData>, TilesetError> {
-		self.current_auto = Some(0);
-		let autos = autos
+	) -> Result<(), TilesetError> {
+		if removed.is_empty() {
+			return Ok(());
+		}
+
+		// Maps each old index to its new index
+		let mut remap = Vec::with_capacity(self.atlas_handles.len());
+		let mut handles = Vec::with_capacity(self.atlas_handles.len());
+		for (index, handle) in std::mem::take(&mut self.atlas_handles)
 			.into_iter()
-			.map(|auto| -> Result<AutoTileData, TilesetError> {
-				let auto = AutoTileData::new(
-					auto.rule,
-					self.create_variants(auto.variants, texture_store)?,
-				);
-				self.current_auto = Some(1 + self.current_auto.unwrap_or(0));
-				Ok(auto)
-			})
-			.flat_map(|x| x.ok())
+			.enumerate()
+		{
+			remap.push(handles.len());
+			if removed.contains(&index) {
+				self.processed.remove(&handle.id());
+			} else {
+				handles.push(handle);
+			}
+		}
+		self.atlas_handles = handles;
+
+		let is_kept = |index: &usize| !removed.contains(index);
+		self.regions = std::mem::take(&mut self.regions)
+			.into_iter()
+			.enumerate()
+			.filter(|(index, ..)| is_kept(index))
+			.map(|(.., rect)| rect)
 			.collect();
-		self.current_auto = None;
-		Ok(autos)
+		self.tile_indices = std::mem::take(&mut self.tile_indices)
+			.into_iter()
+			.filter(|(index, ..)| is_kept(index))
+			.map(|(index, id)| (remap[index], id))
+			.collect();
+		self.tile_handles = std::mem::take(&mut self.tile_handles)
+			.into_iter()
+			.filter(|(index, ..)| is_kept(index))
+			.map(|(index, handle)| (remap[index], handle))
+			.collect();
+		self.tile_trims = std::mem::take(&mut self.tile_trims)
+			.into_iter()
+			.filter(|(index, ..)| is_kept(index))
+			.map(|(index, trim)| (remap[index], trim))
+			.collect();
+		self.processed_indices = std::mem::take(&mut self.processed_indices)
+			.into_iter()
+			.filter(is_kept)
+			.map(|index| remap[index])
+			.collect();
+		for tile in self.tiles.values_mut() {
+			tile.map_indices(|index| remap[index]);
+		}
+
+		self.rebuild_atlas_builder(texture_store)
 	}
 
-	#[cfg(feature = "variants")]
+	/// Creates the builder for the given atlas page
+	fn new_page(&self, kind: &AtlasPage) -> Self {
+		let mut page = Self::default();
+		page.tile_size = self.tile_size;
+		match kind {
+			AtlasPage::Sampler(preset) => {
+				page.tile_processor = self.tile_processor.clone();
+				page.premultiply_alpha = self.premultiply_alpha;
+				page.trim = self.trim;
+				page.sampler(preset.sampler());
+			},
+			AtlasPage::Region(texture) => {
+				page.region_atlas = Some(texture.clone());
+			},
+		}
+		page
+	}
+
+	/// Removes every texture added after the first `len` textures
+	///
+	/// Since textures can't be removed from the atlas builder, it's re-created from the
+	/// remaining textures.
+	fn truncate_textures<TStore: TextureStore>(
+		&mut self,
+		len: usize,
+		texture_store: &TStore,
+	) -> Result<(), TilesetError> {
+		if self.atlas_handles.len() <= len {
+			return Ok(());
+		}
+		for index in len..self.atlas_handles.len() {
+			self.tile_indices.remove(&index);
+			self.tile_handles.remove(&index);
+			self.tile_trims.remove(&index);
+			self.processed_indices.remove(&index);
+		}
+		for handle in self.atlas_handles.drain(len..) {
+			self.processed.remove(&handle.id());
+		}
+		self.regions.truncate(len);
+		self.rebuild_atlas_builder(texture_store)
+	}
+
+	/// Re-creates the atlas builder from the textures that remain in this builder
+	///
+	/// Builders of region tiles don't pack their textures, so they're left as they are.
+	fn rebuild_atlas_builder<TStore: TextureStore>(
+		&mut self,
+		texture_store: &TStore,
+	) -> Result<(), TilesetError> {
+		if self.region_atlas.is_some() {
+			return Ok(());
+		}
+		let mut atlas_builder = TileAtlasBuilder::default();
+		atlas_builder.max_columns(self.max_columns);
+		for handle in &self.atlas_handles {
+			let id = handle.id();
+			let texture = self
+				.processed
+				.get(&id)
+				.or_else(|| texture_store.get(id))
+				.ok_or(TilesetError::ImageNotFound)?;
+			atlas_builder
+				.add_texture(handle.clone_weak(), texture)
+				.map_err(|err| TilesetError::AtlasError(err))?;
+		}
+		self.atlas_builder = atlas_builder;
+		Ok(())
+	}
+
+	fn get_tile_type<TStore: TextureStore>(
+		&mut self,
+		tile: TileHandleType,
+		texture_store: &TStore,
+	) -> Result<TileType, TilesetError> {
+		Ok(match tile {
+			TileHandleType::Standard(handle) => {
+				TileType::Standard(self.insert_handle(&handle, texture_store)?)
+			}
+			TileHandleType::Animated(anim) => {
+				TileType::Animated(self.create_animated(anim, texture_store)?)
+			}
+			TileHandleType::MultiAnimated(tracks) => {
+				if tracks.is_empty() {
+					return Err(TilesetError::InvalidData {
+						expected: String::from("at least one animation track"),
+						found: String::from("no animation tracks"),
+					});
+				}
+				let tracks = tracks
+					.into_iter()
+					.map(|(track, anim)| Ok((track, self.create_animated(anim, texture_store)?)))
+					.collect::<Result<_, TilesetError>>()?;
+				TileType::MultiAnimated(tracks)
+			}
+			TileHandleType::AtlasRegion(handle, rect) => {
+				TileType::Standard(self.insert_region(&handle, rect, texture_store)?)
+			}
+			#[cfg(feature = "variants")]
+			TileHandleType::Variant(variants) => {
+				TileType::Variant(self.create_variants(variants, texture_store)?)
+			}
+			#[cfg(feature = "auto-tile")]
+			TileHandleType::Auto(autos) => TileType::Auto(self.create_autos(autos, texture_store)?),
+			TileHandleType::Empty => TileType::Empty,
+		})
+	}
+
+	#[cfg(feature = "auto-tile")]
+	fn create_autos<TStore: TextureStore>(
+		&mut self,
+		autos: Vec<AutoTileHandle>,
+		texture_store: &TStore,
+	) -> Result<Vec<AutoTileData>, TilesetError> {
+		self.current_auto = Some(0);
+		let autos = autos
+			.into_iter()
+			.map(|auto| -> Result<AutoTileData, TilesetError> {
+				let auto = AutoTileData::new(
+					auto.rule,
+					self.create_variants(auto.variants, texture_store)?,
+				);
+				self.current_auto = Some(1 + self.current_auto.unwrap_or(0));
+				Ok(auto)
+			})
+			.collect::<Result<_, _>>();
+		self.current_auto = None;
+		autos
+	}
+
+	#[cfg(feature = "variants")]
 	fn create_variants<TStore: TextureStore>(
 		&mut self,
 		variants: Vec<VariantTileHandle>,
@@ -186,6 +1159,7 @@ impl TilesetBuilder {
 		let variants = variants
 			.into_iter()
 			.map(|variant| -> Result<VariantTileData, TilesetError> {
+				self.current_variant_transform = variant.transform;
 				let variant = VariantTileData::new(
 					variant.weight,
 					match variant.tile {
@@ -200,10 +1174,10 @@ impl TilesetBuilder {
 				self.current_variant = Some(1 + self.current_variant.unwrap_or(0));
 				Ok(variant)
 			})
-			.filter_map(|x| x.ok())
-			.collect();
+			.collect::<Result<_, _>>();
 		self.current_variant = None;
-		Ok(variants)
+		self.current_variant_transform = None;
+		variants
 	}
 
 	fn create_animated<TStore: TextureStore>(
@@ -211,14 +1185,58 @@ impl TilesetBuilder {
 		anim: AnimatedTileHandle,
 		texture_store: &TStore,
 	) -> Result<AnimatedTileData, TilesetError> {
+		if !anim.regions.is_empty() && anim.regions.len() != anim.frames.len() {
+			return Err(TilesetError::InvalidData {
+				expected: format!("{} animation frame regions", anim.frames.len()),
+				found: format!("{} regions", anim.regions.len()),
+			});
+		}
+		if let Some(start_frame) = anim.start_frame {
+			if !anim.frames.is_empty() && start_frame >= anim.frames.len() {
+				return Err(TilesetError::InvalidStartFrame {
+					tile: self.current_name.clone(),
+					frame: start_frame,
+					frame_count: anim.frames.len(),
+				});
+			}
+		}
+
+		// Every frame is checked up front so that a mismatched frame doesn't leave the others packed
+		let mut first_size = None;
+		for (frame_index, frame) in anim.frames.iter().enumerate() {
+			let size = match anim.regions.get(frame_index) {
+				Some(region) => region.size(),
+				None => texture_store
+					.get(frame)
+					.ok_or(TilesetError::ImageNotFound)?
+					.size(),
+			};
+			match first_size {
+				None => first_size = Some(size),
+				Some(first_size) if first_size != size => {
+					return Err(TilesetError::InconsistentFrameSizes {
+						tile: self.current_name.clone(),
+						frame: frame_index,
+					});
+				},
+				Some(..) => {},
+			}
+		}
+
 		let (mut start, mut end) = (-1, -1);
-		for frame in &anim.frames {
-			let index = self.insert_handle(frame, texture_store)?;
+		for (frame_index, frame) in anim.frames.iter().enumerate() {
+			self.current_animated = true;
+			let index = match anim.regions.get(frame_index) {
+				Some(region) => self.insert_crop(frame, *region, texture_store),
+				None => self.insert_handle(frame, texture_store),
+			};
+			self.current_animated = false;
+			let index = index?;
+			let index = i32::try_from(index).map_err(|_| TilesetError::AtlasIndexOverflow(index))?;
 			if start == -1 {
-				start = index as i32;
-			} else {
-				end = index as i32;
+				start = index;
 			}
+			end = index;
 		}
 
 		if start < 0 || end < 0 {
@@ -228,11 +1246,12 @@ impl TilesetBuilder {
 			});
 		}
 
-		Ok(AnimatedTileData::new(
-			anim.speed,
-			start as usize,
-			end as usize,
-		))
+		let data = AnimatedTileData::new(anim.speed, start as usize, end as usize)
+			.with_reversed(anim.reversed);
+		Ok(match anim.start_frame {
+			Some(start_frame) => data.with_start_frame(start_frame),
+			None => data,
+		})
 	}
 
 	fn insert_handle<TStore: TextureStore>(
@@ -240,23 +1259,236 @@ impl TilesetBuilder {
 		handle: &Handle<Image>,
 		textures: &TStore,
 	) -> Result<usize, TilesetError> {
-		if let Some(texture) = textures.get(handle) {
-			self.add_texture(handle, texture)
-		} else {
-			Err(TilesetError::ImageNotFound)
+		let texture = textures.get(handle).ok_or(TilesetError::ImageNotFound)?;
+		let texture = pixels::to_rgba8(texture)?;
+		match (self.process_texture(&texture), texture) {
+			(Some(processed), _) => self.insert_processed(handle, processed),
+			// Converted textures don't exist in the store, so they're tracked like processed ones
+			(None, Cow::Owned(converted)) => self.insert_processed(handle, converted),
+			(None, Cow::Borrowed(texture)) => self.add_texture(handle, texture),
+		}
+	}
+
+	/// Registers the given region of this builder's atlas texture as a texture, without copying
+	/// any of its pixels
+	fn insert_region<TStore: TextureStore>(
+		&mut self,
+		handle: &Handle<Image>,
+		rect: Rect,
+		textures: &TStore,
+	) -> Result<usize, TilesetError> {
+		let atlas = textures.get(handle).ok_or(TilesetError::ImageNotFound)?;
+		let atlas = pixels::to_rgba8(atlas)?;
+		let (min, size) = self.region_bounds(&atlas, rect)?;
+		self.check_tile_size(size.as_vec2())?;
+
+		let index = self.atlas_handles.len();
+		self.atlas_handles.push(handle.clone_weak());
+		self.regions.push(rect);
+		self.register_texture(index, handle, size, || {
+			pixels::average_region_color(&atlas, min, size)
+		});
+		Ok(index)
+	}
+
+	/// Inserts a copy of the given region of a texture as its own texture
+	fn insert_crop<TStore: TextureStore>(
+		&mut self,
+		handle: &Handle<Image>,
+		rect: Rect,
+		textures: &TStore,
+	) -> Result<usize, TilesetError> {
+		let texture = textures.get(handle).ok_or(TilesetError::ImageNotFound)?;
+		let texture = pixels::to_rgba8(texture)?;
+		let (min, size) = self.region_bounds(&texture, rect)?;
+		let region = pixels::crop(&texture, min.x, min.y, size.x, size.y).ok_or_else(|| {
+			TilesetError::InvalidRegion {
+				tile: self.current_name.clone(),
+				rect,
+			}
+		})?;
+		let region = self.process_texture(&region).unwrap_or(region);
+		self.insert_processed(handle, region)
+	}
+
+	/// Gets the position and size of the given region of a texture in whole pixels
+	///
+	/// Returns an error if the region is empty, isn't made up of whole pixels, or isn't fully
+	/// contained within the texture.
+	fn region_bounds(&self, texture: &Image, rect: Rect) -> Result<(UVec2, UVec2), TilesetError> {
+		let (width, height) = pixels::dimensions(texture);
+		let is_whole = |value: f32| value >= 0.0 && value.fract() == 0.0;
+		let is_valid = [rect.min.x, rect.min.y, rect.max.x, rect.max.y]
+			.into_iter()
+			.all(is_whole)
+			&& !rect.is_empty()
+			&& rect.max.x <= width as f32
+			&& rect.max.y <= height as f32;
+		if !is_valid {
+			return Err(TilesetError::InvalidRegion {
+				tile: self.current_name.clone(),
+				rect,
+			});
+		}
+		Ok((rect.min.as_uvec2(), rect.size().as_uvec2()))
+	}
+
+	/// Inserts a texture that doesn't exist in the user's texture store
+	///
+	/// Since the atlas builder needs to be able to retrieve the texture when building, we track
+	/// these ourselves and add them to the atlas under a new handle.
+	fn insert_processed(
+		&mut self,
+		handle: &Handle<Image>,
+		texture: Image,
+	) -> Result<usize, TilesetError> {
+		let (texture, trim) = self.trim_texture(texture);
+		let id = self.processed_id(handle);
+		self.current_trim = trim;
+		let index = self.add_texture_as(handle, &Handle::weak(id), &texture);
+		self.current_trim = None;
+		let index = index?;
+		self.processed.insert(id, texture);
+		self.processed_count += 1;
+		Ok(index)
+	}
+
+	/// Trims the given texture to the bounds of its non-transparent pixels (if enabled)
+	///
+	/// Returns the texture as-is if it doesn't need trimming (including fully transparent
+	/// textures), along with the border that was trimmed (if any).
+	fn trim_texture(&self, texture: Image) -> (Image, Option<TileTrim>) {
+		if !self.trim || self.current_animated {
+			return (texture, None);
+		}
+
+		let source_size = texture.size().as_uvec2();
+		let trimmed = pixels::opaque_bounds(&texture)
+			.filter(|(.., size)| *size != source_size)
+			.and_then(|(offset, size)| {
+				let trimmed = pixels::crop(&texture, offset.x, offset.y, size.x, size.y)?;
+				let trim = TileTrim {
+					offset,
+					source_size,
+				};
+				Some((trimmed, trim))
+			});
+		match trimmed {
+			Some((trimmed, trim)) => (trimmed, Some(trim)),
+			None => (texture, None),
 		}
 	}
 
+	/// Creates the ID used to add a processed texture to the atlas
+	///
+	/// This is derived from the original handle and the number of textures processed so far
+	/// (rather than being random) so that building the same tileset always produces the same
+	/// result.
+	fn processed_id(&self, handle: &Handle<Image>) -> HandleId {
+		let mut hasher = DefaultHasher::new();
+		handle.id().hash(&mut hasher);
+		self.processed_count.hash(&mut hasher);
+		HandleId::new(Image::TYPE_UUID, hasher.finish())
+	}
+
+	/// Applies any processing required by the current tile to the given texture
+	///
+	/// Returns `None` if the texture can be used as-is.
+	fn process_texture(&self, texture: &Image) -> Option<Image> {
+		#[cfg(feature = "variants")]
+		let variant_transform = self.current_variant_transform;
+		#[cfg(not(feature = "variants"))]
+		let variant_transform = None;
+
+		let transforms = [self.current_transform, variant_transform];
+		if transforms.iter().all(Option::is_none)
+			&& self.current_color_key.is_none()
+			&& self.tile_processor.is_none()
+			&& !self.premultiply_alpha
+			&& !(self.trim && !self.current_animated)
+		{
+			return None;
+		}
+
+		let mut processed = texture.clone();
+		if let Some(color) = self.current_color_key {
+			pixels::color_key(&mut processed, color);
+		}
+		for transform in transforms.into_iter().flatten() {
+			processed = pixels::transform(&processed, transform);
+		}
+		if let Some(processor) = &self.tile_processor {
+			processed = processor(&self.current_name, processed);
+		}
+		if self.premultiply_alpha {
+			pixels::premultiply_alpha(&mut processed);
+		}
+		Some(processed)
+	}
+
 	pub fn add_texture(
 		&mut self,
 		handle: &Handle<Image>,
 		texture: &Image,
 	) -> Result<usize, TilesetError> {
+		self.add_texture_as(handle, handle, texture)
+	}
+
+	/// Adds a texture to the atlas using `atlas_handle`, while registering it under `handle`
+	fn add_texture_as(
+		&mut self,
+		handle: &Handle<Image>,
+		atlas_handle: &Handle<Image>,
+		texture: &Image,
+	) -> Result<usize, TilesetError> {
+		if !pixels::is_rgba8(texture) {
+			return Err(TilesetError::UnsupportedFormat(texture.texture_descriptor.format));
+		}
+
+		// Trimmed textures are validated by their untrimmed size
+		let size = self
+			.current_trim
+			.map(|trim| trim.source_size.as_vec2())
+			.unwrap_or_else(|| texture.size());
+		self.check_tile_size(size)?;
+
 		let index = self
 			.atlas_builder
-			.add_texture(handle.clone_weak(), texture)
+			.add_texture(atlas_handle.clone_weak(), texture)
 			.map_err(|err| TilesetError::AtlasError(err))?;
+		self.atlas_handles.push(atlas_handle.clone_weak());
+		if handle != atlas_handle {
+			self.processed_indices.insert(index);
+		}
+		self.register_texture(index, handle, texture.size().as_uvec2(), || {
+			pixels::average_color(texture)
+		});
 
+		Ok(index)
+	}
+
+	/// Checks that a texture of the given size matches the [tile size](Self::tile_size) (if any)
+	fn check_tile_size(&self, found: Vec2) -> Result<(), TilesetError> {
+		match self.tile_size {
+			Some(expected) if found != expected => Err(TilesetError::InvalidTileSize {
+				tile: self.current_name.clone(),
+				expected,
+				found,
+			}),
+			_ => Ok(()),
+		}
+	}
+
+	/// Registers the texture at the given atlas index as a texture of the current tile
+	///
+	/// The given `color` is only computed for the first texture of each tile.
+	fn register_texture<F: FnOnce() -> [u8; 4]>(
+		&mut self,
+		index: usize,
+		handle: &Handle<Image>,
+		size: UVec2,
+		color: F,
+	) {
 		let id = PartialTileId {
 			group_id: self.current_group,
 			#[cfg(feature = "variants")]
@@ -266,7 +1498,1470 @@ impl TilesetBuilder {
 		};
 		self.tile_indices.insert(index, id);
 		self.tile_handles.insert(index, handle.clone_weak());
+		self.tile_colors.entry(self.current_group).or_insert_with(color);
+		self.tile_sizes.entry(self.current_group).or_insert(size);
+		if let Some(trim) = self.current_trim {
+			self.tile_trims.insert(index, trim);
+		}
+	}
+}
 
-		Ok(index)
+#[cfg(test)]
+mod tests {
+	use bevy::prelude::{Handle, Image, Rect, UVec2, Vec2};
+	use bevy::render::render_resource::{Extent3d, FilterMode, TextureDimension, TextureFormat};
+	use bevy::render::texture::ImageSampler;
+	use bevy_tileset_tiles::prelude::{
+		AnimatedTileHandle, AnimationStrip, StripDirection, TileCollision, TileDef, TileHandle,
+		TileHandleType, TileTransform, DEFAULT_PIVOT,
+	};
+
+	use crate::prelude::*;
+	use crate::test_utils::{solid_image, TestTextureStore};
+	use bevy_tile_atlas::TextureStore;
+
+	#[test]
+	fn should_pad_to_power_of_two() {
+		let mut store = TestTextureStore::default();
+		let mut builder = TilesetBuilder::new(Some(3));
+		builder.power_of_two(true);
+		for group_id in 0..9 {
+			let handle = store.add(solid_image(16, 16, [255; 4]));
+			let tile = TileHandle::new_standard(format!("Tile {}", group_id), handle);
+			builder.add_tile(tile, group_id, &store).unwrap();
+		}
+
+		let tileset = builder.build("Power of Two", 0, &mut store).unwrap();
+		assert_eq!(Vec2::new(64.0, 64.0), tileset.size());
+
+		let texture = store.get(tileset.texture()).unwrap();
+		assert_eq!(Vec2::new(64.0, 64.0), texture.size());
+
+		// Existing tiles should not have shifted
+		let first = tileset.atlas().textures[0];
+		assert_eq!(Vec2::ZERO, first.min);
+		assert_eq!(Vec2::new(16.0, 16.0), first.max);
+	}
+
+	#[test]
+	fn should_error_on_oversized_atlas() {
+		let mut store = TestTextureStore::default();
+		let handle = store.add(solid_image(16, 16, [255; 4]));
+		let build = |store: &mut TestTextureStore, columns: u32| {
+			let mut builder = TilesetBuilder::default();
+			builder.grid_columns(Some(columns)).power_of_two(true);
+			let tile = TileHandle::new_standard("Tile", handle.clone());
+			builder.add_tile(tile, 0, &*store).unwrap();
+			builder.build("Oversized", 0, store)
+		};
+
+		// The grid is wider than an atlas can be
+		assert!(matches!(
+			build(&mut store, u32::MAX),
+			Err(TilesetError::AtlasSizeOverflow { .. })
+		));
+
+		// The grid fits, but its width can't be padded to a power of two
+		let columns = (1 << 31) / 16 + 1;
+		assert!(matches!(
+			build(&mut store, columns),
+			Err(TilesetError::AtlasSizeOverflow { .. })
+		));
+	}
+
+	#[test]
+	fn should_error_on_empty_tileset() {
+		let mut store = TestTextureStore::default();
+		let builder = TilesetBuilder::default();
+		let result = builder.build("Empty", 0, &mut store);
+		assert!(matches!(result, Err(TilesetError::EmptyTileset)));
+	}
+
+	#[test]
+	fn should_replace_tile() {
+		let mut store = TestTextureStore::default();
+		let mut builder = TilesetBuilder::default();
+
+		let handle = store.add(solid_image(16, 16, [255; 4]));
+		let tile = TileHandle::new_standard("Original", handle);
+		builder.add_tile(tile, 7, &store).unwrap();
+
+		let handle = store.add(solid_image(16, 16, [0; 4]));
+		let tile = TileHandle::new_standard("Replacement", handle.clone());
+		assert!(matches!(
+			builder.add_tile(tile.clone(), 7, &store),
+			Err(TilesetError::TileAlreadyExists(7))
+		));
+
+		// A replacement that fails to be added keeps the original tile
+		let missing = TileHandle::new_standard("Missing", Handle::default());
+		assert!(builder.add_or_replace_tile(missing, 7, &store).is_err());
+
+		let replaced = builder.add_or_replace_tile(tile, 7, &store).unwrap();
+		assert_eq!(Some("Original"), replaced.as_ref().map(|data| data.name()));
+		let other = TileHandle::new_standard("Other", store.add(solid_image(16, 16, [9; 4])));
+		builder.add_tile(other, 8, &store).unwrap();
+
+		let tileset = builder.build("Replaced", 0, &mut store).unwrap();
+		// The original tile's texture was dropped from the atlas
+		assert_eq!(2, tileset.tile_count());
+		assert_eq!(2, tileset.atlas().textures.len());
+		assert_eq!(Some(0), tileset.get_base_tile_index("Replacement"));
+		assert_eq!(Some(1), tileset.get_base_tile_index("Other"));
+		assert_eq!(Some(&7), tileset.get_tile_group_id("Replacement"));
+		assert_eq!(None, tileset.get_tile_group_id("Original"));
+		assert_eq!(
+			Some(&String::from("Replacement")),
+			tileset.get_tile_name(&7)
+		);
+	}
+
+	#[test]
+	fn should_rotate_tile() {
+		let mut store = TestTextureStore::default();
+		let mut builder = TilesetBuilder::default();
+
+		// A 2x2 image with a distinct color per pixel
+		let mut source = solid_image(2, 2, [0; 4]);
+		source.data = vec![
+			1, 1, 1, 255, 2, 2, 2, 255, // Top row
+			3, 3, 3, 255, 4, 4, 4, 255, // Bottom row
+		];
+		let handle = store.add(source);
+		let mut tile = TileHandle::new_standard("Rotated", handle);
+		tile.transform = Some(TileTransform::Rotate90);
+		builder.add_tile(tile, 0, &store).unwrap();
+
+		let tileset = builder.build("Rotated", 0, &mut store).unwrap();
+		let texture = store.get(tileset.texture()).unwrap();
+		assert_eq!(
+			vec![
+				3, 3, 3, 255, 1, 1, 1, 255, // Top row
+				4, 4, 4, 255, 2, 2, 2, 255, // Bottom row
+			],
+			texture.data
+		);
+	}
+
+	#[test]
+	fn should_error_on_mismatched_tile_size() {
+		let mut store = TestTextureStore::default();
+		let mut builder = TilesetBuilder::default();
+		builder.tile_size(Some(Vec2::new(16.0, 16.0)));
+
+		let handle = store.add(solid_image(16, 16, [255; 4]));
+		let tile = TileHandle::new_standard("Small", handle);
+		builder.add_tile(tile, 0, &store).unwrap();
+
+		let handle = store.add(solid_image(32, 32, [255; 4]));
+		let tile = TileHandle::new_standard("Large", handle);
+		let result = builder.add_tile(tile, 1, &store);
+		assert!(
+			matches!(result, Err(TilesetError::InvalidTileSize { ref tile, .. }) if tile == "Large")
+		);
+	}
+
+	#[test]
+	fn should_compute_average_color() {
+		let mut store = TestTextureStore::default();
+		let mut builder = TilesetBuilder::default();
+
+		// Half red, half fully transparent
+		let mut source = solid_image(2, 1, [0; 4]);
+		source.data = vec![255, 0, 0, 255, 0, 0, 255, 0];
+		let handle = store.add(source);
+		let tile = TileHandle::new_standard("Red", handle);
+		builder.add_tile(tile, 0, &store).unwrap();
+
+		let tileset = builder.build("Colors", 0, &mut store).unwrap();
+		assert_eq!(Some([255, 0, 0, 127]), tileset.tile_average_color("Red"));
+		assert_eq!(None, tileset.tile_average_color("Missing"));
+	}
+
+	#[test]
+	fn should_build_deterministically() {
+		let mut store = TestTextureStore::default();
+		let handles = (0..4)
+			.map(|index| store.add(solid_image(16, 16, [index * 50; 4])))
+			.collect::<Vec<_>>();
+
+		let build = |store: &mut TestTextureStore| {
+			let mut builder = TilesetBuilder::default();
+			for (group_id, handle) in handles.iter().enumerate() {
+				let mut tile = TileHandle::new_standard(format!("Tile {}", group_id), handle.clone());
+				tile.transform = Some(TileTransform::FlipX);
+				builder.add_tile(tile, group_id as u32, store).unwrap();
+			}
+			builder.build("Deterministic", 0, store).unwrap()
+		};
+
+		let first = build(&mut store);
+		let second = build(&mut store);
+		assert_eq!(first.tile_indices, second.tile_indices);
+		assert_eq!(first.atlas().textures, second.atlas().textures);
+		assert_eq!(
+			store.get(first.texture()).map(|image| &image.data),
+			store.get(second.texture()).map(|image| &image.data)
+		);
+	}
+
+	#[test]
+	fn should_apply_color_key() {
+		let mut store = TestTextureStore::default();
+		let mut builder = TilesetBuilder::default();
+
+		// A tile with a magenta background and a single white pixel
+		let mut source = solid_image(2, 1, [255, 0, 255, 255]);
+		source.data[4..8].copy_from_slice(&[255; 4]);
+		let handle = store.add(source);
+		let mut tile = TileHandle::new_standard("Keyed", handle);
+		tile.color_key = Some([255, 0, 255, 255]);
+		builder.add_tile(tile, 0, &store).unwrap();
+
+		let tileset = builder.build("Keyed", 0, &mut store).unwrap();
+		let texture = store.get(tileset.texture()).unwrap();
+		assert_eq!(vec![0, 0, 0, 0, 255, 255, 255, 255], texture.data);
+	}
+
+	#[test]
+	fn should_validate_names() {
+		let mut store = TestTextureStore::default();
+		let mut builder = TilesetBuilder::default();
+		let handle = store.add(solid_image(16, 16, [255; 4]));
+		builder
+			.add_tile(TileHandle::new_standard("Grass", handle), 0, &store)
+			.unwrap();
+
+		let tileset = builder.build("Names", 0, &mut store).unwrap();
+		assert_eq!(Ok(()), tileset.validate_names(&["Grass"]));
+		assert_eq!(
+			Err(vec![String::from("Gras"), String::from("Dirt")]),
+			tileset.validate_names(&["Grass", "Gras", "Dirt"])
+		);
+	}
+
+	#[test]
+	fn should_group_tiles_into_categories() {
+		let mut store = TestTextureStore::default();
+		let mut builder = TilesetBuilder::default();
+		for (group_id, name) in ["Grass", "Dirt", "Rock"].into_iter().enumerate() {
+			let handle = store.add(solid_image(16, 16, [255; 4]));
+			builder
+				.add_tile(TileHandle::new_standard(name, handle), group_id as u32, &store)
+				.unwrap();
+		}
+		builder
+			.add_to_category("Terrain", 0)
+			.add_to_category("Terrain", 1)
+			.add_to_category("Terrain", 1)
+			.add_to_category("Props", 2)
+			.add_to_category("Walkable", 0);
+
+		let tileset = builder.build("Categories", 0, &mut store).unwrap();
+		assert_eq!(Some(&[0, 1][..]), tileset.tiles_in_category("Terrain"));
+		assert_eq!(Some(&[2][..]), tileset.tiles_in_category("Props"));
+		assert_eq!(Some(&[0][..]), tileset.tiles_in_category("Walkable"));
+		assert_eq!(None, tileset.tiles_in_category("Decals"));
+		assert_eq!(
+			vec!["Props", "Terrain", "Walkable"],
+			tileset.categories().collect::<Vec<_>>()
+		);
+	}
+
+	#[test]
+	fn should_resolve_interned_keys() {
+		let mut store = TestTextureStore::default();
+		let mut builder = TilesetBuilder::default();
+		for (group_id, name) in ["Grass", "Dirt"].into_iter().enumerate() {
+			let handle = store.add(solid_image(16, 16, [255; 4]));
+			builder
+				.add_tile(TileHandle::new_standard(name, handle), group_id as u32, &store)
+				.unwrap();
+		}
+
+		let tileset = builder.build("Interned", 4, &mut store).unwrap();
+		let key = tileset.intern("Dirt").unwrap();
+		assert_eq!(1, key.group_id());
+		assert_eq!(4, key.tileset_id());
+		assert_eq!(tileset.get_tile_index("Dirt"), tileset.index_of(key));
+		assert_eq!(None, tileset.intern("Water"));
+
+		let handle = store.add(solid_image(16, 16, [255; 4]));
+		let mut builder = TilesetBuilder::default();
+		builder
+			.add_tile(TileHandle::new_standard("Dirt", handle), 1, &store)
+			.unwrap();
+		let other = builder.build("Other", 5, &mut store).unwrap();
+		assert_eq!(None, other.index_of(key));
+	}
+
+	#[test]
+	fn should_count_tiles_by_type() {
+		let mut store = TestTextureStore::default();
+		let mut builder = TilesetBuilder::default();
+		for (group_id, name) in ["Grass", "Dirt"].into_iter().enumerate() {
+			let handle = store.add(solid_image(16, 16, [255; 4]));
+			builder
+				.add_tile(TileHandle::new_standard(name, handle), group_id as u32, &store)
+				.unwrap();
+		}
+		let frames = (0..3)
+			.map(|_| store.add(solid_image(16, 16, [255; 4])))
+			.collect();
+		let tile = TileHandle::new_animated(
+			"Water",
+			AnimatedTileHandle {
+				speed: 1.0,
+				frames,
+				regions: Vec::new(),
+				..Default::default()
+			},
+		);
+		builder.add_tile(tile, 2, &store).unwrap();
+		builder
+			.add_tile(TileHandle::new_empty("Air"), 3, &store)
+			.unwrap();
+
+		let tileset = builder.build("Counted", 0, &mut store).unwrap();
+		let counts = tileset.tile_count_by_type();
+		assert_eq!(
+			TileTypeCounts {
+				standard: 2,
+				animated: 1,
+				empty: 1,
+				..Default::default()
+			},
+			counts
+		);
+		assert_eq!(4, counts.total());
+		assert_eq!(5, tileset.tile_count());
+	}
+
+	#[test]
+	fn should_map_animation_frames() {
+		let mut store = TestTextureStore::default();
+		let mut builder = TilesetBuilder::default();
+
+		let handle = store.add(solid_image(16, 16, [255; 4]));
+		builder
+			.add_tile(TileHandle::new_standard("Static", handle), 0, &store)
+			.unwrap();
+		let frames = (0..3)
+			.map(|_| store.add(solid_image(16, 16, [255; 4])))
+			.collect();
+		let tile = TileHandle::new_animated(
+			"Water",
+			AnimatedTileHandle {
+				speed: 1.0,
+				frames,
+				regions: Vec::new(),
+				..Default::default()
+			},
+		);
+		builder.add_tile(tile, 1, &store).unwrap();
+
+		let tileset = builder.build("Animated", 0, &mut store).unwrap();
+		assert_eq!(Some(1), tileset.animation_frame_index("Water", 0));
+		assert_eq!(Some(2), tileset.animation_frame_index("Water", 1));
+		assert_eq!(Some(3), tileset.animation_frame_index("Water", 2));
+		assert_eq!(None, tileset.animation_frame_index("Water", 3));
+		assert_eq!(None, tileset.animation_frame_index("Static", 0));
+
+		let info = tileset.animation_info("Water").unwrap();
+		assert_eq!(3, info.frame_count);
+		assert_eq!(1.0, info.speed);
+		assert!(tileset.is_animated("Water"));
+		assert!(!tileset.is_animated("Static"));
+		assert!(!tileset.is_animated("Missing"));
+	}
+
+	#[test]
+	fn should_collect_source_image_handles() {
+		let mut store = TestTextureStore::default();
+		let mut builder = TilesetBuilder::default();
+
+		let handle = store.add(solid_image(16, 16, [255; 4]));
+		builder
+			.add_tile(TileHandle::new_standard("Static", handle.clone()), 0, &store)
+			.unwrap();
+		let frames = (0..2)
+			.map(|_| store.add(solid_image(16, 16, [127; 4])))
+			.collect::<Vec<_>>();
+		let tile = TileHandle::new_animated(
+			"Water",
+			AnimatedTileHandle {
+				speed: 1.0,
+				frames: frames.clone(),
+				regions: Vec::new(),
+				..Default::default()
+			},
+		);
+		builder.add_tile(tile, 1, &store).unwrap();
+		// Reusing an image should not duplicate its handle
+		builder
+			.add_tile(TileHandle::new_standard("Copy", handle.clone()), 2, &store)
+			.unwrap();
+
+		let tileset = builder.build("Sources", 0, &mut store).unwrap();
+		let expected = vec![handle, frames[0].clone(), frames[1].clone()];
+		assert_eq!(expected, tileset.source_image_handles());
+	}
+
+	#[test]
+	fn should_carry_tile_collision() {
+		let mut store = TestTextureStore::default();
+		let mut builder = TilesetBuilder::default();
+
+		let def: TileDef = ron::from_str(
+			r#"(name: "Wall", tile: Standard("wall.png"), collision: Solid)"#,
+		)
+		.unwrap();
+		assert_eq!(TileCollision::Solid, def.collision);
+
+		let mut tile = TileHandle::new_standard("Wall", store.add(solid_image(16, 16, [255; 4])));
+		tile.collision = def.collision;
+		builder.add_tile(tile, 0, &store).unwrap();
+		let handle = store.add(solid_image(16, 16, [0; 4]));
+		builder
+			.add_tile(TileHandle::new_standard("Floor", handle), 1, &store)
+			.unwrap();
+
+		let tileset = builder.build("Collisions", 0, &mut store).unwrap();
+		assert_eq!(Some(&TileCollision::Solid), tileset.tile_collision("Wall"));
+		assert_eq!(Some(&TileCollision::None), tileset.tile_collision("Floor"));
+		assert_eq!(None, tileset.tile_collision("Missing"));
+
+		let shape = TileCollision::Points(vec![Vec2::ZERO, Vec2::new(16.0, 0.0), Vec2::ONE]);
+		let serialized = ron::to_string(&shape).unwrap();
+		assert_eq!(shape, ron::from_str::<TileCollision>(&serialized).unwrap());
+	}
+
+	#[test]
+	fn should_query_tiles_by_tag() {
+		let mut store = TestTextureStore::default();
+		let mut builder = TilesetBuilder::default();
+
+		let def: TileDef = ron::from_str(
+			r#"(name: "Water", tile: Standard("water.png"), tags: ["water", "impassable"])"#,
+		)
+		.unwrap();
+		assert_eq!(vec!["water", "impassable"], def.tags);
+
+		for (group_id, (name, tags)) in [("Water", def.tags), ("Grass", vec![])]
+			.into_iter()
+			.enumerate()
+		{
+			let handle = store.add(solid_image(16, 16, [255; 4]));
+			let mut tile = TileHandle::new_standard(name, handle);
+			tile.tags = tags;
+			builder.add_tile(tile, group_id as TileGroupId, &store).unwrap();
+		}
+
+		let tileset = builder.build("Tags", 0, &mut store).unwrap();
+		assert_eq!(
+			Some(&[String::from("water"), String::from("impassable")][..]),
+			tileset.tile_tags("Water")
+		);
+		assert_eq!(Some(&[][..]), tileset.tile_tags("Grass"));
+		assert_eq!(None, tileset.tile_tags("Missing"));
+		assert_eq!(vec![&0], tileset.tiles_with_tag("water").collect::<Vec<_>>());
+		assert_eq!(0, tileset.tiles_with_tag("lava").count());
+	}
+
+	#[test]
+	fn should_not_pack_empty_tiles() {
+		let mut store = TestTextureStore::default();
+		let mut builder = TilesetBuilder::default();
+
+		let handle = store.add(solid_image(16, 16, [255; 4]));
+		builder
+			.add_tile(TileHandle::new_standard("Grass", handle), 0, &store)
+			.unwrap();
+		let tileset = builder.build("No Air", 0, &mut store).unwrap();
+		assert_eq!(None, tileset.empty_index());
+
+		let mut builder = TilesetBuilder::default();
+		let handle = store.add(solid_image(16, 16, [255; 4]));
+		builder
+			.add_tile(TileHandle::new_standard("Grass", handle), 0, &store)
+			.unwrap();
+		builder
+			.add_tile(TileHandle::new_empty("Air"), 1, &store)
+			.unwrap();
+		let tileset = builder.build("Air", 0, &mut store).unwrap();
+
+		assert_eq!(1, tileset.tile_count());
+		assert_eq!(Some(&1), tileset.get_tile_group_id("Air"));
+		assert_eq!(Some(TileIndex::Empty), tileset.empty_index());
+		let index = tileset.get_tile_index("Air").unwrap();
+		assert!(index.is_empty());
+		assert!(!tileset.get_tile_index("Grass").unwrap().is_empty());
+	}
+
+	#[test]
+	fn should_apply_tile_processor() {
+		let mut store = TestTextureStore::default();
+		let mut builder = TilesetBuilder::default();
+		builder.tile_processor(|name, image| {
+			if name == "Tinted" {
+				solid_image(16, 16, [255, 0, 0, 255])
+			} else {
+				image
+			}
+		});
+
+		for (group_id, name) in ["Tinted", "Plain"].into_iter().enumerate() {
+			let handle = store.add(solid_image(16, 16, [255; 4]));
+			let tile = TileHandle::new_standard(name, handle);
+			builder.add_tile(tile, group_id as TileGroupId, &store).unwrap();
+		}
+
+		let tileset = builder.build("Processed", 0, &mut store).unwrap();
+		assert_eq!(Some([255, 0, 0, 255]), tileset.tile_average_color("Tinted"));
+		assert_eq!(Some([255; 4]), tileset.tile_average_color("Plain"));
+	}
+
+	#[test]
+	fn should_generate_lod_textures() {
+		let mut store = TestTextureStore::default();
+		let mut builder = TilesetBuilder::default();
+		builder.lod_levels(3);
+		for group_id in 0..2 {
+			let handle = store.add(solid_image(16, 16, [255, 0, 0, 255]));
+			let tile = TileHandle::new_standard(format!("Tile {}", group_id), handle);
+			builder.add_tile(tile, group_id, &store).unwrap();
+		}
+
+		let tileset = builder.build("LODs", 0, &mut store).unwrap();
+		assert_eq!(3, tileset.lod_levels());
+		assert_eq!(Some(tileset.texture()), tileset.atlas_for_lod(0));
+		assert!(tileset.atlas_for_lod(3).is_none());
+
+		let full = store.get(tileset.texture()).unwrap();
+		for level in 1..3 {
+			let lod = store.get(tileset.atlas_for_lod(level).unwrap()).unwrap();
+			assert_eq!(full.size() / 2f32.powi(level as i32), lod.size());
+			assert_eq!([255, 0, 0, 255], lod.data[..4]);
+		}
+	}
+
+	#[test]
+	fn should_clamp_lod_levels_to_atlas_size() {
+		let mut store = TestTextureStore::default();
+		let mut builder = TilesetBuilder::default();
+		builder.lod_levels(100);
+		let handle = store.add(solid_image(16, 16, [255; 4]));
+		builder
+			.add_tile(TileHandle::new_standard("Tile", handle), 0, &store)
+			.unwrap();
+
+		// 16x16 -> 8x8 -> 4x4 -> 2x2 -> 1x1
+		let tileset = builder.build("LODs", 0, &mut store).unwrap();
+		assert_eq!(5, tileset.lod_levels());
+		let smallest = store.get(tileset.atlas_for_lod(4).unwrap()).unwrap();
+		assert_eq!(Vec2::ONE, smallest.size());
+	}
+
+	#[test]
+	fn should_not_bleed_padding_into_lod_textures() {
+		let mut store = TestTextureStore::default();
+		let mut builder = TilesetBuilder::default();
+		builder
+			.lod_levels(2)
+			.power_of_two(true)
+			.gutter_color([0, 0, 255, 255]);
+		let handle = store.add(solid_image(3, 3, [255, 0, 0, 255]));
+		builder
+			.add_tile(TileHandle::new_standard("Tile", handle), 0, &store)
+			.unwrap();
+
+		// The 3x3 tile is padded to 4x4, so its bottom-right LOD pixel overlaps the padding
+		let tileset = builder.build("LODs", 0, &mut store).unwrap();
+		let lod = store.get(tileset.atlas_for_lod(1).unwrap()).unwrap();
+		assert_eq!(Vec2::splat(2.0), lod.size());
+		for pixel in lod.data.chunks(4) {
+			assert_eq!([255, 0, 0, 255], pixel);
+		}
+	}
+
+	#[test]
+	fn should_get_tile_size_of() {
+		let mut store = TestTextureStore::default();
+		let mut builder = TilesetBuilder::default();
+		let handle = store.add(solid_image(16, 16, [255; 4]));
+		builder
+			.add_tile(TileHandle::new_standard("Grass", handle), 0, &store)
+			.unwrap();
+		builder
+			.add_tile(TileHandle::new_empty("Air"), 1, &store)
+			.unwrap();
+
+		let tileset = builder.build("Sizes", 0, &mut store).unwrap();
+		assert_eq!(Some(UVec2::splat(16)), tileset.tile_size_of("Grass"));
+		assert_eq!(Some(UVec2::splat(16)), tileset.tile_size_of("Air"));
+		assert_eq!(None, tileset.tile_size_of("Missing"));
+	}
+
+	#[test]
+	fn should_slice_animation_strip() {
+		let mut store = TestTextureStore::default();
+		let mut builder = TilesetBuilder::default();
+
+		let mut strip = solid_image(48, 16, [255, 0, 0, 255]);
+		// Make the last frame blue
+		for (i, pixel) in strip.data.chunks_exact_mut(4).enumerate() {
+			if i % 48 >= 32 {
+				pixel.copy_from_slice(&[0, 0, 255, 255]);
+			}
+		}
+		let handle = store.add(strip);
+		let def = AnimationStrip {
+			path: String::new(),
+			frame_count: 3,
+			frame_size: UVec2::splat(16),
+			direction: StripDirection::Horizontal,
+		};
+		let tile = TileHandle::new_animated(
+			"Strip",
+			AnimatedTileHandle {
+				speed: 1.0,
+				frames: vec![handle; def.frame_count],
+				regions: def.frame_regions(),
+				..Default::default()
+			},
+		);
+		builder.add_tile(tile, 0, &store).unwrap();
+
+		let tileset = builder.build("Strips", 0, &mut store).unwrap();
+		assert_eq!(3, tileset.animation_info("Strip").unwrap().frame_count);
+		assert_eq!(Vec2::splat(16.0), tileset.tile_size());
+		let last = tileset.animation_frame_index("Strip", 2).unwrap();
+		let preview = tileset.render_tile_preview("Strip", &store).unwrap();
+		assert_eq!([255, 0, 0, 255], preview.data[..4]);
+		let rect = tileset.atlas().textures[last];
+		let atlas = store.get(tileset.texture()).unwrap();
+		let width = atlas.texture_descriptor.size.width as usize;
+		let start = (rect.min.y as usize * width + rect.min.x as usize) * 4;
+		assert_eq!([0, 0, 255, 255], atlas.data[start..start + 4]);
+	}
+
+	#[test]
+	fn should_build_single_frame_animation() {
+		let mut store = TestTextureStore::default();
+		let mut builder = TilesetBuilder::default();
+		let tile = TileHandle::new_animated(
+			"Still",
+			AnimatedTileHandle {
+				speed: 1.0,
+				frames: vec![store.add(solid_image(16, 16, [255; 4]))],
+				regions: Vec::new(),
+				..Default::default()
+			},
+		);
+		builder.add_tile(tile, 0, &store).unwrap();
+
+		let tileset = builder.build("Single", 0, &mut store).unwrap();
+		assert_eq!(1, tileset.animation_info("Still").unwrap().frame_count);
+	}
+
+	#[test]
+	fn should_error_on_out_of_range_start_frame() {
+		let mut store = TestTextureStore::default();
+		let mut builder = TilesetBuilder::default();
+		let tile = TileHandle::new_animated(
+			"Flag",
+			AnimatedTileHandle {
+				speed: 1.0,
+				frames: (0..2)
+					.map(|_| store.add(solid_image(16, 16, [255; 4])))
+					.collect(),
+				regions: Vec::new(),
+				start_frame: Some(2),
+				..Default::default()
+			},
+		);
+
+		assert!(matches!(
+			builder.add_tile(tile, 0, &store),
+			Err(TilesetError::InvalidStartFrame {
+				frame: 2,
+				frame_count: 2,
+				..
+			})
+		));
+	}
+
+	#[test]
+	fn should_carry_render_metadata() {
+		let mut store = TestTextureStore::default();
+		let mut builder = TilesetBuilder::default();
+
+		let def: TileDef = ron::from_str(
+			r#"(name: "Tree", tile: Standard("tree.png"), z_offset: 0.5, layer: 2)"#,
+		)
+		.unwrap();
+		assert_eq!(0.5, def.z_offset);
+		assert_eq!(2, def.layer);
+
+		let serialized = ron::to_string(&def).unwrap();
+		let round_trip: TileDef = ron::from_str(&serialized).unwrap();
+		assert_eq!(0.5, round_trip.z_offset);
+		assert_eq!(2, round_trip.layer);
+
+		let tiles = [("Tree", def.z_offset, def.layer), ("Grass", 0.0, 0)];
+		for (group_id, (name, z_offset, layer)) in tiles.into_iter().enumerate() {
+			let handle = store.add(solid_image(16, 16, [255; 4]));
+			let mut tile = TileHandle::new_standard(name, handle);
+			tile.z_offset = z_offset;
+			tile.layer = layer;
+			builder.add_tile(tile, group_id as TileGroupId, &store).unwrap();
+		}
+
+		let tileset = builder.build("Layers", 0, &mut store).unwrap();
+		assert_eq!(Some(0.5), tileset.tile_z_offset("Tree"));
+		assert_eq!(Some(2), tileset.tile_layer("Tree"));
+		assert_eq!(Some(0.0), tileset.tile_z_offset("Grass"));
+		assert_eq!(Some(0), tileset.tile_layer("Grass"));
+		assert_eq!(None, tileset.tile_z_offset("Missing"));
+	}
+
+	#[test]
+	fn should_carry_tile_pivot() {
+		let mut store = TestTextureStore::default();
+		let mut builder = TilesetBuilder::default();
+
+		let def: TileDef =
+			ron::from_str(r#"(name: "Tree", tile: Standard("tree.png"), pivot: (0.5, 0.0))"#)
+				.unwrap();
+		assert_eq!(Vec2::new(0.5, 0.0), def.pivot);
+
+		let serialized = ron::to_string(&def).unwrap();
+		let round_trip: TileDef = ron::from_str(&serialized).unwrap();
+		assert_eq!(def.pivot, round_trip.pivot);
+
+		let def: TileDef =
+			ron::from_str(r#"(name: "Grass", tile: Standard("grass.png"))"#).unwrap();
+		assert_eq!(DEFAULT_PIVOT, def.pivot);
+
+		for (group_id, (name, pivot)) in [("Tree", Vec2::new(0.5, 0.0)), ("Grass", DEFAULT_PIVOT)]
+			.into_iter()
+			.enumerate()
+		{
+			let handle = store.add(solid_image(16, 16, [255; 4]));
+			let mut tile = TileHandle::new_standard(name, handle);
+			tile.pivot = pivot;
+			builder.add_tile(tile, group_id as TileGroupId, &store).unwrap();
+		}
+
+		let tileset = builder.build("Pivots", 0, &mut store).unwrap();
+		assert_eq!(Some(Vec2::new(0.5, 0.0)), tileset.tile_pivot("Tree"));
+		assert_eq!(Some(Vec2::new(0.5, 0.5)), tileset.tile_pivot("Grass"));
+		assert_eq!(None, tileset.tile_pivot("Missing"));
+	}
+
+	#[test]
+	fn should_resolve_tile_aliases() {
+		let mut store = TestTextureStore::default();
+		let mut builder = TilesetBuilder::default();
+
+		let def: TileDef = ron::from_str(
+			r#"(name: "Cobblestone", tile: Standard("cobble.png"), aliases: ["Stone", "Grass"])"#,
+		)
+		.unwrap();
+		assert_eq!(vec!["Stone", "Grass"], def.aliases);
+
+		let handle = store.add(solid_image(16, 16, [0; 4]));
+		let mut tile = TileHandle::new_standard("Cobblestone", handle);
+		tile.aliases = def.aliases;
+		builder.add_tile(tile, 0, &store).unwrap();
+		let grass = TileHandle::new_standard("Grass", store.add(solid_image(16, 16, [255; 4])));
+		builder.add_tile(grass, 1, &store).unwrap();
+
+		let tileset = builder.build("Aliases", 0, &mut store).unwrap();
+		assert_eq!(2, tileset.tile_count());
+		assert_eq!(
+			tileset.get_tile_index("Cobblestone"),
+			tileset.get_tile_index("Stone")
+		);
+		assert_eq!(Some(&0), tileset.get_tile_group_id("Stone"));
+		assert_eq!(Some("Cobblestone"), tileset.get_tile_name(&0).map(String::as_str));
+		assert_eq!(
+			Some(&[String::from("Stone"), String::from("Grass")][..]),
+			tileset.tile_aliases("Stone")
+		);
+		// Aliases never shadow another tile's name
+		assert_eq!(Some(&1), tileset.get_tile_group_id("Grass"));
+	}
+
+	#[test]
+	fn should_leave_builder_untouched_on_failed_add() {
+		let mut store = TestTextureStore::default();
+		let mut builder = TilesetBuilder::default();
+		let idle = store.add(solid_image(16, 16, [255; 4]));
+
+		// The "idle" track is packed before the "walk" track fails
+		let track = |frame: Handle<Image>| AnimatedTileHandle {
+			speed: 1.0,
+			frames: vec![frame],
+			regions: Vec::new(),
+			..Default::default()
+		};
+		let tracks = [("idle", track(idle)), ("walk", track(Handle::default()))];
+		let tile = TileHandle {
+			tile: TileHandleType::MultiAnimated(
+				tracks
+					.into_iter()
+					.map(|(track, anim)| (String::from(track), anim))
+					.collect(),
+			),
+			aliases: vec![String::from("Walker")],
+			..TileHandle::new_standard("Player", Handle::default())
+		};
+		assert!(matches!(
+			builder.add_tile(tile, 0, &store),
+			Err(TilesetError::ImageNotFound)
+		));
+
+		let tile = TileHandle::new_standard("Grass", store.add(solid_image(16, 16, [0; 4])));
+		builder.add_tile(tile, 0, &store).unwrap();
+		let tileset = builder.build("Atomic", 0, &mut store).unwrap();
+		assert_eq!(1, tileset.tile_count());
+		assert_eq!(1, tileset.atlas().textures.len());
+		assert_eq!(Some(0), tileset.get_base_tile_index("Grass"));
+		assert_eq!(None, tileset.get_tile_group_id("Walker"));
+		assert_eq!(None, tileset.get_tile_group_id("Player"));
+	}
+
+	#[test]
+	fn should_compute_atlas_utilization() {
+		let mut store = TestTextureStore::default();
+		let mut builder = TilesetBuilder::default();
+		for group_id in 0..3 {
+			let handle = store.add(solid_image(16, 16, [255; 4]));
+			let tile = TileHandle::new_standard(format!("Tile {}", group_id), handle);
+			builder.add_tile(tile, group_id, &store).unwrap();
+		}
+		builder
+			.add_tile(TileHandle::new_empty("Air"), 3, &store)
+			.unwrap();
+
+		let tileset = builder.build("Utilization", 0, &mut store).unwrap();
+		let size = tileset.size();
+		let expected = (3.0 * 16.0 * 16.0) / (size.x * size.y);
+		assert_eq!(expected, tileset.atlas_utilization());
+		assert!(tileset.atlas_utilization() <= 1.0);
+	}
+
+	#[test]
+	fn should_premultiply_alpha() {
+		let mut store = TestTextureStore::default();
+		let mut builder = TilesetBuilder::default();
+		builder.premultiply_alpha(true);
+		let handle = store.add(solid_image(16, 16, [255, 255, 255, 128]));
+		builder
+			.add_tile(TileHandle::new_standard("Glass", handle), 0, &store)
+			.unwrap();
+
+		let tileset = builder.build("Premultiplied", 0, &mut store).unwrap();
+		let texture = store.get(tileset.texture()).unwrap();
+		assert_eq!([128, 128, 128, 128], texture.data[..4]);
+	}
+
+	#[test]
+	fn should_reserve_capacity() {
+		let mut store = TestTextureStore::default();
+		let mut builder = TilesetBuilder::with_capacity(32);
+		assert!(builder.tiles.capacity() >= 32);
+		assert!(builder.tile_indices.capacity() >= 32);
+
+		let handle = store.add(solid_image(16, 16, [255; 4]));
+		builder
+			.add_tile(TileHandle::new_standard("Grass", handle), 0, &store)
+			.unwrap();
+		let tileset = builder.build("Reserved", 0, &mut store).unwrap();
+		assert!(tileset.get_tile_index("Grass").is_some());
+	}
+
+	#[test]
+	fn should_lay_out_fixed_grid() {
+		let mut store = TestTextureStore::default();
+		let mut builder = TilesetBuilder::default();
+		builder.grid_columns(Some(2));
+		for group_id in 0..5 {
+			let handle = store.add(solid_image(16, 16, [255; 4]));
+			let tile = TileHandle::new_standard(format!("Tile {}", group_id), handle);
+			builder.add_tile(tile, group_id, &store).unwrap();
+		}
+
+		let tileset = builder.build("Grid", 0, &mut store).unwrap();
+		assert_eq!(Vec2::new(32.0, 48.0), tileset.size());
+		let index = tileset.get_base_tile_index("Tile 3").unwrap();
+		assert_eq!(Vec2::new(16.0, 16.0), tileset.atlas().textures[index].min);
+
+		// The atlas is always as wide as the grid
+		let mut builder = TilesetBuilder::default();
+		builder.grid_columns(Some(4));
+		let handle = store.add(solid_image(16, 16, [255; 4]));
+		builder
+			.add_tile(TileHandle::new_standard("Tile", handle), 0, &store)
+			.unwrap();
+		let tileset = builder.build("Wide Grid", 0, &mut store).unwrap();
+		assert_eq!(Vec2::new(64.0, 16.0), tileset.size());
+	}
+
+	#[test]
+	fn should_convert_grid_coordinates() {
+		let mut store = TestTextureStore::default();
+		let mut builder = TilesetBuilder::default();
+		builder.grid_columns(Some(4));
+		for group_id in 0..6 {
+			let handle = store.add(solid_image(16, 16, [255; 4]));
+			let tile = TileHandle::new_standard(format!("Tile {}", group_id), handle);
+			builder.add_tile(tile, group_id, &store).unwrap();
+		}
+
+		let tileset = builder.build("Grid", 0, &mut store).unwrap();
+		assert_eq!(Some(4), tileset.grid_columns());
+		assert_eq!(Some(0), tileset.index_from_grid(0, 0));
+		assert_eq!(Some(3), tileset.index_from_grid(3, 0));
+		assert_eq!(Some(5), tileset.index_from_grid(1, 1));
+		assert_eq!(Some((1, 1)), tileset.grid_from_index(5));
+		assert_eq!(Some((3, 0)), tileset.grid_from_index(3));
+		for index in 0..6 {
+			let (column, row) = tileset.grid_from_index(index).unwrap();
+			assert_eq!(Some(index), tileset.index_from_grid(column, row));
+			let rect = tileset.atlas().textures[index];
+			assert_eq!(Vec2::new(column as f32, row as f32) * 16.0, rect.min);
+		}
+
+		// Outside the grid (or past the last tile)
+		assert_eq!(None, tileset.index_from_grid(4, 0));
+		assert_eq!(None, tileset.index_from_grid(2, 1));
+		assert_eq!(None, tileset.grid_from_index(6));
+	}
+
+	#[test]
+	fn should_convert_padded_grid_coordinates() {
+		let mut store = TestTextureStore::default();
+		let mut builder = TilesetBuilder::default();
+		builder.grid_columns(Some(3)).power_of_two(true);
+		for group_id in 0..9 {
+			let handle = store.add(solid_image(16, 16, [255; 4]));
+			let tile = TileHandle::new_standard(format!("Tile {}", group_id), handle);
+			builder.add_tile(tile, group_id, &store).unwrap();
+		}
+
+		// The padded atlas fits 4 columns, but the tiles are still laid out in 3
+		let tileset = builder.build("Padded Grid", 0, &mut store).unwrap();
+		assert_eq!(Vec2::new(64.0, 64.0), tileset.size());
+		assert_eq!(Some(3), tileset.grid_columns());
+		assert_eq!(Some(3), tileset.index_from_grid(0, 1));
+		assert_eq!(Some((0, 1)), tileset.grid_from_index(3));
+		assert_eq!(None, tileset.index_from_grid(3, 0));
+		let rect = tileset.atlas().textures[3];
+		assert_eq!(Vec2::new(0.0, 16.0), rect.min);
+
+		// Tilesets that weren't built as a grid have no grid coordinates
+		let mut builder = TilesetBuilder::default();
+		let handle = store.add(solid_image(16, 16, [255; 4]));
+		builder
+			.add_tile(TileHandle::new_standard("Tile", handle), 0, &store)
+			.unwrap();
+		let tileset = builder.build("No Grid", 0, &mut store).unwrap();
+		assert_eq!(None, tileset.grid_columns());
+		assert_eq!(None, tileset.index_from_grid(0, 0));
+		assert_eq!(None, tileset.grid_from_index(0));
+	}
+
+	#[test]
+	fn should_error_on_empty_grid() {
+		let mut store = TestTextureStore::default();
+		let mut builder = TilesetBuilder::default();
+		builder.grid_columns(Some(0));
+		let handle = store.add(solid_image(16, 16, [255; 4]));
+		builder
+			.add_tile(TileHandle::new_standard("Tile", handle), 0, &store)
+			.unwrap();
+
+		let result = builder.build("Empty Grid", 0, &mut store);
+		assert!(matches!(
+			result,
+			Err(TilesetError::InvalidGridLayout { columns: 0, .. })
+		));
+	}
+
+	#[test]
+	fn should_compare_structurally() {
+		let mut store = TestTextureStore::default();
+		let dirt = store.add(solid_image(16, 16, [255, 0, 0, 255]));
+		let grass = store.add(solid_image(16, 16, [0, 255, 0, 255]));
+		let build = |store: &mut TestTextureStore, names: [&str; 2]| {
+			let mut builder = TilesetBuilder::default();
+			for (group_id, (name, handle)) in names.iter().zip([&dirt, &grass]).enumerate() {
+				let tile = TileHandle::new_standard(*name, handle.clone());
+				builder.add_tile(tile, group_id as TileGroupId, &*store).unwrap();
+			}
+			builder.build("Golden", 0, store).unwrap()
+		};
+
+		let tileset = build(&mut store, ["Dirt", "Grass"]);
+		// Each build creates a new atlas texture, so the handles differ
+		assert!(tileset.structurally_eq(&build(&mut store, ["Dirt", "Grass"])));
+		assert!(tileset.structurally_eq(&tileset.clone()));
+		assert!(!tileset.structurally_eq(&build(&mut store, ["Dirt", "Sand"])));
+
+		// The data of each tile is compared as well
+		let mut other = build(&mut store, ["Dirt", "Grass"]);
+		let tile = other.tiles.remove(&0).unwrap().with_tags(vec![String::from("Solid")]);
+		other.tiles.insert(0, tile);
+		assert!(!tileset.structurally_eq(&other));
+
+		// As are the regions of the atlas
+		let mut other = build(&mut store, ["Dirt", "Grass"]);
+		other.atlas.textures.swap(0, 1);
+		assert!(!tileset.structurally_eq(&other));
+	}
+
+	#[test]
+	fn should_apply_sampler_to_atlas() {
+		let mut store = TestTextureStore::default();
+		let mut builder = TilesetBuilder::default();
+		builder.sampler(SamplerPreset::PixelArt.sampler()).lod_levels(2);
+		let handle = store.add(solid_image(16, 16, [255; 4]));
+		builder
+			.add_tile(TileHandle::new_standard("Grass", handle), 0, &store)
+			.unwrap();
+
+		let tileset = builder.build("Sampled", 0, &mut store).unwrap();
+		for texture in [tileset.texture(), tileset.atlas_for_lod(1).unwrap()] {
+			let texture = store.get(texture).unwrap();
+			assert!(matches!(
+				&texture.sampler_descriptor,
+				ImageSampler::Descriptor(descriptor) if descriptor.mag_filter == FilterMode::Nearest
+			));
+		}
+	}
+
+	#[test]
+	fn should_fill_gutters_with_color() {
+		let mut store = TestTextureStore::default();
+		let mut builder = TilesetBuilder::default();
+		builder.power_of_two(true).gutter_color([0, 0, 255, 255]);
+		for group_id in 0..3 {
+			let handle = store.add(solid_image(16, 16, [255; 4]));
+			let tile = TileHandle::new_standard(format!("Tile {}", group_id), handle);
+			builder.add_tile(tile, group_id, &store).unwrap();
+		}
+
+		let tileset = builder.build("Gutters", 0, &mut store).unwrap();
+		let texture = store.get(tileset.texture()).unwrap();
+		assert_eq!(Vec2::new(64.0, 16.0), tileset.size());
+		let pixel = |x: usize, y: usize| &texture.data[(y * 64 + x) * 4..(y * 64 + x) * 4 + 4];
+		assert_eq!(&[255; 4], pixel(47, 15));
+		assert_eq!(&[0, 0, 255, 255], pixel(48, 0));
+		assert_eq!(&[0, 0, 255, 255], pixel(63, 15));
+	}
+
+	#[test]
+	fn should_reference_atlas_regions() {
+		let mut store = TestTextureStore::default();
+		let mut texture = solid_image(32, 16, [255, 0, 0, 255]);
+		for y in 0..16 {
+			for x in 16..32 {
+				let start = (y * 32 + x) * 4;
+				texture.data[start..start + 4].copy_from_slice(&[0, 0, 255, 255]);
+			}
+		}
+		let atlas = store.add(texture);
+		let region = |name: &str, rect: Rect| TileHandle {
+			tile: TileHandleType::AtlasRegion(atlas.clone(), rect),
+			..TileHandle::new_empty(name)
+		};
+
+		let mut builder = TilesetBuilder::default();
+		let handle = store.add(solid_image(16, 16, [255; 4]));
+		builder
+			.add_tile(TileHandle::new_standard("Grass", handle), 0, &store)
+			.unwrap();
+		let water = Rect::new(16.0, 0.0, 32.0, 16.0);
+		builder.add_tile(region("Water", water), 1, &store).unwrap();
+
+		// Regions must be made up of whole pixels within the atlas texture
+		let invalid = [
+			Rect::new(0.5, 0.0, 16.0, 16.0),
+			Rect::new(-16.0, 0.0, 0.0, 16.0),
+			Rect::new(16.0, 0.0, 48.0, 16.0),
+			Rect::new(0.0, 0.0, 0.0, 16.0),
+		];
+		for rect in invalid {
+			assert!(matches!(
+				builder.add_tile(region("Invalid", rect), 2, &store),
+				Err(TilesetError::InvalidRegion { ref tile, .. }) if tile == "Invalid"
+			));
+		}
+		let lava = Rect::new(0.0, 0.0, 16.0, 16.0);
+		builder.add_tile(region("Lava", lava), 2, &store).unwrap();
+
+		let image_count = store.images.len();
+		let tileset = builder.build("Regions", 0, &mut store).unwrap();
+		// Only the main atlas texture is added, since the regions are never copied
+		assert_eq!(image_count + 1, store.images.len());
+		assert_eq!(1, tileset.atlas().len());
+		assert_eq!(2, tileset.page_count());
+		assert_eq!(Some(1), tileset.tile_page("Water"));
+
+		let page = tileset.page(1).unwrap();
+		assert_eq!(&atlas, page.texture());
+		assert_eq!(vec![water, lava], page.atlas().textures);
+		assert_eq!(Some(0), page.get_base_tile_index("Water"));
+		assert_eq!(Some(1), page.get_base_tile_index("Lava"));
+		assert_eq!(Some([0, 0, 255, 255]), page.tile_average_color("Water"));
+		assert_eq!(Some([255, 0, 0, 255]), page.tile_average_color("Lava"));
+
+		// The metadata of region tiles can be found from the tileset itself
+		assert_eq!(Some([0, 0, 255, 255]), tileset.tile_average_color("Water"));
+		assert_eq!(Some(UVec2::splat(16)), tileset.tile_size_of("Lava"));
+		assert!(tileset.get_tile_data("Water").is_some());
+		assert!(tileset.get_tile_handle_by_id(&1).is_some());
+		assert_eq!(None, tileset.get_tile_index("Water"));
+	}
+
+	#[test]
+	fn should_keep_single_atlas_regions_on_first_page() {
+		let mut store = TestTextureStore::default();
+		let atlas = store.add(solid_image(32, 16, [0, 0, 255, 255]));
+		let region = |name: &str, rect: Rect| TileHandle {
+			tile: TileHandleType::AtlasRegion(atlas.clone(), rect),
+			..TileHandle::new_empty(name)
+		};
+
+		let mut builder = TilesetBuilder::default();
+		let water = Rect::new(0.0, 0.0, 16.0, 16.0);
+		builder.add_tile(region("Water", water), 0, &store).unwrap();
+		let lava = Rect::new(16.0, 0.0, 32.0, 16.0);
+		builder.add_tile(region("Lava", lava), 1, &store).unwrap();
+		builder.default_tile(Some(1));
+
+		let image_count = store.images.len();
+		let tileset = builder.build("Regions", 0, &mut store).unwrap();
+		assert_eq!(image_count, store.images.len());
+		assert_eq!(1, tileset.page_count());
+		assert_eq!(&atlas, tileset.texture());
+		assert_eq!(Vec2::splat(16.0), tileset.tile_size());
+		assert_eq!(Some(0), tileset.tile_page("Water"));
+		assert_eq!(Some(TileIndex::Standard(0)), tileset.get_tile_index("Water"));
+		assert_eq!(Some(TileIndex::Standard(1)), tileset.get_tile_index("Lava"));
+		assert_eq!(
+			Some(TileIndex::Standard(1)),
+			tileset.get_tile_index_or_default("Missing")
+		);
+	}
+
+	#[cfg(feature = "variants")]
+	#[test]
+	fn should_error_on_invalid_variant() {
+		use bevy_tileset_tiles::prelude::{SimpleTileHandle, VariantTileHandle};
+
+		let mut store = TestTextureStore::default();
+		let mut builder = TilesetBuilder::default();
+		let variant = |handle| VariantTileHandle {
+			weight: 1.0,
+			tile: SimpleTileHandle::Standard(handle),
+			transform: None,
+		};
+		let handle = store.add(solid_image(16, 16, [255; 4]));
+		let variants = vec![variant(handle), variant(Handle::default())];
+		let tile = TileHandle::new_variant("Flowers", variants);
+
+		assert!(matches!(
+			builder.add_tile(tile, 0, &store),
+			Err(TilesetError::ImageNotFound)
+		));
+		// The valid variant shouldn't have been kept either
+		assert!(builder.tile_indices.is_empty());
+		assert_eq!(None, builder.current_variant);
+	}
+
+	#[test]
+	fn should_error_on_inconsistent_frame_sizes() {
+		let mut store = TestTextureStore::default();
+		let mut builder = TilesetBuilder::default();
+		let frames = vec![
+			store.add(solid_image(16, 16, [255; 4])),
+			store.add(solid_image(16, 32, [255; 4])),
+		];
+		let tile = TileHandle::new_animated(
+			"Water",
+			AnimatedTileHandle {
+				speed: 1.0,
+				frames,
+				regions: Vec::new(),
+				..Default::default()
+			},
+		);
+
+		assert!(matches!(
+			builder.add_tile(tile, 0, &store),
+			Err(TilesetError::InconsistentFrameSizes { ref tile, frame: 1 }) if tile == "Water"
+		));
+		// Nothing from the rejected tile should have been packed
+		assert!(builder.tile_indices.is_empty());
+	}
+
+	#[test]
+	fn should_get_tile_handle_by_name() {
+		let mut store = TestTextureStore::default();
+		let mut builder = TilesetBuilder::default();
+		let handle = store.add(solid_image(16, 16, [255; 4]));
+		builder
+			.add_tile(TileHandle::new_standard("Grass", handle.clone()), 3, &store)
+			.unwrap();
+
+		let tileset = builder.build("Handles", 0, &mut store).unwrap();
+		let tile = tileset.get_tile_handle_by_name("Grass").unwrap();
+		assert_eq!("Grass", tile.name);
+		assert!(matches!(&tile.tile, TileHandleType::Standard(h) if *h == handle));
+		assert!(tileset.get_tile_handle_by_id(&3).is_some());
+		assert!(tileset.get_tile_handle_by_name("Missing").is_none());
+	}
+
+	#[test]
+	fn should_get_animation_frame_handles() {
+		let mut store = TestTextureStore::default();
+		let mut builder = TilesetBuilder::default();
+		let frames = (0..3)
+			.map(|frame| store.add(solid_image(16, 16, [frame * 80; 4])))
+			.collect::<Vec<_>>();
+		let water = TileHandle::new_animated(
+			"Water",
+			AnimatedTileHandle {
+				speed: 1.0,
+				frames: frames.clone(),
+				regions: Vec::new(),
+				..Default::default()
+			},
+		);
+		builder.add_tile(water, 0, &store).unwrap();
+		let grass = store.add(solid_image(16, 16, [255; 4]));
+		builder
+			.add_tile(TileHandle::new_standard("Grass", grass), 1, &store)
+			.unwrap();
+
+		let tileset = builder.build("Frames", 0, &mut store).unwrap();
+		assert_eq!(Some(frames), tileset.animation_frame_handles("Water"));
+		assert_eq!(None, tileset.animation_frame_handles("Grass"));
+		assert_eq!(None, tileset.animation_frame_handles("Missing"));
+	}
+
+	#[test]
+	fn should_pack_sampler_hints_into_pages() {
+		let mut store = TestTextureStore::default();
+		let mut builder = TilesetBuilder::default();
+		let grass = store.add(solid_image(16, 16, [0, 255, 0, 255]));
+		let sky = store.add(solid_image(16, 16, [0, 0, 255, 255]));
+		let glow = store.add(solid_image(16, 16, [255; 4]));
+		builder
+			.add_tile(TileHandle::new_standard("Grass", grass), 0, &store)
+			.unwrap();
+		for (group_id, (name, handle)) in [("Sky", sky), ("Glow", glow)].into_iter().enumerate() {
+			let tile = TileHandle {
+				sampler: Some(SamplerPreset::PixelArt),
+				..TileHandle::new_standard(name, handle)
+			};
+			builder.add_tile(tile, group_id as TileGroupId + 1, &store).unwrap();
+		}
+		let duplicate = TileHandle::new_standard("Sky", Handle::default());
+		assert!(matches!(
+			builder.add_tile(duplicate, 1, &store),
+			Err(TilesetError::TileAlreadyExists(1))
+		));
+
+		// Names are shared across pages
+		let duplicate = TileHandle::new_standard("Sky", Handle::default());
+		assert!(matches!(
+			builder.add_tile(duplicate, 3, &store),
+			Err(TilesetError::DuplicateTileName(ref name)) if name == "Sky"
+		));
+		let duplicate = TileHandle {
+			sampler: Some(SamplerPreset::Smooth),
+			..TileHandle::new_standard("Grass", Handle::default())
+		};
+		assert!(matches!(
+			builder.add_tile(duplicate, 3, &store),
+			Err(TilesetError::DuplicateTileName(ref name)) if name == "Grass"
+		));
+
+		// A hint matching the tileset's own sampler doesn't need a page
+		let dirt = store.add(solid_image(16, 16, [127, 63, 0, 255]));
+		let tile = TileHandle {
+			sampler: Some(SamplerPreset::Default),
+			..TileHandle::new_standard("Dirt", dirt)
+		};
+		builder.add_tile(tile, 3, &store).unwrap();
+
+		let tileset = builder.build("Paged", 0, &mut store).unwrap();
+		assert_eq!(2, tileset.page_count());
+		assert_eq!(2, tileset.tile_count());
+		assert_eq!(Some(0), tileset.tile_page("Grass"));
+		assert_eq!(Some(0), tileset.tile_page("Dirt"));
+		assert_eq!(Some(1), tileset.tile_page("Glow"));
+		assert_eq!(None, tileset.tile_page("Missing"));
+
+		// Paged tiles are found by name, but their indices are only returned alongside their page
+		assert_eq!(Some(&2), tileset.get_tile_group_id("Glow"));
+		assert_eq!(Ok(()), tileset.validate_names(&["Grass", "Sky", "Glow"]));
+		assert!(tileset.get_tile_data("Glow").is_some());
+		assert_eq!(None, tileset.get_tile_index("Glow"));
+		assert_eq!(
+			Some((1, TileIndex::Standard(1))),
+			tileset.get_paged_tile_index("Glow")
+		);
+		assert_eq!(
+			Some((1, TileIndex::Standard(1))),
+			tileset.get_paged_tile_index_by_id(2)
+		);
+		let (page, index, data) = tileset.select_paged_tile("Glow").unwrap();
+		assert_eq!((1, TileIndex::Standard(1)), (page, index));
+		assert_eq!("Glow", data.name());
+		assert_eq!(
+			tileset.get_tile_index("Grass").map(|index| (0, index)),
+			tileset.get_paged_tile_index("Grass")
+		);
+
+		let page = tileset.page(1).unwrap();
+		assert_eq!(2, page.tile_count());
+		assert_eq!(Some(1), page.get_base_tile_index("Glow"));
+		assert!(matches!(
+			&store.get(page.texture()).unwrap().sampler_descriptor,
+			ImageSampler::Descriptor(descriptor) if descriptor.mag_filter == FilterMode::Nearest
+		));
+		assert!(matches!(
+			&store.get(tileset.texture()).unwrap().sampler_descriptor,
+			ImageSampler::Default
+		));
+	}
+
+	#[test]
+	fn should_keep_single_sampler_hint_on_first_page() {
+		let mut store = TestTextureStore::default();
+		let mut builder = TilesetBuilder::default();
+		for (group_id, name) in ["Sky", "Glow"].into_iter().enumerate() {
+			let tile = TileHandle {
+				sampler: Some(SamplerPreset::PixelArt),
+				..TileHandle::new_standard(name, store.add(solid_image(16, 16, [255; 4])))
+			};
+			builder.add_tile(tile, group_id as TileGroupId, &store).unwrap();
+		}
+
+		// Every tile shares the same hint, so no additional page is needed
+		let tileset = builder.build("Hinted", 0, &mut store).unwrap();
+		assert_eq!(1, tileset.page_count());
+		assert_eq!(Some(TileIndex::Standard(1)), tileset.get_tile_index("Glow"));
+		assert!(matches!(
+			&store.get(tileset.texture()).unwrap().sampler_descriptor,
+			ImageSampler::Descriptor(descriptor) if descriptor.mag_filter == FilterMode::Nearest
+		));
+	}
+
+	#[test]
+	fn should_trim_transparent_borders() {
+		let mut store = TestTextureStore::default();
+		let mut builder = TilesetBuilder::default();
+		builder.trim(true).tile_size(Some(Vec2::splat(16.0)));
+		let mut texture = solid_image(16, 16, [0; 4]);
+		for y in 2..14 {
+			for x in 2..14 {
+				let start = (y * 16 + x) * 4;
+				texture.data[start..start + 4].copy_from_slice(&[255; 4]);
+			}
+		}
+		let handle = store.add(texture);
+		let tile = TileHandle {
+			pivot: Vec2::new(0.25, 0.25),
+			..TileHandle::new_standard("Bush", handle)
+		};
+		builder.add_tile(tile, 0, &store).unwrap();
+
+		let tileset = builder.build("Trimmed", 0, &mut store).unwrap();
+		assert_eq!(Some(UVec2::splat(12)), tileset.tile_size_of("Bush"));
+		assert_eq!(Vec2::splat(12.0), tileset.atlas().textures[0].size());
+		assert_eq!(
+			Some(TileTrim {
+				offset: UVec2::splat(2),
+				source_size: UVec2::splat(16),
+			}),
+			tileset.tile_trim("Bush")
+		);
+		// 4px from the bottom-left corner of the source is 2px from that of the trimmed texture
+		let pivot = tileset.tile_packed_pivot("Bush").unwrap();
+		assert!(pivot.abs_diff_eq(Vec2::splat(2.0 / 12.0), 1e-6));
+	}
+
+	#[test]
+	fn should_trim_each_texture_separately() {
+		let mut store = TestTextureStore::default();
+		let mut builder = TilesetBuilder::default();
+		builder.trim(true).tile_size(Some(Vec2::splat(16.0)));
+		// Each frame only has a single opaque pixel, in a different corner
+		let mut frame = |x: usize, y: usize| {
+			let mut texture = solid_image(16, 16, [0; 4]);
+			let start = (y * 16 + x) * 4;
+			texture.data[start..start + 4].copy_from_slice(&[255; 4]);
+			store.add(texture)
+		};
+		let frames = vec![frame(1, 2), frame(14, 13)];
+		let tile = TileHandle::new_animated(
+			"Spark",
+			AnimatedTileHandle {
+				speed: 1.0,
+				frames,
+				regions: Vec::new(),
+				..Default::default()
+			},
+		);
+		builder.add_tile(tile, 0, &store).unwrap();
+
+		let tileset = builder.build("Sparks", 0, &mut store).unwrap();
+		let TileIndex::Animated(start, end, ..) = tileset.get_tile_index("Spark").unwrap() else {
+			panic!("expected an animated tile");
+		};
+		let trim = |x: u32, y: u32| TileTrim {
+			offset: UVec2::new(x, y),
+			source_size: UVec2::splat(16),
+		};
+		assert_eq!(Some(trim(1, 2)), tileset.tile_trim_at(start));
+		assert_eq!(Some(trim(14, 13)), tileset.tile_trim_at(end));
+		assert_eq!(Some(trim(1, 2)), tileset.tile_trim("Spark"));
+		assert_eq!(None, tileset.tile_trim_at(end + 1));
+	}
+
+	#[test]
+	fn should_convert_textures_to_rgba8() {
+		let mut store = TestTextureStore::default();
+		let mut builder = TilesetBuilder::default();
+		let size = Extent3d {
+			width: 2,
+			height: 2,
+			depth_or_array_layers: 1,
+		};
+		let gray = Image::new_fill(size, TextureDimension::D2, &[100], TextureFormat::R8Unorm);
+		let tile = TileHandle::new_standard("Gray", store.add(gray));
+		builder.add_tile(tile, 0, &store).unwrap();
+
+		let tileset = builder.build("Converted", 0, &mut store).unwrap();
+		let texture = store.get(tileset.texture()).unwrap();
+		assert_eq!(TextureFormat::Rgba8UnormSrgb, texture.texture_descriptor.format);
+		assert_eq!(&[100, 100, 100, 255], &texture.data[..4]);
+		assert_eq!(Some([100, 100, 100, 255]), tileset.tile_average_color("Gray"));
+
+		// Compressed textures can't be converted
+		let mut compressed = Image::default();
+		compressed.texture_descriptor.format = TextureFormat::Bc1RgbaUnorm;
+		let tile = TileHandle::new_standard("Compressed", store.add(compressed));
+		let result = TilesetBuilder::default().add_tile(tile, 0, &store);
+		assert!(matches!(
+			result,
+			Err(TilesetError::UnsupportedFormat(TextureFormat::Bc1RgbaUnorm))
+		));
 	}
 }
@@ -0,0 +1,162 @@
+//! Tools for comparing the tiles of two tilesets
+
+use crate::prelude::{RawTileset, TileGroupId, Tileset};
+
+/// The differences between the tiles of two tilesets
+///
+/// This is useful for migrating saved data (such as maps) that reference tiles by
+/// group ID or name when a tileset changes between versions.
+///
+/// All collections are sorted by group ID.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct TilesetDiff {
+	/// Tiles whose group ID only exists in the newer tileset
+	pub added: Vec<(TileGroupId, String)>,
+	/// Tiles whose group ID only exists in the older tileset
+	pub removed: Vec<(TileGroupId, String)>,
+	/// Tiles whose group ID exists in both tilesets, but under a different name
+	pub renamed: Vec<TileRename>,
+	/// Tiles whose name exists in both tilesets, but under a different group ID
+	pub moved: Vec<TileMove>,
+}
+
+/// A tile that kept its group ID but changed its name
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TileRename {
+	pub group_id: TileGroupId,
+	pub old_name: String,
+	pub new_name: String,
+}
+
+/// A tile that kept its name but changed its group ID
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TileMove {
+	pub name: String,
+	pub old_id: TileGroupId,
+	pub new_id: TileGroupId,
+}
+
+impl TilesetDiff {
+	/// Returns true if the two compared tilesets contain the same tiles
+	pub fn is_empty(&self) -> bool {
+		self.added.is_empty()
+			&& self.removed.is_empty()
+			&& self.renamed.is_empty()
+			&& self.moved.is_empty()
+	}
+}
+
+macro_rules! impl_tileset {
+	($name: ident) => {
+		impl $name {
+			/// Compares the tiles of this tileset against a newer version of it
+			///
+			/// # Arguments
+			///
+			/// * `other`: The newer tileset
+			///
+			/// returns: TilesetDiff
+			pub fn diff(&self, other: &$name) -> TilesetDiff {
+				let mut diff = TilesetDiff::default();
+
+				for (group_id, name) in &self.tile_names {
+					match other.tile_names.get(group_id) {
+						Some(new_name) if new_name != name => diff.renamed.push(TileRename {
+							group_id: *group_id,
+							old_name: name.clone(),
+							new_name: new_name.clone(),
+						}),
+						Some(..) => {},
+						None => diff.removed.push((*group_id, name.clone())),
+					}
+
+					if let Some(new_id) = other.tile_ids.get(name) {
+						if new_id != group_id {
+							diff.moved.push(TileMove {
+								name: name.clone(),
+								old_id: *group_id,
+								new_id: *new_id,
+							});
+						}
+					}
+				}
+
+				for (group_id, name) in &other.tile_names {
+					if !self.tile_names.contains_key(group_id) {
+						diff.added.push((*group_id, name.clone()));
+					}
+				}
+
+				diff.added.sort_by_key(|(id, ..)| *id);
+				diff.removed.sort_by_key(|(id, ..)| *id);
+				diff.renamed.sort_by_key(|rename| rename.group_id);
+				diff.moved.sort_by_key(|moved| moved.old_id);
+				diff
+			}
+		}
+	};
+}
+
+impl_tileset!(Tileset);
+impl_tileset!(RawTileset);
+
+#[cfg(test)]
+mod tests {
+	use bevy_tileset_tiles::prelude::TileHandle;
+
+	use super::*;
+	use crate::prelude::TilesetBuilder;
+	use crate::test_utils::{solid_image, TestTextureStore};
+	use bevy_tile_atlas::TextureStore;
+
+	fn build(tiles: &[(TileGroupId, &str)]) -> RawTileset {
+		let mut store = TestTextureStore::default();
+		let mut builder = TilesetBuilder::default();
+		for (group_id, name) in tiles {
+			let handle = store.add(solid_image(1, 1, [255; 4]));
+			builder
+				.add_tile(TileHandle::new_standard(*name, handle), *group_id, &store)
+				.unwrap();
+		}
+		builder.build("Diff", 0, &mut store).unwrap()
+	}
+
+	#[test]
+	fn should_diff_tilesets() {
+		let old = build(&[(0, "Grass"), (1, "Dirt"), (2, "Stone"), (3, "Water")]);
+		let new = build(&[(0, "Grass"), (1, "Mud"), (3, "Sand"), (4, "Water")]);
+
+		let diff = old.diff(&new);
+		assert_eq!(vec![(4, String::from("Water"))], diff.added);
+		assert_eq!(vec![(2, String::from("Stone"))], diff.removed);
+		assert_eq!(
+			vec![
+				TileRename {
+					group_id: 1,
+					old_name: String::from("Dirt"),
+					new_name: String::from("Mud"),
+				},
+				TileRename {
+					group_id: 3,
+					old_name: String::from("Water"),
+					new_name: String::from("Sand"),
+				},
+			],
+			diff.renamed
+		);
+		assert_eq!(
+			vec![TileMove {
+				name: String::from("Water"),
+				old_id: 3,
+				new_id: 4,
+			}],
+			diff.moved
+		);
+	}
+
+	#[test]
+	fn should_not_diff_identical_tilesets() {
+		let tiles = [(0, "Grass"), (1, "Dirt")];
+		assert!(build(&tiles).diff(&build(&tiles)).is_empty());
+	}
+}
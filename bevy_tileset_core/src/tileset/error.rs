@@ -1,7 +1,10 @@
-use crate::prelude::TileGroupId;
+use crate::prelude::{TileGroupId, TilesetId};
 use bevy::asset::AssetIoError;
+use bevy::math::{Rect, Vec2};
+use bevy::render::render_resource::TextureFormat;
 use bevy::render::texture::TextureError;
 use bevy_tile_atlas::TileAtlasBuilderError;
+use std::path::PathBuf;
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -9,15 +12,97 @@ pub enum TilesetError {
 	#[error("image could not be found")]
 	ImageNotFound,
 	#[error("could not load asset: {0:?}")]
-	AssetIoError(AssetIoError),
+	AssetIoError(#[from] AssetIoError),
 	#[error("could not read image: {0:?}")]
 	ImageError(TextureError),
+	#[error("texture format {0:?} is not supported (expected one convertible to 8-bit RGBA)")]
+	UnsupportedFormat(TextureFormat),
 	#[error("could not add tile to atlas: {0:?}")]
 	AtlasError(TileAtlasBuilderError),
 	#[error("invalid tile data (expected {expected:?}, found {found:?})")]
 	InvalidData { expected: String, found: String },
-	#[error("could not read tile definition file: {0:?}")]
-	InvalidDefinition(ron::error::SpannedError),
+	#[error("could not read tile definition file {1:?}: {0}")]
+	InvalidDefinition(ron::error::SpannedError, PathBuf),
+	#[error("could not read tileset definition file {1:?}: {0}")]
+	InvalidTilesetDef(ron::error::SpannedError, PathBuf),
 	#[error("tile with group ID {0:?} already exists in the tileset")]
 	TileAlreadyExists(TileGroupId),
+	#[error("tile name {0:?} is already used in another atlas page of the tileset")]
+	DuplicateTileName(String),
+	#[error("tileset definition {path:?} assigns group ID {group_id:?} to multiple tiles")]
+	DuplicateGroupId { group_id: TileGroupId, path: PathBuf },
+	#[error("tileset definition {path:?} has an out-of-range ID {value} in {field:?}")]
+	InvalidId {
+		field: String,
+		value: i128,
+		path: PathBuf,
+	},
+	#[error("tile {tile:?} requires the {feature:?} feature to be enabled")]
+	FeatureDisabled { feature: &'static str, tile: String },
+	#[error("tilesets {first:?} and {second:?} share the tileset ID {id}")]
+	DuplicateTilesetId {
+		id: TilesetId,
+		first: String,
+		second: String,
+	},
+	#[error("tileset does not contain any tiles")]
+	EmptyTileset,
+	#[error("default tile with group ID {0:?} does not exist in the tileset")]
+	MissingDefaultTile(TileGroupId),
+	#[error("tileset does not contain a textured tile named {0:?}")]
+	TileNotFound(String),
+	#[error("tile {tile:?} has an invalid size (expected {expected:?}, found {found:?})")]
+	InvalidTileSize {
+		tile: String,
+		expected: Vec2,
+		found: Vec2,
+	},
+	#[error("frame {frame} of animated tile {tile:?} is not the same size as its first frame")]
+	InconsistentFrameSizes { tile: String, frame: usize },
+	#[error("animated tile {tile:?} starts on frame {frame}, but only has {frame_count} frames")]
+	InvalidStartFrame {
+		tile: String,
+		frame: usize,
+		frame_count: usize,
+	},
+	#[error("tile {tile:?} has an invalid region {rect:?} (expected whole pixels in its texture)")]
+	InvalidRegion { tile: String, rect: Rect },
+	#[error("tile definition {path:?} (or its bases) is missing the {field:?} field")]
+	MissingTileField { field: &'static str, path: PathBuf },
+	#[error("tile definition {0:?} imports itself through its bases")]
+	CircularTileImport(PathBuf),
+	#[error("atlas index {0} is too large to be represented")]
+	AtlasIndexOverflow(usize),
+	#[error("atlas of size {width}x{height} is too large to be represented")]
+	AtlasSizeOverflow { width: u32, height: u32 },
+	#[error("tile at atlas index {index} is out of the atlas bounds (atlas size {size:?})")]
+	TileOutOfBounds { index: usize, size: Vec2 },
+	#[error("texture at atlas index {index} does not fit a row-major grid of {columns} column(s)")]
+	InvalidGridLayout { index: usize, columns: u32 },
+	#[error("tileset declares {count} tiles, exceeding the limit of {max}")]
+	TooManyTiles { count: u64, max: usize },
+	#[error("image {path:?} is {bytes} bytes, exceeding the limit of {max}")]
+	ImageTooLarge { path: PathBuf, bytes: u64, max: usize },
+	#[error("atlas would contain {pixels} pixels, exceeding the limit of {max}")]
+	AtlasTooLarge { pixels: u64, max: u64 },
+	#[error("path {0:?} escapes the asset root")]
+	PathEscape(PathBuf),
+	#[error("could not bake or read baked tileset: {0}")]
+	BakeError(bincode::Error),
+	#[error("could not determine the image format of {0:?}")]
+	UnknownImageFormat(PathBuf),
+	#[cfg(feature = "png-export")]
+	#[error("could not export atlas texture: {0}")]
+	AtlasExportError(image::ImageError),
+	#[cfg(feature = "tiled")]
+	#[error("could not parse Tiled tileset: {0}")]
+	InvalidTiledTileset(String),
+}
+
+impl From<std::io::Error> for TilesetError {
+	/// Wraps the given IO error so that every failed read surfaces as
+	/// [`TilesetError::AssetIoError`], regardless of where the bytes came from
+	fn from(err: std::io::Error) -> Self {
+		Self::AssetIoError(AssetIoError::Io(err))
+	}
 }
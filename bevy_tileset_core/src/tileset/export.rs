@@ -0,0 +1,126 @@
+//! Exporting the packed atlas texture as a PNG (e.g. for debugging padding and packing issues)
+
+use std::path::Path;
+
+use bevy::prelude::{Handle, Image};
+use bevy::render::render_resource::TextureFormat;
+use bevy_tile_atlas::TextureStore;
+use image::codecs::png::PngEncoder;
+use image::{ColorType, ImageEncoder, ImageError};
+
+use crate::prelude::{RawTileset, Tileset, TilesetError};
+use crate::tileset::pixels;
+
+/// Encodes the given atlas texture as a PNG
+fn encode_png(texture: &Image) -> Result<Vec<u8>, TilesetError> {
+	let format = texture.texture_descriptor.format;
+	if !matches!(format, TextureFormat::Rgba8Unorm | TextureFormat::Rgba8UnormSrgb) {
+		return Err(TilesetError::InvalidData {
+			expected: String::from("An RGBA8 atlas texture"),
+			found: format!("{:?} atlas texture", format),
+		});
+	}
+
+	let (width, height) = pixels::dimensions(texture);
+	let mut bytes = Vec::new();
+	PngEncoder::new(&mut bytes)
+		.write_image(&texture.data, width, height, ColorType::Rgba8)
+		.map_err(TilesetError::AtlasExportError)?;
+	Ok(bytes)
+}
+
+/// Saves the given atlas texture to a PNG file at the given path
+fn save_png<TStore: TextureStore>(
+	texture: &Handle<Image>,
+	path: &Path,
+	texture_store: &TStore,
+) -> Result<(), TilesetError> {
+	let texture = texture_store
+		.get(texture)
+		.ok_or(TilesetError::ImageNotFound)?;
+	let bytes = encode_png(texture)?;
+	std::fs::write(path, bytes)
+		.map_err(|err| TilesetError::AtlasExportError(ImageError::IoError(err)))
+}
+
+impl RawTileset {
+	/// Saves the packed atlas texture to a PNG file
+	///
+	/// This is useful for inspecting how the tiles were packed (including any padding).
+	///
+	/// # Arguments
+	///
+	/// * `path`: The path of the PNG file to write
+	/// * `texture_store`: The store containing the atlas texture
+	///
+	/// returns: Result<(), TilesetError>
+	///
+	pub fn save_atlas_png<TPath: AsRef<Path>, TStore: TextureStore>(
+		&self,
+		path: TPath,
+		texture_store: &TStore,
+	) -> Result<(), TilesetError> {
+		save_png(self.texture(), path.as_ref(), texture_store)
+	}
+}
+
+impl Tileset {
+	/// Saves the packed atlas texture to a PNG file
+	///
+	/// This is useful for inspecting how the tiles were packed (including any padding).
+	///
+	/// # Arguments
+	///
+	/// * `path`: The path of the PNG file to write
+	/// * `texture_store`: The store containing the atlas texture
+	///
+	/// returns: Result<(), TilesetError>
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use bevy::prelude::*;
+	/// # use bevy_tileset_core::prelude::*;
+	///
+	/// fn export(tilesets: Tilesets, images: Res<Assets<Image>>) {
+	/// 	if let Some(tileset) = tilesets.get_by_name("My Tileset") {
+	/// 		tileset.save_atlas_png("my_tileset_atlas.png", &*images).unwrap();
+	/// 	}
+	/// }
+	/// ```
+	pub fn save_atlas_png<TPath: AsRef<Path>, TStore: TextureStore>(
+		&self,
+		path: TPath,
+		texture_store: &TStore,
+	) -> Result<(), TilesetError> {
+		save_png(self.texture(), path.as_ref(), texture_store)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use bevy::render::render_resource::{Extent3d, TextureDimension};
+
+	use super::*;
+	use crate::test_utils::solid_image;
+
+	#[test]
+	fn should_encode_atlas_png() {
+		let bytes = encode_png(&solid_image(8, 4, [255, 0, 0, 255])).unwrap();
+		let decoded = image::load_from_memory(&bytes).unwrap().into_rgba8();
+		assert_eq!((8, 4), decoded.dimensions());
+		assert_eq!(&[255, 0, 0, 255], &decoded.get_pixel(7, 3).0);
+
+		let hdr = Image::new_fill(
+			Extent3d {
+				width: 1,
+				height: 1,
+				depth_or_array_layers: 1,
+			},
+			TextureDimension::D2,
+			&[0; 8],
+			TextureFormat::Rgba16Float,
+		);
+		assert!(encode_png(&hdr).is_err());
+	}
+}
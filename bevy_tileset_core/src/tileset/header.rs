@@ -0,0 +1,207 @@
+//! Reading image dimensions from their headers, without decoding any pixels
+//!
+//! This allows textures to be checked against a tileset's `tile_size` before committing to
+//! decoding them, which can be expensive for large tilesets.
+
+use std::collections::HashMap;
+
+use bevy::asset::HandleId;
+use bevy::prelude::{UVec2, Vec2};
+#[cfg(feature = "variants")]
+use bevy_tileset_tiles::prelude::{SimpleTileHandle, VariantTileHandle};
+use bevy_tileset_tiles::prelude::{TileHandle, TileHandleType, TileTransform};
+
+/// Reads the dimensions of an encoded image from its header
+///
+/// Supports PNG, JPEG, BMP, DDS, and KTX2 images. Returns `None` for any other format (or if
+/// the header is malformed).
+pub(crate) fn image_dimensions(bytes: &[u8]) -> Option<UVec2> {
+	let u32_be = |at: usize| Some(u32::from_be_bytes(bytes.get(at..at + 4)?.try_into().ok()?));
+	let u32_le = |at: usize| Some(u32::from_le_bytes(bytes.get(at..at + 4)?.try_into().ok()?));
+
+	if bytes.starts_with(&[0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A]) {
+		// The IHDR chunk always comes first
+		Some(UVec2::new(u32_be(16)?, u32_be(20)?))
+	} else if bytes.starts_with(&[0xFF, 0xD8]) {
+		jpeg_dimensions(bytes)
+	} else if bytes.starts_with(b"BM") {
+		// Negative heights mark top-down bitmaps
+		let height = i32::from_le_bytes(bytes.get(22..26)?.try_into().ok()?);
+		Some(UVec2::new(u32_le(18)?, height.unsigned_abs()))
+	} else if bytes.starts_with(b"DDS ") {
+		Some(UVec2::new(u32_le(16)?, u32_le(12)?))
+	} else if bytes.starts_with(&[0xAB, b'K', b'T', b'X', b' ', b'2', b'0', 0xBB]) {
+		Some(UVec2::new(u32_le(20)?, u32_le(24)?))
+	} else {
+		None
+	}
+}
+
+/// Gets the maximum number of bytes a single pixel of an encoded image may take once decoded
+///
+/// This is an upper bound based on the image's header (such as 16-bit PNGs decoding to a
+/// 16-bit format). Returns `None` for formats whose decoded format can't be determined from
+/// their header alone (such as DDS and KTX2, which may contain float or compressed formats).
+pub(crate) fn decoded_pixel_size(bytes: &[u8]) -> Option<u64> {
+	if bytes.starts_with(&[0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A]) {
+		// The bit depth directly follows the dimensions in the IHDR chunk
+		match *bytes.get(24)? {
+			16 => Some(8),
+			_ => Some(4),
+		}
+	} else if bytes.starts_with(&[0xFF, 0xD8]) || bytes.starts_with(b"BM") {
+		Some(4)
+	} else {
+		None
+	}
+}
+
+/// Scans the segments of a JPEG for the frame header containing its dimensions
+fn jpeg_dimensions(bytes: &[u8]) -> Option<UVec2> {
+	let u16_be = |at: usize| Some(u16::from_be_bytes(bytes.get(at..at + 2)?.try_into().ok()?));
+
+	let mut at = 2;
+	loop {
+		if *bytes.get(at)? != 0xFF {
+			return None;
+		}
+		let marker = *bytes.get(at + 1)?;
+		match marker {
+			// Padding
+			0xFF => at += 1,
+			// Start of frame (excluding DHT, JPG, and DAC)
+			0xC0..=0xCF if !matches!(marker, 0xC4 | 0xC8 | 0xCC) => {
+				let height = u16_be(at + 5)? as u32;
+				let width = u16_be(at + 7)? as u32;
+				return Some(UVec2::new(width, height));
+			},
+			_ => at += 2 + u16_be(at + 2)? as usize,
+		}
+	}
+}
+
+/// Gets the size each texture of the given tiles is expected to be, mapped by its handle ID
+///
+/// Only textures that make up an entire tile texture are included (i.e. not atlas regions or
+/// animation strips), accounting for any transform that swaps their dimensions. Each texture is
+/// paired with the name of the first tile using it.
+pub(crate) fn expected_sizes<'a>(
+	tile_handles: impl IntoIterator<Item = &'a TileHandle>,
+	tile_size: Vec2,
+) -> HashMap<HandleId, (String, Vec2)> {
+	let mut sizes = HashMap::new();
+	for tile in tile_handles {
+		let mut insert = |id: HandleId, transforms: &[Option<TileTransform>]| {
+			let swaps = transforms
+				.iter()
+				.flatten()
+				.filter(|transform| transform.swaps_dimensions())
+				.count();
+			let size = if swaps % 2 == 1 {
+				Vec2::new(tile_size.y, tile_size.x)
+			} else {
+				tile_size
+			};
+			sizes.entry(id).or_insert_with(|| (tile.name.clone(), size));
+		};
+
+		match &tile.tile {
+			TileHandleType::Standard(handle) => insert(handle.id(), &[tile.transform]),
+			TileHandleType::Animated(anim) if anim.regions.is_empty() => {
+				for frame in &anim.frames {
+					insert(frame.id(), &[tile.transform]);
+				}
+			},
+			TileHandleType::MultiAnimated(tracks) => {
+				for anim in tracks.values().filter(|anim| anim.regions.is_empty()) {
+					for frame in &anim.frames {
+						insert(frame.id(), &[tile.transform]);
+					}
+				}
+			},
+			#[cfg(feature = "variants")]
+			TileHandleType::Variant(variants) => {
+				variant_ids(variants, tile.transform, &mut insert);
+			},
+			#[cfg(feature = "auto-tile")]
+			TileHandleType::Auto(autos) => {
+				for auto in autos {
+					variant_ids(&auto.variants, tile.transform, &mut insert);
+				}
+			},
+			_ => {},
+		}
+	}
+	sizes
+}
+
+/// Passes the ID (and transforms) of every whole texture of the given variants to `insert`
+#[cfg(feature = "variants")]
+fn variant_ids(
+	variants: &[VariantTileHandle],
+	transform: Option<TileTransform>,
+	insert: &mut impl FnMut(HandleId, &[Option<TileTransform>]),
+) {
+	for variant in variants {
+		let transforms = [transform, variant.transform];
+		match &variant.tile {
+			SimpleTileHandle::Standard(handle) => insert(handle.id(), &transforms),
+			SimpleTileHandle::Animated(anim) if anim.regions.is_empty() => {
+				for frame in &anim.frames {
+					insert(frame.id(), &transforms);
+				}
+			},
+			SimpleTileHandle::Animated(..) => {},
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn should_read_png_dimensions() {
+		let mut png = vec![0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A];
+		png.extend_from_slice(&[0, 0, 0, 13]);
+		png.extend_from_slice(b"IHDR");
+		png.extend_from_slice(&48u32.to_be_bytes());
+		png.extend_from_slice(&16u32.to_be_bytes());
+		assert_eq!(Some(UVec2::new(48, 16)), image_dimensions(&png));
+
+		// Truncated headers can't be read
+		assert_eq!(None, image_dimensions(&png[..18]));
+	}
+
+	#[test]
+	fn should_read_jpeg_dimensions() {
+		let jpeg = [
+			0xFF, 0xD8, // Start of image
+			0xFF, 0xE0, 0x00, 0x04, 0x00, 0x00, // APP0 (with an empty payload)
+			0xFF, 0xC0, 0x00, 0x0B, 0x08, 0x00, 0x20, 0x00, 0x10, // SOF0 (16x32)
+		];
+		assert_eq!(Some(UVec2::new(16, 32)), image_dimensions(&jpeg));
+	}
+
+	#[test]
+	fn should_get_decoded_pixel_size() {
+		let mut png = vec![0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A];
+		png.extend_from_slice(&[0, 0, 0, 13]);
+		png.extend_from_slice(b"IHDR");
+		png.extend_from_slice(&[0, 0, 0, 1, 0, 0, 0, 1]);
+		assert_eq!(None, decoded_pixel_size(&png));
+
+		png.push(8);
+		assert_eq!(Some(4), decoded_pixel_size(&png));
+		png[24] = 16;
+		assert_eq!(Some(8), decoded_pixel_size(&png));
+
+		assert_eq!(Some(4), decoded_pixel_size(&[0xFF, 0xD8]));
+		assert_eq!(None, decoded_pixel_size(b"DDS "));
+	}
+
+	#[test]
+	fn should_not_read_unknown_dimensions() {
+		assert_eq!(None, image_dimensions(b"GIF89a"));
+	}
+}
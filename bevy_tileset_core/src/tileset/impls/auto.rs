@@ -126,23 +126,52 @@ macro_rules! impl_tileset {
 				}
 			}
 
+			/// Like [`get_auto_index`](Self::get_auto_index), but selects the variant using the given
+			/// tile position as the seed
+			///
+			/// When multiple variants satisfy the matched rule, a weighted pick is made among them (just
+			/// like [`get_auto_index`](Self::get_auto_index)). However, the same position and salt will
+			/// always select the same variant, making it stable across frames and reloads.
+			///
+			/// # Arguments
+			///
+			/// * `name`: The name of the tile
+			/// * `rule`: The rule to match
+			/// * `x`: The x position of the tile
+			/// * `y`: The y position of the tile
+			/// * `salt`: An additional seed value (useful for giving different layers or maps different results)
+			///
+			/// returns: Option<TileIndex>
+			///
+			pub fn get_auto_index_at(
+				&self,
+				name: &str,
+				rule: AutoTileRule,
+				x: i32,
+				y: i32,
+				salt: u64,
+			) -> Option<TileIndex> {
+				let group_id = self.get_tile_group_id(name)?;
+				let data = self.tiles.get(group_id)?;
+
+				match data.tile() {
+					TileType::Auto(autos) => {
+						let id = PartialTileId::from(*group_id);
+						let tile = Self::select_auto_tile(autos, &rule, &id)?;
+						let variant = Self::select_variant_at(tile.variants(), x, y, salt)?;
+						Some(variant.tile().into())
+					}
+					_ => self.get_tile_index(name),
+				}
+			}
+
 			pub(crate) fn select_auto<TId: Into<PartialTileId>>(
 				auto_tiles: &[AutoTileData],
 				rule: AutoTileRule,
 				id: TId,
 			) -> Option<TileIndex> {
 				let id = id.into();
-				let tile = if let Some(idx) = id.auto_index {
-					auto_tiles.get(idx)?
-				} else {
-					match auto_tiles
-						.iter()
-						.find(|&auto| auto.rule().is_subset_of(&rule))
-					{
-						Some(t) => t,
-						None => auto_tiles.last()?,
-					}
-				};
+				let tile = Self::select_auto_tile(auto_tiles, &rule, &id)?;
 
 				let variant = if let Some(idx) = id.variant_index {
 					tile.variants().get(idx)?
@@ -152,9 +181,156 @@ macro_rules! impl_tileset {
 
 				Some(variant.tile().into())
 			}
+
+			/// Selects the auto tile matching the given rule (or the one specified by the ID)
+			///
+			/// If no auto tile matches, the last one is used as a fallback.
+			fn select_auto_tile<'a>(
+				auto_tiles: &'a [AutoTileData],
+				rule: &AutoTileRule,
+				id: &PartialTileId,
+			) -> Option<&'a AutoTileData> {
+				if let Some(idx) = id.auto_index {
+					return auto_tiles.get(idx);
+				}
+
+				match auto_tiles.iter().find(|&auto| auto.rule().is_subset_of(rule)) {
+					Some(tile) => Some(tile),
+					None => auto_tiles.last(),
+				}
+			}
 		}
 	};
 }
 
 impl_tileset!(Tileset);
 impl_tileset!(RawTileset);
+
+#[cfg(test)]
+mod tests {
+	use bevy_tile_atlas::TextureStore;
+	use bevy_tileset_tiles::prelude::*;
+
+	use crate::prelude::{TileIndex, TilesetBuilder};
+	use crate::test_utils::{solid_image, TestTextureStore};
+
+	#[test]
+	fn should_select_animated_auto_variant() {
+		let mut store = TestTextureStore::default();
+		let mut image = || store.add(solid_image(4, 4, [255; 4]));
+
+		let corner_rule = AutoTileRule {
+			north: Some(false),
+			west: Some(false),
+			east: Some(true),
+			south: Some(true),
+			..Default::default()
+		};
+		let corner = AutoTileHandle {
+			rule: corner_rule,
+			variants: vec![VariantTileHandle {
+				weight: 1.0,
+				transform: None,
+				tile: SimpleTileHandle::Animated(AnimatedTileHandle {
+					speed: 2.0,
+					frames: vec![image(), image(), image()],
+					regions: Vec::new(),
+					..Default::default()
+				}),
+			}],
+		};
+		let fallback = AutoTileHandle {
+			rule: AutoTileRule::default(),
+			variants: vec![VariantTileHandle {
+				weight: 1.0,
+				transform: None,
+				tile: SimpleTileHandle::Standard(image()),
+			}],
+		};
+
+		let mut builder = TilesetBuilder::default();
+		builder
+			.add_tile(TileHandle::new_auto("Wall", vec![corner, fallback]), 0, &store)
+			.unwrap();
+		let tileset = builder.build("Auto", 0, &mut store).unwrap();
+
+		let index = tileset.get_auto_index("Wall", corner_rule).unwrap();
+		let (start, end) = match index {
+			TileIndex::Animated(start, end, speed) => {
+				assert_eq!(2.0, speed);
+				(start, end)
+			},
+			index => panic!("expected an animated index, found {:?}", index),
+		};
+		assert_eq!(2, end - start);
+
+		// Every frame should be recognized as belonging to the rule so the
+		// auto tiler doesn't reset the tile mid-animation
+		for frame in start..=end {
+			assert!(tileset.is_auto_variant("Wall", &frame, &corner_rule));
+		}
+
+		let index = tileset
+			.get_auto_index("Wall", AutoTileRule::default_false())
+			.unwrap();
+		assert!(matches!(index, TileIndex::Standard(..)));
+	}
+
+	#[test]
+	fn should_pick_weighted_auto_variants() {
+		let mut store = TestTextureStore::default();
+		let mut image = || store.add(solid_image(4, 4, [255; 4]));
+
+		let corner_rule = AutoTileRule {
+			north: Some(false),
+			west: Some(false),
+			east: Some(true),
+			south: Some(true),
+			..Default::default()
+		};
+		let corner = AutoTileHandle {
+			rule: corner_rule,
+			variants: vec![
+				VariantTileHandle {
+					weight: 1.0,
+					transform: None,
+					tile: SimpleTileHandle::Standard(image()),
+				},
+				VariantTileHandle {
+					weight: 1.0,
+					transform: None,
+					tile: SimpleTileHandle::Standard(image()),
+				},
+			],
+		};
+
+		let mut builder = TilesetBuilder::default();
+		builder
+			.add_tile(TileHandle::new_auto("Wall", vec![corner]), 0, &store)
+			.unwrap();
+		let tileset = builder.build("Auto", 0, &mut store).unwrap();
+
+		let mut counts = [0usize; 2];
+		for x in 0..32 {
+			for y in 0..32 {
+				let index = tileset
+					.get_auto_index_at("Wall", corner_rule, x, y, 0)
+					.unwrap();
+				assert_eq!(
+					index,
+					tileset
+						.get_auto_index_at("Wall", corner_rule, x, y, 0)
+						.unwrap()
+				);
+				match index {
+					TileIndex::Standard(index) => counts[index] += 1,
+					_ => panic!("expected a standard index"),
+				}
+			}
+		}
+
+		// Expect a roughly even split
+		let ratio = counts[0] as f32 / counts[1] as f32;
+		assert!(0.8 < ratio && ratio < 1.25, "unexpected ratio: {}", ratio);
+	}
+}
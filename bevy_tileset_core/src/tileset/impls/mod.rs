@@ -1,6 +1,9 @@
 //! Implementation details for [`Tileset`] and [`RawTileset`]
 
-use bevy::prelude::{Handle, Image, TextureAtlas, Vec2};
+use std::path::PathBuf;
+
+use bevy::prelude::{Handle, Image, Rect, TextureAtlas, UVec2, Vec2};
+use serde::{Deserialize, Serialize};
 
 #[cfg(feature = "auto-tile")]
 pub use auto::*;
@@ -15,6 +18,50 @@ mod auto;
 #[cfg(feature = "variants")]
 mod variants;
 
+/// Metadata describing the animation of an animated tile
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct AnimationInfo {
+	/// The number of frames in the animation
+	pub frame_count: usize,
+	/// The speed of the animation (in frames per second)
+	pub speed: f32,
+}
+
+/// The transparent border trimmed from a tile's texture before it was packed
+///
+/// See [`TilesetBuilder::trim`](crate::prelude::TilesetBuilder::trim).
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct TileTrim {
+	/// The position of the trimmed texture's top-left corner within the original texture
+	pub offset: UVec2,
+	/// The size of the original texture (before it was trimmed)
+	pub source_size: UVec2,
+}
+
+/// The number of tiles of each type in a tileset
+///
+/// See [`Tileset::tile_count_by_type`].
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct TileTypeCounts {
+	/// The number of [`TileType::Standard`] tiles (including atlas region tiles)
+	pub standard: usize,
+	/// The number of [`TileType::Animated`] and [`TileType::MultiAnimated`] tiles
+	pub animated: usize,
+	/// The number of variant tiles
+	pub variant: usize,
+	/// The number of auto tiles
+	pub auto: usize,
+	/// The number of [`TileType::Empty`] tiles
+	pub empty: usize,
+}
+
+impl TileTypeCounts {
+	/// The total number of tiles
+	pub fn total(&self) -> usize {
+		self.standard + self.animated + self.variant + self.auto + self.empty
+	}
+}
+
 macro_rules! impl_tileset {
 	($name: ident) => {
 		impl $name {
@@ -38,6 +85,194 @@ macro_rules! impl_tileset {
 				self.tile_size
 			}
 
+			/// Gets the dimensions of the atlas texture (in pixels)
+			pub fn atlas_dimensions(&self) -> UVec2 {
+				self.size.as_uvec2()
+			}
+
+			/// Gets the number of LOD levels of the atlas texture (including the full-resolution one)
+			pub fn lod_levels(&self) -> usize {
+				self.lod_textures.len() + 1
+			}
+
+			/// Gets the atlas texture for the given LOD level
+			///
+			/// Level `0` is the full-resolution atlas texture, and each following level is half the
+			/// size of the previous one.
+			///
+			/// # Arguments
+			///
+			/// * `level`: The LOD level
+			///
+			/// returns: Option<&Handle<Image>>
+			///
+			pub fn atlas_for_lod(&self, level: usize) -> Option<&Handle<Image>> {
+				match level {
+					0 => Some(self.texture()),
+					level => self.lod_textures.get(level - 1),
+				}
+			}
+
+			/// Gets the number of atlas pages in this tileset (including this one)
+			///
+			/// Tiles with a [sampler hint](TileDef::sampler) are packed into a separate atlas page
+			/// for each distinct sampler, since an atlas texture can only have a single sampler.
+			/// Likewise, the region tiles of each existing atlas texture get a page that uses that
+			/// texture as its atlas texture. Each page is a complete tileset with its own atlas
+			/// texture, so every page adds a texture (and usually a draw batch) when rendering.
+			pub fn page_count(&self) -> usize {
+				self.pages.len() + 1
+			}
+
+			/// Gets the atlas page at the given index
+			///
+			/// Page `0` is this tileset, and each following page contains the tiles of one sampler
+			/// hint (or the region tiles of one atlas texture). Tiles can be found by name from
+			/// any page (see [`Self::get_paged_tile_index`]), but their atlas indices are local
+			/// to their page.
+			///
+			/// # Arguments
+			///
+			/// * `index`: The index of the page
+			///
+			/// returns: Option<&Self>
+			///
+			pub fn page(&self, index: usize) -> Option<&Self> {
+				match index {
+					0 => Some(self),
+					index => self.pages.get(index - 1),
+				}
+			}
+
+			/// Gets the index of the atlas page containing the given tile
+			///
+			/// Lookups of a tile's metadata by name (such as [`Self::get_tile_data`]) check every
+			/// page, while lookups of its atlas indices (such as [`Self::get_tile_index`]) only
+			/// cover page `0`, since those indices are local to the tile's page.
+			///
+			/// # Arguments
+			///
+			/// * `name`: The tile's name
+			///
+			/// returns: Option<usize>
+			///
+			pub fn tile_page(&self, name: &str) -> Option<usize> {
+				if self.tile_ids.contains_key(name) {
+					return Some(0);
+				}
+				self.pages
+					.iter()
+					.position(|page| page.tile_ids.contains_key(name))
+					.map(|index| index + 1)
+			}
+
+			/// Gets the atlas page containing the given tile
+			fn tile_owner(&self, name: &str) -> Option<&Self> {
+				self.page(self.tile_page(name)?)
+			}
+
+			/// Gets the number of textures packed into the atlas
+			///
+			/// Note that this counts every frame of an animated tile and every variant of a variant
+			/// tile individually.
+			pub fn tile_count(&self) -> usize {
+				self.tile_indices.len()
+			}
+
+			/// Gets the number of tiles of each type in this tileset (including its atlas pages)
+			///
+			/// Unlike [`Self::tile_count`], this counts tiles rather than textures, so an animated
+			/// tile only counts once.
+			pub fn tile_count_by_type(&self) -> TileTypeCounts {
+				let mut counts = TileTypeCounts::default();
+				for data in self.tiles.values() {
+					match data.tile() {
+						TileType::Standard(..) => counts.standard += 1,
+						TileType::Animated(..) | TileType::MultiAnimated(..) => {
+							counts.animated += 1
+						},
+						#[cfg(feature = "variants")]
+						TileType::Variant(..) => counts.variant += 1,
+						#[cfg(feature = "auto-tile")]
+						TileType::Auto(..) => counts.auto += 1,
+						TileType::Empty => counts.empty += 1,
+					}
+				}
+				for page in &self.pages {
+					let page = page.tile_count_by_type();
+					counts.standard += page.standard;
+					counts.animated += page.animated;
+					counts.variant += page.variant;
+					counts.auto += page.auto;
+					counts.empty += page.empty;
+				}
+				counts
+			}
+
+			/// Gets the estimated size of the atlas texture (in bytes)
+			///
+			/// Tile textures are converted to an uncompressed 8-bit RGBA texture format when
+			/// they're read, so this is the format every atlas uses.
+			pub fn atlas_byte_size(&self) -> usize {
+				let dimensions = self.atlas_dimensions();
+				dimensions.x as usize * dimensions.y as usize * 4
+			}
+
+			/// Gets the number of columns the atlas was laid out in
+			///
+			/// This is the column count given to [`TilesetBuilder::grid_columns`], which may be
+			/// fewer than the number of tiles that fit across the atlas (such as when its size was
+			/// padded to a power of two).
+			///
+			/// returns: Option<u32>
+			///
+			/// Returns `None` if the tileset wasn't built as a grid.
+			pub fn grid_columns(&self) -> Option<u32> {
+				self.grid_columns
+			}
+
+			/// Get the atlas index of the tile at the given position in the atlas grid
+			///
+			/// This is useful for importing maps authored against a grid layout (such as Tiled
+			/// maps), which reference tiles by their position. See [`Self::grid_columns`].
+			///
+			/// # Arguments
+			///
+			/// * `column`: The column of the tile (starting at `0` on the left)
+			/// * `row`: The row of the tile (starting at `0` at the top)
+			///
+			/// returns: Option<usize>
+			///
+			/// Returns `None` if the position is outside the grid or doesn't contain a tile.
+			pub fn index_from_grid(&self, column: u32, row: u32) -> Option<usize> {
+				let columns = self.grid_columns()?;
+				if column >= columns {
+					return None;
+				}
+				let index = row as usize * columns as usize + column as usize;
+				(index < self.tile_count()).then_some(index)
+			}
+
+			/// Get the position in the atlas grid of the tile at the given atlas index
+			///
+			/// This is the inverse of [`Self::index_from_grid`].
+			///
+			/// # Arguments
+			///
+			/// * `index`: The atlas index of the tile
+			///
+			/// returns: Option<(u32, u32)>
+			///
+			/// Returns the `(column, row)` of the tile, or `None` if the index is out of range.
+			pub fn grid_from_index(&self, index: usize) -> Option<(u32, u32)> {
+				if index >= self.tile_count() {
+					return None;
+				}
+				let columns = self.grid_columns()? as usize;
+				let row = u32::try_from(index / columns).ok()?;
+				Some(((index % columns) as u32, row))
+			}
+
 			/// Get the name of a tile by its group ID
 			///
 			/// # Arguments
@@ -65,6 +300,8 @@ macro_rules! impl_tileset {
 
 			/// Get the group ID of a tile by its name
 			///
+			/// This includes the tiles of every additional atlas page.
+			///
 			/// # Arguments
 			///
 			/// * `name`: The tile's name
@@ -72,7 +309,221 @@ macro_rules! impl_tileset {
 			/// returns: Option<&u32>
 			///
 			pub fn get_tile_group_id(&self, name: &str) -> Option<&TileGroupId> {
-				self.tile_ids.get(name)
+				self.tile_ids
+					.get(name)
+					.or_else(|| self.pages.iter().find_map(|page| page.tile_ids.get(name)))
+			}
+
+			/// Checks that every given tile name exists in this tileset
+			///
+			/// This is useful for failing fast (e.g. in a startup system) rather than having a
+			/// typo in a tile name silently fail when the tile is first used.
+			///
+			/// # Arguments
+			///
+			/// * `names`: The tile names to validate
+			///
+			/// returns: Result<(), Vec<String>>
+			///
+			/// The error contains every name that could not be found (in the order given).
+			///
+			/// # Examples
+			///
+			/// ```
+			/// # use bevy_tileset_core::prelude::*;
+			///
+			/// fn validate(tileset: &Tileset) {
+			/// 	if let Err(missing) = tileset.validate_names(&["Grass", "Dirt"]) {
+			/// 		panic!("tileset is missing tiles: {:?}", missing);
+			/// 	}
+			/// }
+			/// ```
+			pub fn validate_names(&self, names: &[&str]) -> Result<(), Vec<String>> {
+				let missing = names
+					.iter()
+					.filter(|name| self.get_tile_group_id(name).is_none())
+					.map(|name| name.to_string())
+					.collect::<Vec<_>>();
+				if missing.is_empty() {
+					Ok(())
+				} else {
+					Err(missing)
+				}
+			}
+
+			/// Gets the names of all categories in this tileset (in alphabetical order)
+			pub fn categories(&self) -> impl Iterator<Item = &str> {
+				self.categories.keys().map(String::as_str)
+			}
+
+			/// Get the group IDs of the tiles in the given category
+			///
+			/// # Arguments
+			///
+			/// * `category`: The name of the category
+			///
+			/// returns: Option<&[u32]>
+			///
+			pub fn tiles_in_category(&self, category: &str) -> Option<&[TileGroupId]> {
+				self.categories.get(category).map(Vec::as_slice)
+			}
+
+			/// Get the average color of a tile by its name
+			///
+			/// This is computed from the tile's first texture (after any transforms) and is useful
+			/// for things like minimaps, where a single representative color is needed per tile.
+			///
+			/// # Arguments
+			///
+			/// * `name`: The tile's name
+			///
+			/// returns: Option<[u8; 4]>
+			///
+			pub fn tile_average_color(&self, name: &str) -> Option<[u8; 4]> {
+				let page = self.tile_owner(name)?;
+				page.tile_colors.get(page.tile_ids.get(name)?).copied()
+			}
+
+			/// Gets the index used by this tileset's empty tiles
+			///
+			/// This is always [`TileIndex::Empty`], but is only returned if this tileset actually
+			/// contains a [`TileType::Empty`] tile.
+			///
+			/// returns: Option<TileIndex>
+			///
+			pub fn empty_index(&self) -> Option<TileIndex> {
+				self.tiles
+					.values()
+					.any(TileData::is_empty)
+					.then_some(TileIndex::Empty)
+			}
+
+			/// Get the size a tile was stored at in the atlas (in pixels) by its name
+			///
+			/// This is the size of the tile's first texture. Tiles without any textures (such as
+			/// [`TileType::Empty`] tiles) use the tileset's [`tile_size`](Self::tile_size).
+			///
+			/// # Arguments
+			///
+			/// * `name`: The tile's name
+			///
+			/// returns: Option<UVec2>
+			///
+			pub fn tile_size_of(&self, name: &str) -> Option<UVec2> {
+				let page = self.tile_owner(name)?;
+				Some(
+					page.tile_sizes
+						.get(page.tile_ids.get(name)?)
+						.copied()
+						.unwrap_or_else(|| page.tile_size.as_uvec2()),
+				)
+			}
+
+			/// Gets the paths of every file this tileset was loaded from
+			///
+			/// This includes the tileset definition itself, every tile definition (along with
+			/// their bases), and every texture, in sorted order. Paths are relative to the asset
+			/// root (just like the paths given to the `AssetServer`), so joining them with the
+			/// asset directory gives their location on disk. This is useful for tracking when a
+			/// tileset needs to be rebuilt, such as in build tools or file watchers.
+			///
+			/// Baked tilesets only depend on their baked file, and tilesets that weren't loaded
+			/// from files (such as those built with a [`TilesetBuilder`]) don't have any.
+			pub fn dependency_paths(&self) -> &[PathBuf] {
+				&self.dependency_paths
+			}
+
+			/// Gets the warnings for any tiles that were skipped while loading this tileset
+			///
+			/// Tiles are only skipped if the tileset definition enables
+			/// [`skip_invalid_tiles`](TilesetDef::skip_invalid_tiles). Each warning names the
+			/// skipped tile and the reason it was skipped.
+			pub fn load_warnings(&self) -> &[String] {
+				&self.load_warnings
+			}
+
+			/// Get the raw RON source of a tile's definition by its name
+			///
+			/// This is only available if the tileset definition enables
+			/// [`retain_tile_sources`](TilesetDef::retain_tile_sources), and contains the original
+			/// text of the tile's own definition file (including any comments).
+			///
+			/// # Arguments
+			///
+			/// * `name`: The tile's name
+			///
+			/// returns: Option<&str>
+			///
+			pub fn tile_source(&self, name: &str) -> Option<&str> {
+				let group_id = self.get_tile_group_id(name)?;
+				self.tile_sources.get(group_id).map(String::as_str)
+			}
+
+			/// Checks if this tileset is structurally equal to another
+			///
+			/// This compares the tilesets' names, IDs, sizes, tile data, and index tables while
+			/// ignoring any asset handles (which are meaningless when comparing tilesets across
+			/// loads). It's mainly useful for comparing tilesets in tests.
+			///
+			/// The atlas regions of a [`RawTileset`] are compared as well. Since a [`Tileset`]
+			/// only holds a handle to its `TextureAtlas`, its atlas regions aren't compared.
+			///
+			/// # Arguments
+			///
+			/// * `other`: The tileset to compare against
+			///
+			/// returns: bool
+			///
+			pub fn structurally_eq(&self, other: &Self) -> bool {
+				self.id == other.id
+					&& self.name == other.name
+					&& self.size == other.size
+					&& self.tile_size == other.tile_size
+					&& self.tiles == other.tiles
+					&& self.atlas_rects() == other.atlas_rects()
+					&& self.tile_ids == other.tile_ids
+					&& self.tile_names == other.tile_names
+					&& self.tile_indices == other.tile_indices
+					&& self.tile_colors == other.tile_colors
+					&& self.tile_sizes == other.tile_sizes
+					&& self.tile_trims == other.tile_trims
+					&& self.categories == other.categories
+					&& self.default_tile == other.default_tile
+					&& self.lod_textures.len() == other.lod_textures.len()
+					&& self.pages.len() == other.pages.len()
+					&& self
+						.pages
+						.iter()
+						.zip(&other.pages)
+						.all(|(page, other)| page.structurally_eq(other))
+			}
+
+			/// Get the fraction of the atlas area covered by packed tiles
+			///
+			/// This is the combined area of every texture in the atlas divided by the atlas's total
+			/// area. Low values mean the atlas contains a lot of unused space (and wasted VRAM),
+			/// which may be reduced by tweaking the packing and padding settings.
+			///
+			/// returns: f32
+			///
+			pub fn atlas_utilization(&self) -> f32 {
+				let total = self.size.x * self.size.y;
+				if total <= 0.0 {
+					return 0.0;
+				}
+
+				let packed: f32 = self
+					.tile_indices
+					.values()
+					.map(|id| {
+						self.tile_sizes
+							.get(&id.group_id)
+							.map(|size| size.as_vec2())
+							.unwrap_or(self.tile_size)
+					})
+					.map(|size| size.x * size.y)
+					.sum();
+				(packed / total).min(1.0)
 			}
 
 			/// Get the ID of a tile by its index in the texture atlas
@@ -99,8 +550,122 @@ macro_rules! impl_tileset {
 				self.tile_handles.get(index)
 			}
 
+			/// Gets the handles of the original images used to build this tileset
+			///
+			/// This includes every frame of an animated tile and every variant of variant and auto
+			/// tiles, ordered by their index in the `TextureAtlas` (duplicates are only included
+			/// once). These handles are weak, so they can be used for dependency tracking (such as
+			/// checking that the images are loaded) or re-packing without keeping the images alive.
+			///
+			/// Note that baked tilesets don't contain their source images, so this will be empty.
+			///
+			/// returns: Vec<Handle<Image>>
+			///
+			pub fn source_image_handles(&self) -> Vec<Handle<Image>> {
+				let mut indices = self.tile_handles.keys().copied().collect::<Vec<_>>();
+				indices.sort_unstable();
+
+				let mut handles: Vec<Handle<Image>> = Vec::with_capacity(indices.len());
+				for index in indices {
+					let handle = &self.tile_handles[&index];
+					if !handles.contains(handle) {
+						handles.push(handle.clone_weak());
+					}
+				}
+				handles
+			}
+
+			/// Gets the atlas indices of every texture packed from the given source image
+			///
+			/// This is the reverse of [`get_tile_handle`](Self::get_tile_handle), and is useful for
+			/// finding the tiles affected when a source image changes. Note that this doesn't
+			/// include the indices within any additional atlas pages.
+			///
+			/// # Arguments
+			///
+			/// * `handle`: The handle of the source image
+			///
+			/// returns: Vec<usize>
+			///
+			pub fn get_source_image_indices(&self, handle: &Handle<Image>) -> Vec<usize> {
+				let mut indices = self
+					.tile_handles
+					.iter()
+					.filter(|(.., tile_handle)| *tile_handle == handle)
+					.map(|(index, ..)| *index)
+					.collect::<Vec<_>>();
+				indices.sort_unstable();
+				indices
+			}
+
+			/// Get the [`TileHandle`] a tile was built from by its name
+			///
+			/// Every image handle contained in the returned tile handle is weak. Note that baked
+			/// tilesets don't contain their tile handles, so this always returns `None` for them.
+			///
+			/// This is separate from [`get_tile_handle`](Self::get_tile_handle), which gets the
+			/// image handle at a given atlas index.
+			///
+			/// # Arguments
+			///
+			/// * `name`: The tile's name
+			///
+			/// returns: Option<&TileHandle>
+			///
+			pub fn get_tile_handle_by_name(&self, name: &str) -> Option<&TileHandle> {
+				let group_id = self.get_tile_group_id(name)?;
+				self.get_tile_handle_by_id(group_id)
+			}
+
+			/// Get the [`TileHandle`] a tile was built from by its group ID
+			///
+			/// See [`get_tile_handle_by_name`](Self::get_tile_handle_by_name) for details.
+			///
+			/// # Arguments
+			///
+			/// * `group_id`: The tile's group ID
+			///
+			/// returns: Option<&TileHandle>
+			///
+			pub fn get_tile_handle_by_id(&self, group_id: &TileGroupId) -> Option<&TileHandle> {
+				self.group_handles.get(group_id).or_else(|| {
+					self.pages
+						.iter()
+						.find_map(|page| page.group_handles.get(group_id))
+				})
+			}
+
+			/// Get the image handles of every animation frame of a tile by its name (in order)
+			///
+			/// This is useful for making sure a tile's animation is loaded before it's shown. For
+			/// variant and auto tiles, this contains the frames of every animated variant (in the
+			/// order they were defined).
+			///
+			/// Every returned handle is weak. Since baked tilesets don't contain their tile handles,
+			/// this always returns `None` for them.
+			///
+			/// # Arguments
+			///
+			/// * `name`: The tile's name
+			///
+			/// returns: Option<Vec<Handle<Image>>>
+			///
+			/// Returns `None` if the tile doesn't exist or doesn't contain any animation frames.
+			pub fn animation_frame_handles(&self, name: &str) -> Option<Vec<Handle<Image>>> {
+				let tile = self.get_tile_handle_by_name(name)?;
+				let frames = animation_frames(tile);
+				if frames.is_empty() {
+					None
+				} else {
+					Some(frames.into_iter().map(Handle::clone_weak).collect())
+				}
+			}
+
 			/// Get the data of a tile by its name
 			///
+			/// This finds tiles in any atlas page, so the atlas indices within the data are local
+			/// to the tile's page (see [`Self::tile_page`]).
+			///
 			/// # Arguments
 			///
 			/// * `name`: The tile's name
@@ -108,8 +673,313 @@ macro_rules! impl_tileset {
 			/// returns: Option<&TileData>
 			///
 			pub fn get_tile_data(&self, name: &str) -> Option<&TileData> {
-				let id = self.tile_ids.get(name)?;
-				self.tiles.get(id)
+				self.tile_owner(name)?.local_tile_data(name)
+			}
+
+			/// Get the data of a tile in this tileset's own atlas by its name
+			fn local_tile_data(&self, name: &str) -> Option<&TileData> {
+				self.tiles.get(self.tile_ids.get(name)?)
+			}
+
+			/// Get the collision shape of a tile by its name
+			///
+			/// # Arguments
+			///
+			/// * `name`: The tile's name
+			///
+			/// returns: Option<&TileCollision>
+			///
+			pub fn tile_collision(&self, name: &str) -> Option<&TileCollision> {
+				Some(self.get_tile_data(name)?.collision())
+			}
+
+			/// Get the tags attached to a tile by its name
+			///
+			/// # Arguments
+			///
+			/// * `name`: The tile's name
+			///
+			/// returns: Option<&[String]>
+			///
+			pub fn tile_tags(&self, name: &str) -> Option<&[String]> {
+				Some(self.get_tile_data(name)?.tags())
+			}
+
+			/// Get the render ordering offset of a tile by its name
+			///
+			/// # Arguments
+			///
+			/// * `name`: The tile's name
+			///
+			/// returns: Option<f32>
+			///
+			pub fn tile_z_offset(&self, name: &str) -> Option<f32> {
+				Some(self.get_tile_data(name)?.z_offset())
+			}
+
+			/// Get the render layer of a tile by its name
+			///
+			/// # Arguments
+			///
+			/// * `name`: The tile's name
+			///
+			/// returns: Option<i32>
+			///
+			pub fn tile_layer(&self, name: &str) -> Option<i32> {
+				Some(self.get_tile_data(name)?.layer())
+			}
+
+			/// Get the normalized origin of a tile by its name
+			///
+			/// See [`TileDef::pivot`] for how this is defined.
+			///
+			/// # Arguments
+			///
+			/// * `name`: The tile's name
+			///
+			/// returns: Option<Vec2>
+			///
+			pub fn tile_pivot(&self, name: &str) -> Option<Vec2> {
+				Some(self.get_tile_data(name)?.pivot())
+			}
+
+			/// Get the aliases of a tile by its name (or one of its aliases)
+			///
+			/// # Arguments
+			///
+			/// * `name`: The tile's name
+			///
+			/// returns: Option<&[String]>
+			///
+			pub fn tile_aliases(&self, name: &str) -> Option<&[String]> {
+				Some(self.get_tile_data(name)?.aliases())
+			}
+
+			/// Get the transparent border trimmed from a tile by its name (if it was trimmed)
+			///
+			/// Each texture is trimmed separately, so for tiles with multiple textures (such as
+			/// animated tiles), this is the trim of their first texture. Use
+			/// [`Self::tile_trim_at`] to get the trim of any other texture.
+			///
+			/// # Arguments
+			///
+			/// * `name`: The tile's name
+			///
+			/// returns: Option<TileTrim>
+			///
+			pub fn tile_trim(&self, name: &str) -> Option<TileTrim> {
+				let page = self.tile_owner(name)?;
+				let group_id = page.tile_ids.get(name)?;
+				let index = page
+					.tile_indices
+					.iter()
+					.filter(|(.., id)| id.group_id == *group_id)
+					.map(|(index, ..)| *index)
+					.min()?;
+				page.tile_trim_at(index)
+			}
+
+			/// Get the transparent border trimmed from the texture at the given atlas index (if it
+			/// was trimmed)
+			///
+			/// # Arguments
+			///
+			/// * `index`: The index of the texture in the atlas
+			///
+			/// returns: Option<TileTrim>
+			///
+			pub fn tile_trim_at(&self, index: usize) -> Option<TileTrim> {
+				self.tile_trims.get(&index).copied()
+			}
+
+			/// Get the pivot of a tile, relative to the texture it was packed as
+			///
+			/// For trimmed tiles, this is the [pivot](Self::tile_pivot) moved to account for the
+			/// trimmed borders of their first texture (so it may lie outside of `0.0..=1.0`).
+			/// Otherwise, it's the same as the tile's pivot.
+			///
+			/// # Arguments
+			///
+			/// * `name`: The tile's name
+			///
+			/// returns: Option<Vec2>
+			///
+			pub fn tile_packed_pivot(&self, name: &str) -> Option<Vec2> {
+				let pivot = self.tile_pivot(name)?;
+				let page = self.tile_owner(name)?;
+				let trim = self.tile_trim(name);
+				let (trim, size) = match trim.zip(page.tile_sizes.get(page.tile_ids.get(name)?)) {
+					Some((trim, size)) => (trim, size.as_vec2()),
+					None => return Some(pivot),
+				};
+				// Pivots start at the bottom-left corner, while offsets start at the top-left
+				let from_top_left = Vec2::new(pivot.x, 1.0 - pivot.y) * trim.source_size.as_vec2();
+				let packed = (from_top_left - trim.offset.as_vec2()) / size;
+				Some(Vec2::new(packed.x, 1.0 - packed.y))
+			}
+
+			/// Get the group IDs of every tile with the given tag (in any atlas page)
+			///
+			/// The tiles are returned in no particular order.
+			///
+			/// # Arguments
+			///
+			/// * `tag`: The tag to search for
+			///
+			/// returns: impl Iterator<Item = &u32>
+			///
+			pub fn tiles_with_tag<'a>(
+				&'a self,
+				tag: &'a str,
+			) -> impl Iterator<Item = &'a TileGroupId> + 'a {
+				self.tiles
+					.iter()
+					.chain(self.pages.iter().flat_map(|page| page.tiles.iter()))
+					.filter(move |(_, data)| data.has_tag(tag))
+					.map(|(group_id, _)| group_id)
+			}
+
+			/// Get the animation metadata of an animated tile
+			///
+			/// # Arguments
+			///
+			/// * `name`: The name of the animated tile
+			///
+			/// returns: Option<AnimationInfo>
+			///
+			/// Returns `None` if the tile doesn't exist or isn't a [`TileType::Animated`] tile. For
+			/// [`TileType::MultiAnimated`] tiles, this is the metadata of their first track.
+			pub fn animation_info(&self, name: &str) -> Option<AnimationInfo> {
+				let anim = default_animation(self.get_tile_data(name)?.tile())?;
+				Some(AnimationInfo {
+					frame_count: anim.frame_count(),
+					speed: anim.speed(),
+				})
+			}
+
+			/// Get the names of every animation track of a [`TileType::MultiAnimated`] tile
+			///
+			/// Tracks are ordered by name.
+			///
+			/// # Arguments
+			///
+			/// * `name`: The name of the tile
+			///
+			/// returns: Option<Vec<&str>>
+			///
+			/// Returns `None` if the tile doesn't exist or isn't a [`TileType::MultiAnimated`]
+			/// tile.
+			pub fn animation_tracks(&self, name: &str) -> Option<Vec<&str>> {
+				match self.get_tile_data(name)?.tile() {
+					TileType::MultiAnimated(tracks) => {
+						Some(tracks.keys().map(String::as_str).collect())
+					},
+					_ => None,
+				}
+			}
+
+			/// Get the animation metadata of a single track of a [`TileType::MultiAnimated`] tile
+			///
+			/// # Arguments
+			///
+			/// * `name`: The name of the tile
+			/// * `track`: The name of the animation track
+			///
+			/// returns: Option<AnimationInfo>
+			///
+			pub fn animation_track_info(&self, name: &str, track: &str) -> Option<AnimationInfo> {
+				let anim = self.get_animation_track(name, track)?;
+				Some(AnimationInfo {
+					frame_count: anim.frame_count(),
+					speed: anim.speed(),
+				})
+			}
+
+			/// Checks if the tile with the given name is a [`TileType::Animated`] (or
+			/// [`TileType::MultiAnimated`]) tile
+			///
+			/// # Arguments
+			///
+			/// * `name`: The name of the tile
+			///
+			/// returns: bool
+			pub fn is_animated(&self, name: &str) -> bool {
+				self.animation_info(name).is_some()
+			}
+
+			/// Get the atlas index of the given frame of an animated tile
+			///
+			/// This can be used to translate an animation frame (e.g. one computed by an animation
+			/// system or an external renderer) into the concrete index within the `TextureAtlas`.
+			///
+			/// # Arguments
+			///
+			/// * `name`: The name of the animated tile
+			/// * `frame`: The frame of the animation (starting at `0`)
+			///
+			/// returns: Option<usize>
+			///
+			/// Returns `None` if the tile doesn't exist in this tileset's own atlas, isn't a
+			/// [`TileType::Animated`] tile, or the frame is out of range. For
+			/// [`TileType::MultiAnimated`] tiles, this uses their first track (see
+			/// [`Self::animation_track_frame_index`] for choosing one).
+			pub fn animation_frame_index(&self, name: &str, frame: usize) -> Option<usize> {
+				default_animation(self.local_tile_data(name)?.tile())?.frame_index(frame)
+			}
+
+			/// Get the atlas index of the given frame of one track of a [`TileType::MultiAnimated`]
+			/// tile
+			///
+			/// # Arguments
+			///
+			/// * `name`: The name of the tile
+			/// * `track`: The name of the animation track (e.g. `"walk"`)
+			/// * `frame`: The frame of the animation (starting at `0`)
+			///
+			/// returns: Option<usize>
+			///
+			/// Returns `None` if the tile doesn't exist in this tileset's own atlas, doesn't have
+			/// the given track, or the frame is out of range.
+			pub fn animation_track_frame_index(
+				&self,
+				name: &str,
+				track: &str,
+				frame: usize,
+			) -> Option<usize> {
+				match self.local_tile_data(name)?.tile() {
+					TileType::MultiAnimated(tracks) => tracks.get(track)?.frame_index(frame),
+					_ => None,
+				}
+			}
+
+			/// Get the animation data of an animated tile
+			///
+			/// For [`TileType::MultiAnimated`] tiles, this is their first track.
+			///
+			/// # Arguments
+			///
+			/// * `name`: The name of the tile
+			///
+			/// returns: Option<&AnimatedTileData>
+			///
+			pub fn get_animation(&self, name: &str) -> Option<&AnimatedTileData> {
+				default_animation(self.get_tile_data(name)?.tile())
+			}
+
+			/// Get the data of a single animation track of a [`TileType::MultiAnimated`] tile
+			///
+			/// # Arguments
+			///
+			/// * `name`: The name of the tile
+			/// * `track`: The name of the animation track
+			///
+			/// returns: Option<&AnimatedTileData>
+			///
+			pub fn get_animation_track(&self, name: &str, track: &str) -> Option<&AnimatedTileData> {
+				match self.get_tile_data(name)?.tile() {
+					TileType::MultiAnimated(tracks) => tracks.get(track),
+					_ => None,
+				}
 			}
 
 			/// Tries to get the [`TileIndex`] into the `TextureAtlas` for a tile with the given name
@@ -121,6 +991,11 @@ macro_rules! impl_tileset {
 			/// However, keep in mind that the auto tile system should automatically pick up an auto tile,
 			/// assuming it has the [`AutoTile`] component attached to it.
 			///
+			/// This only covers the tiles of this tileset's own atlas (page `0`). Tiles packed
+			/// into another atlas page (such as those with a [sampler hint](TileDef::sampler))
+			/// return `None`, since their index is only meaningful alongside their page's atlas.
+			/// Use [`Self::get_paged_tile_index`] to find a tile in any page.
+			///
 			/// # Arguments
 			///
 			/// * `name`: The name of the tile
@@ -139,6 +1014,99 @@ macro_rules! impl_tileset {
 				Some(index)
 			}
 
+			/// Tries to get the atlas page and [`TileIndex`] of a tile with the given name
+			///
+			/// Unlike [`Self::get_tile_index`], which only covers the tiles packed into this
+			/// tileset's own atlas, this finds tiles in any atlas page (see [`Self::page`]).
+			///
+			/// # Arguments
+			///
+			/// * `name`: The name of the tile
+			///
+			/// returns: Option<(usize, TileIndex)>
+			///
+			/// The returned index is local to the returned page.
+			pub fn get_paged_tile_index(&self, name: &str) -> Option<(usize, TileIndex)> {
+				let (page, index, ..) = self.select_paged_tile(name)?;
+				Some((page, index))
+			}
+
+			/// Tries to get the atlas page and [`TileIndex`] of a tile with the given ID
+			///
+			/// This is the paged version of [`Self::get_tile_index_by_id`] (see
+			/// [`Self::get_paged_tile_index`]).
+			///
+			/// # Arguments
+			///
+			/// * `id`: The ID of the tile
+			///
+			/// returns: Option<(usize, TileIndex)>
+			///
+			/// The returned index is local to the returned page.
+			pub fn get_paged_tile_index_by_id<TId: Into<PartialTileId>>(
+				&self,
+				id: TId,
+			) -> Option<(usize, TileIndex)> {
+				let (page, index, ..) = self.select_paged_tile_by_id(id)?;
+				Some((page, index))
+			}
+
+			/// Tries to get the [`TileIndex`] for a tile with the given name, falling back to the
+			/// [default tile](Self::default_tile) if no such tile exists
+			///
+			/// # Arguments
+			///
+			/// * `name`: The name of the tile
+			///
+			/// returns: Option<TileIndex>
+			///
+			/// Returns `None` only if the tile doesn't exist and this tileset has no default tile.
+			pub fn get_tile_index_or_default(&self, name: &str) -> Option<TileIndex> {
+				self.get_tile_index(name)
+					.or_else(|| self.get_tile_index_by_id(self.default_tile?))
+			}
+
+			/// The group ID of the tile used in place of unknown tile names (if any)
+			pub fn default_tile(&self) -> Option<TileGroupId> {
+				self.default_tile
+			}
+
+			/// Intern the name of a tile into a [`TileKey`]
+			///
+			/// The key can then be passed to [`Self::index_of`] to resolve the tile without looking
+			/// up its name again, which is useful for code resolving many tiles per frame (such as
+			/// when streaming in chunks of a map).
+			///
+			/// # Arguments
+			///
+			/// * `name`: The name of the tile
+			///
+			/// returns: Option<TileKey>
+			///
+			pub fn intern(&self, name: &str) -> Option<TileKey> {
+				let group_id = self.get_tile_group_id(name)?;
+				Some(TileKey::new(*group_id, self.id))
+			}
+
+			/// Tries to get the [`TileIndex`] of the tile with the given key
+			///
+			/// This behaves just like [`Self::get_tile_index`], but skips the name lookup.
+			///
+			/// # Arguments
+			///
+			/// * `key`: The key of the tile (see [`Self::intern`])
+			///
+			/// returns: Option<TileIndex>
+			///
+			/// Returns `None` if the key belongs to a different tileset or the tile no longer
+			/// exists.
+			pub fn index_of(&self, key: TileKey) -> Option<TileIndex> {
+				if key.tileset_id() != self.id {
+					return None;
+				}
+				self.get_tile_index_by_id(key.group_id())
+			}
+
 			pub fn get_tile_index_by_id<TId: Into<PartialTileId>>(
 				&self,
 				id: TId,
@@ -152,6 +1120,8 @@ macro_rules! impl_tileset {
 			/// This is a convenience method around [`get_tile_index`] that performs the match expression
 			/// returning the index if [`TileIndex::Standard`] or the start index if [`TileIndex::Animated`]
 			///
+			/// Returns `None` for [`TileIndex::Empty`] tiles, since they have no atlas texture
+			///
 			/// # Arguments
 			///
 			/// * `name`: The name of the tile
@@ -167,6 +1137,7 @@ macro_rules! impl_tileset {
 				match self.get_tile_index(name)? {
 					TileIndex::Standard(index) => Some(index),
 					TileIndex::Animated(start, ..) => Some(start),
+					TileIndex::Empty => None,
 				}
 			}
 
@@ -176,6 +1147,9 @@ macro_rules! impl_tileset {
 			///
 			/// If the tile is an Auto tile, the tile matching the default rule will be chosen.
 			///
+			/// Like [`Self::get_tile_index`], this only covers page `0` (see
+			/// [`Self::select_paged_tile`]).
+			///
 			/// # Arguments
 			///
 			/// * `name`: The name of the tile
@@ -195,6 +1169,9 @@ macro_rules! impl_tileset {
 			/// If the tile is an Auto tile, the designated auto tile will be chosen. Otherwise,
 			/// the tile matching the default rule will be chosen.
 			///
+			/// Like [`Self::get_tile_index`], this only covers page `0` (see
+			/// [`Self::select_paged_tile_by_id`]).
+			///
 			/// # Arguments
 			///
 			/// * `tile_id`: The ID of the tile
@@ -215,6 +1192,10 @@ macro_rules! impl_tileset {
 						TileType::Animated(anim) => {
 							TileIndex::Animated(anim.start(), anim.end(), anim.speed())
 						}
+						TileType::MultiAnimated(tracks) => {
+							let anim = tracks.values().next()?;
+							TileIndex::Animated(anim.start(), anim.end(), anim.speed())
+						}
 						#[cfg(feature = "variants")]
 						TileType::Variant(variants) => {
 							let variant = if let Some(idx) = id.variant_index {
@@ -226,10 +1207,53 @@ macro_rules! impl_tileset {
 						}
 						#[cfg(feature = "auto-tile")]
 						TileType::Auto(autos) => Self::select_auto(autos, AutoTileRule::default(), id)?,
+						TileType::Empty => TileIndex::Empty,
 					},
 					data,
 				))
 			}
+
+			/// Select a tile by its name from any atlas page
+			///
+			/// This behaves just like [`Self::select_tile`], but also returns the index of the
+			/// page containing the tile (see [`Self::page`]).
+			///
+			/// # Arguments
+			///
+			/// * `name`: The name of the tile
+			///
+			/// returns: Option<(usize, TileIndex, &TileData)>
+			///
+			/// The returned index is local to the returned page.
+			pub fn select_paged_tile(&self, name: &str) -> Option<(usize, TileIndex, &TileData)> {
+				let page = self.tile_page(name)?;
+				let (index, data) = self.page(page)?.select_tile(name)?;
+				Some((page, index, data))
+			}
+
+			/// Select a tile by its ID from any atlas page
+			///
+			/// This behaves just like [`Self::select_tile_by_id`], but also returns the index of
+			/// the page containing the tile (see [`Self::page`]).
+			///
+			/// # Arguments
+			///
+			/// * `tile_id`: The ID of the tile
+			///
+			/// returns: Option<(usize, TileIndex, &TileData)>
+			///
+			/// The returned index is local to the returned page.
+			pub fn select_paged_tile_by_id<TId: Into<PartialTileId>>(
+				&self,
+				tile_id: TId,
+			) -> Option<(usize, TileIndex, &TileData)> {
+				let id = tile_id.into();
+				let page = std::iter::once(self)
+					.chain(&self.pages)
+					.position(|page| page.tiles.contains_key(&id.group_id))?;
+				let (index, data) = self.page(page)?.select_tile_by_id(id)?;
+				Some((page, index, data))
+			}
 		}
 	};
 }
@@ -237,6 +1261,48 @@ macro_rules! impl_tileset {
 impl_tileset!(Tileset);
 impl_tileset!(RawTileset);
 
+/// Gets the animation used when a single animation is expected from the given tile
+///
+/// For multi-animated tiles, this is their first track (in name order).
+fn default_animation(tile: &TileType) -> Option<&AnimatedTileData> {
+	match tile {
+		TileType::Animated(anim) => Some(anim),
+		TileType::MultiAnimated(tracks) => tracks.values().next(),
+		_ => None,
+	}
+}
+
+/// Gets every animation frame contained within the given tile (in order)
+fn animation_frames(tile: &TileHandle) -> Vec<&Handle<Image>> {
+	match &tile.tile {
+		TileHandleType::Animated(anim) => anim.frames.iter().collect(),
+		TileHandleType::MultiAnimated(tracks) => {
+			tracks.values().flat_map(|anim| anim.frames.iter()).collect()
+		},
+		#[cfg(feature = "variants")]
+		TileHandleType::Variant(variants) => variant_frames(variants.iter()),
+		#[cfg(feature = "auto-tile")]
+		TileHandleType::Auto(autos) => {
+			variant_frames(autos.iter().flat_map(|auto| auto.variants.iter()))
+		},
+		_ => Vec::new(),
+	}
+}
+
+/// Gets every animation frame contained within the given variants (in order)
+#[cfg(feature = "variants")]
+fn variant_frames<'a>(
+	variants: impl Iterator<Item = &'a VariantTileHandle>,
+) -> Vec<&'a Handle<Image>> {
+	variants
+		.filter_map(|variant| match &variant.tile {
+			SimpleTileHandle::Animated(anim) => Some(anim.frames.iter()),
+			SimpleTileHandle::Standard(..) => None,
+		})
+		.flatten()
+		.collect()
+}
+
 impl RawTileset {
 	/// Gets the tileset `TextureAtlas`
 	pub fn atlas(&self) -> &TextureAtlas {
@@ -247,6 +1313,11 @@ impl RawTileset {
 	pub fn texture(&self) -> &Handle<Image> {
 		&self.atlas.texture
 	}
+
+	/// Gets the region of every texture in the atlas
+	fn atlas_rects(&self) -> Option<&[Rect]> {
+		Some(&self.atlas.textures)
+	}
 }
 
 impl Tileset {
@@ -259,4 +1330,10 @@ impl Tileset {
 	pub fn texture(&self) -> &Handle<Image> {
 		&self.texture
 	}
+
+	/// Gets the region of every texture in the atlas (which isn't available without the
+	/// `TextureAtlas` asset)
+	fn atlas_rects(&self) -> Option<&[Rect]> {
+		None
+	}
 }
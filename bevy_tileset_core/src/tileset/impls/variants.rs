@@ -22,9 +22,157 @@ macro_rules! impl_tileset {
 				let idx = dist.sample(&mut rng);
 				variants.get(idx)
 			}
+
+			/// Selects a variant from a collection of variants based on their weights, using the
+			/// given tile position as the seed
+			///
+			/// Unlike [`select_variant`](Self::select_variant), this will always return the same variant
+			/// for the same position and salt, making it stable across frames and reloads.
+			///
+			/// # Arguments
+			///
+			/// * `variants`: The variants to choose from
+			/// * `x`: The x position of the tile
+			/// * `y`: The y position of the tile
+			/// * `salt`: An additional seed value (useful for giving different layers or maps different results)
+			///
+			/// returns: Option<&VariantTileData>
+			pub fn select_variant_at(
+				variants: &[VariantTileData],
+				x: i32,
+				y: i32,
+				salt: u64,
+			) -> Option<&VariantTileData> {
+				let idx = weighted_index_for_pos(
+					variants.iter().map(|variant| variant.weight()),
+					x,
+					y,
+					salt,
+				)?;
+				variants.get(idx)
+			}
 		}
 	};
 }
 
 impl_tileset!(Tileset);
 impl_tileset!(RawTileset);
+
+/// Deterministically selects a variant based on its weight and the given tile position
+///
+/// The same position and salt will always result in the same index, making this useful for
+/// tilemaps that need stable-looking randomness.
+///
+/// # Arguments
+///
+/// * `variants`: The variants to choose from
+/// * `x`: The x position of the tile
+/// * `y`: The y position of the tile
+/// * `salt`: An additional seed value (useful for giving different layers or maps different results)
+///
+/// returns: usize
+///
+/// # Examples
+///
+/// ```
+/// # use bevy_tileset_core::prelude::*;
+/// # use bevy_tileset_tiles::prelude::*;
+/// fn pick(variants: &[VariantTileHandle]) {
+/// 	let a = select_variant_for_pos(variants, 3, 7, 0);
+/// 	let b = select_variant_for_pos(variants, 3, 7, 0);
+/// 	assert_eq!(a, b);
+/// }
+/// ```
+pub fn select_variant_for_pos(variants: &[VariantTileHandle], x: i32, y: i32, salt: u64) -> usize {
+	weighted_index_for_pos(variants.iter().map(|variant| variant.weight), x, y, salt).unwrap_or(0)
+}
+
+/// Performs a weighted pick among the given weights, seeded by the given position
+///
+/// Returns `None` if there are no positive weights to choose from.
+pub(crate) fn weighted_index_for_pos<TWeights: IntoIterator<Item = f32>>(
+	weights: TWeights,
+	x: i32,
+	y: i32,
+	salt: u64,
+) -> Option<usize> {
+	let weights: Vec<f32> = weights.into_iter().map(|weight| weight.max(0.0)).collect();
+	let total: f32 = weights.iter().sum();
+	if total <= 0.0 {
+		return None;
+	}
+
+	// Map the top 53 bits of the hash into the range [0, 1)
+	let hash = hash_pos(x, y, salt);
+	let unit = (hash >> 11) as f64 / (1u64 << 53) as f64;
+	let target = unit * total as f64;
+
+	let mut cumulative = 0.0;
+	for (idx, weight) in weights.iter().enumerate() {
+		cumulative += *weight as f64;
+		if *weight > 0.0 && target < cumulative {
+			return Some(idx);
+		}
+	}
+
+	// Account for floating point error by returning the last valid variant
+	weights.iter().rposition(|weight| *weight > 0.0)
+}
+
+/// Hashes a tile position into a well-distributed `u64`
+///
+/// This uses the SplitMix64 finalizer so results are stable across platforms and Rust versions.
+fn hash_pos(x: i32, y: i32, salt: u64) -> u64 {
+	let mut hash = salt ^ 0x9E37_79B9_7F4A_7C15;
+	for value in [x as u32 as u64, y as u32 as u64] {
+		hash = mix(hash ^ value);
+	}
+	hash
+}
+
+fn mix(mut z: u64) -> u64 {
+	z = z.wrapping_add(0x9E37_79B9_7F4A_7C15);
+	z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+	z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+	z ^ (z >> 31)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::weighted_index_for_pos;
+
+	#[test]
+	fn should_be_stable_per_position() {
+		let weights = [1.0, 2.0, 3.0];
+		for x in -8..8 {
+			for y in -8..8 {
+				assert_eq!(
+					weighted_index_for_pos(weights, x, y, 42),
+					weighted_index_for_pos(weights, x, y, 42)
+				);
+			}
+		}
+	}
+
+	#[test]
+	fn should_respect_weights() {
+		let weights = [0.0, 1.0, 3.0];
+		let mut counts = [0usize; 3];
+		for x in 0..64 {
+			for y in 0..64 {
+				counts[weighted_index_for_pos(weights, x, y, 0).unwrap()] += 1;
+			}
+		}
+
+		assert_eq!(0, counts[0]);
+		// Expect a roughly 1:3 split
+		let ratio = counts[2] as f32 / counts[1] as f32;
+		assert!(2.5 < ratio && ratio < 3.5, "unexpected ratio: {}", ratio);
+	}
+
+	#[test]
+	fn should_not_select_without_weights() {
+		assert_eq!(None, weighted_index_for_pos([0.0, 0.0], 1, 2, 3));
+		assert_eq!(None, weighted_index_for_pos([0.0f32; 0], 1, 2, 3));
+	}
+}
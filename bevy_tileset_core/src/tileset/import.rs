@@ -0,0 +1,174 @@
+//! Support for wrapping an existing, hand-built `TextureAtlas` in a tileset
+//!
+//! This lets projects with atlases packed by an external pipeline gain name-based lookups
+//! (and the rest of the tileset API) without re-authoring them as tile definitions. The atlas is
+//! used as-is, so nothing is re-packed and every atlas index stays the same.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use bevy::prelude::{Assets, TextureAtlas, Vec2};
+use bevy_tileset_tiles::prelude::{TileData, TileHandle, TileHandleType, TileType};
+
+use crate::prelude::{RawTileset, TileGroupId, TileId, Tileset, TilesetError, TilesetId};
+
+impl RawTileset {
+	/// Create a tileset from an existing texture atlas
+	///
+	/// Each texture in the atlas becomes a standard tile, named after the name at the same
+	/// index. Its group ID is also its atlas index.
+	///
+	/// # Arguments
+	///
+	/// * `name`: The name of the tileset
+	/// * `id`: The ID of the tileset
+	/// * `atlas`: The atlas to wrap
+	/// * `names`: The name of each texture in the atlas (in order)
+	///
+	/// returns: Result<RawTileset, TilesetError>
+	///
+	pub fn from_texture_atlas<TName: Into<String>>(
+		name: TName,
+		id: TilesetId,
+		atlas: TextureAtlas,
+		names: &[&str],
+	) -> Result<RawTileset, TilesetError> {
+		if names.len() != atlas.textures.len() {
+			return Err(TilesetError::InvalidData {
+				expected: format!("{} tile names", atlas.textures.len()),
+				found: format!("{} tile names", names.len()),
+			});
+		}
+
+		let mut tiles = HashMap::with_capacity(names.len());
+		let mut tile_ids = HashMap::with_capacity(names.len());
+		let mut tile_names = HashMap::with_capacity(names.len());
+		let mut group_handles = HashMap::with_capacity(names.len());
+		let mut tile_indices = HashMap::with_capacity(names.len());
+		let mut tile_sizes = HashMap::with_capacity(names.len());
+		for (index, (tile_name, rect)) in names.iter().zip(atlas.textures.iter()).enumerate() {
+			let group_id = TileGroupId::try_from(index)
+				.map_err(|_| TilesetError::AtlasIndexOverflow(index))?;
+			if tile_ids.insert(tile_name.to_string(), group_id).is_some() {
+				return Err(TilesetError::InvalidData {
+					expected: String::from("unique tile names"),
+					found: format!("duplicate tile name {:?}", tile_name),
+				});
+			}
+
+			let handle = TileHandle {
+				tile: TileHandleType::AtlasRegion(atlas.texture.clone_weak(), *rect),
+				..TileHandle::new_empty(*tile_name)
+			};
+			tiles.insert(
+				group_id,
+				TileData::new(tile_name.to_string(), TileType::Standard(index)),
+			);
+			tile_names.insert(group_id, tile_name.to_string());
+			group_handles.insert(group_id, handle);
+			tile_indices.insert(index, TileId::new(group_id, id));
+			tile_sizes.insert(group_id, rect.size().as_uvec2());
+		}
+
+		let tile_size = atlas
+			.textures
+			.first()
+			.map(|rect| rect.size())
+			.unwrap_or(Vec2::ZERO);
+		Ok(RawTileset {
+			id,
+			name: name.into(),
+			tiles,
+			size: atlas.size,
+			tile_size,
+			tile_ids,
+			tile_names,
+			tile_handles: HashMap::new(),
+			group_handles,
+			tile_indices,
+			tile_colors: HashMap::new(),
+			tile_sizes,
+			categories: BTreeMap::new(),
+			tile_trims: HashMap::new(),
+			processed_indices: HashSet::new(),
+			lod_textures: Vec::new(),
+			load_warnings: Vec::new(),
+			tile_sources: HashMap::new(),
+			dependency_paths: Vec::new(),
+			default_tile: None,
+			grid_columns: None,
+			atlas,
+			pages: Vec::new(),
+		})
+	}
+}
+
+impl Tileset {
+	/// Create a tileset from an existing texture atlas
+	///
+	/// See [`RawTileset::from_texture_atlas`] for details.
+	///
+	/// # Arguments
+	///
+	/// * `name`: The name of the tileset
+	/// * `id`: The ID of the tileset
+	/// * `atlas`: The atlas to wrap
+	/// * `names`: The name of each texture in the atlas (in order)
+	/// * `atlases`: The `TextureAtlas` assets to add the atlas to
+	///
+	/// returns: Result<Tileset, TilesetError>
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use bevy::prelude::*;
+	/// # use bevy_tileset_core::prelude::*;
+	///
+	/// fn import(
+	/// 	texture: Handle<Image>,
+	/// 	atlases: &mut Assets<TextureAtlas>,
+	/// ) -> Result<Tileset, TilesetError> {
+	/// 	let atlas = TextureAtlas::from_grid(texture, Vec2::splat(16.0), 2, 1, None, None);
+	/// 	Tileset::from_texture_atlas("Legacy", 0, atlas, &["Grass", "Dirt"], atlases)
+	/// }
+	/// ```
+	pub fn from_texture_atlas<TName: Into<String>>(
+		name: TName,
+		id: TilesetId,
+		atlas: TextureAtlas,
+		names: &[&str],
+		atlases: &mut Assets<TextureAtlas>,
+	) -> Result<Tileset, TilesetError> {
+		Ok(RawTileset::from_texture_atlas(name, id, atlas, names)?.into_asset(atlases))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use bevy::prelude::{Handle, Vec2};
+
+	use super::*;
+
+	#[test]
+	fn should_import_texture_atlas() {
+		let atlas = TextureAtlas::from_grid(Handle::default(), Vec2::splat(16.0), 2, 2, None, None);
+		let names = ["Grass", "Dirt", "Water", "Sand"];
+
+		let tileset = RawTileset::from_texture_atlas("Legacy", 3, atlas, &names).unwrap();
+		assert_eq!(4, tileset.tile_count());
+		assert_eq!(Ok(()), tileset.validate_names(&names));
+		assert_eq!(Some(2), tileset.get_base_tile_index("Water"));
+		assert_eq!(Vec2::splat(16.0), tileset.tile_size());
+		assert_eq!(Vec2::splat(32.0), tileset.size());
+		assert!(matches!(
+			tileset.get_tile_handle_by_name("Sand").map(|tile| &tile.tile),
+			Some(TileHandleType::AtlasRegion(..))
+		));
+	}
+
+	#[test]
+	fn should_require_a_name_per_texture() {
+		let atlas = TextureAtlas::from_grid(Handle::default(), Vec2::splat(16.0), 2, 1, None, None);
+		assert!(RawTileset::from_texture_atlas("Legacy", 0, atlas.clone(), &["Grass"]).is_err());
+		assert!(RawTileset::from_texture_atlas("Legacy", 0, atlas, &["Grass", "Grass"]).is_err());
+	}
+}
@@ -0,0 +1,567 @@
+//! Support for loading tilesets lazily, deferring the decoding of tile textures until first use
+//!
+//! Loading a tileset normally decodes every one of its textures and packs them all into the
+//! atlas up front. For huge tilesets where only some tiles are ever visible at once, this wastes
+//! memory on textures that are never shown. A [`LazyTileset`] instead only reads the tile
+//! definitions, decoding a tile's textures (and packing them) once that tile is first requested.
+//! Tilesets whose definition sets `lazy: true` are loaded this way by the asset loader (see
+//! [`LazyTilesetAsset`]).
+//!
+//! This trades a lower memory footprint for a hitch whenever new tiles are requested, since
+//! their textures are decoded and packed right away. Each request packs its new tiles into a
+//! new atlas page (see [`RawTileset::page`]), so the atlas indices of existing tiles never
+//! change, but tiles beyond the first request need to be looked up with the paged methods (such
+//! as [`RawTileset::get_paged_tile_index`]).
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::ops::{Deref, DerefMut};
+use std::path::{Path, PathBuf};
+
+use bevy::asset::{AssetServer, HandleId, LoadContext};
+use bevy::prelude::Image;
+use bevy::reflect::{TypePath, TypeUuid};
+use bevy::render::texture::CompressedImageFormats;
+use bevy_tile_atlas::TextureStore;
+use bevy_tileset_tiles::prelude::TileHandle;
+
+use crate::prelude::{RawTileset, TileGroupId, TilesetDef, TilesetError, TilesetLoadLimits};
+use crate::tileset::asset::{
+	build_tiles, load_image, read_tile_defs, LoadReport, PendingTiles, ResolvedTextureStore,
+	ResolverSource, TilesetSource,
+};
+
+/// The newly requested tiles of a [`LazyTileset`] along with their newly decoded textures
+type DecodedTiles = (Vec<(TileGroupId, TileHandle)>, HashMap<HandleId, Image>);
+
+/// The resolver of a [`LazyTilesetAsset`], which reads files through the app's `AssetIo`
+type AssetResolver = Box<dyn Fn(&Path) -> Option<Vec<u8>> + Send + Sync>;
+
+/// A tileset whose tile textures are only decoded (and packed) once their tile is requested
+///
+/// Unlike loading a tileset eagerly, a texture that fails to load always returns an error from
+/// [`Self::request`] (regardless of the tileset's `missing_texture_policy` or
+/// `skip_invalid_tiles`).
+///
+/// # Examples
+///
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_tileset_core::prelude::*;
+/// fn load(textures: &mut Assets<Image>) -> Result<(), TilesetError> {
+/// 	let mut tileset = LazyTileset::new("tilesets/forest.ron", |path| {
+/// 		std::fs::read(std::path::Path::new("assets").join(path)).ok()
+/// 	})?;
+///
+/// 	// Only the textures of "Grass" (and the default tile, if any) are decoded
+/// 	let raw = tileset.request(&["Grass"], textures)?;
+/// 	assert!(raw.get_tile_group_id("Grass").is_some());
+/// 	Ok(())
+/// }
+/// ```
+pub struct LazyTileset<TResolver: Fn(&Path) -> Option<Vec<u8>>> {
+	source: ResolverSource<TResolver>,
+	config: TilesetDef,
+	/// The tiles that haven't been requested yet, mapped by their name
+	pending: HashMap<String, (TileGroupId, TileHandle)>,
+	/// The tiles that have been requested so far (in the order they were requested)
+	requested: Vec<(TileGroupId, TileHandle)>,
+	/// The path of every texture used by the tiles, mapped by the ID of its handle
+	texture_paths: HashMap<HandleId, PathBuf>,
+	/// The IDs of the textures decoded so far
+	///
+	/// The textures themselves are dropped once they've been packed into the atlas.
+	decoded: HashSet<HandleId>,
+	report: LoadReport,
+	/// The tileset built from the requested tiles (if any have been requested)
+	tileset: Option<RawTileset>,
+	/// The limits enforced while reading the tileset and decoding its textures
+	limits: TilesetLoadLimits,
+	/// The compressed texture formats supported when decoding textures
+	formats: CompressedImageFormats,
+}
+
+impl<TResolver: Fn(&Path) -> Option<Vec<u8>>> LazyTileset<TResolver> {
+	/// Reads the tileset definition at the given path, along with each of its tile definitions
+	///
+	/// Only the definition files are read, not any of the tiles' textures.
+	///
+	/// # Arguments
+	///
+	/// * `path`: The path to the tileset definition (see [`TilesetDef`])
+	/// * `resolver`: A function that returns the bytes of the file at the given path (or `None` if
+	///   the file doesn't exist)
+	///
+	/// returns: Result<LazyTileset<TResolver>, TilesetError>
+	///
+	pub fn new<TPath: Into<PathBuf>>(
+		path: TPath,
+		resolver: TResolver,
+	) -> Result<Self, TilesetError> {
+		Self::with_limits(path, resolver, TilesetLoadLimits::default())
+	}
+
+	/// Reads the tileset definition at the given path, enforcing the given limits
+	///
+	/// The `max_tiles` limit is checked right away, while `max_image_bytes` is checked as each
+	/// texture is decoded (see [`Self::new`] for details).
+	///
+	/// # Arguments
+	///
+	/// * `path`: The path to the tileset definition (see [`TilesetDef`])
+	/// * `resolver`: A function that returns the bytes of the file at the given path (or `None` if
+	///   the file doesn't exist)
+	/// * `limits`: The limits to enforce
+	///
+	/// returns: Result<LazyTileset<TResolver>, TilesetError>
+	///
+	pub fn with_limits<TPath: Into<PathBuf>>(
+		path: TPath,
+		resolver: TResolver,
+		limits: TilesetLoadLimits,
+	) -> Result<Self, TilesetError> {
+		let source = ResolverSource {
+			path: path.into(),
+			resolver,
+		};
+		let def = futures::executor::block_on(source.read_bytes(&source.path))?;
+		let pending = futures::executor::block_on(read_tile_defs(&def, &source, None, &limits))?;
+		Ok(Self::from_pending(source, pending, limits))
+	}
+
+	/// Creates a lazy tileset from the given tile definitions, without decoding any textures
+	fn from_pending(
+		source: ResolverSource<TResolver>,
+		pending: PendingTiles,
+		limits: TilesetLoadLimits,
+	) -> Self {
+		let PendingTiles {
+			config,
+			tile_handles,
+			texture_paths,
+			report,
+		} = pending;
+		let pending = tile_handles
+			.into_iter()
+			.map(|(group_id, tile)| (tile.name.clone(), (group_id, tile)))
+			.collect();
+		Self {
+			source,
+			config,
+			pending,
+			requested: Vec::new(),
+			texture_paths,
+			decoded: HashSet::new(),
+			report,
+			tileset: None,
+			limits,
+			formats: CompressedImageFormats::all(),
+		}
+	}
+
+	/// Set the compressed texture formats supported when decoding textures
+	///
+	/// This should match the formats supported by the app's renderer, such as the ones in the
+	/// [`TilesetCompressedFormats`](crate::prelude::TilesetCompressedFormats) resource. Lazy
+	/// tilesets loaded by the asset loader use the loader's formats instead.
+	///
+	/// Default: [`CompressedImageFormats::all`]
+	pub fn compressed_formats(&mut self, formats: CompressedImageFormats) -> &mut Self {
+		self.formats = formats;
+		self
+	}
+
+	/// Request the tiles with the given names, decoding and packing their textures if any of
+	/// them weren't requested before
+	///
+	/// The tileset's default tile (if any) is always requested along with the first tiles. Any
+	/// later tiles are packed into a new atlas page, leaving the existing atlases untouched.
+	///
+	/// # Arguments
+	///
+	/// * `names`: The names of the tiles to request
+	/// * `texture_store`: The store the generated atlas textures are added to
+	///
+	/// returns: Result<&RawTileset, TilesetError>
+	///
+	/// Returns an error if a tile doesn't exist or one of its textures fails to load, in which
+	/// case none of the given tiles are requested.
+	pub fn request<TStore: TextureStore>(
+		&mut self,
+		names: &[&str],
+		texture_store: &mut TStore,
+	) -> Result<&RawTileset, TilesetError> {
+		let mut names = names
+			.iter()
+			.map(|name| name.to_string())
+			.collect::<BTreeSet<_>>();
+		if self.tileset.is_none() {
+			names.extend(self.default_tile_name());
+		}
+		names.retain(|name| !self.is_requested(name));
+
+		if !names.is_empty() || self.tileset.is_none() {
+			let (tiles, images) = self.decode(&names)?;
+			self.pack(tiles, images, texture_store)?;
+		}
+		self.tileset.as_ref().ok_or(TilesetError::EmptyTileset)
+	}
+
+	/// Decodes the textures of the pending tiles with the given names
+	///
+	/// Returns the tiles along with their newly decoded textures, without marking them as
+	/// requested.
+	fn decode(&self, names: &BTreeSet<String>) -> Result<DecodedTiles, TilesetError> {
+		let mut tiles = Vec::with_capacity(names.len());
+		let mut images = HashMap::new();
+		for name in names {
+			let (group_id, tile) = self.pending.get(name).ok_or_else(|| TilesetError::InvalidData {
+				expected: String::from("the name of a tile in the tileset"),
+				found: format!("unknown tile {:?}", name),
+			})?;
+			for handle in tile.iter_handles() {
+				let id = handle.id();
+				if images.contains_key(&id) {
+					continue;
+				}
+				let path = self.texture_paths.get(&id).ok_or(TilesetError::ImageNotFound)?;
+				let image = load_image(&self.source, path.clone(), self.formats, &self.limits);
+				images.insert(id, futures::executor::block_on(image)?);
+			}
+			tiles.push((*group_id, tile.clone()));
+		}
+		Ok((tiles, images))
+	}
+
+	/// Checks whether the tile with the given name has been requested
+	///
+	/// # Arguments
+	///
+	/// * `name`: The name of the tile
+	///
+	/// returns: bool
+	///
+	pub fn is_requested(&self, name: &str) -> bool {
+		self.requested.iter().any(|(_, tile)| tile.name == name)
+	}
+
+	/// The number of distinct textures that have been decoded so far
+	pub fn decoded_count(&self) -> usize {
+		self.decoded.len()
+	}
+
+	/// The tileset built from the tiles requested so far
+	///
+	/// Returns `None` if no tiles have been requested yet.
+	pub fn tileset(&self) -> Option<&RawTileset> {
+		self.tileset.as_ref()
+	}
+
+	/// Gets the name of the tileset's default tile (if any)
+	fn default_tile_name(&self) -> Option<String> {
+		let default_tile = self.config.default_tile?;
+		self.pending
+			.iter()
+			.find(|(_, (group_id, _))| *group_id == default_tile)
+			.map(|(name, _)| name.clone())
+	}
+
+	/// Packs the given newly requested tiles into the tileset
+	///
+	/// The first tiles to be requested make up the tileset itself, while later ones are packed
+	/// into a new atlas page. The new tiles are only marked as requested if they could be
+	/// packed, so a failed request leaves everything as it was.
+	fn pack<TStore: TextureStore>(
+		&mut self,
+		tiles: Vec<(TileGroupId, TileHandle)>,
+		images: HashMap<HandleId, Image>,
+		texture_store: &mut TStore,
+	) -> Result<(), TilesetError> {
+		let mut config = self.config.clone();
+		let group_ids = tiles
+			.iter()
+			.map(|(group_id, _)| *group_id)
+			.collect::<BTreeSet<_>>();
+		config.tiles.retain(|group_id, _| group_ids.contains(group_id));
+		for category in config.categories.values_mut() {
+			category.retain(|group_id| group_ids.contains(group_id));
+		}
+		if self.tileset.is_some() {
+			// The default tile was already packed along with the first tiles
+			config.default_tile = None;
+		}
+
+		let new_ids = images.keys().copied().collect::<Vec<_>>();
+		let mut store = ResolvedTextureStore {
+			store: texture_store,
+			images,
+		};
+		let mut page = build_tiles(config, tiles.clone(), self.report.clone(), &mut store)?;
+		store.store_region_atlases(&mut page);
+
+		match &mut self.tileset {
+			Some(tileset) => {
+				let pages = std::mem::take(&mut page.pages);
+				tileset.pages.push(page);
+				tileset.pages.extend(pages);
+			},
+			None => self.tileset = Some(page),
+		}
+		self.decoded.extend(new_ids);
+		for (_, tile) in &tiles {
+			self.pending.remove(&tile.name);
+		}
+		self.requested.extend(tiles);
+		Ok(())
+	}
+}
+
+/// A [`LazyTileset`] loaded by the asset loader from a tileset definition with `lazy: true`
+///
+/// This is loaded in place of a [`Tileset`](crate::prelude::Tileset), so it needs to be loaded
+/// as a `LazyTilesetAsset` instead. Only the tile definitions are read by the loader, while the
+/// textures are read through the app's `AssetIo` (and decoded with the loader's compressed
+/// formats) once their tile is requested. Since that happens on the calling thread, the
+/// textures are read synchronously.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_tileset_core::prelude::*;
+/// fn request_grass(
+/// 	asset_server: Res<AssetServer>,
+/// 	mut lazy_tilesets: ResMut<Assets<LazyTilesetAsset>>,
+/// 	mut textures: ResMut<Assets<Image>>,
+/// ) {
+/// 	let handle = asset_server.load("tilesets/forest.ron");
+/// 	if let Some(lazy) = lazy_tilesets.get_mut(&handle) {
+/// 		let raw = lazy.request(&["Grass"], &mut *textures).unwrap();
+/// 		// ...
+/// 	}
+/// }
+/// ```
+#[derive(TypeUuid, TypePath)]
+#[uuid = "b3d5f7e1-6c2a-4e8f-9a41-2d7c5e0f8b93"]
+pub struct LazyTilesetAsset(LazyTileset<AssetResolver>);
+
+impl LazyTilesetAsset {
+	/// Reads the tile definitions of the given lazy tileset definition
+	pub(crate) async fn load(
+		bytes: &[u8],
+		load_context: &LoadContext<'_>,
+		asset_server: AssetServer,
+		formats: CompressedImageFormats,
+		subset: Option<&BTreeSet<TileGroupId>>,
+		limits: &TilesetLoadLimits,
+	) -> Result<Self, TilesetError> {
+		let pending = read_tile_defs(bytes, load_context, subset, limits).await?;
+		let resolver: AssetResolver = Box::new(move |path: &Path| {
+			futures::executor::block_on(asset_server.asset_io().load_path(path)).ok()
+		});
+		let source = ResolverSource {
+			path: load_context.path().to_path_buf(),
+			resolver,
+		};
+		let mut lazy = LazyTileset::from_pending(source, pending, *limits);
+		lazy.compressed_formats(formats);
+		Ok(Self(lazy))
+	}
+}
+
+impl Deref for LazyTilesetAsset {
+	type Target = LazyTileset<AssetResolver>;
+
+	fn deref(&self) -> &Self::Target {
+		&self.0
+	}
+}
+
+impl DerefMut for LazyTilesetAsset {
+	fn deref_mut(&mut self) -> &mut Self::Target {
+		&mut self.0
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::sync::Mutex;
+
+	use bevy::asset::{AddAsset, AssetPlugin, Assets, Handle, LoadState};
+	use bevy::prelude::{App, MinimalPlugins, Mut, TextureAtlas};
+
+	use super::*;
+	use crate::prelude::TilesetPlugin;
+	use crate::test_utils::{MemoryAssetIo, TestTextureStore, PIXEL_PNG};
+
+	#[test]
+	fn should_defer_decoding_until_requested() {
+		let reads = Mutex::new(Vec::new());
+		let resolver = |path: &Path| {
+			reads.lock().unwrap().push(path.to_path_buf());
+			let file: &[u8] = match path.to_str()? {
+				"tilesets/lazy.ron" => br#"(id: 1, tiles: {0: "dirt.ron", 1: "grass.ron"})"#,
+				"tilesets/dirt.ron" => br#"(name: "Dirt", tile: Standard("dirt.png"))"#,
+				"tilesets/grass.ron" => br#"(name: "Grass", tile: Standard("grass.png"))"#,
+				"dirt.png" | "grass.png" => PIXEL_PNG,
+				_ => return None,
+			};
+			Some(file.to_vec())
+		};
+		let images = || {
+			let reads = reads.lock().unwrap();
+			let images = reads.iter().filter(|path| path.extension().unwrap() == "png");
+			images.cloned().collect::<Vec<_>>()
+		};
+
+		let mut store = TestTextureStore::default();
+		let mut tileset = LazyTileset::new("tilesets/lazy.ron", resolver).unwrap();
+		assert!(tileset.tileset().is_none());
+		assert!(images().is_empty());
+		assert_eq!(0, tileset.decoded_count());
+
+		let raw = tileset.request(&["Grass"], &mut store).unwrap();
+		assert_eq!(1, raw.tile_count());
+		assert!(raw.get_tile_group_id("Dirt").is_none());
+		let texture = raw.atlas().texture.clone();
+		assert_eq!(vec![PathBuf::from("grass.png")], images());
+		assert!(tileset.is_requested("Grass"));
+		assert!(!tileset.is_requested("Dirt"));
+
+		// Requesting the same tile again doesn't decode anything
+		tileset.request(&["Grass"], &mut store).unwrap();
+		assert_eq!(1, images().len());
+
+		let raw = tileset.request(&["Dirt"], &mut store).unwrap();
+		// The new tile is packed into its own page, leaving the existing atlas untouched
+		assert_eq!(1, raw.tile_count());
+		assert_eq!(2, raw.page_count());
+		assert_eq!(2, raw.tile_count_by_type().total());
+		assert_eq!(Some(1), raw.tile_page("Dirt"));
+		assert_eq!(texture, raw.atlas().texture);
+		assert_eq!(Some(&1), raw.get_tile_group_id("Grass"));
+		assert_eq!(2, tileset.decoded_count());
+
+		assert!(tileset.request(&["Water"], &mut store).is_err());
+		assert_eq!(2, tileset.tileset().unwrap().tile_count_by_type().total());
+	}
+
+	#[test]
+	fn should_not_request_tiles_that_fail_to_build() {
+		let resolver = |path: &Path| {
+			let file: &[u8] = match path.to_str()? {
+				"tilesets/lazy.ron" => br#"(id: 1, tiles: {0: "grass.ron", 1: "water.ron"})"#,
+				"tilesets/grass.ron" => br#"(name: "Grass", tile: Standard("grass.png"))"#,
+				"tilesets/water.ron" => {
+					br#"(name: "Water", tile: Animated((frames: ["water.png"], start_frame: 3)))"#
+				},
+				"grass.png" | "water.png" => PIXEL_PNG,
+				_ => return None,
+			};
+			Some(file.to_vec())
+		};
+
+		let mut store = TestTextureStore::default();
+		let mut tileset = LazyTileset::new("tilesets/lazy.ron", resolver).unwrap();
+		tileset.request(&["Grass"], &mut store).unwrap();
+		assert_eq!(1, tileset.decoded_count());
+
+		// The start frame is out of range, so the tileset can't be rebuilt with "Water"
+		assert!(tileset.request(&["Water"], &mut store).is_err());
+		assert!(!tileset.is_requested("Water"));
+		assert_eq!(1, tileset.decoded_count());
+		assert_eq!(1, tileset.tileset().unwrap().tile_count());
+
+		// The tile is still pending, so requesting it again fails again
+		assert!(tileset.request(&["Water"], &mut store).is_err());
+		assert!(!tileset.is_requested("Water"));
+		let raw = tileset.request(&["Grass"], &mut store).unwrap();
+		assert!(raw.get_tile_group_id("Water").is_none());
+	}
+
+	#[test]
+	fn should_enforce_limits_when_decoding() {
+		let resolver = |path: &Path| {
+			let file: &[u8] = match path.to_str()? {
+				"tilesets/lazy.ron" => br#"(id: 1, tiles: {0: "grass.ron"})"#,
+				"tilesets/grass.ron" => br#"(name: "Grass", tile: Standard("grass.png"))"#,
+				"grass.png" => PIXEL_PNG,
+				_ => return None,
+			};
+			Some(file.to_vec())
+		};
+
+		let limits = TilesetLoadLimits {
+			max_tiles: Some(0),
+			..Default::default()
+		};
+		let result = LazyTileset::with_limits("tilesets/lazy.ron", resolver, limits);
+		assert!(matches!(result, Err(TilesetError::TooManyTiles { max: 0, .. })));
+
+		let limits = TilesetLoadLimits {
+			max_image_bytes: Some(16),
+			..Default::default()
+		};
+		let mut store = TestTextureStore::default();
+		let mut tileset = LazyTileset::with_limits("tilesets/lazy.ron", resolver, limits).unwrap();
+		assert!(matches!(
+			tileset.request(&["Grass"], &mut store),
+			Err(TilesetError::ImageTooLarge { max: 16, .. })
+		));
+		assert_eq!(0, tileset.decoded_count());
+	}
+
+	#[test]
+	fn should_load_lazy_tilesets_through_the_loader() {
+		let files = HashMap::from([
+			(
+				PathBuf::from("tilesets/lazy.ron"),
+				br#"(id: 1, lazy: true, tiles: {0: "dirt.ron", 1: "grass.ron"})"#.to_vec(),
+			),
+			(
+				PathBuf::from("tilesets/dirt.ron"),
+				br#"(name: "Dirt", tile: Standard("dirt.png"))"#.to_vec(),
+			),
+			(
+				PathBuf::from("tilesets/grass.ron"),
+				br#"(name: "Grass", tile: Standard("grass.png"))"#.to_vec(),
+			),
+			(PathBuf::from("dirt.png"), PIXEL_PNG.to_vec()),
+			(PathBuf::from("grass.png"), PIXEL_PNG.to_vec()),
+		]);
+
+		let mut app = App::new();
+		app.insert_resource(AssetServer::new(MemoryAssetIo(files)))
+			.add_plugins((MinimalPlugins, AssetPlugin::default()))
+			.add_asset::<Image>()
+			.add_asset::<TextureAtlas>()
+			.add_plugins(TilesetPlugin::default());
+
+		let handle: Handle<LazyTilesetAsset> = app
+			.world
+			.resource::<AssetServer>()
+			.load("tilesets/lazy.ron");
+		for _ in 0..100 {
+			app.update();
+			match app.world.resource::<AssetServer>().get_load_state(&handle) {
+				LoadState::NotLoaded | LoadState::Loading => {
+					std::thread::sleep(std::time::Duration::from_millis(10))
+				},
+				_ => break,
+			}
+		}
+		// Loaded assets are only added to their storage on the following update
+		app.update();
+
+		app.world.resource_scope(|world, mut images: Mut<Assets<Image>>| {
+			let mut lazy_tilesets = world.resource_mut::<Assets<LazyTilesetAsset>>();
+			let lazy = lazy_tilesets.get_mut(&handle).unwrap();
+			assert_eq!(0, lazy.decoded_count());
+
+			// The texture is only read through the `AssetIo` once its tile is requested
+			let raw = lazy.request(&["Grass"], &mut *images).unwrap();
+			assert_eq!(Some(&1), raw.get_tile_group_id("Grass"));
+			assert!(raw.get_tile_group_id("Dirt").is_none());
+			assert_eq!(1, lazy.decoded_count());
+		});
+	}
+}
@@ -1,19 +1,33 @@
-use bevy::asset::{Asset, AssetPath, AssetServer, Handle};
+use std::path::Path;
+
+use bevy::asset::{AssetIo, AssetIoError, AssetPath, AssetServer, Handle};
 use bevy::prelude::{Image, Res};
 use bevy_tileset_tiles::prelude::*;
 
+use crate::prelude::TilesetError;
+
+/// The file extensions treated as images when loading tiles from a directory
+const IMAGE_EXTENSIONS: &[&str] = &[
+	"png", "jpg", "jpeg", "bmp", "tga", "gif", "webp", "dds", "ktx2",
+];
+
+/// A trait for types that can load tile textures, such as the `AssetServer`
+///
+/// This can be implemented to load tile textures from a custom source (in-memory, a VFS, etc.).
+/// See the `custom_loader` example for more details.
 pub trait TextureLoader {
-	fn load_texture<'a, T: Asset, P: Into<AssetPath<'a>>>(&self, path: P) -> Handle<Image>;
+	/// Start loading the texture at the given path, returning a handle to it
+	fn load_texture<'a, P: Into<AssetPath<'a>>>(&self, path: P) -> Handle<Image>;
 }
 
 impl TextureLoader for AssetServer {
-	fn load_texture<'a, T: Asset, P: Into<AssetPath<'a>>>(&self, path: P) -> Handle<Image> {
+	fn load_texture<'a, P: Into<AssetPath<'a>>>(&self, path: P) -> Handle<Image> {
 		self.load(path)
 	}
 }
 
 impl<'w> TextureLoader for Res<'w, AssetServer> {
-	fn load_texture<'a, T: Asset, P: Into<AssetPath<'a>>>(&self, path: P) -> Handle<Image> {
+	fn load_texture<'a, P: Into<AssetPath<'a>>>(&self, path: P) -> Handle<Image> {
 		self.load(path)
 	}
 }
@@ -50,45 +64,190 @@ pub fn load_tile_handles<TTiles: IntoIterator<Item = TileDef>, TLoader: TextureL
 ) -> Vec<TileHandle> {
 	tiles
 		.into_iter()
-		.map(|tile_def| TileHandle {
-			name: tile_def.name.clone(),
-			tile: match &tile_def.tile {
-				TileDefType::Standard(path) => TileHandleType::Standard(
-					asset_loader.load_texture::<Image, &str>(path.as_str()),
-				),
-				TileDefType::Animated(anim) => {
-					TileHandleType::Animated(load_animated(anim, asset_loader))
-				}
-				#[cfg(feature = "variants")]
-				TileDefType::Variant(variants) => TileHandleType::Variant(
-					variants
-						.iter()
-						.map(|variant| load_variant(variant, asset_loader))
-						.collect(),
-				),
-				#[cfg(feature = "auto-tile")]
-				TileDefType::Auto(autos) => TileHandleType::Auto(
-					autos
-						.iter()
-						.map(|auto| load_auto(auto, asset_loader))
-						.collect(),
-				),
-			},
-		})
+		.map(|tile_def| load_tile_handle(tile_def, asset_loader))
 		.collect::<Vec<_>>()
 }
 
+/// Load the intermediate tile handle from a single tile definition
+///
+/// # Arguments
+///
+/// * `tile_def`: The tile definition
+/// * `asset_loader`: The `AssetServer` or other loader for loading the textures
+///
+/// returns: TileHandle
+///
+/// # Examples
+///
+/// ```
+/// # use bevy_tileset_core::prelude::*;
+/// # use bevy_tileset_tiles::prelude::*;
+/// # use bevy::prelude::*;
+///
+/// fn create_handle(tile: TileDef, asset_server: &AssetServer) -> TileHandle {
+/// 	load_tile_handle(tile, asset_server)
+/// }
+/// ```
+pub fn load_tile_handle<TLoader: TextureLoader>(
+	tile_def: TileDef,
+	asset_loader: &TLoader,
+) -> TileHandle {
+	TileHandle {
+		name: tile_def.name,
+		transform: tile_def.transform,
+		color_key: tile_def.color_key,
+		collision: tile_def.collision,
+		tags: tile_def.tags,
+		z_offset: tile_def.z_offset,
+		layer: tile_def.layer,
+		pivot: tile_def.pivot,
+		aliases: tile_def.aliases,
+		sampler: tile_def.sampler,
+		tile: match &tile_def.tile {
+			TileDefType::Standard(path) => {
+				TileHandleType::Standard(asset_loader.load_texture(path.as_str()))
+			}
+			TileDefType::Animated(anim) => {
+				TileHandleType::Animated(load_animated(anim, asset_loader))
+			}
+			TileDefType::MultiAnimated(tracks) => TileHandleType::MultiAnimated(
+				tracks
+					.iter()
+					.map(|(track, anim)| (track.clone(), load_animated(anim, asset_loader)))
+					.collect(),
+			),
+			TileDefType::AtlasRegion { atlas, rect } => {
+				TileHandleType::AtlasRegion(asset_loader.load_texture(atlas.as_str()), *rect)
+			}
+			#[cfg(feature = "variants")]
+			TileDefType::Variant(variants) => TileHandleType::Variant(
+				variants
+					.iter()
+					.map(|variant| load_variant(variant, asset_loader))
+					.collect(),
+			),
+			#[cfg(feature = "auto-tile")]
+			TileDefType::Auto(autos) => TileHandleType::Auto(
+				autos
+					.iter()
+					.map(|auto| load_auto(auto, asset_loader))
+					.collect(),
+			),
+			TileDefType::Empty => TileHandleType::Empty,
+		},
+	}
+}
+
+/// Load a standard tile handle for every image in the given directory
+///
+/// Each tile is named after its file stem. Files are sorted by name so that the returned order
+/// (and therefore any sequentially assigned group IDs) stays stable between runs. Files without
+/// an image extension are skipped, as are subdirectories.
+///
+/// The directory is read through the given `AssetIo` (such as the one returned by
+/// `AssetServer::asset_io`), so it sees the same files the asset server does, including ones
+/// provided by a custom `AssetIo`.
+///
+/// The returned handles can then be added to a builder with
+/// [`TilesetBuilder::from_tile_handles`](crate::tileset::TilesetBuilder::from_tile_handles)
+/// once their textures have loaded.
+///
+/// # Arguments
+///
+/// * `asset_io`: The `AssetIo` to read the directory from
+/// * `dir`: The directory containing the tile images, relative to the asset root
+/// * `asset_loader`: The `AssetServer` or other loader for loading the textures
+///
+/// returns: Result<Vec<TileHandle, Global>, TilesetError>
+///
+/// # Examples
+///
+/// ```no_run
+/// # use bevy_tileset_core::prelude::*;
+/// # use bevy_tileset_tiles::prelude::*;
+/// # use bevy::prelude::*;
+///
+/// fn load_tiles(asset_server: Res<AssetServer>) {
+/// 	let asset_io = asset_server.asset_io();
+/// 	let handles = load_directory_tile_handles(asset_io, "tiles", &asset_server).unwrap();
+/// 	// ...
+/// }
+/// ```
+pub fn load_directory_tile_handles<TDir: AsRef<Path>, TLoader: TextureLoader>(
+	asset_io: &dyn AssetIo,
+	dir: TDir,
+	asset_loader: &TLoader,
+) -> Result<Vec<TileHandle>, TilesetError> {
+	let tile_defs = directory_tile_defs(asset_io, dir.as_ref())?;
+	Ok(load_tile_handles(tile_defs, asset_loader))
+}
+
+/// Create a standard tile definition for every image in the given directory
+fn directory_tile_defs(asset_io: &dyn AssetIo, dir: &Path) -> Result<Vec<TileDef>, AssetIoError> {
+	let mut paths = asset_io.read_directory(dir)?.collect::<Vec<_>>();
+	paths.sort();
+
+	Ok(paths
+		.into_iter()
+		.filter(|path| is_image(path))
+		.filter_map(|path| {
+			let name = path.file_stem()?.to_str()?.to_string();
+			let file_name = path.file_name()?.to_str()?;
+			let asset_path = dir.join(file_name);
+			if !asset_io.is_file(&asset_path) {
+				return None;
+			}
+			let asset_path = asset_path.to_str()?.replace('\\', "/");
+			Some(TileDef {
+				name,
+				tile: TileDefType::Standard(asset_path),
+				transform: None,
+				color_key: None,
+				collision: TileCollision::None,
+				tags: Vec::new(),
+				z_offset: 0.0,
+				layer: 0,
+				pivot: DEFAULT_PIVOT,
+				aliases: Vec::new(),
+				sampler: None,
+			})
+		})
+		.collect())
+}
+
+/// Checks whether the given path has an image extension
+fn is_image(path: &Path) -> bool {
+	path.extension()
+		.and_then(|ext| ext.to_str())
+		.map(|ext| IMAGE_EXTENSIONS.contains(&ext.to_lowercase().as_str()))
+		.unwrap_or_default()
+}
+
 fn load_animated<TLoader: TextureLoader>(
 	def: &AnimatedTileDef,
 	asset_loader: &TLoader,
 ) -> AnimatedTileHandle {
+	if let Some(strip) = &def.strip {
+		let handle = asset_loader.load_texture(strip.path.as_str());
+		return AnimatedTileHandle {
+			speed: def.fps(),
+			frames: vec![handle; strip.frame_count],
+			regions: strip.frame_regions(),
+			reversed: def.reversed,
+			start_frame: def.start_frame,
+		};
+	}
+
 	AnimatedTileHandle {
-		speed: def.speed,
+		speed: def.fps(),
 		frames: def
 			.frames
 			.iter()
-			.map(|frame| asset_loader.load_texture::<Image, &str>(frame.as_str()))
+			.map(|frame| asset_loader.load_texture(frame.as_str()))
 			.collect(),
+		regions: Vec::new(),
+		reversed: def.reversed,
+		start_frame: def.start_frame,
 	}
 }
 
@@ -99,9 +258,10 @@ fn load_variant<TLoader: TextureLoader>(
 ) -> VariantTileHandle {
 	VariantTileHandle {
 		weight: def.weight,
+		transform: def.transform,
 		tile: match &def.tile {
 			SimpleTileDefType::Standard(path) => {
-				SimpleTileHandle::Standard(asset_loader.load_texture::<Image, &str>(path.as_str()))
+				SimpleTileHandle::Standard(asset_loader.load_texture(path.as_str()))
 			}
 			SimpleTileDefType::Animated(anim) => {
 				SimpleTileHandle::Animated(load_animated(anim, asset_loader))
@@ -121,3 +281,115 @@ fn load_auto<TLoader: TextureLoader>(def: &AutoTileDef, asset_loader: &TLoader)
 			.collect(),
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use std::path::PathBuf;
+
+	use bevy::asset::{BoxedFuture, ChangeWatcher, FileType, HandleId, Metadata};
+
+	use super::*;
+	use crate::prelude::TilesetBuilder;
+	use crate::test_utils::{solid_image, TestTextureStore};
+
+	/// An `AssetIo` listing a fixed set of files and directories, without any contents
+	struct ListingAssetIo(Vec<(PathBuf, FileType)>);
+
+	impl AssetIo for ListingAssetIo {
+		fn load_path<'a>(
+			&'a self,
+			path: &'a Path,
+		) -> BoxedFuture<'a, Result<Vec<u8>, AssetIoError>> {
+			Box::pin(async move { Err(AssetIoError::NotFound(path.to_path_buf())) })
+		}
+
+		fn read_directory(
+			&self,
+			path: &Path,
+		) -> Result<Box<dyn Iterator<Item = PathBuf>>, AssetIoError> {
+			let entries = self
+				.0
+				.iter()
+				.map(|(entry, _)| entry.clone())
+				.filter(|entry| entry.parent() == Some(path))
+				.collect::<Vec<_>>();
+			if entries.is_empty() {
+				return Err(AssetIoError::NotFound(path.to_path_buf()));
+			}
+			Ok(Box::new(entries.into_iter()))
+		}
+
+		fn get_metadata(&self, path: &Path) -> Result<Metadata, AssetIoError> {
+			self.0
+				.iter()
+				.find(|(entry, _)| entry == path)
+				.map(|(_, file_type)| Metadata::new(*file_type))
+				.ok_or_else(|| AssetIoError::NotFound(path.to_path_buf()))
+		}
+
+		fn watch_path_for_changes(
+			&self,
+			_to_watch: &Path,
+			_to_reload: Option<PathBuf>,
+		) -> Result<(), AssetIoError> {
+			Ok(())
+		}
+
+		fn watch_for_changes(&self, _configuration: &ChangeWatcher) -> Result<(), AssetIoError> {
+			Ok(())
+		}
+	}
+
+	/// A loader whose handles are derived from the path, like the `AssetServer`'s
+	struct PathLoader;
+
+	impl TextureLoader for PathLoader {
+		fn load_texture<'a, P: Into<AssetPath<'a>>>(&self, path: P) -> Handle<Image> {
+			Handle::weak(HandleId::from(path.into()))
+		}
+	}
+
+	#[test]
+	fn should_create_tile_defs_from_directory() {
+		let asset_io = ListingAssetIo(vec![
+			(PathBuf::from("tiles/b.png"), FileType::File),
+			(PathBuf::from("tiles/a.PNG"), FileType::File),
+			(PathBuf::from("tiles/notes.txt"), FileType::File),
+			(PathBuf::from("tiles/nested.png"), FileType::Directory),
+			(PathBuf::from("other/c.png"), FileType::File),
+		]);
+
+		let defs = directory_tile_defs(&asset_io, Path::new("tiles")).unwrap();
+
+		let tiles = defs
+			.iter()
+			.map(|def| match &def.tile {
+				TileDefType::Standard(path) => (def.name.as_str(), path.as_str()),
+				_ => panic!("expected a standard tile"),
+			})
+			.collect::<Vec<_>>();
+		assert_eq!(vec![("a", "tiles/a.PNG"), ("b", "tiles/b.png")], tiles);
+
+		let missing = directory_tile_defs(&asset_io, Path::new("missing"));
+		assert!(matches!(missing, Err(AssetIoError::NotFound(..))));
+	}
+
+	#[test]
+	fn should_build_from_directory() {
+		let asset_io = ListingAssetIo(vec![
+			(PathBuf::from("tiles/grass.png"), FileType::File),
+			(PathBuf::from("tiles/dirt.png"), FileType::File),
+		]);
+		let mut store = TestTextureStore::default();
+		for path in ["tiles/grass.png", "tiles/dirt.png"] {
+			let id = HandleId::from(AssetPath::from(path));
+			store.images.insert(id, solid_image(8, 8, [255; 4]));
+		}
+
+		let builder =
+			TilesetBuilder::from_directory(&asset_io, "tiles", &PathLoader, &store).unwrap();
+		let tileset = builder.build("Directory", 0, &mut store).unwrap();
+		assert_eq!(Some(&0), tileset.get_tile_group_id("dirt"));
+		assert_eq!(Some(&1), tileset.get_tile_group_id("grass"));
+	}
+}
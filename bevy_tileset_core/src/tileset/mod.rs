@@ -1,31 +1,68 @@
 //! Types for generating and managing tilesets
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::PathBuf;
 
-use bevy::prelude::{Component, Handle, Image, TextureAtlas, Vec2};
-use bevy::reflect::{TypeUuid, TypePath};
+use bevy::prelude::{Component, Handle, Image, TextureAtlas, UVec2, Vec2};
+use bevy::reflect::{TypePath, TypeUuid};
 
+pub use animation::TileAnimationState;
 pub(crate) use asset::TilesetAssetLoader;
-pub use asset::TilesetDef;
+pub use asset::{MissingTexturePolicy, TilesetDef};
+pub use bake::BAKED_TILESET_EXTENSION;
+pub use bevy_tile_atlas::TextureStore;
+pub use bevy_tileset_tiles::prelude::SamplerPreset;
 pub use builder::TilesetBuilder;
+pub use diff::{TileMove, TileRename, TilesetDiff};
 pub use error::TilesetError;
 pub use impls::*;
-pub use load::load_tile_handles;
+#[cfg(feature = "lazy")]
+pub use lazy::{LazyTileset, LazyTilesetAsset};
+pub use load::{load_directory_tile_handles, load_tile_handle, load_tile_handles, TextureLoader};
+pub use palette::PaletteEntry;
 pub(crate) use param::TilesetMap;
 pub use param::Tilesets;
+pub use rescale::ScaleFilter;
+pub use source::MultiSourceAssetIo;
+pub use task::{OwnedTextureStore, PackedTileset};
 pub use tile_index::TileIndex;
+#[cfg(feature = "tiled")]
+pub use tiled::TiledTileset;
+pub use validate::{TilesetValidationCategory, TilesetValidationIssue};
+pub use world::TilesetWorldExt;
 
 use crate::prelude::*;
 use bevy_tileset_tiles::prelude::*;
 
+mod animation;
 mod asset;
+mod atlas;
+mod bake;
 mod builder;
+mod diff;
 pub mod error;
+#[cfg(feature = "png-export")]
+mod export;
+mod header;
 mod impls;
+mod import;
+#[cfg(feature = "lazy")]
+mod lazy;
 mod load;
+mod palette;
 mod param;
+mod patch;
+mod pixels;
+mod preview;
 mod raw;
+mod rescale;
+mod source;
+mod task;
 mod tile_index;
+#[cfg(feature = "tiled")]
+mod tiled;
+mod validate;
+mod world;
 
 macro_rules! define_tileset {
 	($(#[$attr:meta])* $vis: vis $name: ident { $($(#[$field_attr:meta])* $field: ident : $type: ty),* $(,)? }) => {
@@ -47,8 +84,33 @@ macro_rules! define_tileset {
 			tile_names: HashMap<TileGroupId, String>,
 			/// The tile handles mapped by their index in the atlas
 			tile_handles: HashMap<usize, Handle<Image>>,
+			/// The (weak) handles of the tiles used to build this tileset mapped by their ID
+			group_handles: HashMap<TileGroupId, TileHandle>,
 			/// The tile IDs mapped by their index in the atlas
 			tile_indices: HashMap<usize, TileId>,
+			/// The average color of each tile mapped by their ID
+			tile_colors: HashMap<TileGroupId, [u8; 4]>,
+			/// The size each tile was stored at in the atlas (in pixels) mapped by their ID
+			tile_sizes: HashMap<TileGroupId, UVec2>,
+			/// The tile group IDs mapped by the name of the category they belong to
+			categories: BTreeMap<String, Vec<TileGroupId>>,
+			/// The transparent borders trimmed from each texture before packing mapped by their
+			/// index in the atlas
+			tile_trims: HashMap<usize, TileTrim>,
+			/// The atlas indices whose texture was processed from its source image
+			processed_indices: HashSet<usize>,
+			/// The progressively halved copies of the atlas texture (starting at LOD level 1)
+			lod_textures: Vec<Handle<Image>>,
+			/// The warnings for any tiles that were skipped while loading this tileset
+			load_warnings: Vec<String>,
+			/// The raw RON source of each tile's definition mapped by its ID (if retained)
+			tile_sources: HashMap<TileGroupId, String>,
+			/// The paths of every file this tileset was loaded from (relative to the asset root)
+			dependency_paths: Vec<PathBuf>,
+			/// The ID of the tile used in place of unknown tile names (if any)
+			default_tile: Option<TileGroupId>,
+			/// The number of columns the atlas was laid out in (if it was built as a grid)
+			grid_columns: Option<u32>,
 			$(
 				$(#[$field_attr])*
 				$field : $type
@@ -62,22 +124,26 @@ define_tileset!(
 	///
 	/// This is useful for creating a tileset and using it immediately. Whereas, a standard [Tileset] breaks
 	/// things up a bit more by transferring ownership of the `TextureAtlas` to the `Assets<TextureAtlas>` resource
-	#[derive(Debug)]
+	#[derive(Debug, Clone)]
 	pub RawTileset {
 		/// The atlas for all registered tiles
 		atlas: TextureAtlas,
+		/// The additional atlas pages for tiles that requested their own sampler
+		pages: Vec<RawTileset>,
 	}
 );
 
 define_tileset!(
 	/// A structure containing the registered tiles as well as a handle to their generated `TextureAtlas`
-	#[derive(Debug, TypeUuid, TypePath)]
+	#[derive(Debug, Clone, TypeUuid, TypePath)]
 	#[uuid = "4a176882-d7b2-429d-af5c-be418ccc3c52"]
 	pub Tileset {
 		/// A handle to the generated texture atlas
 		atlas: Handle<TextureAtlas>,
 		/// A handle to the generated texture atlas's texture
-		texture: Handle<Image>
+		texture: Handle<Image>,
+		/// The additional atlas pages for tiles that requested their own sampler
+		pages: Vec<Tileset>,
 	}
 );
 
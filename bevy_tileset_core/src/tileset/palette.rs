@@ -0,0 +1,161 @@
+//! Data for rendering a tileset as a palette of tiles
+//!
+//! This is meant to back tile pickers in editors and other immediate-mode UIs, without tying
+//! this crate to any specific UI library.
+
+use bevy::prelude::{Rect, TextureAtlas};
+use bevy_tileset_tiles::prelude::TileType;
+
+use crate::prelude::{PartialTileId, RawTileset, TileGroupId, Tileset};
+
+/// A single tile within a tileset's palette
+#[derive(Debug, Clone, PartialEq)]
+pub struct PaletteEntry {
+	/// The name of the tile
+	pub name: String,
+	/// The group ID of the tile
+	pub group_id: TileGroupId,
+	/// The normalized UV rect of the tile's preview texture within the atlas texture
+	///
+	/// This is the first frame of animated tiles and the first variant of variant tiles (for
+	/// auto tiles, this is the variant matching the default rule). It's `None` for tiles
+	/// without a texture, such as [`TileType::Empty`] tiles.
+	pub uv: Option<Rect>,
+	/// Whether the tile is a [`TileType::Animated`] tile
+	pub is_animated: bool,
+}
+
+macro_rules! impl_palette {
+	($name: ident) => {
+		impl $name {
+			/// Assembles the palette entries of this tileset using the given atlas
+			fn palette_entries(&self, atlas: &TextureAtlas) -> Vec<PaletteEntry> {
+				let mut group_ids = self.tiles.keys().copied().collect::<Vec<_>>();
+				group_ids.sort_unstable();
+				group_ids
+					.into_iter()
+					.filter_map(|group_id| {
+						#[allow(unused_mut)]
+						let mut id = PartialTileId::new(group_id);
+						#[cfg(feature = "variants")]
+						{
+							id.variant_index = Some(0);
+						}
+
+						let (index, data) = self.select_tile_by_id(id)?;
+						let uv = index.base_index().and_then(|index| {
+							atlas.textures.get(*index).map(|rect| Rect {
+								min: rect.min / atlas.size,
+								max: rect.max / atlas.size,
+							})
+						});
+
+						Some(PaletteEntry {
+							name: data.name().to_string(),
+							group_id,
+							uv,
+							is_animated: matches!(
+								data.tile(),
+								TileType::Animated(..) | TileType::MultiAnimated(..)
+							),
+						})
+					})
+					.collect()
+			}
+		}
+	};
+}
+
+impl_palette!(RawTileset);
+impl_palette!(Tileset);
+
+impl RawTileset {
+	/// Gets the palette entries of every tile in this tileset, ordered by their group ID
+	///
+	/// returns: Vec<PaletteEntry>
+	///
+	pub fn palette(&self) -> Vec<PaletteEntry> {
+		self.palette_entries(&self.atlas)
+	}
+}
+
+impl Tileset {
+	/// Gets the palette entries of every tile in this tileset, ordered by their group ID
+	///
+	/// # Arguments
+	///
+	/// * `atlas`: This tileset's `TextureAtlas` (see [`Tileset::atlas`])
+	///
+	/// returns: Vec<PaletteEntry>
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use bevy::prelude::*;
+	/// # use bevy_tileset_core::prelude::*;
+	///
+	/// fn palette(tileset: &Tileset, atlases: &Assets<TextureAtlas>) -> Vec<PaletteEntry> {
+	/// 	atlases
+	/// 		.get(tileset.atlas())
+	/// 		.map(|atlas| tileset.palette(atlas))
+	/// 		.unwrap_or_default()
+	/// }
+	/// ```
+	pub fn palette(&self, atlas: &TextureAtlas) -> Vec<PaletteEntry> {
+		self.palette_entries(atlas)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use bevy::prelude::Vec2;
+	use bevy_tile_atlas::TextureStore;
+	use bevy_tileset_tiles::prelude::{AnimatedTileHandle, TileHandle};
+
+	use crate::prelude::TilesetBuilder;
+	use crate::test_utils::{solid_image, TestTextureStore};
+
+	#[test]
+	fn should_assemble_palette() {
+		let mut store = TestTextureStore::default();
+		let mut builder = TilesetBuilder::default();
+		let handle = store.add(solid_image(16, 16, [255; 4]));
+		builder
+			.add_tile(TileHandle::new_standard("Grass", handle), 2, &store)
+			.unwrap();
+		let frames = (0..2)
+			.map(|_| store.add(solid_image(16, 16, [127; 4])))
+			.collect();
+		let tile = TileHandle::new_animated(
+			"Water",
+			AnimatedTileHandle {
+				speed: 1.0,
+				frames,
+				regions: Vec::new(),
+				..Default::default()
+			},
+		);
+		builder.add_tile(tile, 0, &store).unwrap();
+		builder
+			.add_tile(TileHandle::new_empty("Eraser"), 1, &store)
+			.unwrap();
+
+		let tileset = builder.build("Palette", 0, &mut store).unwrap();
+		let palette = tileset.palette();
+		let names = palette
+			.iter()
+			.map(|entry| (entry.group_id, entry.name.as_str(), entry.is_animated))
+			.collect::<Vec<_>>();
+		assert_eq!(
+			vec![(0, "Water", true), (1, "Eraser", false), (2, "Grass", false)],
+			names
+		);
+
+		assert_eq!(None, palette[1].uv);
+		let rect = tileset.atlas().textures[tileset.get_base_tile_index("Grass").unwrap()];
+		let uv = palette[2].uv.unwrap();
+		assert_eq!(rect.min / tileset.size(), uv.min);
+		assert_eq!(rect.size() / tileset.size(), uv.size());
+		assert!(uv.min.cmpge(Vec2::ZERO).all() && uv.max.cmple(Vec2::ONE).all());
+	}
+}
@@ -1,4 +1,4 @@
-use crate::prelude::{Tileset, TilesetId};
+use crate::prelude::{Tileset, TilesetError, TilesetId};
 use bevy::asset::{Assets, Handle};
 use bevy::ecs::system::SystemParam;
 use bevy::prelude::{Query, Res, Resource};
@@ -21,6 +21,10 @@ pub struct TilesetMap {
 	id_to_handle: HashMap<TilesetId, Handle<Tileset>>,
 	handle_to_id: HashMap<Handle<Tileset>, TilesetId>,
 	id_to_name: HashMap<TilesetId, String>,
+	/// The path each tileset was loaded from (or its name if it wasn't loaded from a file)
+	id_to_source: HashMap<TilesetId, String>,
+	/// Whether tilesets sharing the ID of an already registered tileset should be skipped
+	strict_ids: bool,
 }
 
 impl<'w, 's> Deref for Tilesets<'w, 's> {
@@ -90,15 +94,66 @@ impl<'w, 's> Tilesets<'w, 's> {
 }
 
 impl TilesetMap {
+	/// Create a map that optionally rejects tilesets with duplicate IDs
+	///
+	/// # Arguments
+	///
+	/// * `strict_ids`: Whether a tileset sharing the ID of a registered tileset is skipped
+	///
+	/// returns: TilesetMap
+	pub(crate) fn new(strict_ids: bool) -> Self {
+		Self {
+			strict_ids,
+			..Default::default()
+		}
+	}
+
+	/// Whether tilesets sharing the ID of an already registered tileset should be skipped
+	pub(crate) fn strict_ids(&self) -> bool {
+		self.strict_ids
+	}
+
+	/// Checks that no other tileset has been registered with the same ID as the given one
+	///
+	/// # Arguments
+	///
+	/// * `tileset`: The tileset to check
+	/// * `handle`: The handle to the tileset
+	/// * `source`: The path the tileset was loaded from (or its name)
+	///
+	/// returns: Result<(), TilesetError>
+	pub(crate) fn check_unique_id(
+		&self,
+		tileset: &Tileset,
+		handle: &Handle<Tileset>,
+		source: &str,
+	) -> Result<(), TilesetError> {
+		match self.id_to_handle.get(tileset.id()) {
+			Some(registered) if registered != handle => Err(TilesetError::DuplicateTilesetId {
+				id: *tileset.id(),
+				first: self.id_to_source.get(tileset.id()).cloned().unwrap_or_default(),
+				second: source.to_string(),
+			}),
+			_ => Ok(()),
+		}
+	}
+
 	/// Register a tileset for easy lookup in the [Tilesets] system param.
 	///
 	/// # Arguments
 	///
 	/// * `tileset`: The tileset to register
 	/// * `handle`: The handle to the tileset
+	/// * `source`: The path the tileset was loaded from (or its name)
 	///
 	/// returns: ()
-	pub(crate) fn register_tileset(&mut self, tileset: &Tileset, handle: &Handle<Tileset>) {
+	pub(crate) fn register_tileset(
+		&mut self,
+		tileset: &Tileset,
+		handle: &Handle<Tileset>,
+		source: String,
+	) {
+		self.id_to_source.insert(*tileset.id(), source);
 		self.handle_to_id.insert(handle.clone_weak(), *tileset.id());
 		self.id_to_name
 			.insert(*tileset.id(), tileset.name().to_string());
@@ -116,9 +171,14 @@ impl TilesetMap {
 	/// returns: ()
 	pub(crate) fn deregister_tileset(&mut self, handle: &Handle<Tileset>) {
 		if let Some(ref id) = self.handle_to_id.remove(handle) {
+			// The ID may have been taken over by another tileset sharing it
+			if self.id_to_handle.get(id) != Some(handle) {
+				return;
+			}
 			if let Some(ref name) = self.id_to_name.remove(id) {
 				self.name_to_id.remove(name);
 			}
+			self.id_to_source.remove(id);
 			self.id_to_handle.remove(id);
 		}
 	}
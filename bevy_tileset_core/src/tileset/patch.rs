@@ -0,0 +1,385 @@
+//! Support for updating a single tile's texture without re-packing the entire atlas
+//!
+//! Re-building a tileset requires packing every one of its textures again, which can be slow
+//! for large tilesets. When only a single texture changes (such as when hot-reloading it) and its
+//! dimensions stay the same, it can instead be copied directly over its existing atlas region.
+//! Since nothing is moved, every index into the atlas remains valid.
+
+use bevy::prelude::{Assets, Handle, Image, TextureAtlas};
+
+use crate::prelude::{RawTileset, Tileset, TilesetError};
+use crate::tileset::pixels;
+
+/// Copies the given texture over the atlas region at the given index
+///
+/// Returns `false` if the texture's dimensions don't match the region's.
+fn patch_atlas(
+	atlas: &TextureAtlas,
+	atlas_texture: &mut Image,
+	index: usize,
+	texture: &Image,
+) -> Result<bool, TilesetError> {
+	let rect = atlas
+		.textures
+		.get(index)
+		.ok_or(TilesetError::TileOutOfBounds {
+			index,
+			size: atlas.size,
+		})?;
+	if texture.size() != rect.size() {
+		return Ok(false);
+	}
+
+	let format = atlas_texture.texture_descriptor.format;
+	let converted;
+	let texture = if texture.texture_descriptor.format == format {
+		texture
+	} else {
+		converted = texture
+			.convert(format)
+			.ok_or_else(|| TilesetError::InvalidData {
+				expected: format!("a texture convertible to {:?}", format),
+				found: format!("{:?}", texture.texture_descriptor.format),
+			})?;
+		&converted
+	};
+
+	pixels::blit(texture, atlas_texture, rect.min.x as u32, rect.min.y as u32);
+	Ok(true)
+}
+
+impl RawTileset {
+	/// Replaces the texture at the given atlas index in place
+	///
+	/// This is much faster than re-building the tileset, but requires the new texture to have
+	/// the same dimensions as the old one. If they differ, nothing is changed and `false` is
+	/// returned, meaning the tileset needs to be fully re-built (e.g. with a
+	/// [`TilesetBuilder`](crate::prelude::TilesetBuilder)) instead.
+	///
+	/// Keep in mind that the texture is copied as-is, so any transform or color key applied
+	/// to the original tile must already be applied to the new texture.
+	///
+	/// # Arguments
+	///
+	/// * `index`: The index of the texture within the atlas
+	/// * `texture`: The new texture
+	/// * `atlas_texture`: This tileset's atlas texture
+	///
+	/// returns: Result<bool, TilesetError>
+	///
+	pub fn update_tile_texture(
+		&self,
+		index: usize,
+		texture: &Image,
+		atlas_texture: &mut Image,
+	) -> Result<bool, TilesetError> {
+		patch_atlas(self.atlas(), atlas_texture, index, texture)
+	}
+}
+
+impl Tileset {
+	/// Replaces the texture at the given atlas index in place
+	///
+	/// This is much faster than re-building the tileset, but requires the new texture to have
+	/// the same dimensions as the old one. If they differ, nothing is changed and `false` is
+	/// returned, meaning the tileset needs to be fully re-built (or reloaded) instead.
+	///
+	/// Keep in mind that the texture is copied as-is, so any transform or color key applied
+	/// to the original tile must already be applied to the new texture.
+	///
+	/// # Arguments
+	///
+	/// * `index`: The index of the texture within the atlas
+	/// * `texture`: The new texture
+	/// * `atlases`: The `TextureAtlas` assets containing this tileset's atlas
+	/// * `images`: The `Image` assets containing this tileset's atlas texture
+	///
+	/// returns: Result<bool, TilesetError>
+	///
+	/// To leave this tileset untouched, use [`Self::with_tile_texture`] instead.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use bevy::prelude::*;
+	/// # use bevy_tileset_core::prelude::*;
+	///
+	/// fn reload_tile(
+	/// 	tileset: &Tileset,
+	/// 	texture: &Image,
+	/// 	atlases: &Assets<TextureAtlas>,
+	/// 	images: &mut Assets<Image>,
+	/// ) {
+	/// 	let index = tileset.get_base_tile_index("Grass").unwrap();
+	/// 	if !tileset.update_tile_texture(index, texture, atlases, images).unwrap() {
+	/// 		// The texture changed size, so the tileset needs to be re-built
+	/// 	}
+	/// }
+	/// ```
+	pub fn update_tile_texture(
+		&self,
+		index: usize,
+		texture: &Image,
+		atlases: &Assets<TextureAtlas>,
+		images: &mut Assets<Image>,
+	) -> Result<bool, TilesetError> {
+		let atlas = atlases.get(self.atlas()).ok_or(TilesetError::InvalidData {
+			expected: String::from("A loaded texture atlas"),
+			found: String::from("Missing texture atlas"),
+		})?;
+		let atlas_texture = images
+			.get_mut(self.texture())
+			.ok_or(TilesetError::ImageNotFound)?;
+		patch_atlas(atlas, atlas_texture, index, texture)
+	}
+
+	/// Creates a copy of this tileset with the texture of the given tile replaced
+	///
+	/// Like [`Self::update_tile_texture`], the texture is copied over the tile's existing atlas
+	/// region, so the new tileset shares all of this tileset's indices. The difference is that
+	/// the copy gets its own atlas texture (and `TextureAtlas`), leaving this tileset unchanged.
+	/// The tile is found on any atlas page, and for animated tiles only their first frame is
+	/// replaced. Keep in mind that LOD textures aren't updated.
+	///
+	/// # Arguments
+	///
+	/// * `name`: The name of the tile
+	/// * `texture`: The new texture
+	/// * `atlases`: The `TextureAtlas` assets containing this tileset's atlas
+	/// * `images`: The `Image` assets containing this tileset's atlas texture
+	///
+	/// returns: Result<Tileset, TilesetError>
+	///
+	/// Errors with [`TilesetError::TileNotFound`] if there's no tile with a texture by that name,
+	/// or [`TilesetError::InvalidTileSize`] if the texture's dimensions don't match the tile's
+	/// (meaning the tileset needs to be fully re-built instead).
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use bevy::prelude::*;
+	/// # use bevy_tileset_core::prelude::*;
+	///
+	/// fn reload_tile(
+	/// 	tileset: &Tileset,
+	/// 	texture: &Image,
+	/// 	atlases: &mut Assets<TextureAtlas>,
+	/// 	images: &mut Assets<Image>,
+	/// ) -> Result<Tileset, TilesetError> {
+	/// 	tileset.with_tile_texture("Grass", texture, atlases, images)
+	/// }
+	/// ```
+	pub fn with_tile_texture(
+		&self,
+		name: &str,
+		texture: &Image,
+		atlases: &mut Assets<TextureAtlas>,
+		images: &mut Assets<Image>,
+	) -> Result<Tileset, TilesetError> {
+		let Some(index) = self.get_base_tile_index(name) else {
+			// The tile may be packed into one of the additional atlas pages instead
+			let page = self
+				.tile_page(name)
+				.filter(|page| *page > 0)
+				.ok_or_else(|| TilesetError::TileNotFound(name.to_string()))?;
+			let mut tileset = self.clone();
+			tileset.pages[page - 1] =
+				self.pages[page - 1].with_tile_texture(name, texture, atlases, images)?;
+			return Ok(tileset);
+		};
+
+		let mut atlas = atlases
+			.get(self.atlas())
+			.ok_or(TilesetError::InvalidData {
+				expected: String::from("A loaded texture atlas"),
+				found: String::from("Missing texture atlas"),
+			})?
+			.clone();
+		let rect = atlas
+			.textures
+			.get(index)
+			.ok_or(TilesetError::TileOutOfBounds {
+				index,
+				size: atlas.size,
+			})?;
+		if texture.size() != rect.size() {
+			return Err(TilesetError::InvalidTileSize {
+				tile: name.to_string(),
+				expected: rect.size(),
+				found: texture.size(),
+			});
+		}
+
+		let mut atlas_texture = images
+			.get(self.texture())
+			.ok_or(TilesetError::ImageNotFound)?
+			.clone();
+		patch_atlas(&atlas, &mut atlas_texture, index, texture)?;
+
+		let mut tileset = self.clone();
+		tileset.texture = images.add(atlas_texture);
+		atlas.texture = tileset.texture.clone();
+		tileset.atlas = atlases.add(atlas);
+		Ok(tileset)
+	}
+
+	/// Copies the given source image over every atlas region packed from it, including the
+	/// regions within any additional atlas pages
+	///
+	/// This allows a source image to be edited without reloading the entire tileset (and is
+	/// done automatically by the [`TilesetPlugin`](crate::prelude::TilesetPlugin) whenever a
+	/// source image in the `Image` assets is modified, which excludes the images read by the
+	/// asset loader). Regions whose texture was processed (such as by a transform,
+	/// color key, or trim) can't be copied as-is, and neither can regions whose size no longer
+	/// matches. If any region is skipped, `false` is returned, meaning the tileset needs to be
+	/// fully re-built (or reloaded) to show the change. Keep in mind that LOD textures aren't
+	/// updated either way.
+	///
+	/// # Arguments
+	///
+	/// * `handle`: The handle of the modified source image
+	/// * `atlases`: The `TextureAtlas` assets containing this tileset's atlas
+	/// * `images`: The `Image` assets containing the source image and this tileset's atlas texture
+	///
+	/// returns: Result<bool, TilesetError>
+	///
+	pub fn update_source_image(
+		&self,
+		handle: &Handle<Image>,
+		atlases: &Assets<TextureAtlas>,
+		images: &mut Assets<Image>,
+	) -> Result<bool, TilesetError> {
+		let mut is_updated = true;
+		// The regions of a page using the source image as its atlas texture are already updated
+		let indices = if self.texture() == handle {
+			Vec::new()
+		} else {
+			self.get_source_image_indices(handle)
+		};
+		if !indices.is_empty() {
+			let texture = images
+				.get(handle)
+				.ok_or(TilesetError::ImageNotFound)?
+				.clone();
+			for index in indices {
+				if self.processed_indices.contains(&index) {
+					is_updated = false;
+					continue;
+				}
+				is_updated &= self.update_tile_texture(index, &texture, atlases, images)?;
+			}
+		}
+
+		for page in &self.pages {
+			is_updated &= page.update_source_image(handle, atlases, images)?;
+		}
+		Ok(is_updated)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use bevy::asset::{AddAsset, AssetPlugin};
+	use bevy::prelude::{App, Assets, Image, MinimalPlugins, Mut, TextureAtlas};
+	use bevy_tileset_tiles::prelude::TileHandle;
+
+	use crate::prelude::{TilesetBuilder, TilesetError};
+	use crate::test_utils::{solid_image, TestTextureStore};
+	use bevy_tile_atlas::TextureStore;
+
+	#[test]
+	fn should_update_tile_texture_in_place() {
+		let mut store = TestTextureStore::default();
+		let mut builder = TilesetBuilder::default();
+		for group_id in 0..2 {
+			let handle = store.add(solid_image(16, 16, [255; 4]));
+			let tile = TileHandle::new_standard(format!("Tile {}", group_id), handle);
+			builder.add_tile(tile, group_id, &store).unwrap();
+		}
+		let tileset = builder.build("Patched", 0, &mut store).unwrap();
+		let index = tileset.get_base_tile_index("Tile 1").unwrap();
+		let mut atlas_texture = store.get(tileset.texture()).unwrap().clone();
+
+		let texture = solid_image(16, 16, [0, 255, 0, 255]);
+		assert!(tileset
+			.update_tile_texture(index, &texture, &mut atlas_texture)
+			.unwrap());
+
+		let pixel_at = |image: &Image, index: usize| {
+			let rect = tileset.atlas().textures[index];
+			let width = image.texture_descriptor.size.width as usize;
+			let start = (rect.min.y as usize * width + rect.min.x as usize) * 4;
+			[
+				image.data[start],
+				image.data[start + 1],
+				image.data[start + 2],
+				image.data[start + 3],
+			]
+		};
+		let other = tileset.get_base_tile_index("Tile 0").unwrap();
+		assert_eq!([0, 255, 0, 255], pixel_at(&atlas_texture, index));
+		assert_eq!([255; 4], pixel_at(&atlas_texture, other));
+
+		// Mismatched sizes require a full rebuild
+		let texture = solid_image(8, 8, [0, 0, 255, 255]);
+		assert!(!tileset
+			.update_tile_texture(index, &texture, &mut atlas_texture)
+			.unwrap());
+		assert_eq!([0, 255, 0, 255], pixel_at(&atlas_texture, index));
+	}
+
+	#[test]
+	fn should_create_tileset_with_tile_texture() {
+		let mut app = App::new();
+		app.add_plugins((MinimalPlugins, AssetPlugin::default()))
+			.add_asset::<Image>()
+			.add_asset::<TextureAtlas>();
+
+		app.world.resource_scope(|world, mut images: Mut<Assets<Image>>| {
+			let mut atlases = world.resource_mut::<Assets<TextureAtlas>>();
+			let mut builder = TilesetBuilder::default();
+			for group_id in 0..2 {
+				let handle = images.add(solid_image(16, 16, [255; 4]));
+				let tile = TileHandle::new_standard(format!("Tile {}", group_id), handle);
+				builder.add_tile(tile, group_id, &*images).unwrap();
+			}
+			let raw = builder.build("Patched", 0, &mut *images).unwrap();
+			let rects = raw.atlas().textures.clone();
+			let tileset = raw.into_asset(&mut atlases);
+
+			let texture = solid_image(16, 16, [0, 255, 0, 255]);
+			let patched = tileset
+				.with_tile_texture("Tile 1", &texture, &mut atlases, &mut images)
+				.unwrap();
+			assert_ne!(tileset.texture(), patched.texture());
+			let index = patched.get_base_tile_index("Tile 1").unwrap();
+			assert_eq!(tileset.get_base_tile_index("Tile 1"), Some(index));
+			let atlas = atlases.get(patched.atlas()).unwrap();
+			assert_eq!(patched.texture(), &atlas.texture);
+
+			let pixel_at = |image: &Image| {
+				let rect = rects[index];
+				let width = image.texture_descriptor.size.width as usize;
+				let start = (rect.min.y as usize * width + rect.min.x as usize) * 4;
+				image.data[start..start + 4].to_vec()
+			};
+			let patched_texture = images.get(patched.texture()).unwrap();
+			assert_eq!(vec![0, 255, 0, 255], pixel_at(patched_texture));
+			// The original tileset is left untouched
+			let original_texture = images.get(tileset.texture()).unwrap();
+			assert_eq!(vec![255; 4], pixel_at(original_texture));
+
+			let missing = tileset
+				.with_tile_texture("Missing", &texture, &mut atlases, &mut images);
+			assert!(matches!(
+				missing,
+				Err(TilesetError::TileNotFound(name)) if name == "Missing"
+			));
+
+			let texture = solid_image(8, 8, [0, 0, 255, 255]);
+			let resized = tileset
+				.with_tile_texture("Tile 1", &texture, &mut atlases, &mut images);
+			assert!(matches!(resized, Err(TilesetError::InvalidTileSize { .. })));
+		});
+	}
+}
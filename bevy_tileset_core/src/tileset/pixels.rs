@@ -0,0 +1,412 @@
+//! Helpers for operating on the raw pixel data of tile and atlas images
+//!
+//! All helpers here assume an 8-bit RGBA texture format. Tile textures are converted to one with
+//! [`to_rgba8`] as soon as they're read, so the generated atlas always uses one as well.
+
+use std::borrow::Cow;
+
+use bevy::prelude::{Image, Rect, UVec2, Vec2};
+use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+use bevy_tileset_tiles::prelude::TileTransform;
+
+use crate::prelude::{ScaleFilter, TilesetError};
+
+/// The number of bytes used by a single RGBA8 pixel
+pub(crate) const PIXEL_SIZE: usize = 4;
+
+/// Checks whether the given image uses an 8-bit RGBA texture format
+pub(crate) fn is_rgba8(image: &Image) -> bool {
+	matches!(
+		image.texture_descriptor.format,
+		TextureFormat::Rgba8Unorm | TextureFormat::Rgba8UnormSrgb
+	)
+}
+
+/// Converts the given image to an 8-bit RGBA texture format, borrowing it if it already is one
+///
+/// Errors with [`TilesetError::UnsupportedFormat`] if the image can't be converted (such as a
+/// compressed texture).
+pub(crate) fn to_rgba8(image: &Image) -> Result<Cow<Image>, TilesetError> {
+	if is_rgba8(image) {
+		return Ok(Cow::Borrowed(image));
+	}
+
+	let format = image.texture_descriptor.format;
+	let mut converted = image
+		.convert(TextureFormat::Rgba8UnormSrgb)
+		.ok_or(TilesetError::UnsupportedFormat(format))?;
+	converted.sampler_descriptor = image.sampler_descriptor.clone();
+	Ok(Cow::Owned(converted))
+}
+
+/// Converts the given image to an 8-bit RGBA texture format, if it isn't one already
+///
+/// See [`to_rgba8`] for details.
+pub(crate) fn into_rgba8(image: Image) -> Result<Image, TilesetError> {
+	if is_rgba8(&image) {
+		return Ok(image);
+	}
+	to_rgba8(&image).map(Cow::into_owned)
+}
+
+/// Gets the width and height of the given image (in pixels)
+pub(crate) fn dimensions(image: &Image) -> (u32, u32) {
+	let size = image.texture_descriptor.size;
+	(size.width, size.height)
+}
+
+/// Creates a new image with the same format as the given template, filled with the given color
+pub(crate) fn new_filled(template: &Image, width: u32, height: u32, color: [u8; 4]) -> Image {
+	let mut image = Image::new_fill(
+		Extent3d {
+			width,
+			height,
+			depth_or_array_layers: 1,
+		},
+		TextureDimension::D2,
+		&color,
+		template.texture_descriptor.format,
+	);
+	image.sampler_descriptor = template.sampler_descriptor.clone();
+	image
+}
+
+/// Pads the given image to the given size by extending its right and bottom edges
+///
+/// The original pixels remain at the top-left corner so any existing UV rects stay valid.
+pub(crate) fn pad(image: &Image, width: u32, height: u32, color: [u8; 4]) -> Image {
+	let (src_width, src_height) = dimensions(image);
+	let mut padded = new_filled(image, width.max(src_width), height.max(src_height), color);
+	blit(image, &mut padded, 0, 0);
+	padded
+}
+
+/// Copies the entirety of `src` into `dest`, placing its top-left corner at the given position
+///
+/// Any pixels that would fall outside of `dest` are skipped.
+pub(crate) fn blit(src: &Image, dest: &mut Image, x: u32, y: u32) {
+	let (src_width, src_height) = dimensions(src);
+	let (dest_width, dest_height) = dimensions(dest);
+	if x >= dest_width || y >= dest_height {
+		return;
+	}
+
+	let copy_width = src_width.min(dest_width - x) as usize;
+	let copy_height = src_height.min(dest_height - y);
+	for row in 0..copy_height {
+		let src_start = (row * src_width) as usize * PIXEL_SIZE;
+		let dest_start = (((y + row) * dest_width) + x) as usize * PIXEL_SIZE;
+		let len = copy_width * PIXEL_SIZE;
+		dest.data[dest_start..dest_start + len]
+			.copy_from_slice(&src.data[src_start..src_start + len]);
+	}
+}
+
+/// Creates a magenta and black checkerboard image, used as a placeholder for missing textures
+pub(crate) fn checkerboard(width: u32, height: u32) -> Image {
+	const MAGENTA: [u8; 4] = [255, 0, 255, 255];
+	const BLACK: [u8; 4] = [0, 0, 0, 255];
+
+	let cell_size = (width.max(height) / 4).max(1);
+	let mut data = Vec::with_capacity((width * height) as usize * PIXEL_SIZE);
+	for y in 0..height {
+		for x in 0..width {
+			let is_even = ((x / cell_size) + (y / cell_size)) % 2 == 0;
+			data.extend_from_slice(if is_even { &MAGENTA } else { &BLACK });
+		}
+	}
+
+	Image::new(
+		Extent3d {
+			width,
+			height,
+			depth_or_array_layers: 1,
+		},
+		TextureDimension::D2,
+		data,
+		TextureFormat::Rgba8UnormSrgb,
+	)
+}
+
+/// Fills every pixel of the given image that isn't covered by any of the given regions
+///
+/// Regions are given in pixels and clamped to the bounds of the image.
+pub(crate) fn fill_uncovered(image: &mut Image, regions: &[Rect], color: [u8; 4]) {
+	let (width, height) = dimensions(image);
+	let mut covered = vec![false; width as usize * height as usize];
+	for region in regions {
+		let min_x = (region.min.x.max(0.0) as u32).min(width);
+		let min_y = (region.min.y.max(0.0) as u32).min(height);
+		let max_x = (region.max.x.max(0.0) as u32).min(width);
+		let max_y = (region.max.y.max(0.0) as u32).min(height);
+		for y in min_y..max_y {
+			let row = (y * width) as usize;
+			covered[row + min_x as usize..row + max_x as usize].fill(true);
+		}
+	}
+
+	for (pixel, covered) in image.data.chunks_exact_mut(PIXEL_SIZE).zip(covered) {
+		if !covered {
+			pixel.copy_from_slice(&color);
+		}
+	}
+}
+
+/// Creates a copy of the given image with the given transform applied
+pub(crate) fn transform(image: &Image, transform: TileTransform) -> Image {
+	let (width, height) = dimensions(image);
+	let (dest_width, dest_height) = if transform.swaps_dimensions() {
+		(height, width)
+	} else {
+		(width, height)
+	};
+
+	let mut transformed = new_filled(image, dest_width, dest_height, [0; 4]);
+	for y in 0..dest_height {
+		for x in 0..dest_width {
+			let (src_x, src_y) = transform.source_coords(x, y, width, height);
+			let src = ((src_y * width + src_x) as usize) * PIXEL_SIZE;
+			let dest = ((y * dest_width + x) as usize) * PIXEL_SIZE;
+			transformed.data[dest..dest + PIXEL_SIZE]
+				.copy_from_slice(&image.data[src..src + PIXEL_SIZE]);
+		}
+	}
+	transformed
+}
+
+/// Replaces every pixel exactly matching the given color with a fully transparent pixel
+pub(crate) fn color_key(image: &mut Image, color: [u8; 4]) {
+	for pixel in image.data.chunks_exact_mut(PIXEL_SIZE) {
+		if *pixel == color {
+			pixel.copy_from_slice(&[0; PIXEL_SIZE]);
+		}
+	}
+}
+
+/// Multiplies the color channels of every pixel by its alpha (rounding to the nearest value)
+pub(crate) fn premultiply_alpha(image: &mut Image) {
+	for pixel in image.data.chunks_exact_mut(PIXEL_SIZE) {
+		let alpha = pixel[3] as u32;
+		for channel in &mut pixel[..3] {
+			*channel = ((*channel as u32 * alpha + 127) / 255) as u8;
+		}
+	}
+}
+
+/// Creates a copy of the given image scaled up by the given (non-zero) integer factor
+///
+/// Linear filtering clamps to the edges of the image, so the result never blends in any
+/// pixels from outside of it.
+pub(crate) fn upscale(image: &Image, factor: u32, filter: ScaleFilter) -> Image {
+	let (src_width, src_height) = dimensions(image);
+	let (width, height) = (src_width * factor, src_height * factor);
+	let mut scaled = new_filled(image, width, height, [0; 4]);
+	let pixel = |x: u32, y: u32| {
+		let start = (y * src_width + x) as usize * PIXEL_SIZE;
+		&image.data[start..start + PIXEL_SIZE]
+	};
+	// Gets the two source pixels surrounding the given destination pixel (and their blend)
+	let sample = |dest: u32, size: u32| {
+		let src = ((dest as f32 + 0.5) / factor as f32 - 0.5).clamp(0.0, (size - 1) as f32);
+		let low = src.floor() as u32;
+		(low, (low + 1).min(size - 1), src - low as f32)
+	};
+
+	for y in 0..height {
+		for x in 0..width {
+			let start = (y * width + x) as usize * PIXEL_SIZE;
+			match filter {
+				ScaleFilter::Nearest => scaled.data[start..start + PIXEL_SIZE]
+					.copy_from_slice(pixel(x / factor, y / factor)),
+				ScaleFilter::Linear => {
+					let (x0, x1, tx) = sample(x, src_width);
+					let (y0, y1, ty) = sample(y, src_height);
+					for channel in 0..PIXEL_SIZE {
+						let lerp = |a: &[u8], b: &[u8], t: f32| {
+							a[channel] as f32 * (1.0 - t) + b[channel] as f32 * t
+						};
+						let top = lerp(pixel(x0, y0), pixel(x1, y0), tx);
+						let bottom = lerp(pixel(x0, y1), pixel(x1, y1), tx);
+						let value = top * (1.0 - ty) + bottom * ty;
+						scaled.data[start + channel] = value.round() as u8;
+					}
+				},
+			}
+		}
+	}
+	scaled
+}
+
+/// Creates a new image from the given region of an image
+///
+/// Returns `None` if the region is empty or not fully contained within the image.
+pub(crate) fn crop(image: &Image, x: u32, y: u32, width: u32, height: u32) -> Option<Image> {
+	let (src_width, src_height) = dimensions(image);
+	let is_outside =
+		|start: u32, len: u32, max: u32| start.checked_add(len).map_or(true, |end| end > max);
+	if width == 0
+		|| height == 0
+		|| is_outside(x, width, src_width)
+		|| is_outside(y, height, src_height)
+	{
+		return None;
+	}
+
+	let mut cropped = new_filled(image, width, height, [0; 4]);
+	let len = width as usize * PIXEL_SIZE;
+	for row in 0..height {
+		let src_start = (((y + row) * src_width) + x) as usize * PIXEL_SIZE;
+		let dest_start = (row * width) as usize * PIXEL_SIZE;
+		cropped.data[dest_start..dest_start + len]
+			.copy_from_slice(&image.data[src_start..src_start + len]);
+	}
+	Some(cropped)
+}
+
+/// Finds the smallest region of the given image containing every non-transparent pixel
+///
+/// Returns the position of the region's top-left corner and its size, or `None` if the image is
+/// fully transparent.
+pub(crate) fn opaque_bounds(image: &Image) -> Option<(UVec2, UVec2)> {
+	let (width, ..) = dimensions(image);
+	let (mut min, mut max) = (UVec2::MAX, UVec2::ZERO);
+	for (index, pixel) in image.data.chunks_exact(PIXEL_SIZE).enumerate() {
+		if pixel[3] != 0 {
+			let pos = UVec2::new(index as u32 % width, index as u32 / width);
+			min = min.min(pos);
+			max = max.max(pos);
+		}
+	}
+	(min.cmple(max).all()).then(|| (min, max - min + UVec2::ONE))
+}
+
+/// Computes the average color of the given image
+///
+/// The color channels are weighted by each pixel's alpha so that fully transparent pixels
+/// don't darken the result, while the alpha channel is a plain average.
+pub(crate) fn average_color(image: &Image) -> [u8; 4] {
+	average_pixels(image.data.chunks_exact(PIXEL_SIZE))
+}
+
+/// Computes the average color of the given region of an image
+///
+/// The region must be fully contained within the image.
+pub(crate) fn average_region_color(image: &Image, min: UVec2, size: UVec2) -> [u8; 4] {
+	let (width, ..) = dimensions(image);
+	let len = size.x as usize * PIXEL_SIZE;
+	average_pixels((min.y..min.y + size.y).flat_map(|y| {
+		let start = (y as usize * width as usize + min.x as usize) * PIXEL_SIZE;
+		image.data[start..start + len].chunks_exact(PIXEL_SIZE)
+	}))
+}
+
+/// Computes the average color of the given pixels, weighted by their alpha
+fn average_pixels<'a>(pixels: impl Iterator<Item = &'a [u8]>) -> [u8; 4] {
+	let (mut r, mut g, mut b, mut a) = (0u64, 0u64, 0u64, 0u64);
+	let mut count = 0u64;
+	for pixel in pixels {
+		let alpha = pixel[3] as u64;
+		r += pixel[0] as u64 * alpha;
+		g += pixel[1] as u64 * alpha;
+		b += pixel[2] as u64 * alpha;
+		a += alpha;
+		count += 1;
+	}
+
+	if count == 0 || a == 0 {
+		return [0; 4];
+	}
+
+	[
+		(r / a) as u8,
+		(g / a) as u8,
+		(b / a) as u8,
+		(a / count) as u8,
+	]
+}
+
+/// Creates a copy of the given image at half its size (rounded down, with a minimum of 1x1)
+///
+/// Each destination pixel is the average of the (up to) 2x2 block of source pixels it covers.
+pub(crate) fn downscale(image: &Image) -> Image {
+	let (src_width, src_height) = dimensions(image);
+	let (width, height) = ((src_width / 2).max(1), (src_height / 2).max(1));
+	let mut scaled = new_filled(image, width, height, [0; 4]);
+	let bounds = UVec2::new(src_width, src_height);
+	for y in 0..height {
+		for x in 0..width {
+			average_block(image, &mut scaled, UVec2::new(x, y), UVec2::ZERO, bounds);
+		}
+	}
+	scaled
+}
+
+/// Creates a copy of the given atlas texture at half its size, downscaling each region separately
+///
+/// Unlike [`downscale`], source pixels are only ever averaged with pixels from the same region,
+/// so tiles don't bleed into each other or into the padding around them. Every pixel not covered
+/// by a region is filled with the given color.
+///
+/// # Arguments
+///
+/// * `image`: The atlas texture to downscale
+/// * `regions`: The regions (in pixels) of the tiles within the texture
+/// * `color`: The color used for any pixels not covered by a region
+///
+/// returns: (Image, Vec<Rect>)
+///
+/// The returned regions are the given ones scaled to the downscaled texture (rounded outwards).
+pub(crate) fn downscale_regions(
+	image: &Image,
+	regions: &[Rect],
+	color: [u8; 4],
+) -> (Image, Vec<Rect>) {
+	let (src_width, src_height) = dimensions(image);
+	let (width, height) = ((src_width / 2).max(1), (src_height / 2).max(1));
+	let mut scaled = new_filled(image, width, height, color);
+	let mut scaled_regions = Vec::with_capacity(regions.len());
+	for region in regions {
+		let min = region.min.max(Vec2::ZERO).as_uvec2();
+		let max = region.max.as_uvec2().min(UVec2::new(src_width, src_height));
+		let dest_min = min / 2;
+		let dest_max = ((max + 1) / 2).min(UVec2::new(width, height));
+		for y in dest_min.y..dest_max.y {
+			for x in dest_min.x..dest_max.x {
+				average_block(image, &mut scaled, UVec2::new(x, y), min, max);
+			}
+		}
+		scaled_regions.push(Rect {
+			min: dest_min.as_vec2(),
+			max: dest_max.as_vec2(),
+		});
+	}
+	(scaled, scaled_regions)
+}
+
+/// Sets the given pixel of `dest` to the average of the 2x2 block of `src` pixels it covers
+///
+/// Only source pixels within `min..max` are included. If none are, `dest` is left untouched.
+fn average_block(src: &Image, dest: &mut Image, pixel: UVec2, min: UVec2, max: UVec2) {
+	let (src_width, _) = dimensions(src);
+	let (width, _) = dimensions(dest);
+	let mut sum = [0u32; PIXEL_SIZE];
+	let mut count = 0;
+	for src_y in (pixel.y * 2).max(min.y)..(pixel.y * 2 + 2).min(max.y) {
+		for src_x in (pixel.x * 2).max(min.x)..(pixel.x * 2 + 2).min(max.x) {
+			let start = (src_y * src_width + src_x) as usize * PIXEL_SIZE;
+			for (channel, value) in src.data[start..start + PIXEL_SIZE].iter().enumerate() {
+				sum[channel] += *value as u32;
+			}
+			count += 1;
+		}
+	}
+
+	if count == 0 {
+		return;
+	}
+
+	let start = (pixel.y * width + pixel.x) as usize * PIXEL_SIZE;
+	for (channel, value) in sum.iter().enumerate() {
+		dest.data[start + channel] = (value / count) as u8;
+	}
+}
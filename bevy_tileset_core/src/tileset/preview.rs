@@ -0,0 +1,154 @@
+//! Support for rendering individual tiles out of the atlas (e.g. for palettes and thumbnails)
+
+use bevy::prelude::{Assets, Image, TextureAtlas};
+use bevy_tile_atlas::TextureStore;
+
+use crate::prelude::{RawTileset, TileGroupId, TileId, Tileset};
+use crate::tileset::pixels;
+
+macro_rules! preview_index {
+	($tileset: ident, $name: ident) => {{
+		let group_id = $tileset.get_tile_group_id($name)?;
+		first_index($tileset.tile_indices.iter(), group_id)
+	}};
+}
+
+/// Gets the lowest atlas index belonging to the given tile group
+///
+/// This is the first frame of an animated tile and the first variant of variant and auto
+/// tiles, which keeps previews deterministic.
+fn first_index<'a>(
+	tile_indices: impl Iterator<Item = (&'a usize, &'a TileId)>,
+	group_id: &TileGroupId,
+) -> Option<usize> {
+	tile_indices
+		.filter(|(_, id)| id.group_id == *group_id)
+		.map(|(index, _)| *index)
+		.min()
+}
+
+/// Copies the texture at the given atlas index into a new image
+fn render_preview(atlas: &TextureAtlas, texture: &Image, index: usize) -> Option<Image> {
+	let rect = atlas.textures.get(index)?;
+	let size = rect.size();
+	pixels::crop(
+		texture,
+		rect.min.x as u32,
+		rect.min.y as u32,
+		size.x as u32,
+		size.y as u32,
+	)
+}
+
+impl RawTileset {
+	/// Renders a single tile into its own standalone image
+	///
+	/// For animated tiles, this is the first frame. For variant and auto tiles, this is the
+	/// first variant.
+	///
+	/// # Arguments
+	///
+	/// * `name`: The name of the tile
+	/// * `texture_store`: The store containing the atlas texture
+	///
+	/// returns: Option<Image>
+	///
+	pub fn render_tile_preview<TStore: TextureStore>(
+		&self,
+		name: &str,
+		texture_store: &TStore,
+	) -> Option<Image> {
+		let index = preview_index!(self, name)?;
+		let texture = texture_store.get(self.texture())?;
+		render_preview(self.atlas(), texture, index)
+	}
+}
+
+impl Tileset {
+	/// Renders a single tile into its own standalone image
+	///
+	/// For animated tiles, this is the first frame. For variant and auto tiles, this is the
+	/// first variant.
+	///
+	/// # Arguments
+	///
+	/// * `name`: The name of the tile
+	/// * `atlases`: The `TextureAtlas` assets containing this tileset's atlas
+	/// * `texture_store`: The store containing the atlas texture
+	///
+	/// returns: Option<Image>
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use bevy::prelude::*;
+	/// # use bevy_tileset_core::prelude::*;
+	///
+	/// fn create_thumbnail(
+	/// 	tileset: &Tileset,
+	/// 	atlases: &Assets<TextureAtlas>,
+	/// 	images: &mut Assets<Image>,
+	/// ) -> Option<Handle<Image>> {
+	/// 	let preview = tileset.render_tile_preview("Grass", atlases, images)?;
+	/// 	Some(images.add(preview))
+	/// }
+	/// ```
+	pub fn render_tile_preview<TStore: TextureStore>(
+		&self,
+		name: &str,
+		atlases: &Assets<TextureAtlas>,
+		texture_store: &TStore,
+	) -> Option<Image> {
+		let index = preview_index!(self, name)?;
+		let atlas = atlases.get(self.atlas())?;
+		let texture = texture_store.get(self.texture())?;
+		render_preview(atlas, texture, index)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use bevy_tileset_tiles::prelude::{AnimatedTileHandle, TileHandle};
+
+	use crate::prelude::TilesetBuilder;
+	use crate::test_utils::{solid_image, TestTextureStore};
+
+	use super::*;
+
+	#[test]
+	fn should_render_tile_preview() {
+		let mut store = TestTextureStore::default();
+		let mut builder = TilesetBuilder::default();
+
+		let handle = store.add(solid_image(16, 16, [255, 0, 0, 255]));
+		builder
+			.add_tile(TileHandle::new_standard("Red", handle), 0, &store)
+			.unwrap();
+		let frames = vec![
+			store.add(solid_image(16, 16, [0, 0, 255, 255])),
+			store.add(solid_image(16, 16, [0, 255, 0, 255])),
+		];
+		let tile = TileHandle::new_animated(
+			"Water",
+			AnimatedTileHandle {
+				speed: 1.0,
+				frames,
+				regions: Vec::new(),
+				..Default::default()
+			},
+		);
+		builder.add_tile(tile, 1, &store).unwrap();
+
+		let tileset = builder.build("Previews", 0, &mut store).unwrap();
+
+		let preview = tileset.render_tile_preview("Red", &store).unwrap();
+		assert_eq!(tileset.tile_size(), preview.size());
+		assert_eq!([255, 0, 0, 255], preview.data[..4]);
+
+		let preview = tileset.render_tile_preview("Water", &store).unwrap();
+		assert_eq!(tileset.tile_size(), preview.size());
+		assert_eq!([0, 0, 255, 255], preview.data[..4]);
+
+		assert!(tileset.render_tile_preview("Missing", &store).is_none());
+	}
+}
@@ -1,12 +1,204 @@
-use crate::prelude::{RawTileset, Tileset};
-use bevy::prelude::Assets;
+use crate::prelude::{RawTileset, TileGroupId, Tileset, TilesetError, TilesetLoadLimits};
+use crate::tileset::asset::{
+	build_tiles, load_tiles, LoadedTiles, ResolvedTextureStore, ResolverSource,
+};
+use bevy::prelude::{Assets, Image};
+use bevy::render::texture::CompressedImageFormats;
 use bevy::sprite::TextureAtlas;
+use bevy_tile_atlas::TextureStore;
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
 
 impl RawTileset {
+	/// Loads and builds a tileset from the bytes of its RON definition, without needing an
+	/// `AssetServer`
+	///
+	/// This performs the same steps as loading a tileset asset, which makes it useful for tests,
+	/// tools, and headless (e.g. server-side) validation.
+	///
+	/// # Arguments
+	///
+	/// * `def`: The bytes of the tileset definition (see [`TilesetDef`](crate::prelude::TilesetDef))
+	/// * `resolver`: A function that returns the bytes of the file at the given path (or `None` if
+	///   the file doesn't exist). This is used to read both tile definitions and textures.
+	/// * `texture_store`: The store the generated atlas texture is added to
+	///
+	/// returns: Result<RawTileset, TilesetError>
+	///
+	/// Since there is no tileset file, the tile definition paths in `def` are passed to the
+	/// resolver as-is (rather than relative to the tileset file).
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// # use bevy::prelude::*;
+	/// # use bevy_tileset_core::prelude::*;
+	/// fn validate(textures: &mut Assets<Image>) -> Result<(), TilesetError> {
+	/// 	let def = std::fs::read("assets/tilesets/my_tileset.ron").unwrap();
+	/// 	let tileset = RawTileset::from_ron_bytes(
+	/// 		&def,
+	/// 		|path| std::fs::read(std::path::Path::new("assets/tilesets").join(path)).ok(),
+	/// 		textures,
+	/// 	)?;
+	/// 	tileset.validate_names(&["Dirt", "Grass"]).unwrap();
+	/// 	Ok(())
+	/// }
+	/// ```
+	pub fn from_ron_bytes<TResolver, TStore>(
+		def: &[u8],
+		resolver: TResolver,
+		texture_store: &mut TStore,
+	) -> Result<RawTileset, TilesetError>
+	where
+		TResolver: Fn(&Path) -> Option<Vec<u8>>,
+		TStore: TextureStore,
+	{
+		let limits = TilesetLoadLimits::default();
+		Self::load_ron_bytes(def, PathBuf::new(), None, &limits, resolver, texture_store)
+	}
+
+	/// Loads and builds a tileset from the bytes of its RON definition, enforcing the given limits
+	///
+	/// This is the same as [`RawTileset::from_ron_bytes`], except the tileset is checked against
+	/// the given limits just like a tileset asset is (see [`TilesetLoadLimits`]).
+	///
+	/// # Arguments
+	///
+	/// * `def`: The bytes of the tileset definition (see [`TilesetDef`](crate::prelude::TilesetDef))
+	/// * `resolver`: A function that returns the bytes of the file at the given path (or `None` if
+	///   the file doesn't exist)
+	/// * `limits`: The limits to enforce
+	/// * `texture_store`: The store the generated atlas texture is added to
+	///
+	/// returns: Result<RawTileset, TilesetError>
+	///
+	pub fn from_ron_bytes_with_limits<TResolver, TStore>(
+		def: &[u8],
+		resolver: TResolver,
+		limits: &TilesetLoadLimits,
+		texture_store: &mut TStore,
+	) -> Result<RawTileset, TilesetError>
+	where
+		TResolver: Fn(&Path) -> Option<Vec<u8>>,
+		TStore: TextureStore,
+	{
+		Self::load_ron_bytes(def, PathBuf::new(), None, limits, resolver, texture_store)
+	}
+
+	/// Loads and builds only the given tiles of a tileset from the bytes of its RON definition
+	///
+	/// Tiles whose group ID isn't in `group_ids` are never read, so the resulting atlas only
+	/// contains the given tiles. Its indices are dense over those tiles, but every tile keeps its
+	/// original group ID.
+	///
+	/// See [`RawTileset::from_ron_bytes`] for details.
+	///
+	/// # Arguments
+	///
+	/// * `def`: The bytes of the tileset definition (see [`TilesetDef`](crate::prelude::TilesetDef))
+	/// * `group_ids`: The group IDs of the tiles to load
+	/// * `resolver`: A function that returns the bytes of the file at the given path (or `None` if
+	///   the file doesn't exist)
+	/// * `texture_store`: The store the generated atlas texture is added to
+	///
+	/// returns: Result<RawTileset, TilesetError>
+	///
+	pub fn from_ron_bytes_subset<TResolver, TStore>(
+		def: &[u8],
+		group_ids: &[TileGroupId],
+		resolver: TResolver,
+		texture_store: &mut TStore,
+	) -> Result<RawTileset, TilesetError>
+	where
+		TResolver: Fn(&Path) -> Option<Vec<u8>>,
+		TStore: TextureStore,
+	{
+		let subset = group_ids.iter().copied().collect::<BTreeSet<_>>();
+		let limits = TilesetLoadLimits::default();
+		let path = PathBuf::new();
+		Self::load_ron_bytes(def, path, Some(&subset), &limits, resolver, texture_store)
+	}
+
+	/// Loads and builds the tileset at the given path, reading every file with `std::fs`
+	///
+	/// Unlike loading a tileset asset, this blocks until the tileset is built and doesn't need
+	/// an `AssetServer` or any async runtime, which makes it useful for build scripts and
+	/// offline tools (such as baking tilesets ahead of time).
+	///
+	/// # Arguments
+	///
+	/// * `asset_root`: The directory that tile and texture paths are resolved from (usually the
+	///   `assets` directory)
+	/// * `path`: The path to the tileset definition, relative to `asset_root`
+	/// * `texture_store`: The store the generated atlas texture is added to
+	///
+	/// returns: Result<RawTileset, TilesetError>
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// # use bevy_tileset_core::prelude::*;
+	///
+	/// fn bake<TStore: TextureStore>(store: &mut TStore) -> Result<(), TilesetError> {
+	/// 	let tileset = RawTileset::load_blocking("assets", "tilesets/forest.ron", store)?;
+	/// 	let bytes = tileset.bake(store)?;
+	/// 	std::fs::write("assets/tilesets/forest.tileset.bin", bytes)?;
+	/// 	Ok(())
+	/// }
+	/// ```
+	pub fn load_blocking<TRoot: AsRef<Path>, TPath: AsRef<Path>, TStore: TextureStore>(
+		asset_root: TRoot,
+		path: TPath,
+		texture_store: &mut TStore,
+	) -> Result<RawTileset, TilesetError> {
+		let asset_root = asset_root.as_ref();
+		let path = path.as_ref();
+		let def = std::fs::read(asset_root.join(path))?;
+		let resolver = |file: &Path| std::fs::read(asset_root.join(file)).ok();
+		let (path, limits) = (path.to_path_buf(), TilesetLoadLimits::default());
+		Self::load_ron_bytes(&def, path, None, &limits, resolver, texture_store)
+	}
+
+	fn load_ron_bytes<TResolver, TStore>(
+		def: &[u8],
+		path: PathBuf,
+		subset: Option<&BTreeSet<TileGroupId>>,
+		limits: &TilesetLoadLimits,
+		resolver: TResolver,
+		texture_store: &mut TStore,
+	) -> Result<RawTileset, TilesetError>
+	where
+		TResolver: Fn(&Path) -> Option<Vec<u8>>,
+		TStore: TextureStore,
+	{
+		let source = ResolverSource { path, resolver };
+		let formats = CompressedImageFormats::all();
+		let LoadedTiles {
+			config,
+			tile_handles,
+			images,
+			report,
+			..
+		} = futures::executor::block_on(load_tiles(def, &source, formats, subset, limits))?;
+
+		let mut store = ResolvedTextureStore {
+			store: texture_store,
+			images,
+		};
+		let mut tileset = build_tiles(config, tile_handles, report, &mut store)?;
+		store.store_region_atlases(&mut tileset);
+		Ok(tileset)
+	}
+
 	/// Converts this raw tileset into a finalized tileset asset
 	pub fn into_asset(self, assets: &mut Assets<TextureAtlas>) -> Tileset {
 		let texture = self.atlas().texture.clone();
 		let atlas = assets.add(self.atlas);
+		let pages = self
+			.pages
+			.into_iter()
+			.map(|page| page.into_asset(assets))
+			.collect();
 
 		Tileset {
 			id: self.id,
@@ -17,9 +209,564 @@ impl RawTileset {
 			tile_ids: self.tile_ids,
 			tile_names: self.tile_names,
 			tile_handles: self.tile_handles,
+			group_handles: self.group_handles,
 			tile_indices: self.tile_indices,
+			tile_colors: self.tile_colors,
+			tile_sizes: self.tile_sizes,
+			categories: self.categories,
+			tile_trims: self.tile_trims,
+			processed_indices: self.processed_indices,
+			lod_textures: self.lod_textures,
+			load_warnings: self.load_warnings,
+			tile_sources: self.tile_sources,
+			dependency_paths: self.dependency_paths,
+			default_tile: self.default_tile,
+			grid_columns: self.grid_columns,
 			atlas,
 			texture,
+			pages,
 		}
 	}
 }
+
+impl Tileset {
+	/// Loads and builds the tileset at the given path, reading every file with `std::fs`
+	///
+	/// See [`RawTileset::load_blocking`] for details.
+	///
+	/// # Arguments
+	///
+	/// * `asset_root`: The directory that tile and texture paths are resolved from
+	/// * `path`: The path to the tileset definition, relative to `asset_root`
+	/// * `atlases`: The `TextureAtlas` assets to add the atlas to
+	/// * `images`: The `Image` assets to add the atlas texture to
+	///
+	/// returns: Result<Tileset, TilesetError>
+	///
+	pub fn load_blocking<TRoot: AsRef<Path>, TPath: AsRef<Path>>(
+		asset_root: TRoot,
+		path: TPath,
+		atlases: &mut Assets<TextureAtlas>,
+		images: &mut Assets<Image>,
+	) -> Result<Tileset, TilesetError> {
+		Ok(RawTileset::load_blocking(asset_root, path, images)?.into_asset(atlases))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::cell::RefCell;
+	use std::collections::HashMap;
+	use std::path::{Path, PathBuf};
+
+	use bevy::asset::{AddAsset, AssetPlugin, Assets};
+	use bevy::prelude::{App, Image, MinimalPlugins, TextureAtlas, Vec2};
+	use bevy_tile_atlas::TextureStore;
+
+	use crate::prelude::{RawTileset, TilesetError, TilesetLoadLimits};
+	use crate::test_utils::{TestTextureStore, PIXEL_PNG};
+
+	fn files() -> HashMap<PathBuf, Vec<u8>> {
+		let mut files = HashMap::new();
+		files.insert(
+			PathBuf::from("dirt.ron"),
+			br#"(name: "Dirt", tile: Standard("dirt.png"))"#.to_vec(),
+		);
+		files.insert(
+			PathBuf::from("grass.ron"),
+			br#"(name: "Grass", tile: Standard("grass.png"))"#.to_vec(),
+		);
+		files.insert(PathBuf::from("dirt.png"), PIXEL_PNG.to_vec());
+		files.insert(PathBuf::from("grass.png"), PIXEL_PNG.to_vec());
+		files
+	}
+
+	#[test]
+	fn should_load_from_ron_bytes() {
+		let files = files();
+		let mut store = TestTextureStore::default();
+		let def = br#"(name: Some("Headless"), id: 1, tiles: {0: "dirt.ron", 1: "grass.ron"})"#;
+
+		let tileset = RawTileset::from_ron_bytes(
+			def,
+			|path: &Path| files.get(path).cloned(),
+			&mut store,
+		)
+		.unwrap();
+		assert_eq!("Headless", tileset.name());
+		assert_eq!(Ok(()), tileset.validate_names(&["Dirt", "Grass"]));
+		assert_eq!(2, tileset.tile_count());
+	}
+
+	#[test]
+	fn should_enforce_limits_without_asset_server() {
+		let files = files();
+		let resolver = |path: &Path| files.get(path).cloned();
+		let mut store = TestTextureStore::default();
+		let def = br#"(id: 1, tiles: {0: "dirt.ron", 1: "grass.ron"})"#;
+
+		let limits = TilesetLoadLimits {
+			max_tiles: Some(1),
+			..Default::default()
+		};
+		let result = RawTileset::from_ron_bytes_with_limits(def, resolver, &limits, &mut store);
+		assert!(matches!(result, Err(TilesetError::TooManyTiles { count: 2, max: 1 })));
+
+		let limits = TilesetLoadLimits {
+			max_image_bytes: Some(16),
+			..Default::default()
+		};
+		let result = RawTileset::from_ron_bytes_with_limits(def, resolver, &limits, &mut store);
+		assert!(matches!(result, Err(TilesetError::ImageTooLarge { max: 16, .. })));
+
+		let limits = TilesetLoadLimits::default();
+		let result = RawTileset::from_ron_bytes_with_limits(def, resolver, &limits, &mut store);
+		assert_eq!(2, result.unwrap().tile_count());
+	}
+
+	#[test]
+	fn should_stop_reading_after_oversized_image() {
+		let mut files = files();
+		let big = br#"(name: "Big", tile: Standard("big.png"))"#;
+		files.insert(PathBuf::from("big.ron"), big.to_vec());
+		let mut bytes = PIXEL_PNG.to_vec();
+		bytes.resize(PIXEL_PNG.len() + 100, 0);
+		files.insert(PathBuf::from("big.png"), bytes);
+
+		let reads = RefCell::new(Vec::new());
+		let resolver = |path: &Path| {
+			reads.borrow_mut().push(path.to_path_buf());
+			files.get(path).cloned()
+		};
+		let limits = TilesetLoadLimits {
+			max_image_bytes: Some(128),
+			..Default::default()
+		};
+		let mut store = TestTextureStore::default();
+		let def = br#"(id: 1, tiles: {0: "grass.ron", 1: "big.ron", 2: "dirt.ron"})"#;
+		let result = RawTileset::from_ron_bytes_with_limits(def, resolver, &limits, &mut store);
+		assert!(matches!(
+			result,
+			Err(TilesetError::ImageTooLarge { ref path, max: 128, .. })
+				if path == Path::new("big.png")
+		));
+
+		// Images are read in order of their paths, so none are read after the oversized one
+		let images = reads
+			.into_inner()
+			.into_iter()
+			.filter(|path| path.extension().map_or(false, |ext| ext == "png"))
+			.collect::<Vec<_>>();
+		assert_eq!(vec![PathBuf::from("big.png")], images);
+	}
+
+	#[test]
+	fn should_error_on_missing_file() {
+		let mut files = files();
+		files.remove(Path::new("grass.png"));
+		let mut store = TestTextureStore::default();
+		let def = br#"(id: 1, tiles: {0: "dirt.ron", 1: "grass.ron"})"#;
+
+		let result = RawTileset::from_ron_bytes(
+			def,
+			|path: &Path| files.get(path).cloned(),
+			&mut store,
+		);
+		assert!(matches!(result, Err(TilesetError::AssetIoError(..))));
+	}
+
+	#[test]
+	fn should_load_textures_without_extensions() {
+		// Packed archives may store files under opaque keys rather than their original paths
+		let mut files = HashMap::new();
+		files.insert(
+			PathBuf::from("blob/0"),
+			br#"(name: "Dirt", tile: Standard("blob/1"))"#.to_vec(),
+		);
+		files.insert(PathBuf::from("blob/1"), PIXEL_PNG.to_vec());
+		let mut store = TestTextureStore::default();
+		let def = br#"(name: "Packed", id: 1, tiles: {0: "blob/0"})"#;
+
+		let tileset = RawTileset::from_ron_bytes(
+			def,
+			|path: &Path| files.get(path).cloned(),
+			&mut store,
+		)
+		.unwrap();
+		assert_eq!(Ok(()), tileset.validate_names(&["Dirt"]));
+	}
+
+	#[test]
+	fn should_check_sizes_before_decoding() {
+		let mut files = files();
+		// A PNG header of the correct size, but without any image data to decode
+		let mut header = PIXEL_PNG[..16].to_vec();
+		header.extend_from_slice(&16u32.to_be_bytes());
+		header.extend_from_slice(&16u32.to_be_bytes());
+		files.insert(PathBuf::from("grass.png"), header);
+		let mut store = TestTextureStore::default();
+
+		let def = br#"(id: 1, tile_size: (16.0, 16.0), tiles: {0: "dirt.ron", 1: "grass.ron"})"#;
+		let result = RawTileset::from_ron_bytes(
+			def,
+			|path: &Path| files.get(path).cloned(),
+			&mut store,
+		);
+		assert!(matches!(result, Err(TilesetError::ImageError(..))));
+
+		let def = br#"(
+			id: 1,
+			tile_size: (16.0, 16.0),
+			preflight_size_check: true,
+			tiles: {0: "dirt.ron", 1: "grass.ron"},
+		)"#;
+		let result = RawTileset::from_ron_bytes(
+			def,
+			|path: &Path| files.get(path).cloned(),
+			&mut store,
+		);
+		assert!(matches!(
+			result,
+			Err(TilesetError::InvalidTileSize { ref tile, .. }) if tile == "Dirt"
+		));
+	}
+
+	#[test]
+	fn should_skip_invalid_tiles() {
+		let mut files = files();
+		files.insert(
+			PathBuf::from("water.ron"),
+			br#"(name: "Water", tile: Standard("water.png"))"#.to_vec(),
+		);
+		let mut store = TestTextureStore::default();
+
+		let def = br#"(
+			id: 1,
+			skip_invalid_tiles: true,
+			tiles: {0: "dirt.ron", 1: "grass.ron", 2: "missing.ron", 3: "water.ron"},
+		)"#;
+		let tileset = RawTileset::from_ron_bytes(
+			def,
+			|path: &Path| files.get(path).cloned(),
+			&mut store,
+		)
+		.unwrap();
+		assert_eq!(2, tileset.tile_count());
+		assert_eq!(Ok(()), tileset.validate_names(&["Dirt", "Grass"]));
+		assert!(tileset.get_tile_group_id("Water").is_none());
+
+		let warnings = tileset.load_warnings();
+		assert_eq!(2, warnings.len());
+		assert!(warnings[0].starts_with("skipped tile 2 (missing.ron)"));
+		assert!(warnings[1].starts_with("skipped tile 3 (Water)"));
+
+		// Tiles that fail to be added to the atlas are skipped as well
+		files.insert(
+			PathBuf::from("water.ron"),
+			br#"(name: "Water", tile: Animated((frames: ["dirt.png"], start_frame: 3)))"#.to_vec(),
+		);
+		let def = br#"(id: 1, skip_invalid_tiles: true, tiles: {0: "dirt.ron", 3: "water.ron"})"#;
+		let tileset = RawTileset::from_ron_bytes(
+			def,
+			|path: &Path| files.get(path).cloned(),
+			&mut store,
+		)
+		.unwrap();
+		assert_eq!(Ok(()), tileset.validate_names(&["Dirt"]));
+		assert!(tileset.get_tile_group_id("Water").is_none());
+		assert_eq!(1, tileset.atlas().len());
+		let warnings = tileset.load_warnings();
+		assert_eq!(1, warnings.len());
+		assert!(warnings[0].starts_with("skipped tile 3 (Water)"));
+
+		// Tiles are not skipped by default
+		let def = br#"(id: 1, tiles: {0: "dirt.ron", 1: "grass.ron", 3: "water.ron"})"#;
+		let result = RawTileset::from_ron_bytes(
+			def,
+			|path: &Path| files.get(path).cloned(),
+			&mut store,
+		);
+		assert!(result.is_err());
+	}
+
+	#[test]
+	fn should_substitute_placeholder_for_missing_textures() {
+		let mut files = files();
+		files.remove(Path::new("grass.png"));
+		files.insert(PathBuf::from("dirt.png"), b"not a png".to_vec());
+		files.insert(
+			PathBuf::from("stone.ron"),
+			br#"(name: "Stone", tile: Standard("stone.png"))"#.to_vec(),
+		);
+		files.insert(PathBuf::from("stone.png"), PIXEL_PNG.to_vec());
+		let mut store = TestTextureStore::default();
+
+		let def = br#"(
+			id: 1,
+			tiles: {0: "dirt.ron", 1: "grass.ron", 2: "stone.ron"},
+			missing_texture_policy: Placeholder,
+		)"#;
+		let tileset = RawTileset::from_ron_bytes(
+			def,
+			|path: &Path| files.get(path).cloned(),
+			&mut store,
+		)
+		.unwrap();
+
+		// Both the unreadable and the undecodable texture are replaced (and still packed)
+		assert_eq!(3, tileset.tile_count());
+		assert!(tileset.load_warnings().is_empty());
+		let texture = store.get(tileset.texture()).unwrap();
+		let width = texture.texture_descriptor.size.width as usize;
+		for name in ["Dirt", "Grass"] {
+			let index = tileset.get_base_tile_index(name).unwrap();
+			let rect = tileset.atlas().textures[index];
+			assert_eq!(Vec2::ONE, rect.size());
+			let start = (rect.min.y as usize * width + rect.min.x as usize) * 4;
+			assert_eq!([255, 0, 255, 255], texture.data[start..start + 4]);
+		}
+
+		// Textures fail to load by default
+		let def = br#"(id: 1, tiles: {0: "dirt.ron", 1: "grass.ron", 2: "stone.ron"})"#;
+		let result = RawTileset::from_ron_bytes(
+			def,
+			|path: &Path| files.get(path).cloned(),
+			&mut store,
+		);
+		assert!(result.is_err());
+	}
+
+	#[test]
+	fn should_report_disabled_features() {
+		let mut files = files();
+		files.insert(
+			PathBuf::from("mossy.ron"),
+			br#"(name: "Mossy", tile: Variant([(tile: Standard("grass.png"))]))"#.to_vec(),
+		);
+		files.insert(
+			PathBuf::from("wall.ron"),
+			br#"(
+				name: "Wall",
+				tile: Auto([(rule: (n: Some(true)), variants: [(tile: Standard("dirt.png"))])]),
+			)"#
+			.to_vec(),
+		);
+		let mut store = TestTextureStore::default();
+		let mut load = |tile: &str| {
+			let def = format!(r#"(id: 1, tiles: {{0: "dirt.ron", 1: "{}"}})"#, tile);
+			RawTileset::from_ron_bytes(
+				def.as_bytes(),
+				|path: &Path| files.get(path).cloned(),
+				&mut store,
+			)
+		};
+
+		let result = load("mossy.ron");
+		if cfg!(feature = "variants") {
+			assert_eq!(2, result.unwrap().tile_count());
+		} else {
+			let err = result.unwrap_err();
+			assert!(matches!(err, TilesetError::FeatureDisabled { feature: "variants", .. }));
+			let message = r#"tile "Mossy" requires the "variants" feature to be enabled"#;
+			assert_eq!(message, err.to_string());
+		}
+
+		let result = load("wall.ron");
+		if cfg!(feature = "auto-tile") {
+			assert_eq!(2, result.unwrap().tile_count());
+		} else {
+			let err = result.unwrap_err();
+			assert!(matches!(err, TilesetError::FeatureDisabled { feature: "auto-tile", .. }));
+			let message = r#"tile "Wall" requires the "auto-tile" feature to be enabled"#;
+			assert_eq!(message, err.to_string());
+		}
+	}
+
+	#[test]
+	fn should_retain_tile_sources() {
+		let mut files = files();
+		let grass = "// Lush and green\n(tile: Standard(\"grass.png\"), name: \"Grass\")";
+		files.insert(PathBuf::from("grass.ron"), grass.as_bytes().to_vec());
+		let mut store = TestTextureStore::default();
+
+		let def = br#"(id: 1, retain_tile_sources: true, tiles: {0: "dirt.ron", 1: "grass.ron"})"#;
+		let tileset = RawTileset::from_ron_bytes(
+			def,
+			|path: &Path| files.get(path).cloned(),
+			&mut store,
+		)
+		.unwrap();
+		assert_eq!(Some(grass), tileset.tile_source("Grass"));
+		assert!(tileset.tile_source("Dirt").is_some());
+
+		// Sources are kept for the tiles of every atlas page
+		let glow = "(name: \"Glow\", tile: Standard(\"dirt.png\"), sampler: Some(PixelArt))";
+		files.insert(PathBuf::from("glow.ron"), glow.as_bytes().to_vec());
+		let def = br#"(id: 1, retain_tile_sources: true, tiles: {0: "dirt.ron", 1: "glow.ron"})"#;
+		let tileset = RawTileset::from_ron_bytes(
+			def,
+			|path: &Path| files.get(path).cloned(),
+			&mut store,
+		)
+		.unwrap();
+		assert_eq!(Some(1), tileset.tile_page("Glow"));
+		assert_eq!(Some(glow), tileset.tile_source("Glow"));
+
+		// Sources are not retained by default
+		let def = br#"(id: 1, tiles: {0: "dirt.ron", 1: "grass.ron"})"#;
+		let tileset = RawTileset::from_ron_bytes(
+			def,
+			|path: &Path| files.get(path).cloned(),
+			&mut store,
+		)
+		.unwrap();
+		assert_eq!(None, tileset.tile_source("Grass"));
+	}
+
+	#[test]
+	fn should_load_subset() {
+		let files = files();
+		let mut store = TestTextureStore::default();
+
+		let def = br#"(
+			id: 1,
+			tiles: {0: "dirt.ron", 1: "grass.ron"},
+			categories: {"Ground": [0, 1]},
+		)"#;
+		let tileset = RawTileset::from_ron_bytes_subset(
+			def,
+			&[1],
+			|path: &Path| files.get(path).cloned(),
+			&mut store,
+		)
+		.unwrap();
+		assert_eq!(1, tileset.tile_count());
+		assert_eq!(Some(0), tileset.get_base_tile_index("Grass"));
+		assert_eq!(Some(1), tileset.get_tile_id(&0).map(|id| id.group_id));
+		assert_eq!(None, tileset.get_tile_index("Dirt"));
+		assert_eq!(Some(&[1][..]), tileset.tiles_in_category("Ground"));
+	}
+
+	#[test]
+	fn should_load_categories() {
+		let mut app = App::new();
+		app.add_plugins((MinimalPlugins, AssetPlugin::default()))
+			.add_asset::<Image>()
+			.add_asset::<TextureAtlas>();
+		let files = files();
+		let mut store = TestTextureStore::default();
+
+		let def = br#"(
+			id: 1,
+			tiles: {0: "dirt.ron", 1: "grass.ron"},
+			categories: {"Terrain": [0, 1], "Decals": [1], "Props": []},
+		)"#;
+		let raw = RawTileset::from_ron_bytes(
+			def,
+			|path: &Path| files.get(path).cloned(),
+			&mut store,
+		)
+		.unwrap();
+		let tileset = raw.into_asset(&mut app.world.resource_mut::<Assets<TextureAtlas>>());
+
+		// Categories don't affect packing and a tile may belong to several of them
+		assert_eq!(2, tileset.tile_count());
+		assert_eq!(
+			vec!["Decals", "Props", "Terrain"],
+			tileset.categories().collect::<Vec<_>>()
+		);
+		assert_eq!(Some(&[0, 1][..]), tileset.tiles_in_category("Terrain"));
+		assert_eq!(Some(&[1][..]), tileset.tiles_in_category("Decals"));
+		assert_eq!(Some(&[][..]), tileset.tiles_in_category("Props"));
+		assert_eq!(None, tileset.tiles_in_category("Water"));
+		let grass = tileset.get_tile_group_id("Grass").copied();
+		assert_eq!(grass, tileset.tiles_in_category("Decals").map(|ids| ids[0]));
+	}
+
+	#[test]
+	fn should_fall_back_to_default_tile() {
+		let files = files();
+		let mut store = TestTextureStore::default();
+
+		let def = br#"(id: 1, tiles: {0: "dirt.ron", 1: "grass.ron"}, default_tile: Some(0))"#;
+		let tileset = RawTileset::from_ron_bytes_subset(
+			def,
+			&[1],
+			|path: &Path| files.get(path).cloned(),
+			&mut store,
+		)
+		.unwrap();
+		assert_eq!(2, tileset.tile_count());
+		assert_eq!(Some(0), tileset.default_tile());
+		assert_eq!(
+			tileset.get_tile_index("Grass"),
+			tileset.get_tile_index_or_default("Grass")
+		);
+		assert_eq!(
+			tileset.get_tile_index("Dirt"),
+			tileset.get_tile_index_or_default("Unknown")
+		);
+		assert_eq!(None, tileset.get_tile_index("Unknown"));
+
+		let def = br#"(id: 1, tiles: {0: "dirt.ron"}, default_tile: Some(5))"#;
+		let missing = RawTileset::from_ron_bytes(
+			def,
+			|path: &Path| files.get(path).cloned(),
+			&mut store,
+		);
+		assert!(matches!(missing, Err(TilesetError::MissingDefaultTile(5))));
+	}
+
+	#[test]
+	fn should_load_blocking() {
+		let root = std::env::temp_dir().join(format!("bevy_tileset_{}", std::process::id()));
+		std::fs::create_dir_all(root.join("tilesets")).unwrap();
+		std::fs::create_dir_all(root.join("tiles")).unwrap();
+		for (path, bytes) in files() {
+			std::fs::write(root.join("tiles").join(path), bytes).unwrap();
+		}
+		let def = br#"(id: 1, tiles: {0: "../tiles/dirt.ron"}, texture_root: "tiles")"#;
+		std::fs::write(root.join("tilesets/forest.ron"), def).unwrap();
+
+		let mut store = TestTextureStore::default();
+		let result = RawTileset::load_blocking(&root, "tilesets/forest.ron", &mut store);
+		let missing = RawTileset::load_blocking(&root, "tilesets/missing.ron", &mut store);
+		std::fs::remove_dir_all(&root).unwrap();
+
+		let tileset = result.unwrap();
+		assert_eq!("forest", tileset.name());
+		assert_eq!(Some(0), tileset.get_base_tile_index("Dirt"));
+		assert!(matches!(missing, Err(TilesetError::AssetIoError(..))));
+	}
+
+	#[test]
+	fn should_record_dependency_paths() {
+		let mut files = files();
+		files.insert(
+			PathBuf::from("tiles/dirt.ron"),
+			br#"(base: "../dirt.ron", name: "Dry Dirt")"#.to_vec(),
+		);
+		let mut store = TestTextureStore::default();
+
+		let def = br#"(id: 1, tiles: {0: "dirt.ron", 1: "grass.ron"})"#;
+		let tileset = RawTileset::from_ron_bytes_subset(
+			def,
+			&[0],
+			|path: &Path| files.get(path).cloned(),
+			&mut store,
+		)
+		.unwrap();
+		let expected = ["dirt.png", "dirt.ron"].map(PathBuf::from);
+		assert_eq!(&expected[..], tileset.dependency_paths());
+
+		let def = br#"(id: 1, tiles: {0: "tiles/dirt.ron"})"#;
+		let tileset = RawTileset::from_ron_bytes(
+			def,
+			|path: &Path| files.get(path).cloned(),
+			&mut store,
+		)
+		.unwrap();
+		let expected = ["dirt.png", "dirt.ron", "tiles/dirt.ron"].map(PathBuf::from);
+		assert_eq!(&expected[..], tileset.dependency_paths());
+	}
+}
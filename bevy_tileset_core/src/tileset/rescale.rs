@@ -0,0 +1,243 @@
+//! Support for rescaling a tileset to a different tile resolution
+//!
+//! This allows a single set of source art to back multiple resolutions (such as a low-res retro
+//! mode alongside a crisp high-res one). Each atlas region is scaled on its own, so the layout of
+//! the atlas (and every index into it) stays the same.
+
+use bevy::prelude::{Assets, Handle, Image, TextureAtlas};
+use bevy_tile_atlas::TextureStore;
+
+use crate::prelude::{RawTileset, Tileset, TilesetError};
+use crate::tileset::pixels;
+
+/// The filter used when rescaling a tileset
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+pub enum ScaleFilter {
+	/// Each pixel is repeated, keeping pixel art crisp
+	#[default]
+	Nearest,
+	/// Pixels are bilinearly interpolated
+	Linear,
+}
+
+/// Creates a copy of the given atlas (and its LOD textures) scaled by the given factor
+///
+/// Returns the new atlas along with the handles to its LOD textures.
+fn rescale_atlas<TStore: TextureStore>(
+	atlas: &TextureAtlas,
+	lod_levels: usize,
+	factor: u32,
+	filter: ScaleFilter,
+	texture_store: &mut TStore,
+) -> Result<(TextureAtlas, Vec<Handle<Image>>), TilesetError> {
+	if factor == 0 {
+		return Err(TilesetError::InvalidData {
+			expected: String::from("A non-zero scale factor"),
+			found: String::from("0"),
+		});
+	}
+
+	let texture = texture_store
+		.get(&atlas.texture)
+		.ok_or(TilesetError::ImageNotFound)?;
+	let (width, height) = pixels::dimensions(texture);
+	let (scaled_width, scaled_height) = width
+		.checked_mul(factor)
+		.zip(height.checked_mul(factor))
+		.ok_or(TilesetError::AtlasSizeOverflow { width, height })?;
+
+	// Regions are scaled individually so filtering never blends neighboring tiles together
+	let mut scaled = pixels::new_filled(texture, scaled_width, scaled_height, [0; 4]);
+	for (index, rect) in atlas.textures.iter().enumerate() {
+		let region = pixels::crop(
+			texture,
+			rect.min.x as u32,
+			rect.min.y as u32,
+			rect.width() as u32,
+			rect.height() as u32,
+		)
+		.ok_or(TilesetError::TileOutOfBounds {
+			index,
+			size: atlas.size,
+		})?;
+		let region = pixels::upscale(&region, factor, filter);
+		let min = rect.min * factor as f32;
+		pixels::blit(&region, &mut scaled, min.x as u32, min.y as u32);
+	}
+
+	let mut lod_textures = Vec::new();
+	let mut lod = scaled.clone();
+	for _ in 1..lod_levels {
+		lod = pixels::downscale(&lod);
+		lod_textures.push(texture_store.add(lod.clone()));
+	}
+
+	let texture = texture_store.add(scaled);
+	let mut rescaled = TextureAtlas::new_empty(texture, atlas.size * factor as f32);
+	rescaled.textures = atlas
+		.textures
+		.iter()
+		.map(|rect| {
+			let mut rect = *rect;
+			rect.min *= factor as f32;
+			rect.max *= factor as f32;
+			rect
+		})
+		.collect();
+	Ok((rescaled, lod_textures))
+}
+
+/// Scales the sizes stored on the given tileset by the given factor
+macro_rules! scale_sizes {
+	($tileset: ident, $factor: ident) => {
+		$tileset.size *= $factor as f32;
+		$tileset.tile_size *= $factor as f32;
+		for size in $tileset.tile_sizes.values_mut() {
+			*size *= $factor;
+		}
+	};
+}
+
+impl RawTileset {
+	/// Creates a copy of this tileset with every tile (and the atlas) scaled by the given factor
+	///
+	/// The new atlas texture (and any LOD textures) are added to the given store. Tiles keep
+	/// their atlas indices, so existing indices remain valid for the rescaled tileset. Every
+	/// [atlas page](Self::page) is rescaled as well.
+	///
+	/// # Arguments
+	///
+	/// * `factor`: The (non-zero) factor to scale by
+	/// * `filter`: The filter to scale with
+	/// * `texture_store`: The store containing this tileset's atlas texture
+	///
+	/// returns: Result<RawTileset, TilesetError>
+	///
+	pub fn rescale<TStore: TextureStore>(
+		&self,
+		factor: u32,
+		filter: ScaleFilter,
+		texture_store: &mut TStore,
+	) -> Result<RawTileset, TilesetError> {
+		let (atlas, lod_textures) =
+			rescale_atlas(self.atlas(), self.lod_levels(), factor, filter, texture_store)?;
+
+		let pages = self
+			.pages
+			.iter()
+			.map(|page| page.rescale(factor, filter, texture_store))
+			.collect::<Result<Vec<_>, _>>()?;
+
+		let mut tileset = self.clone();
+		scale_sizes!(tileset, factor);
+		tileset.atlas = atlas;
+		tileset.lod_textures = lod_textures;
+		tileset.pages = pages;
+		Ok(tileset)
+	}
+}
+
+impl Tileset {
+	/// Creates a copy of this tileset with every tile (and the atlas) scaled by the given factor
+	///
+	/// See [`RawTileset::rescale`] for details.
+	///
+	/// # Arguments
+	///
+	/// * `factor`: The (non-zero) factor to scale by
+	/// * `filter`: The filter to scale with
+	/// * `atlases`: The `TextureAtlas` assets containing this tileset's atlas
+	/// * `images`: The `Image` assets containing this tileset's atlas texture
+	///
+	/// returns: Result<Tileset, TilesetError>
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use bevy::prelude::*;
+	/// # use bevy_tileset_core::prelude::*;
+	///
+	/// fn high_res(
+	/// 	tileset: &Tileset,
+	/// 	atlases: &mut Assets<TextureAtlas>,
+	/// 	images: &mut Assets<Image>,
+	/// ) -> Result<Tileset, TilesetError> {
+	/// 	tileset.rescale(2, ScaleFilter::Nearest, atlases, images)
+	/// }
+	/// ```
+	pub fn rescale(
+		&self,
+		factor: u32,
+		filter: ScaleFilter,
+		atlases: &mut Assets<TextureAtlas>,
+		images: &mut Assets<Image>,
+	) -> Result<Tileset, TilesetError> {
+		let atlas = atlases.get(self.atlas()).ok_or(TilesetError::InvalidData {
+			expected: String::from("A loaded texture atlas"),
+			found: String::from("Missing texture atlas"),
+		})?;
+		let (atlas, lod_textures) =
+			rescale_atlas(atlas, self.lod_levels(), factor, filter, images)?;
+		let pages = self
+			.pages
+			.iter()
+			.map(|page| page.rescale(factor, filter, atlases, images))
+			.collect::<Result<Vec<_>, _>>()?;
+
+		let mut tileset = self.clone();
+		scale_sizes!(tileset, factor);
+		tileset.texture = atlas.texture.clone();
+		tileset.atlas = atlases.add(atlas);
+		tileset.lod_textures = lod_textures;
+		tileset.pages = pages;
+		Ok(tileset)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use bevy::prelude::{UVec2, Vec2};
+	use bevy_tile_atlas::TextureStore;
+	use bevy_tileset_tiles::prelude::TileHandle;
+
+	use crate::prelude::{ScaleFilter, TilesetBuilder, TilesetError};
+	use crate::test_utils::{solid_image, TestTextureStore};
+
+	#[test]
+	fn should_rescale_tileset() {
+		let mut store = TestTextureStore::default();
+		let mut builder = TilesetBuilder::default();
+		builder.lod_levels(2);
+		let grass = store.add(solid_image(16, 16, [0, 255, 0, 255]));
+		let dirt = store.add(solid_image(16, 16, [127, 63, 0, 255]));
+		builder
+			.add_tile(TileHandle::new_standard("Grass", grass), 0, &store)
+			.unwrap();
+		builder
+			.add_tile(TileHandle::new_standard("Dirt", dirt), 1, &store)
+			.unwrap();
+		let tileset = builder.build("Retro", 0, &mut store).unwrap();
+
+		let scaled = tileset.rescale(2, ScaleFilter::Nearest, &mut store).unwrap();
+		assert_eq!(tileset.tile_size() * 2.0, scaled.tile_size());
+		assert_eq!(tileset.atlas_dimensions() * 2, scaled.atlas_dimensions());
+		assert_eq!(Some(UVec2::splat(32)), scaled.tile_size_of("Dirt"));
+		assert_eq!(tileset.get_base_tile_index("Dirt"), scaled.get_base_tile_index("Dirt"));
+		assert_eq!(2, scaled.lod_levels());
+
+		let index = scaled.get_base_tile_index("Dirt").unwrap();
+		let rect = scaled.atlas().textures[index];
+		assert_eq!(tileset.atlas().textures[index].min * 2.0, rect.min);
+		assert_eq!(Vec2::splat(32.0), rect.size());
+
+		let texture = store.get(scaled.texture()).unwrap();
+		let width = texture.size().x as usize;
+		let pixel = ((rect.max.y as usize - 1) * width + rect.max.x as usize - 1) * 4;
+		assert_eq!([127, 63, 0, 255], texture.data[pixel..pixel + 4]);
+
+		assert!(matches!(
+			tileset.rescale(0, ScaleFilter::Linear, &mut store),
+			Err(TilesetError::InvalidData { .. })
+		));
+	}
+}
@@ -0,0 +1,157 @@
+use std::collections::HashMap;
+use std::path::{Component, Path, PathBuf};
+
+use bevy::asset::{AssetIo, AssetIoError, BoxedFuture, ChangeWatcher, Metadata};
+
+/// An `AssetIo` that reads from one of several named asset sources
+///
+/// Paths prefixed with the name of a registered source (such as `remote://tiles/grass.png`)
+/// are read from that source, with the prefix removed. All other paths are read from the
+/// default source.
+///
+/// This allows a tileset to read its textures from a different location than its definition
+/// files (see [`TilesetDef::texture_source`](crate::prelude::TilesetDef::texture_source)). To
+/// use it, replace the app's `AssetServer` before adding the `AssetPlugin`:
+///
+/// ```ignore
+/// let local = AssetPlugin::default().create_platform_default_asset_io();
+/// let asset_io = MultiSourceAssetIo::new(local).with_source("remote", RemoteAssetIo::new(url));
+/// app.insert_resource(AssetServer::new(asset_io));
+/// ```
+pub struct MultiSourceAssetIo {
+	default: Box<dyn AssetIo>,
+	sources: HashMap<String, Box<dyn AssetIo>>,
+}
+
+impl MultiSourceAssetIo {
+	/// Create a new `MultiSourceAssetIo` that reads unprefixed paths from the given `AssetIo`
+	pub fn new(default: impl AssetIo) -> Self {
+		Self {
+			default: Box::new(default),
+			sources: HashMap::new(),
+		}
+	}
+
+	/// Register an asset source
+	///
+	/// # Arguments
+	///
+	/// * `name`: The name of the source (used as the `name://` path prefix)
+	/// * `asset_io`: The `AssetIo` to read the source's paths from
+	///
+	/// returns: MultiSourceAssetIo
+	pub fn with_source(mut self, name: impl Into<String>, asset_io: impl AssetIo) -> Self {
+		self.sources.insert(name.into(), Box::new(asset_io));
+		self
+	}
+
+	/// Get the `AssetIo` for the given path, along with the path within that source
+	fn route<'a>(&self, path: &'a Path) -> (&dyn AssetIo, &'a Path) {
+		split_source(path)
+			.and_then(|(name, path)| Some((self.sources.get(name)?.as_ref(), path)))
+			.unwrap_or((self.default.as_ref(), path))
+	}
+}
+
+impl AssetIo for MultiSourceAssetIo {
+	fn load_path<'a>(&'a self, path: &'a Path) -> BoxedFuture<'a, Result<Vec<u8>, AssetIoError>> {
+		let (asset_io, path) = self.route(path);
+		asset_io.load_path(path)
+	}
+
+	fn read_directory(
+		&self,
+		path: &Path,
+	) -> Result<Box<dyn Iterator<Item = PathBuf>>, AssetIoError> {
+		let (asset_io, inner) = self.route(path);
+		let entries = asset_io.read_directory(inner)?;
+		match split_source(path) {
+			// Entries keep the source prefix so they can be read back through this `AssetIo`
+			Some((name, _)) if self.sources.contains_key(name) => {
+				let name = name.to_string();
+				let entries = entries.map(move |entry| with_source(&name, &entry));
+				Ok(Box::new(entries))
+			},
+			_ => Ok(entries),
+		}
+	}
+
+	fn get_metadata(&self, path: &Path) -> Result<Metadata, AssetIoError> {
+		let (asset_io, path) = self.route(path);
+		asset_io.get_metadata(path)
+	}
+
+	fn watch_path_for_changes(
+		&self,
+		to_watch: &Path,
+		to_reload: Option<PathBuf>,
+	) -> Result<(), AssetIoError> {
+		let (asset_io, path) = self.route(to_watch);
+		asset_io.watch_path_for_changes(path, to_reload)
+	}
+
+	fn watch_for_changes(&self, configuration: &ChangeWatcher) -> Result<(), AssetIoError> {
+		self.default.watch_for_changes(configuration)?;
+		for asset_io in self.sources.values() {
+			asset_io.watch_for_changes(configuration)?;
+		}
+		Ok(())
+	}
+}
+
+/// Splits the source name off of a path prefixed with `name://`
+///
+/// returns: The source name and the path within that source, or `None` if the path has no
+/// source prefix
+pub(crate) fn split_source(path: &Path) -> Option<(&str, &Path)> {
+	let mut components = path.components();
+	let Some(Component::Normal(first)) = components.next() else {
+		return None;
+	};
+	let name = first.to_str()?.strip_suffix(':')?;
+	(!name.is_empty()).then_some((name, components.as_path()))
+}
+
+/// Prefixes the given path with the name of an asset source
+pub(crate) fn with_source(name: &str, path: &Path) -> PathBuf {
+	PathBuf::from(format!("{}://{}", name, path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+	use bevy::asset::FileType;
+
+	use super::*;
+	use crate::test_utils::MemoryAssetIo;
+
+	#[test]
+	fn should_split_source_prefixes() {
+		assert_eq!(
+			Some(("remote", Path::new("tiles/grass.png"))),
+			split_source(Path::new("remote://tiles/grass.png"))
+		);
+		assert_eq!(None, split_source(Path::new("tiles/grass.png")));
+		assert_eq!(None, split_source(Path::new("://tiles/grass.png")));
+		assert_eq!(
+			Some(("remote", Path::new("tiles/grass.png"))),
+			split_source(&with_source("remote", Path::new("tiles/grass.png")))
+		);
+	}
+
+	#[test]
+	fn should_route_paths_to_sources() {
+		let local = MemoryAssetIo(HashMap::from([(PathBuf::from("a.png"), vec![1])]));
+		let remote = MemoryAssetIo(HashMap::from([(PathBuf::from("a.png"), vec![2])]));
+		let asset_io = MultiSourceAssetIo::new(local).with_source("remote", remote);
+
+		let read = |path: &str| futures::executor::block_on(asset_io.load_path(Path::new(path)));
+		assert_eq!(vec![1], read("a.png").unwrap());
+		assert_eq!(vec![2], read("remote://a.png").unwrap());
+		// Unregistered sources are read from the default source as-is
+		let result = read("other://a.png");
+		assert!(matches!(result, Err(AssetIoError::NotFound(..))));
+
+		let metadata = asset_io.get_metadata(Path::new("remote://a.png")).unwrap();
+		assert_eq!(FileType::File, metadata.file_type());
+	}
+}
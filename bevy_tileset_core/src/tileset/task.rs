@@ -0,0 +1,334 @@
+//! Support for packing tilesets on a background task
+//!
+//! Packing a large tileset at runtime (rather than in the asset loader) can stall the schedule
+//! for several frames. The methods here instead run it on the [`AsyncComputeTaskPool`], returning
+//! a [`Task`] that can be polled across frames. Since `Assets<Image>` can't be sent to another
+//! thread, the textures are moved in and out of the task with an [`OwnedTextureStore`].
+
+use std::collections::{HashMap, HashSet};
+
+use bevy::asset::HandleId;
+use bevy::prelude::{Assets, Handle, Image, TextureAtlas};
+use bevy::tasks::{AsyncComputeTaskPool, Task};
+use bevy_tile_atlas::TextureStore;
+use bevy_tileset_tiles::prelude::TileHandle;
+
+use crate::prelude::{RawTileset, ScaleFilter, Tileset, TilesetBuilder, TilesetError, TilesetId};
+
+/// A [`TextureStore`] that owns its textures, so that it can be moved onto a background task
+///
+/// Textures keep the ID of the handle they were stored with, so they can be moved back into
+/// the `Image` assets (see [`Self::insert_into`]) without invalidating any handle to them.
+#[derive(Debug, Default, Clone)]
+pub struct OwnedTextureStore {
+	images: HashMap<HandleId, Image>,
+	/// The IDs of the textures added to this store (rather than copied from the `Image` assets)
+	added: HashSet<HandleId>,
+}
+
+/// A tileset packed on a background task, along with the textures generated for it
+pub struct PackedTileset {
+	/// The packed tileset
+	pub tileset: RawTileset,
+	/// The store containing every texture the tileset was packed from (and the generated atlas
+	/// textures)
+	pub textures: OwnedTextureStore,
+}
+
+impl OwnedTextureStore {
+	/// Create a store containing a copy of each of the given textures
+	///
+	/// Handles to textures that aren't loaded are skipped.
+	///
+	/// # Arguments
+	///
+	/// * `images`: The `Image` assets to copy from
+	/// * `handles`: The handles of the textures to copy
+	///
+	/// returns: OwnedTextureStore
+	///
+	pub fn copy_from<'a>(
+		images: &Assets<Image>,
+		handles: impl IntoIterator<Item = &'a Handle<Image>>,
+	) -> Self {
+		let images = handles
+			.into_iter()
+			.filter_map(|handle| Some((handle.id(), images.get(handle)?.clone())))
+			.collect();
+		Self {
+			images,
+			added: HashSet::new(),
+		}
+	}
+
+	/// Create a store containing a copy of every texture of the given tiles
+	///
+	/// # Arguments
+	///
+	/// * `images`: The `Image` assets to copy from
+	/// * `tiles`: The tiles whose textures should be copied
+	///
+	/// returns: OwnedTextureStore
+	///
+	pub fn copy_tiles_from<'a>(
+		images: &Assets<Image>,
+		tiles: impl IntoIterator<Item = &'a TileHandle>,
+	) -> Self {
+		Self::copy_from(images, tiles.into_iter().flat_map(TileHandle::iter_handles))
+	}
+
+	/// Moves every texture added to this store (such as the generated atlas, LOD, and processed
+	/// textures) into the given `Image` assets
+	///
+	/// Each texture keeps its handle ID, so the handles stored on a tileset packed with this
+	/// store remain valid. Textures copied from the assets (see [`Self::copy_from`]) are left
+	/// untouched, so they aren't overwritten with a stale copy or reported as modified.
+	///
+	/// Since the handles given out by this store are weak, a strong handle is returned for each
+	/// moved texture. The textures are removed from the assets once these are dropped, so they
+	/// should be kept for as long as the textures are used ([`PackedTileset::into_asset`] swaps
+	/// them into the tileset).
+	///
+	/// # Arguments
+	///
+	/// * `images`: The `Image` assets to move the textures into
+	///
+	/// returns: HashMap<HandleId, Handle<Image>>
+	///
+	pub fn insert_into(mut self, images: &mut Assets<Image>) -> HashMap<HandleId, Handle<Image>> {
+		let mut handles = HashMap::with_capacity(self.added.len());
+		for id in self.added {
+			if let Some(image) = self.images.remove(&id) {
+				handles.insert(id, images.set(id, image));
+			}
+		}
+		handles
+	}
+}
+
+impl TextureStore for OwnedTextureStore {
+	fn add(&mut self, asset: Image) -> Handle<Image> {
+		let id = HandleId::random::<Image>();
+		self.images.insert(id, asset);
+		self.added.insert(id);
+		Handle::weak(id)
+	}
+
+	fn get<H: Into<HandleId>>(&self, handle: H) -> Option<&Image> {
+		self.images.get(&handle.into())
+	}
+}
+
+impl PackedTileset {
+	/// Moves the textures into the given `Image` assets and converts the tileset into an asset
+	///
+	/// The tileset holds strong handles to its generated textures, so they're removed from the
+	/// assets along with the tileset.
+	///
+	/// # Arguments
+	///
+	/// * `images`: The `Image` assets to move the textures into
+	/// * `atlases`: The `TextureAtlas` assets to add the atlas to
+	///
+	/// returns: Tileset
+	///
+	pub fn into_asset(
+		self,
+		images: &mut Assets<Image>,
+		atlases: &mut Assets<TextureAtlas>,
+	) -> Tileset {
+		let handles = self.textures.insert_into(images);
+		let mut tileset = self.tileset;
+		tileset.swap_handles(&handles);
+		tileset.into_asset(atlases)
+	}
+}
+
+impl TilesetBuilder {
+	/// Build the tileset on the [`AsyncComputeTaskPool`]
+	///
+	/// This is the same as [`Self::build`], except the atlas is packed on a background task
+	/// which can be polled across frames. The given store must contain every texture of the
+	/// added tiles (see [`OwnedTextureStore::copy_tiles_from`]).
+	///
+	/// # Arguments
+	///
+	/// * `name`: The name of the tileset
+	/// * `id`: The ID of the tileset
+	/// * `texture_store`: The store containing the textures of the added tiles
+	///
+	/// returns: Task<Result<PackedTileset, TilesetError>>
+	///
+	/// # Panics
+	///
+	/// Panics if the [`AsyncComputeTaskPool`] hasn't been initialized (which Bevy's
+	/// `TaskPoolPlugin` does).
+	pub fn build_task<TName: Into<String>>(
+		self,
+		name: TName,
+		id: TilesetId,
+		mut texture_store: OwnedTextureStore,
+	) -> Task<Result<PackedTileset, TilesetError>> {
+		let name = name.into();
+		AsyncComputeTaskPool::get().spawn(async move {
+			let tileset = self.build(name, id, &mut texture_store)?;
+			Ok(PackedTileset {
+				tileset,
+				textures: texture_store,
+			})
+		})
+	}
+}
+
+impl RawTileset {
+	/// Replaces the handles to this tileset's generated textures (including those of its pages)
+	/// with the given handles of the same ID
+	fn swap_handles(&mut self, handles: &HashMap<HandleId, Handle<Image>>) {
+		let swap = |handle: &mut Handle<Image>| {
+			if let Some(strong) = handles.get(&handle.id()) {
+				*handle = strong.clone();
+			}
+		};
+		swap(&mut self.atlas.texture);
+		self.lod_textures.iter_mut().for_each(swap);
+		for page in &mut self.pages {
+			page.swap_handles(handles);
+		}
+	}
+
+	/// Rescale a copy of this tileset on the [`AsyncComputeTaskPool`]
+	///
+	/// This is the same as [`Self::rescale`], except the atlas is scaled on a background task
+	/// which can be polled across frames. The given store must contain the atlas texture of this
+	/// tileset and every one of its [pages](Self::page).
+	///
+	/// # Arguments
+	///
+	/// * `factor`: The (non-zero) factor to scale by
+	/// * `filter`: The filter to scale with
+	/// * `texture_store`: The store containing this tileset's atlas textures
+	///
+	/// returns: Task<Result<PackedTileset, TilesetError>>
+	///
+	/// # Panics
+	///
+	/// Panics if the [`AsyncComputeTaskPool`] hasn't been initialized (which Bevy's
+	/// `TaskPoolPlugin` does).
+	pub fn rescale_task(
+		&self,
+		factor: u32,
+		filter: ScaleFilter,
+		mut texture_store: OwnedTextureStore,
+	) -> Task<Result<PackedTileset, TilesetError>> {
+		let tileset = self.clone();
+		AsyncComputeTaskPool::get().spawn(async move {
+			let tileset = tileset.rescale(factor, filter, &mut texture_store)?;
+			Ok(PackedTileset {
+				tileset,
+				textures: texture_store,
+			})
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use bevy::asset::{AddAsset, AssetPlugin};
+	use bevy::prelude::{App, MinimalPlugins, Mut, Vec2};
+	use bevy::tasks::TaskPool;
+
+	use super::*;
+	use crate::test_utils::solid_image;
+
+	#[test]
+	fn should_pack_on_background_task() {
+		AsyncComputeTaskPool::init(TaskPool::default);
+
+		let mut store = OwnedTextureStore::default();
+		let mut builder = TilesetBuilder::default();
+		for (group_id, name) in ["Grass", "Dirt"].into_iter().enumerate() {
+			let handle = store.add(solid_image(16, 16, [255; 4]));
+			builder
+				.add_tile(TileHandle::new_standard(name, handle), group_id as u32, &store)
+				.unwrap();
+		}
+
+		let task = builder.build_task("Background", 0, store);
+		let packed = futures::executor::block_on(task).unwrap();
+		assert_eq!(2, packed.tileset.tile_count());
+		assert_eq!(Some(1), packed.tileset.get_base_tile_index("Dirt"));
+		assert!(packed.textures.get(packed.tileset.texture()).is_some());
+
+		let task = packed.tileset.rescale_task(2, ScaleFilter::Nearest, packed.textures);
+		let scaled = futures::executor::block_on(task).unwrap();
+		assert_eq!(Vec2::splat(32.0), scaled.tileset.tile_size());
+		assert!(scaled.textures.get(scaled.tileset.texture()).is_some());
+	}
+
+	#[test]
+	fn should_only_insert_added_textures() {
+		let mut app = App::new();
+		app.add_plugins((MinimalPlugins, AssetPlugin::default()))
+			.add_asset::<Image>();
+
+		let mut images = app.world.resource_mut::<Assets<Image>>();
+		let grass = images.add(solid_image(16, 16, [255; 4]));
+		let store = OwnedTextureStore::copy_from(&images, [&grass]);
+		let mut builder = TilesetBuilder::default();
+		builder
+			.add_tile(TileHandle::new_standard("Grass", grass.clone()), 0, &store)
+			.unwrap();
+
+		let task = builder.build_task("Background", 0, store);
+		let packed = futures::executor::block_on(task).unwrap();
+
+		// The source texture is edited while the tileset is being packed
+		*images.get_mut(&grass).unwrap() = solid_image(16, 16, [0, 255, 0, 255]);
+		let atlas = packed.tileset.texture().clone();
+		packed.textures.insert_into(&mut images);
+
+		assert!(images.get(&atlas).is_some());
+		assert_eq!([0, 255, 0, 255], images.get(&grass).unwrap().data[..4]);
+	}
+
+	#[test]
+	fn should_remove_textures_with_tileset() {
+		AsyncComputeTaskPool::init(TaskPool::default);
+		let mut app = App::new();
+		app.add_plugins((MinimalPlugins, AssetPlugin::default()))
+			.add_asset::<Image>()
+			.add_asset::<TextureAtlas>();
+
+		let mut store = OwnedTextureStore::default();
+		let mut builder = TilesetBuilder::default();
+		builder.lod_levels(2);
+		let handle = store.add(solid_image(16, 16, [255; 4]));
+		builder
+			.add_tile(TileHandle::new_standard("Grass", handle), 0, &store)
+			.unwrap();
+		let task = builder.build_task("Background", 0, store);
+		let packed = futures::executor::block_on(task).unwrap();
+
+		let tileset = app
+			.world
+			.resource_scope(|world, mut images: Mut<Assets<Image>>| {
+				let mut atlases = world.resource_mut::<Assets<TextureAtlas>>();
+				packed.into_asset(&mut images, &mut atlases)
+			});
+		let atlas = tileset.texture().id();
+		let lod = tileset.atlas_for_lod(1).unwrap().id();
+		app.update();
+		let images = app.world.resource::<Assets<Image>>();
+		assert!(images.get(atlas).is_some());
+		assert!(images.get(lod).is_some());
+
+		// The atlas texture is only freed once the `TextureAtlas` asset holding it is freed
+		drop(tileset);
+		for _ in 0..3 {
+			app.update();
+		}
+		let images = app.world.resource::<Assets<Image>>();
+		assert!(images.get(atlas).is_none());
+		assert!(images.get(lod).is_none());
+	}
+}
@@ -1,28 +1,45 @@
 use bevy_tileset_tiles::prelude::*;
 
 /// A structure defining the index or indexes into the `TextureAtlas`
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub enum TileIndex {
 	/// Index for a standard tile
 	Standard(usize),
 	/// Indexes for an animated tile.
 	///
 	/// Takes the form (start, end, speed)
+	///
+	/// This doesn't include the direction or start frame of the animation, so it always
+	/// describes an animation playing forwards from `start`. To respect them, use the tile's
+	/// [`AnimatedTileData`](bevy_tileset_tiles::prelude::AnimatedTileData) (such as through
+	/// [`TileAnimationState`](crate::prelude::TileAnimationState)) instead.
 	Animated(usize, usize, f32),
+	/// Index for a [`TileType::Empty`] tile
+	///
+	/// Empty tiles don't occupy any space in the atlas, so there's no texture to index. Map code
+	/// should skip rendering these tiles.
+	Empty,
 }
 
 impl TileIndex {
 	/// Get the base index
 	///
 	/// This is the regular index for [`TileIndex::Standard`] and the start index
-	/// for [`TileIndex::Animated`]
+	/// for [`TileIndex::Animated`]. Returns `None` for [`TileIndex::Empty`], since empty
+	/// tiles have no texture in the atlas.
 	///
-	pub fn base_index(&self) -> &usize {
+	pub fn base_index(&self) -> Option<&usize> {
 		match self {
-			Self::Standard(idx) => idx,
-			Self::Animated(idx, ..) => idx,
+			Self::Standard(idx) => Some(idx),
+			Self::Animated(idx, ..) => Some(idx),
+			Self::Empty => None,
 		}
 	}
+
+	/// Checks if this is the index of an empty tile (see [`TileIndex::Empty`])
+	pub fn is_empty(&self) -> bool {
+		matches!(self, Self::Empty)
+	}
 }
 
 impl From<AnimatedTileData> for TileIndex {
@@ -0,0 +1,446 @@
+//! Support for importing tilesets authored in [Tiled](https://www.mapeditor.org/)
+//!
+//! Tiled stores its tilesets as `.tsx` XML files, either slicing a single image into a grid of
+//! tiles or collecting one image per tile. Each Tiled tile ID becomes the tile's group ID, so
+//! maps authored in Tiled can reference tiles by the same IDs.
+
+use std::collections::btree_map::Entry;
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::str::FromStr;
+
+use bevy::math::Rect;
+use bevy::prelude::Vec2;
+use bevy_tile_atlas::TextureStore;
+use bevy_tileset_tiles::prelude::{TileCollision, TileDef, TileDefType, TileHandle, DEFAULT_PIVOT};
+use roxmltree::{Document, Node};
+
+use crate::prelude::{
+	load_tile_handles, RawTileset, TextureLoader, TileGroupId, TilesetBuilder, TilesetError,
+	TilesetId, TilesetLoadLimits,
+};
+use crate::tileset::asset::checked_path;
+
+/// A tileset imported from a Tiled `.tsx` file
+///
+/// Tiles are named after their `name` property if they have one. Otherwise, tiles sliced from
+/// a single image are named `"{tileset name} {id}"`, while tiles with their own image are named
+/// after that image's file stem.
+///
+/// The tile's class (or type) becomes one of its tags, as does every other custom property
+/// (formatted as `"{name}={value}"`), except for these, which are mapped to the matching
+/// [`TileDef`] fields:
+///
+/// * `name`: The name of the tile
+/// * `z_offset`: The tile's z-offset
+/// * `layer`: The tile's layer
+///
+/// Tiled animations and collision shapes aren't imported.
+#[derive(Debug, Clone)]
+pub struct TiledTileset {
+	/// The name of the tileset
+	pub name: String,
+	/// The size of every tile (in pixels), as given by the tileset's `tilewidth` and `tileheight`
+	///
+	/// For tilesets with one image per tile, Tiled sets these to the size of its largest image,
+	/// so individual images may be smaller.
+	pub tile_size: Vec2,
+	/// The number of columns of the tileset image (if the tiles are sliced from a single image)
+	pub columns: Option<u32>,
+	/// The tiles mapped by their Tiled tile ID
+	pub tiles: BTreeMap<TileGroupId, TileDef>,
+}
+
+impl TiledTileset {
+	/// Parse the contents of a Tiled `.tsx` file
+	///
+	/// # Arguments
+	///
+	/// * `tsx`: The XML contents of the `.tsx` file
+	/// * `directory`: The directory containing the `.tsx` file (relative to the asset root),
+	///   which image paths are resolved against (without escaping the asset root)
+	/// * `limits`: The limits to enforce while parsing (only `max_tiles` applies, since no
+	///   images are read)
+	///
+	/// returns: Result<TiledTileset, TilesetError>
+	///
+	pub fn from_tsx<TPath: AsRef<Path>>(
+		tsx: &str,
+		directory: TPath,
+		limits: &TilesetLoadLimits,
+	) -> Result<TiledTileset, TilesetError> {
+		let document = Document::parse(tsx)
+			.map_err(|err| TilesetError::InvalidTiledTileset(err.to_string()))?;
+		let root = document.root_element();
+		if !root.has_tag_name("tileset") {
+			return Err(TilesetError::InvalidTiledTileset(format!(
+				"expected a <tileset> element, found <{}>",
+				root.tag_name().name()
+			)));
+		}
+
+		let name = root.attribute("name").unwrap_or_default().to_string();
+		let tile_width = parse_attribute::<u32>(root, "tilewidth")?;
+		let tile_height = parse_attribute::<u32>(root, "tileheight")?;
+		let tile_size = Vec2::new(tile_width as f32, tile_height as f32);
+		let resolve = |node: Node| -> Result<String, TilesetError> {
+			let source = required_attribute(node, "source")?;
+			let path = checked_path(&directory.as_ref().join(source))?;
+			Ok(path.to_string_lossy().replace('\\', "/"))
+		};
+
+		// === Single Image === //
+		let mut tiles = BTreeMap::new();
+		let mut columns = None;
+		if let Some(image) = child(root, "image") {
+			let atlas = resolve(image)?;
+			let spacing = optional_attribute::<u32>(root, "spacing")?.unwrap_or_default();
+			let margin = optional_attribute::<u32>(root, "margin")?.unwrap_or_default();
+			let column_count = parse_attribute::<u32>(root, "columns")?.max(1);
+			let tile_count = parse_attribute::<TileGroupId>(root, "tilecount")?;
+			limits.check_tiles(tile_count as u64)?;
+			// The offset of the given column or row within the image
+			let offset = |index: u32, size: u32| -> Option<f32> {
+				let offset = size.checked_add(spacing)?.checked_mul(index)?;
+				Some(offset.checked_add(margin)? as f32)
+			};
+			for id in 0..tile_count {
+				let (column, row) = (id % column_count, id / column_count);
+				let (x, y) = offset(column, tile_width)
+					.zip(offset(row, tile_height))
+					.ok_or_else(|| {
+						TilesetError::InvalidTiledTileset(format!(
+							"tile {} would be positioned past the maximum image size",
+							id
+						))
+					})?;
+				let min = Vec2::new(x, y);
+				let tile = TileDefType::AtlasRegion {
+					atlas: atlas.clone(),
+					rect: Rect::from_corners(min, min + tile_size),
+				};
+				tiles.insert(id, tile_def(format!("{} {}", name, id), tile));
+			}
+			columns = Some(column_count);
+		}
+
+		// === Tiles === //
+		for node in root.children().filter(|node| node.has_tag_name("tile")) {
+			let id = parse_attribute::<TileGroupId>(node, "id")?;
+			let count = tiles.len() as u64;
+			let tile = match tiles.entry(id) {
+				Entry::Occupied(entry) => entry.into_mut(),
+				Entry::Vacant(entry) => {
+					limits.check_tiles(count + 1)?;
+					let image = child(node, "image").ok_or_else(|| {
+						TilesetError::InvalidTiledTileset(format!("tile {} has no image", id))
+					})?;
+					let path = resolve(image)?;
+					let name = Path::new(&path)
+						.file_stem()
+						.and_then(|stem| stem.to_str())
+						.map(String::from)
+						.unwrap_or_else(|| format!("{} {}", name, id));
+					entry.insert(tile_def(name, TileDefType::Standard(path)))
+				},
+			};
+			if let Some(class) = node.attribute("class").or_else(|| node.attribute("type")) {
+				tile.tags.push(class.to_string());
+			}
+			apply_properties(node, tile)?;
+		}
+
+		Ok(TiledTileset {
+			name,
+			tile_size,
+			columns,
+			tiles,
+		})
+	}
+
+	/// Load the handles of every tile in this tileset
+	///
+	/// # Arguments
+	///
+	/// * `loader`: The loader to load the tile textures with (such as the `AssetServer`)
+	///
+	/// returns: Vec<(TileGroupId, TileHandle)>
+	///
+	pub fn tile_handles<TLoader: TextureLoader>(
+		&self,
+		loader: &TLoader,
+	) -> Vec<(TileGroupId, TileHandle)> {
+		let handles = load_tile_handles(self.tiles.values().cloned(), loader);
+		self.tiles.keys().copied().zip(handles).collect()
+	}
+
+	/// Build a tileset from the given tile handles (see [`Self::tile_handles`])
+	///
+	/// Every texture must already be loaded. Tilesets sliced from a single image use that image
+	/// as their atlas texture (see [`TileDefType::AtlasRegion`]) and keep its grid layout, so
+	/// each tile's atlas index matches its Tiled tile ID as long as every tile is given in order
+	/// of their IDs (as returned by [`Self::tile_handles`]).
+	///
+	/// # Arguments
+	///
+	/// * `id`: The ID of the tileset
+	/// * `tile_handles`: The handles of the tiles to add
+	/// * `texture_store`: The store containing the tile textures
+	///
+	/// returns: Result<RawTileset, TilesetError>
+	///
+	pub fn build<TStore: TextureStore>(
+		&self,
+		id: TilesetId,
+		tile_handles: Vec<(TileGroupId, TileHandle)>,
+		texture_store: &mut TStore,
+	) -> Result<RawTileset, TilesetError> {
+		let mut builder = TilesetBuilder::with_capacity(tile_handles.len());
+		if self.columns.is_some() {
+			builder
+				.tile_size(Some(self.tile_size))
+				.grid_columns(self.columns);
+		}
+		for (group_id, tile_handle) in tile_handles {
+			builder.add_tile(tile_handle, group_id, texture_store)?;
+		}
+		builder.build(self.name.clone(), id, texture_store)
+	}
+}
+
+/// Creates a tile definition with the given name and type (and no other metadata)
+fn tile_def(name: String, tile: TileDefType) -> TileDef {
+	TileDef {
+		name,
+		tile,
+		transform: None,
+		color_key: None,
+		collision: TileCollision::None,
+		tags: Vec::new(),
+		z_offset: 0.0,
+		layer: 0,
+		pivot: DEFAULT_PIVOT,
+		aliases: Vec::new(),
+		sampler: None,
+	}
+}
+
+/// Applies the custom properties of the given `<tile>` element to its tile definition
+fn apply_properties(node: Node, tile: &mut TileDef) -> Result<(), TilesetError> {
+	let Some(properties) = child(node, "properties") else {
+		return Ok(());
+	};
+
+	for property in properties
+		.children()
+		.filter(|node| node.has_tag_name("property"))
+	{
+		let name = required_attribute(property, "name")?;
+		// Multi-line string properties store their value as text instead
+		let value = property
+			.attribute("value")
+			.or_else(|| property.text())
+			.unwrap_or_default();
+		match name {
+			"name" => tile.name = value.to_string(),
+			"z_offset" => tile.z_offset = parse_attribute(property, "value")?,
+			"layer" => tile.layer = parse_attribute(property, "value")?,
+			_ => tile.tags.push(format!("{}={}", name, value)),
+		}
+	}
+	Ok(())
+}
+
+/// Gets the first child element of the given node with the given tag name
+fn child<'a, 'input>(node: Node<'a, 'input>, tag: &str) -> Option<Node<'a, 'input>> {
+	node.children().find(|child| child.has_tag_name(tag))
+}
+
+/// Gets an attribute of the given element, erroring if it's missing
+fn required_attribute<'a>(node: Node<'a, '_>, name: &str) -> Result<&'a str, TilesetError> {
+	node.attribute(name).ok_or_else(|| {
+		TilesetError::InvalidTiledTileset(format!(
+			"<{}> is missing the {:?} attribute",
+			node.tag_name().name(),
+			name
+		))
+	})
+}
+
+/// Parses an attribute of the given element, erroring if it's missing or invalid
+fn parse_attribute<T: FromStr>(node: Node, name: &str) -> Result<T, TilesetError> {
+	optional_attribute(node, name)?.ok_or_else(|| {
+		TilesetError::InvalidTiledTileset(format!(
+			"<{}> is missing the {:?} attribute",
+			node.tag_name().name(),
+			name
+		))
+	})
+}
+
+/// Parses an attribute of the given element (if it has one), erroring if it's invalid
+fn optional_attribute<T: FromStr>(node: Node, name: &str) -> Result<Option<T>, TilesetError> {
+	let Some(value) = node.attribute(name) else {
+		return Ok(None);
+	};
+	value.trim().parse().map(Some).map_err(|_| {
+		TilesetError::InvalidTiledTileset(format!(
+			"<{}> has an invalid {:?} attribute: {:?}",
+			node.tag_name().name(),
+			name,
+			value
+		))
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use bevy::asset::AssetPath;
+	use bevy::prelude::{Handle, Image};
+
+	use super::*;
+	use crate::prelude::TileIndex;
+	use crate::test_utils::{solid_image, TestTextureStore};
+
+	#[test]
+	fn should_import_image_collection() {
+		let tsx = r#"<?xml version="1.0" encoding="UTF-8"?>
+<tileset version="1.10" name="Terrain" tilewidth="16" tileheight="16" tilecount="2" columns="0">
+ <tile id="3" class="ground">
+  <properties>
+   <property name="name" value="Grass"/>
+   <property name="walkable" type="bool" value="true"/>
+   <property name="layer" type="int" value="2"/>
+  </properties>
+  <image width="16" height="16" source="../textures/grass.png"/>
+ </tile>
+ <tile id="7">
+  <properties>
+   <property name="z_offset" type="float" value="0.5"/>
+   <property name="note">Slippery
+when wet</property>
+  </properties>
+  <image width="16" height="16" source="ice.png"/>
+ </tile>
+</tileset>"#;
+
+		let tileset =
+			TiledTileset::from_tsx(tsx, "tilesets", &TilesetLoadLimits::default()).unwrap();
+		assert_eq!("Terrain", tileset.name);
+		assert_eq!(None, tileset.columns);
+		assert_eq!(vec![3, 7], tileset.tiles.keys().copied().collect::<Vec<_>>());
+
+		let grass = &tileset.tiles[&3];
+		assert_eq!("Grass", grass.name);
+		assert_eq!(vec!["ground", "walkable=true"], grass.tags);
+		assert_eq!(2, grass.layer);
+		assert!(matches!(
+			&grass.tile,
+			TileDefType::Standard(path) if path == "textures/grass.png"
+		));
+
+		let ice = &tileset.tiles[&7];
+		assert_eq!("ice", ice.name);
+		assert_eq!(0.5, ice.z_offset);
+		assert_eq!(vec!["note=Slippery\nwhen wet"], ice.tags);
+	}
+
+	#[test]
+	fn should_import_single_image() {
+		let tsx = r#"<tileset name="Dungeon" tilewidth="8" tileheight="8" spacing="2" margin="1"
+	tilecount="4" columns="2">
+ <image source="dungeon.png" width="19" height="19"/>
+ <tile id="3">
+  <properties>
+   <property name="name" value="Door"/>
+  </properties>
+ </tile>
+</tileset>"#;
+
+		let tileset = TiledTileset::from_tsx(tsx, "", &TilesetLoadLimits::default()).unwrap();
+		assert_eq!(Some(2), tileset.columns);
+		assert_eq!(Vec2::splat(8.0), tileset.tile_size);
+		assert_eq!(4, tileset.tiles.len());
+		assert_eq!("Dungeon 0", tileset.tiles[&0].name);
+
+		let door = &tileset.tiles[&3];
+		assert_eq!("Door", door.name);
+		assert!(matches!(
+			&door.tile,
+			TileDefType::AtlasRegion { atlas, rect }
+				if atlas == "dungeon.png"
+					&& *rect == Rect::new(11.0, 11.0, 19.0, 19.0)
+		));
+
+		let limits = TilesetLoadLimits::default();
+		assert!(matches!(
+			TiledTileset::from_tsx("<map/>", "", &limits),
+			Err(TilesetError::InvalidTiledTileset(..))
+		));
+		assert!(matches!(
+			TiledTileset::from_tsx(r#"<tileset tilewidth="8"/>"#, "", &limits),
+			Err(TilesetError::InvalidTiledTileset(..))
+		));
+	}
+
+	#[test]
+	fn should_build_single_image() {
+		/// A loader that gives every texture the same handle
+		struct AtlasLoader(Handle<Image>);
+
+		impl TextureLoader for AtlasLoader {
+			fn load_texture<'a, P: Into<AssetPath<'a>>>(&self, _path: P) -> Handle<Image> {
+				self.0.clone()
+			}
+		}
+
+		let tsx = r#"<tileset name="Grid" tilewidth="8" tileheight="8" tilecount="4" columns="2">
+ <image source="grid.png" width="16" height="16"/>
+</tileset>"#;
+		let tiled = TiledTileset::from_tsx(tsx, "", &TilesetLoadLimits::default()).unwrap();
+		let mut store = TestTextureStore::default();
+		let loader = AtlasLoader(store.add(solid_image(16, 16, [255; 4])));
+		let tile_handles = tiled.tile_handles(&loader);
+		let tileset = tiled.build(0, tile_handles, &mut store).unwrap();
+
+		assert_eq!(1, tileset.page_count());
+		assert_eq!(&loader.0, tileset.texture());
+		assert_eq!(Some(2), tileset.grid_columns());
+		for id in 0..4 {
+			let name = format!("Grid {}", id);
+			assert_eq!(Some(id as usize), tileset.get_base_tile_index(&name));
+		}
+		assert_eq!(Some(TileIndex::Standard(3)), tileset.get_tile_index("Grid 3"));
+		assert_eq!(Some(3), tileset.index_from_grid(1, 1));
+	}
+
+	#[test]
+	fn should_bound_single_image_tiles() {
+		let tsx = |tile_count: u32, spacing: u32| {
+			format!(
+				r#"<tileset name="Huge" tilewidth="8" tileheight="8" spacing="{}"
+	tilecount="{}" columns="4">
+ <image source="huge.png" width="64" height="64"/>
+</tileset>"#,
+				spacing, tile_count
+			)
+		};
+
+		let limits = TilesetLoadLimits {
+			max_tiles: Some(16),
+			..Default::default()
+		};
+		assert!(matches!(
+			TiledTileset::from_tsx(&tsx(u32::MAX, 0), "", &limits),
+			Err(TilesetError::TooManyTiles { max: 16, .. })
+		));
+		assert!(TiledTileset::from_tsx(&tsx(16, 0), "", &limits).is_ok());
+
+		// The third column of tiles would start past `u32::MAX`
+		let limits = TilesetLoadLimits::default();
+		assert!(matches!(
+			TiledTileset::from_tsx(&tsx(3, u32::MAX / 2), "", &limits),
+			Err(TilesetError::InvalidTiledTileset(..))
+		));
+	}
+}
@@ -0,0 +1,333 @@
+//! Support for validating a tileset definition in full
+//!
+//! Loading a tileset stops at the first problem it finds, which makes fixing a broken tileset
+//! a slow back-and-forth. Validation instead checks every tile and texture, collecting all of
+//! the issues it finds into a single report (such as for an editor to display as a list).
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use bevy::render::texture::CompressedImageFormats;
+#[cfg(feature = "variants")]
+use bevy_tileset_tiles::prelude::{TileDefType, VariantTileDef};
+
+use crate::prelude::{RawTileset, TilesetError, TilesetLoadLimits};
+use crate::tileset::asset::{
+	create_tile_handles, duplicate_group_ids, load_image, load_tile, parse_tileset_def,
+	ResolverSource, TilesetSource,
+};
+use crate::tileset::header;
+
+/// The kind of problem found while validating a tileset
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum TilesetValidationCategory {
+	/// The tileset or a tile definition could not be parsed
+	InvalidDefinition,
+	/// A tile definition file could not be read
+	MissingDefinition,
+	/// A texture file could not be read
+	MissingTexture,
+	/// A texture could not be decoded
+	InvalidTexture,
+	/// Multiple tiles share the same name
+	DuplicateName,
+	/// Multiple tiles share the same group ID
+	DuplicateGroupId,
+	/// A variant has a negative or non-finite weight (or all of a tile's variants weigh nothing)
+	InvalidWeight,
+	/// A texture does not match the tileset's `tile_size`
+	SizeMismatch,
+	/// A tile uses a tile type whose cargo feature is disabled
+	DisabledFeature,
+	/// A path escapes the asset root
+	PathEscape,
+}
+
+/// A single problem found while validating a tileset
+#[derive(Debug, Clone, PartialEq)]
+pub struct TilesetValidationIssue {
+	/// The kind of problem this is
+	pub category: TilesetValidationCategory,
+	/// The name of the tile this issue belongs to (if known)
+	pub tile: Option<String>,
+	/// The path of the file this issue belongs to (if any)
+	pub path: Option<PathBuf>,
+	/// A human-readable description of the issue
+	pub message: String,
+}
+
+impl TilesetValidationIssue {
+	/// Creates an issue from the error encountered while reading a file
+	///
+	/// Errors reading the file itself are reported as `missing`.
+	fn from_error(
+		err: TilesetError,
+		missing: TilesetValidationCategory,
+		tile: Option<String>,
+		path: PathBuf,
+	) -> Self {
+		let (category, tile) = match err {
+			TilesetError::FeatureDisabled { ref tile, .. } => {
+				(TilesetValidationCategory::DisabledFeature, Some(tile.clone()))
+			},
+			TilesetError::PathEscape(..) => (TilesetValidationCategory::PathEscape, tile),
+			TilesetError::AssetIoError(..) => (missing, tile),
+			TilesetError::ImageError(..) | TilesetError::UnknownImageFormat(..) => {
+				(TilesetValidationCategory::InvalidTexture, tile)
+			},
+			_ => (TilesetValidationCategory::InvalidDefinition, tile),
+		};
+		Self {
+			category,
+			tile,
+			path: Some(path),
+			message: err.to_string(),
+		}
+	}
+}
+
+/// Gets the weights of every variant set in the given tile
+#[cfg(feature = "variants")]
+fn variant_weights(tile: &TileDefType) -> Vec<Vec<f32>> {
+	let weights = |variants: &[VariantTileDef]| -> Vec<f32> {
+		variants.iter().map(|variant| variant.weight).collect()
+	};
+	match tile {
+		TileDefType::Variant(variants) => vec![weights(variants)],
+		#[cfg(feature = "auto-tile")]
+		TileDefType::Auto(autos) => autos.iter().map(|auto| weights(&auto.variants)).collect(),
+		_ => Vec::new(),
+	}
+}
+
+/// Checks every tile and texture of the given tileset definition, collecting all issues found
+async fn validate_tileset<TSource: TilesetSource>(
+	bytes: &[u8],
+	source: &TSource,
+	supported_compressed_formats: CompressedImageFormats,
+) -> Vec<TilesetValidationIssue> {
+	let mut issues = Vec::new();
+	let config = match parse_tileset_def(bytes, source.path()) {
+		Ok(config) => config,
+		Err(err) => {
+			issues.push(TilesetValidationIssue {
+				category: TilesetValidationCategory::InvalidDefinition,
+				tile: None,
+				path: Some(source.path().to_path_buf()),
+				message: err.to_string(),
+			});
+			return issues;
+		},
+	};
+
+	for group_id in duplicate_group_ids(bytes) {
+		issues.push(TilesetValidationIssue {
+			category: TilesetValidationCategory::DuplicateGroupId,
+			tile: None,
+			path: Some(source.path().to_path_buf()),
+			message: format!("group ID {} is assigned to multiple tiles", group_id),
+		});
+	}
+
+	// === Tile Definitions === //
+	let mut defs = Vec::with_capacity(config.tiles.len());
+	let mut names = HashMap::<String, PathBuf>::with_capacity(config.tiles.len());
+	for tile_path in config.tiles.values() {
+		let path = PathBuf::from(tile_path);
+		let def = match load_tile(source, tile_path).await {
+			Ok(def) => def,
+			Err(err) => {
+				issues.push(TilesetValidationIssue::from_error(
+					err,
+					TilesetValidationCategory::MissingDefinition,
+					None,
+					path,
+				));
+				continue;
+			},
+		};
+
+		if let Some(other) = names.insert(def.name.clone(), path.clone()) {
+			issues.push(TilesetValidationIssue {
+				category: TilesetValidationCategory::DuplicateName,
+				tile: Some(def.name.clone()),
+				path: Some(path.clone()),
+				message: format!("tile name {:?} is also used by {:?}", def.name, other),
+			});
+		}
+
+		#[cfg(feature = "variants")]
+		for weights in variant_weights(&def.tile) {
+			let is_invalid = |weight: &f32| !weight.is_finite() || *weight < 0.0;
+			if weights.iter().any(is_invalid) || weights.iter().sum::<f32>() <= 0.0 {
+				issues.push(TilesetValidationIssue {
+					category: TilesetValidationCategory::InvalidWeight,
+					tile: Some(def.name.clone()),
+					path: Some(path.clone()),
+					message: format!("invalid variant weights {:?}", weights),
+				});
+			}
+		}
+
+		defs.push(def);
+	}
+
+	// === Textures === //
+	// Handles are created exactly as they are when loading, so textures resolve identically
+	let texture_root = config.texture_root.clone();
+	let texture_source = config.texture_source.clone();
+	let (tile_handles, texture_paths) =
+		create_tile_handles(defs, source, texture_root, texture_source);
+	let expected_sizes = config
+		.tile_size
+		.map(|tile_size| header::expected_sizes(&tile_handles, tile_size))
+		.unwrap_or_default();
+	let mut texture_paths = texture_paths.into_iter().collect::<Vec<_>>();
+	texture_paths.sort_by(|(_, a), (_, b)| a.cmp(b));
+	let limits = TilesetLoadLimits::default();
+	for (id, path) in texture_paths {
+		let image = load_image(source, path.clone(), supported_compressed_formats, &limits);
+		let image = match image.await {
+			Ok(image) => image,
+			Err(err) => {
+				// Report the texture once for every tile that uses it
+				let issue = TilesetValidationIssue::from_error(
+					err,
+					TilesetValidationCategory::MissingTexture,
+					None,
+					path,
+				);
+				let users = tile_handles
+					.iter()
+					.filter(|tile| tile.iter_handles().any(|handle| handle.id() == id));
+				for tile in users {
+					issues.push(TilesetValidationIssue {
+						tile: Some(tile.name.clone()),
+						..issue.clone()
+					});
+				}
+				continue;
+			},
+		};
+
+		let found = image.size();
+		if let Some((tile, expected)) = expected_sizes.get(&id) {
+			if found != *expected {
+				issues.push(TilesetValidationIssue {
+					category: TilesetValidationCategory::SizeMismatch,
+					tile: Some(tile.clone()),
+					path: Some(path),
+					message: format!("expected a size of {:?}, found {:?}", expected, found),
+				});
+			}
+		}
+	}
+
+	issues
+}
+
+impl RawTileset {
+	/// Validates a tileset definition, collecting every issue found
+	///
+	/// Unlike [`from_ron_bytes`](Self::from_ron_bytes), this doesn't stop at the first issue
+	/// and doesn't build the tileset. Files are resolved exactly as they are when loading.
+	///
+	/// # Arguments
+	///
+	/// * `def`: The bytes of the tileset definition file
+	/// * `resolver`: A function that reads the file at the given path (relative to the
+	///   tileset definition file)
+	///
+	/// returns: Result<(), Vec<TilesetValidationIssue>>
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// # use bevy_tileset_core::prelude::*;
+	/// let def = std::fs::read("assets/tilesets/my_tileset.ron").unwrap();
+	/// let result = RawTileset::validate_ron_bytes(&def, |path| {
+	/// 	std::fs::read(std::path::Path::new("assets/tilesets").join(path)).ok()
+	/// });
+	/// for issue in result.err().unwrap_or_default() {
+	/// 	println!("{:?}: {}", issue.category, issue.message);
+	/// }
+	/// ```
+	pub fn validate_ron_bytes<TResolver>(
+		def: &[u8],
+		resolver: TResolver,
+	) -> Result<(), Vec<TilesetValidationIssue>>
+	where
+		TResolver: Fn(&Path) -> Option<Vec<u8>>,
+	{
+		let source = ResolverSource {
+			path: PathBuf::new(),
+			resolver,
+		};
+		let issues = futures::executor::block_on(validate_tileset(
+			def,
+			&source,
+			CompressedImageFormats::all(),
+		));
+		if issues.is_empty() {
+			Ok(())
+		} else {
+			Err(issues)
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::test_utils::PIXEL_PNG;
+
+	#[test]
+	fn should_collect_every_issue() {
+		let mut files = HashMap::new();
+		files.insert(
+			PathBuf::from("dirt.ron"),
+			br#"(name: "Dirt", tile: Standard("dirt.png"))"#.to_vec(),
+		);
+		files.insert(
+			PathBuf::from("grass.ron"),
+			br#"(name: "Dirt", tile: Standard("grass.png"))"#.to_vec(),
+		);
+		files.insert(PathBuf::from("dirt.png"), PIXEL_PNG.to_vec());
+		let def = br#"(
+			id: 0,
+			tile_size: (16.0, 16.0),
+			tiles: {0: "dirt.ron", 1: "grass.ron", 2: "missing.ron", 0: "dirt.ron"},
+		)"#;
+
+		let issues = RawTileset::validate_ron_bytes(def, |path: &Path| files.get(path).cloned())
+			.unwrap_err()
+			.into_iter()
+			.map(|issue| issue.category)
+			.collect::<Vec<_>>();
+		for category in [
+			TilesetValidationCategory::DuplicateGroupId,
+			TilesetValidationCategory::DuplicateName,
+			TilesetValidationCategory::MissingDefinition,
+			TilesetValidationCategory::MissingTexture,
+			TilesetValidationCategory::SizeMismatch,
+		] {
+			assert!(issues.contains(&category), "missing {:?} in {:?}", category, issues);
+		}
+	}
+
+	#[test]
+	fn should_pass_valid_tileset() {
+		let mut files = HashMap::new();
+		files.insert(
+			PathBuf::from("dirt.ron"),
+			br#"(name: "Dirt", tile: Standard("dirt.png"))"#.to_vec(),
+		);
+		files.insert(PathBuf::from("dirt.png"), PIXEL_PNG.to_vec());
+		let def = br#"(id: 0, tiles: {0: "dirt.ron"})"#;
+
+		assert_eq!(
+			Ok(()),
+			RawTileset::validate_ron_bytes(def, |path: &Path| files.get(path).cloned())
+		);
+	}
+}
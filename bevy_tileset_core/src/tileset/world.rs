@@ -0,0 +1,125 @@
+//! Convenience queries for reaching a [`Tileset`] through its handle
+
+use bevy::asset::{Assets, Handle};
+use bevy::prelude::World;
+
+use crate::prelude::{TileGroupId, TileIndex, Tileset};
+
+/// An extension trait for looking up a tileset's data from its handle in a single call
+///
+/// This is implemented for both `World` (for exclusive systems and commands) and
+/// `Assets<Tileset>` (for regular systems using `Res<Assets<Tileset>>`).
+///
+/// # Examples
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_tileset_core::prelude::*;
+///
+/// fn grass_index(world: &World, handle: &Handle<Tileset>) -> Option<usize> {
+/// 	world.tile_index(handle, "Grass")
+/// }
+/// ```
+pub trait TilesetWorldExt {
+	/// Gets the tileset with the given handle
+	///
+	/// Returns `None` if the tileset isn't loaded.
+	fn tileset(&self, handle: &Handle<Tileset>) -> Option<&Tileset>;
+
+	/// Gets the base atlas index of a tile by its name
+	///
+	/// See [`Tileset::get_base_tile_index`] for details.
+	///
+	/// # Arguments
+	///
+	/// * `handle`: The handle to the tileset
+	/// * `name`: The name of the tile
+	///
+	/// returns: Option<usize>
+	///
+	fn tile_index(&self, handle: &Handle<Tileset>, name: &str) -> Option<usize> {
+		self.tileset(handle)?.get_base_tile_index(name)
+	}
+
+	/// Gets the [`TileIndex`] of a tile by its name
+	///
+	/// See [`Tileset::get_tile_index`] for details.
+	///
+	/// # Arguments
+	///
+	/// * `handle`: The handle to the tileset
+	/// * `name`: The name of the tile
+	///
+	/// returns: Option<TileIndex>
+	///
+	fn full_tile_index(&self, handle: &Handle<Tileset>, name: &str) -> Option<TileIndex> {
+		self.tileset(handle)?.get_tile_index(name)
+	}
+
+	/// Gets the group ID of a tile by its name
+	///
+	/// # Arguments
+	///
+	/// * `handle`: The handle to the tileset
+	/// * `name`: The name of the tile
+	///
+	/// returns: Option<TileGroupId>
+	///
+	fn tile_group_id(&self, handle: &Handle<Tileset>, name: &str) -> Option<TileGroupId> {
+		self.tileset(handle)?.get_tile_group_id(name).copied()
+	}
+}
+
+impl TilesetWorldExt for World {
+	fn tileset(&self, handle: &Handle<Tileset>) -> Option<&Tileset> {
+		self.get_resource::<Assets<Tileset>>()?.get(handle)
+	}
+}
+
+impl TilesetWorldExt for Assets<Tileset> {
+	fn tileset(&self, handle: &Handle<Tileset>) -> Option<&Tileset> {
+		self.get(handle)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use bevy::asset::{AddAsset, AssetPlugin, Assets, Handle};
+	use bevy::prelude::{App, Image, MinimalPlugins, TextureAtlas};
+	use bevy_tile_atlas::TextureStore;
+	use bevy_tileset_tiles::prelude::TileHandle;
+
+	use crate::prelude::{TileIndex, Tileset, TilesetBuilder, TilesetWorldExt};
+	use crate::test_utils::{solid_image, TestTextureStore};
+
+	#[test]
+	fn should_query_tileset_by_handle() {
+		let mut app = App::new();
+		app.add_plugins((MinimalPlugins, AssetPlugin::default()))
+			.add_asset::<Image>()
+			.add_asset::<TextureAtlas>()
+			.add_asset::<Tileset>();
+
+		let mut store = TestTextureStore::default();
+		let mut builder = TilesetBuilder::default();
+		let handle = store.add(solid_image(16, 16, [255; 4]));
+		builder
+			.add_tile(TileHandle::new_standard("Grass", handle), 3, &store)
+			.unwrap();
+		let raw = builder.build("World", 0, &mut store).unwrap();
+		let tileset = raw.into_asset(&mut app.world.resource_mut::<Assets<TextureAtlas>>());
+		let handle = app.world.resource_mut::<Assets<Tileset>>().add(tileset);
+
+		assert_eq!(Some(0), app.world.tile_index(&handle, "Grass"));
+		assert_eq!(Some(3), app.world.tile_group_id(&handle, "Grass"));
+		assert_eq!(
+			Some(TileIndex::Standard(0)),
+			app.world.full_tile_index(&handle, "Grass")
+		);
+		assert_eq!(None, app.world.tile_index(&handle, "Dirt"));
+		assert_eq!(None, app.world.tile_index(&Handle::default(), "Grass"));
+
+		let tilesets = app.world.resource::<Assets<Tileset>>();
+		assert_eq!(Some(0), tilesets.tile_index(&handle, "Grass"));
+	}
+}
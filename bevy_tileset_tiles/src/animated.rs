@@ -1,29 +1,61 @@
 use bevy_asset::Handle;
+use bevy_math::{Rect, UVec2, Vec2};
 use bevy_render::texture::Image;
 use serde::{Deserialize, Serialize};
 
 /// A structure defining an animated tile
 ///
 /// Made to be easily used with [`bevy_ecs_tilemap::GPUAnimated`] component
-#[derive(Debug, Copy, Clone, Serialize)]
+#[derive(Debug, Copy, Clone, PartialEq, Deserialize, Serialize)]
 pub struct AnimatedTileData {
-	/// The speed of the animation
+	/// The speed of the animation (in frames per second)
 	speed: f32,
 	/// The start index of the animation (inclusive)
 	start: usize,
 	/// The end index of the animation (inclusive)
 	end: usize,
+	/// Whether the animation plays from its last frame to its first
+	#[serde(default)]
+	reversed: bool,
+	/// The frame the animation starts on (in the order the frames were defined)
+	///
+	/// If `None`, the animation starts on the first frame it plays (the last frame if reversed).
+	#[serde(default)]
+	start_frame: Option<usize>,
 }
 
 /// A structure defining an animated tile
 #[derive(Debug, Clone)]
 pub struct AnimatedTileHandle {
-	/// The speed of the animation
+	/// The speed of the animation (in frames per second)
 	pub speed: f32,
 	/// The frames of the animation
 	///
 	/// Each frame is a registered [`Handle`]
 	pub frames: Vec<Handle<Image>>,
+	/// The region of each frame's texture to use (in pixels)
+	///
+	/// If empty, the entirety of each frame's texture is used. Otherwise, this must contain
+	/// exactly one region per frame (such as when slicing frames out of a single strip image).
+	pub regions: Vec<Rect>,
+	/// Whether the animation plays from its last frame to its first
+	pub reversed: bool,
+	/// The frame the animation starts on (in the order of `frames`)
+	///
+	/// If `None`, the animation starts on the first frame it plays (the last frame if reversed).
+	pub start_frame: Option<usize>,
+}
+
+impl Default for AnimatedTileHandle {
+	fn default() -> Self {
+		Self {
+			speed: default_speed(),
+			frames: Vec::new(),
+			regions: Vec::new(),
+			reversed: false,
+			start_frame: None,
+		}
+	}
 }
 
 /// A structure defining an animated tile
@@ -31,11 +63,22 @@ pub struct AnimatedTileHandle {
 /// Made to be easily used with [`bevy_ecs_tilemap::GPUAnimated`] component
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct AnimatedTileDef {
-	/// The speed of the animation
+	/// The speed of the animation (in frames per second)
+	///
+	/// This is the same rate used by `bevy_ecs_tilemap`, so a speed of `2.0` advances two
+	/// frames every second. To specify the speed in seconds per frame, use `frame_duration`
+	/// instead.
 	///
 	/// Default: 1.0
 	#[serde(default = "default_speed")]
 	pub speed: f32,
+	/// The duration of each frame of the animation (in seconds)
+	///
+	/// If given (and positive), this takes precedence over `speed`.
+	///
+	/// Default: `None`
+	#[serde(default)]
+	pub frame_duration: Option<f32>,
 	/// The frames of the animation
 	///
 	/// Each entry is a path to a texture relative to the configuration file
@@ -55,11 +98,229 @@ pub struct AnimatedTileDef {
 	/// ```
 	#[serde(default)]
 	pub frames: Vec<String>,
+	/// A single strip image containing every frame of the animation
+	///
+	/// This can be used instead of listing each frame in `frames` (and takes precedence over it).
+	///
+	/// # Examples
+	///
+	/// ```ron
+	/// (
+	/// 	// ...
+	/// 	strip: (
+	/// 		path: "water-strip.png",
+	/// 		frame_count: 4,
+	/// 		frame_size: (16, 16),
+	/// 	)
+	/// 	// ...
+	/// )
+	/// ```
+	///
+	/// Default: `None`
+	#[serde(default)]
+	pub strip: Option<AnimationStrip>,
+	/// Whether the animation should play from its last frame to its first
+	///
+	/// Default: `false`
+	#[serde(default)]
+	pub reversed: bool,
+	/// The frame the animation starts on (in the order the frames are defined)
+	///
+	/// This allows tiles sharing an animation to start mid-sequence, so that they don't all
+	/// animate in lockstep. It must be less than the number of frames, otherwise the tile fails
+	/// to load. If `None`, the animation starts on the first frame it plays (which is the last
+	/// frame if `reversed`).
+	///
+	/// Default: `None`
+	#[serde(default)]
+	pub start_frame: Option<usize>,
+}
+
+/// A single image containing every frame of an animation laid out in a row or column
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct AnimationStrip {
+	/// The path to the strip image, relative to the configuration file
+	pub path: String,
+	/// The number of frames in the strip
+	pub frame_count: usize,
+	/// The size of each frame (in pixels)
+	pub frame_size: UVec2,
+	/// The direction the frames are laid out in
+	///
+	/// Default: [`StripDirection::Horizontal`]
+	#[serde(default)]
+	pub direction: StripDirection,
+}
+
+/// The direction the frames of an [`AnimationStrip`] are laid out in
+#[derive(Deserialize, Serialize, Debug, Default, Copy, Clone, Eq, PartialEq)]
+pub enum StripDirection {
+	/// Frames are laid out from left to right
+	#[default]
+	Horizontal,
+	/// Frames are laid out from top to bottom
+	Vertical,
+}
+
+impl AnimationStrip {
+	/// Gets the region of each frame within the strip image (in pixels)
+	pub fn frame_regions(&self) -> Vec<Rect> {
+		let size = self.frame_size.as_vec2();
+		let step = match self.direction {
+			StripDirection::Horizontal => Vec2::new(size.x, 0.0),
+			StripDirection::Vertical => Vec2::new(0.0, size.y),
+		};
+		(0..self.frame_count)
+			.map(|frame| {
+				let min = step * frame as f32;
+				Rect::from_corners(min, min + size)
+			})
+			.collect()
+	}
+}
+
+impl Default for AnimatedTileDef {
+	fn default() -> Self {
+		Self {
+			speed: default_speed(),
+			frame_duration: None,
+			frames: Vec::new(),
+			strip: None,
+			reversed: false,
+			start_frame: None,
+		}
+	}
+}
+
+impl AnimatedTileDef {
+	/// Create a new [`AnimatedTileDef`] from the given frames
+	///
+	/// # Arguments
+	///
+	/// * `frames`: The paths to the frame textures (in order)
+	///
+	/// returns: AnimatedTileDef
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use bevy_tileset_tiles::prelude::*;
+	/// let anim = AnimatedTileDef::new(["frame-001.png", "frame-002.png"]).with_speed(4.0);
+	/// ```
+	pub fn new<TFrame: Into<String>>(frames: impl IntoIterator<Item = TFrame>) -> Self {
+		Self {
+			frames: frames.into_iter().map(Into::into).collect(),
+			..Default::default()
+		}
+	}
+
+	/// Create a new [`AnimatedTileDef`] from a single strip image
+	///
+	/// # Arguments
+	///
+	/// * `strip`: The strip containing every frame
+	///
+	/// returns: AnimatedTileDef
+	///
+	pub fn from_strip(strip: AnimationStrip) -> Self {
+		Self {
+			strip: Some(strip),
+			..Default::default()
+		}
+	}
+
+	/// Sets the speed of this animation
+	///
+	/// # Arguments
+	///
+	/// * `speed`: The speed (in frames per second)
+	///
+	/// returns: AnimatedTileDef
+	///
+	pub fn with_speed(mut self, speed: f32) -> Self {
+		self.speed = speed;
+		self
+	}
+
+	/// Sets the duration of each frame of this animation
+	///
+	/// # Arguments
+	///
+	/// * `frame_duration`: The duration (in seconds)
+	///
+	/// returns: AnimatedTileDef
+	///
+	pub fn with_frame_duration(mut self, frame_duration: f32) -> Self {
+		self.frame_duration = Some(frame_duration);
+		self
+	}
+
+	/// Sets whether this animation plays from its last frame to its first
+	///
+	/// # Arguments
+	///
+	/// * `reversed`: Whether the animation is reversed
+	///
+	/// returns: AnimatedTileDef
+	///
+	pub fn with_reversed(mut self, reversed: bool) -> Self {
+		self.reversed = reversed;
+		self
+	}
+
+	/// Sets the frame this animation starts on
+	///
+	/// # Arguments
+	///
+	/// * `start_frame`: The frame to start on (in the order the frames are defined)
+	///
+	/// returns: AnimatedTileDef
+	///
+	pub fn with_start_frame(mut self, start_frame: usize) -> Self {
+		self.start_frame = Some(start_frame);
+		self
+	}
+
+	/// Gets the speed of this animation (in frames per second)
+	///
+	/// This is derived from `frame_duration` if given, or `speed` otherwise.
+	pub fn fps(&self) -> f32 {
+		match self.frame_duration {
+			Some(duration) if duration > 0.0 => duration.recip(),
+			_ => self.speed,
+		}
+	}
 }
 
 impl AnimatedTileData {
 	pub fn new(speed: f32, start: usize, end: usize) -> Self {
-		Self { speed, start, end }
+		Self {
+			speed,
+			start,
+			end,
+			reversed: false,
+			start_frame: None,
+		}
+	}
+
+	/// Replaces the start and end indices of this animation with the result of the given function
+	///
+	/// The frames must still be contiguous once mapped.
+	pub(crate) fn map_indices(&mut self, map: &dyn Fn(usize) -> usize) {
+		self.start = map(self.start);
+		self.end = map(self.end);
+	}
+
+	/// Sets whether this animation plays from its last frame to its first
+	pub fn with_reversed(mut self, reversed: bool) -> Self {
+		self.reversed = reversed;
+		self
+	}
+
+	/// Sets the frame this animation starts on (in the order the frames were defined)
+	pub fn with_start_frame(mut self, start_frame: usize) -> Self {
+		self.start_frame = Some(start_frame);
+		self
 	}
 
 	/// Gets the start animation index (inclusive)
@@ -72,14 +333,99 @@ impl AnimatedTileData {
 		self.end
 	}
 
-	/// Gets the animation speed
+	/// Gets the animation speed (in frames per second)
 	pub fn speed(&self) -> f32 {
 		self.speed
 	}
 
+	/// Checks whether this animation plays from its last frame to its first
+	pub fn is_reversed(&self) -> bool {
+		self.reversed
+	}
+
+	/// Gets the frame this animation starts on (in the order the frames were defined)
+	///
+	/// Unless a start frame was set, this is the first frame the animation plays: its first
+	/// frame, or its last frame if reversed.
+	pub fn start_frame(&self) -> usize {
+		match self.start_frame {
+			Some(start_frame) => start_frame,
+			None if self.reversed => self.frame_count() - 1,
+			None => 0,
+		}
+	}
+
+	/// Gets the duration of each frame of this animation (in seconds)
+	pub fn frame_duration(&self) -> f32 {
+		self.speed.recip()
+	}
+
 	/// Gets the number of frames in this animation
 	pub fn frame_count(&self) -> usize {
-		self.end - self.start
+		self.end - self.start + 1
+	}
+
+	/// Gets the atlas index of the given frame of this animation
+	///
+	/// Returns `None` if the frame is out of range.
+	pub fn frame_index(&self, frame: usize) -> Option<usize> {
+		let index = self.start.checked_add(frame)?;
+		if index <= self.end {
+			Some(index)
+		} else {
+			None
+		}
+	}
+
+	/// Gets the frame shown after the animation has advanced the given number of frames
+	///
+	/// This accounts for the start frame and direction of the animation, looping back around
+	/// once the end of the animation is reached.
+	///
+	/// # Arguments
+	///
+	/// * `elapsed_frames`: The number of frames the animation has advanced
+	///
+	/// returns: usize
+	///
+	pub fn playback_frame(&self, elapsed_frames: usize) -> usize {
+		let count = self.frame_count();
+		let start = self.start_frame() % count;
+		let offset = elapsed_frames % count;
+		if self.reversed {
+			(start + count - offset) % count
+		} else {
+			(start + offset) % count
+		}
+	}
+
+	/// Gets the atlas index of the frame shown once the given amount of time has elapsed
+	///
+	/// The animation loops, starting from its start frame at `0.0` and playing in its
+	/// direction.
+	///
+	/// # Arguments
+	///
+	/// * `elapsed`: The time since the animation started (in seconds)
+	///
+	/// returns: usize
+	///
+	pub fn frame_at(&self, elapsed: f32) -> usize {
+		let elapsed_frames = (elapsed * self.speed).max(0.0) as usize;
+		self.start + self.playback_frame(elapsed_frames)
+	}
+}
+
+impl AnimatedTileHandle {
+	/// Clones this handle, making every frame handle weak
+	pub fn clone_weak(&self) -> Self {
+		Self {
+			speed: self.speed,
+			frames: self.frames.iter().map(Handle::clone_weak).collect(),
+			regions: self.regions.clone(),
+			reversed: self.reversed,
+			start_frame: self.start_frame,
+		}
 	}
 }
 
@@ -90,3 +436,91 @@ impl AnimatedTileData {
 fn default_speed() -> f32 {
 	1.0
 }
+
+#[cfg(test)]
+mod tests {
+	use bevy_math::{Rect, UVec2, Vec2};
+
+	use super::{AnimatedTileData, AnimatedTileDef, AnimationStrip, StripDirection};
+
+	#[test]
+	fn should_normalize_frame_duration() {
+		let mut def = AnimatedTileDef {
+			speed: 2.0,
+			frame_duration: None,
+			frames: Vec::new(),
+			strip: None,
+			..Default::default()
+		};
+		assert_eq!(2.0, def.fps());
+
+		def.frame_duration = Some(0.125);
+		assert_eq!(8.0, def.fps());
+
+		def.frame_duration = Some(0.0);
+		assert_eq!(2.0, def.fps());
+	}
+
+	#[test]
+	fn should_build_animated_def() {
+		let anim = AnimatedTileDef::new(["a.png", "b.png"]).with_speed(4.0);
+		assert_eq!(vec![String::from("a.png"), String::from("b.png")], anim.frames);
+		assert_eq!(4.0, anim.fps());
+		assert!(anim.strip.is_none());
+
+		let anim = AnimatedTileDef::default().with_frame_duration(0.5);
+		assert_eq!(1.0, anim.speed);
+		assert_eq!(2.0, anim.fps());
+	}
+
+	#[test]
+	fn should_get_frame_at_time() {
+		let anim = AnimatedTileData::new(4.0, 3, 5);
+		assert_eq!(0.25, anim.frame_duration());
+		assert_eq!(3, anim.frame_at(0.0));
+		assert_eq!(4, anim.frame_at(0.3));
+		assert_eq!(5, anim.frame_at(0.5));
+		// Loops back to the first frame
+		assert_eq!(3, anim.frame_at(0.75));
+	}
+
+	#[test]
+	fn should_play_in_reverse() {
+		let anim = AnimatedTileData::new(4.0, 3, 5).with_reversed(true);
+		assert_eq!(2, anim.start_frame());
+		assert_eq!([5, 4, 3, 5], [0.0, 0.25, 0.5, 0.75].map(|elapsed| anim.frame_at(elapsed)));
+	}
+
+	#[test]
+	fn should_start_on_start_frame() {
+		let anim = AnimatedTileData::new(4.0, 3, 5).with_start_frame(1);
+		assert_eq!([4, 5, 3, 4], [0.0, 0.25, 0.5, 0.75].map(|elapsed| anim.frame_at(elapsed)));
+
+		let anim = anim.with_reversed(true);
+		assert_eq!([4, 3, 5, 4], [0.0, 0.25, 0.5, 0.75].map(|elapsed| anim.frame_at(elapsed)));
+	}
+
+	#[test]
+	fn should_slice_strip_into_frames() {
+		let mut strip = AnimationStrip {
+			path: String::from("strip.png"),
+			frame_count: 3,
+			frame_size: UVec2::new(16, 8),
+			direction: StripDirection::Horizontal,
+		};
+		assert_eq!(
+			vec![
+				Rect::new(0.0, 0.0, 16.0, 8.0),
+				Rect::new(16.0, 0.0, 32.0, 8.0),
+				Rect::new(32.0, 0.0, 48.0, 8.0),
+			],
+			strip.frame_regions()
+		);
+
+		strip.direction = StripDirection::Vertical;
+		assert_eq!(
+			Some(Rect::from_corners(Vec2::new(0.0, 16.0), Vec2::new(16.0, 24.0))),
+			strip.frame_regions().last().copied()
+		);
+	}
+}
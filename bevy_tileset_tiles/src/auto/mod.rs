@@ -1,14 +1,14 @@
 mod rules;
 
 use crate::prelude::{VariantTileData, VariantTileDef, VariantTileHandle};
-pub use rules::AutoTileRule;
+pub use rules::{AutoTileRule, NeighborMask};
 use serde::{Deserialize, Serialize};
 
 /// A structure defining an auto tile
 ///
 /// An auto tile contains rules that are applied when placed, removed, or changed
 /// to itself and to its neighbors of the same type
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 pub struct AutoTileData {
 	/// The rule defining this tile
 	rule: AutoTileRule,
@@ -39,6 +39,70 @@ pub struct AutoTileDef {
 	pub variants: Vec<VariantTileDef>,
 }
 
+/// Pairs every neighbor configuration with the index of the first rule that matches it
+///
+/// Configurations without a matching rule are skipped.
+fn rule_table<'a>(
+	rules: impl Iterator<Item = AutoTileRule> + Clone + 'a,
+) -> impl Iterator<Item = (NeighborMask, usize)> + 'a {
+	NeighborMask::all().filter_map(move |mask| {
+		rules
+			.clone()
+			.position(|rule| rule.matches(mask))
+			.map(|index| (mask, index))
+	})
+}
+
+/// Gets every neighbor configuration that isn't matched by any rule
+fn unmatched_configurations(
+	rules: impl Iterator<Item = AutoTileRule> + Clone,
+) -> Vec<NeighborMask> {
+	NeighborMask::all()
+		.filter(|mask| !rules.clone().any(|rule| rule.matches(*mask)))
+		.collect()
+}
+
+impl AutoTileHandle {
+	/// Iterates over the full rule table of the given auto tiles
+	///
+	/// Each neighbor configuration is paired with the index of the auto tile it selects (the
+	/// first one whose rule matches). Configurations that aren't matched by any auto tile are
+	/// skipped (see [`missing_configurations`](Self::missing_configurations)).
+	///
+	/// # Arguments
+	///
+	/// * `autos`: The auto tiles making up a single tile (in order)
+	///
+	/// returns: impl Iterator<Item=(NeighborMask, usize)>
+	///
+	pub fn iter_rules(autos: &[Self]) -> impl Iterator<Item = (NeighborMask, usize)> + '_ {
+		rule_table(autos.iter().map(|auto| auto.rule))
+	}
+
+	/// Gets every neighbor configuration that isn't matched by any of the given auto tiles
+	///
+	/// When placed, these configurations fall back to the last auto tile, which is usually
+	/// a sign of missing art.
+	///
+	/// # Arguments
+	///
+	/// * `autos`: The auto tiles making up a single tile (in order)
+	///
+	/// returns: Vec<NeighborMask>
+	///
+	pub fn missing_configurations(autos: &[Self]) -> Vec<NeighborMask> {
+		unmatched_configurations(autos.iter().map(|auto| auto.rule))
+	}
+
+	/// Clones this handle, making every image handle weak
+	pub fn clone_weak(&self) -> Self {
+		Self {
+			rule: self.rule,
+			variants: self.variants.iter().map(VariantTileHandle::clone_weak).collect(),
+		}
+	}
+}
+
 impl AutoTileData {
 	pub fn new(rule: AutoTileRule, variants: Vec<VariantTileData>) -> Self {
 		AutoTileData { rule, variants }
@@ -53,4 +117,62 @@ impl AutoTileData {
 	pub fn variants(&self) -> &Vec<VariantTileData> {
 		&self.variants
 	}
+
+	/// Replaces every atlas index used by this auto tile with the result of the given function
+	pub(crate) fn map_indices(&mut self, map: &dyn Fn(usize) -> usize) {
+		for variant in &mut self.variants {
+			variant.map_indices(map);
+		}
+	}
+
+	/// Iterates over the full rule table of the given auto tiles
+	///
+	/// See [`AutoTileHandle::iter_rules`] for details.
+	pub fn iter_rules(autos: &[Self]) -> impl Iterator<Item = (NeighborMask, usize)> + '_ {
+		rule_table(autos.iter().map(Self::rule))
+	}
+
+	/// Gets every neighbor configuration that isn't matched by any of the given auto tiles
+	///
+	/// See [`AutoTileHandle::missing_configurations`] for details.
+	pub fn missing_configurations(autos: &[Self]) -> Vec<NeighborMask> {
+		unmatched_configurations(autos.iter().map(Self::rule))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn auto_tile(rule: AutoTileRule) -> AutoTileHandle {
+		AutoTileHandle {
+			rule,
+			variants: Vec::new(),
+		}
+	}
+
+	#[test]
+	fn should_iterate_rule_table() {
+		let autos = [
+			auto_tile(AutoTileRule {
+				north: Some(true),
+				..Default::default()
+			}),
+			auto_tile(AutoTileRule {
+				north: Some(false),
+				south: Some(true),
+				..Default::default()
+			}),
+		];
+
+		let table = AutoTileHandle::iter_rules(&autos).collect::<Vec<_>>();
+		assert!(table.contains(&(NeighborMask::NORTH, 0)));
+		assert!(table.contains(&(NeighborMask::NORTH | NeighborMask::SOUTH, 0)));
+		assert!(table.contains(&(NeighborMask::SOUTH, 1)));
+
+		let missing = AutoTileHandle::missing_configurations(&autos);
+		assert!(missing.contains(&NeighborMask::default()));
+		assert!(!missing.contains(&NeighborMask::SOUTH));
+		assert_eq!(256, table.len() + missing.len());
+	}
 }
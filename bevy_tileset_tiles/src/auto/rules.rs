@@ -34,7 +34,68 @@ pub struct AutoTileRule {
 	pub south_west: Option<bool>,
 }
 
+/// A fully specified configuration of an auto tile's neighbors
+///
+/// Each bit marks whether the neighbor in the corresponding direction is a matching tile.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct NeighborMask(pub u8);
+
+impl NeighborMask {
+	pub const NORTH: Self = Self(1 << 0);
+	pub const EAST: Self = Self(1 << 1);
+	pub const SOUTH: Self = Self(1 << 2);
+	pub const WEST: Self = Self(1 << 3);
+	pub const NORTH_EAST: Self = Self(1 << 4);
+	pub const NORTH_WEST: Self = Self(1 << 5);
+	pub const SOUTH_EAST: Self = Self(1 << 6);
+	pub const SOUTH_WEST: Self = Self(1 << 7);
+
+	/// Iterates over every possible neighbor configuration
+	pub fn all() -> impl Iterator<Item = Self> {
+		(0..=u8::MAX).map(Self)
+	}
+
+	/// Checks if every neighbor set in `other` is also set in this mask
+	pub fn contains(&self, other: Self) -> bool {
+		self.0 & other.0 == other.0
+	}
+
+	/// Converts this mask into an [`AutoTileRule`] with every direction specified
+	pub fn to_rule(&self) -> AutoTileRule {
+		AutoTileRule {
+			north: Some(self.contains(Self::NORTH)),
+			east: Some(self.contains(Self::EAST)),
+			south: Some(self.contains(Self::SOUTH)),
+			west: Some(self.contains(Self::WEST)),
+			north_east: Some(self.contains(Self::NORTH_EAST)),
+			north_west: Some(self.contains(Self::NORTH_WEST)),
+			south_east: Some(self.contains(Self::SOUTH_EAST)),
+			south_west: Some(self.contains(Self::SOUTH_WEST)),
+		}
+	}
+}
+
+impl std::ops::BitOr for NeighborMask {
+	type Output = Self;
+
+	fn bitor(self, rhs: Self) -> Self::Output {
+		Self(self.0 | rhs.0)
+	}
+}
+
 impl AutoTileRule {
+	/// Checks if this rule matches the given neighbor configuration
+	///
+	/// # Arguments
+	///
+	/// * `mask`: The neighbor configuration
+	///
+	/// returns: bool
+	///
+	pub fn matches(&self, mask: NeighborMask) -> bool {
+		self.is_subset_of(&mask.to_rule())
+	}
+
 	/// Checks if the given rule is a superset of this one.
 	///
 	/// > __ORDER MATTERS!!!__ This method checks if it itself is a subset of the given rule.
@@ -123,7 +184,7 @@ impl AutoTileRule {
 
 #[cfg(test)]
 mod tests {
-	use crate::prelude::AutoTileRule;
+	use crate::prelude::{AutoTileRule, NeighborMask};
 
 	#[test]
 	fn should_be_subset() {
@@ -141,4 +202,19 @@ mod tests {
 		assert!(a.is_subset_of(&b));
 		assert!(!b.is_subset_of(&a));
 	}
+
+	#[test]
+	fn should_match_neighbor_mask() {
+		let rule = AutoTileRule {
+			north: Some(true),
+			south: Some(false),
+			..Default::default()
+		};
+
+		assert!(rule.matches(NeighborMask::NORTH));
+		assert!(rule.matches(NeighborMask::NORTH | NeighborMask::EAST));
+		assert!(!rule.matches(NeighborMask::NORTH | NeighborMask::SOUTH));
+		assert!(!rule.matches(NeighborMask::default()));
+		assert_eq!(256, NeighborMask::all().count());
+	}
 }
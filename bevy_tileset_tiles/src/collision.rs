@@ -0,0 +1,27 @@
+use bevy_math::{Rect, Vec2};
+use serde::{Deserialize, Serialize};
+
+/// The collision shape of a tile
+///
+/// This data is never used by the tileset itself. It's simply carried through the build
+/// process so that a physics integration can consume it.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+pub enum TileCollision {
+	/// The tile has no collision
+	#[default]
+	None,
+	/// The tile collides across its entire area
+	Solid,
+	/// The tile collides within the given rectangle (in pixels, relative to the tile)
+	Rect(Rect),
+	/// The tile collides within the polygon formed by the given points (in pixels, relative to
+	/// the tile)
+	Points(Vec<Vec2>),
+}
+
+impl TileCollision {
+	/// Checks if this tile has any collision
+	pub fn is_none(&self) -> bool {
+		matches!(self, Self::None)
+	}
+}
@@ -1,9 +1,16 @@
 //! Tile data, including tile definitions (for config files) and auto tiling
 pub mod prelude {
-	pub use super::animated::{AnimatedTileData, AnimatedTileDef, AnimatedTileHandle};
+	pub use super::animated::{
+		AnimatedTileData, AnimatedTileDef, AnimatedTileHandle, AnimationStrip, StripDirection,
+	};
 	#[cfg(feature = "auto-tile")]
-	pub use super::auto::{AutoTileData, AutoTileDef, AutoTileHandle, AutoTileRule};
-	pub use super::tile::{TileData, TileDef, TileDefType, TileHandle, TileHandleType, TileType};
+	pub use super::auto::{AutoTileData, AutoTileDef, AutoTileHandle, AutoTileRule, NeighborMask};
+	pub use super::collision::TileCollision;
+	pub use super::sampler::SamplerPreset;
+	pub use super::tile::{
+		TileData, TileDef, TileDefType, TileHandle, TileHandleType, TileType, DEFAULT_PIVOT,
+	};
+	pub use super::transform::TileTransform;
 	#[cfg(feature = "variants")]
 	pub use super::variants::{
 		SimpleTileDefType, SimpleTileHandle, SimpleTileType, VariantTileData, VariantTileDef,
@@ -14,6 +21,9 @@ pub mod prelude {
 pub mod animated;
 #[cfg(feature = "auto-tile")]
 pub mod auto;
+pub mod collision;
+pub mod sampler;
 pub mod tile;
+pub mod transform;
 #[cfg(feature = "variants")]
 pub mod variants;
@@ -0,0 +1,40 @@
+use bevy_render::render_resource::{AddressMode, FilterMode, SamplerDescriptor};
+use bevy_render::texture::ImageSampler;
+use serde::{Deserialize, Serialize};
+
+/// A preset sampler configuration for an atlas texture
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Deserialize, Serialize)]
+pub enum SamplerPreset {
+	/// Use the sampler configured for the app (e.g. via `ImagePlugin`)
+	#[default]
+	Default,
+	/// Nearest-neighbor filtering, keeping pixel art crisp
+	PixelArt,
+	/// Linear filtering
+	Smooth,
+	/// Linear filtering with repeating address modes and anisotropic filtering
+	///
+	/// This is useful for ground textures that are repeated and viewed at oblique angles.
+	Tiling,
+}
+
+impl SamplerPreset {
+	/// Gets the [`ImageSampler`] described by this preset
+	pub fn sampler(&self) -> ImageSampler {
+		match self {
+			Self::Default => ImageSampler::Default,
+			Self::PixelArt => ImageSampler::nearest(),
+			Self::Smooth => ImageSampler::linear(),
+			Self::Tiling => ImageSampler::Descriptor(SamplerDescriptor {
+				address_mode_u: AddressMode::Repeat,
+				address_mode_v: AddressMode::Repeat,
+				address_mode_w: AddressMode::Repeat,
+				mag_filter: FilterMode::Linear,
+				min_filter: FilterMode::Linear,
+				mipmap_filter: FilterMode::Linear,
+				anisotropy_clamp: 16,
+				..Default::default()
+			}),
+		}
+	}
+}
@@ -1,35 +1,88 @@
+use std::collections::BTreeMap;
+
 use bevy_asset::{AssetServer, Handle, LoadState};
+use bevy_math::{Rect, Vec2};
 use bevy_render::texture::Image;
 use serde::{Deserialize, Serialize};
 
 #[cfg(feature = "auto-tile")]
 use crate::auto::*;
-use crate::prelude::{AnimatedTileData, AnimatedTileDef, AnimatedTileHandle};
+use crate::prelude::{
+	AnimatedTileData, AnimatedTileDef, AnimatedTileHandle, SamplerPreset, TileCollision,
+	TileTransform,
+};
 #[cfg(feature = "variants")]
 use crate::variants::*;
 
 /// Top-level structure defining a tile
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 pub struct TileData {
 	/// The name of this tile
 	name: String,
 	/// The actual tile data
 	tile: TileType,
+	/// The collision shape of this tile
+	collision: TileCollision,
+	/// The tags attached to this tile
+	tags: Vec<String>,
+	/// The render ordering offset of this tile
+	z_offset: f32,
+	/// The render layer of this tile
+	layer: i32,
+	/// The normalized origin of this tile
+	pivot: Vec2,
+	/// The additional names this tile can be referenced by
+	aliases: Vec<String>,
 }
 
+/// The pivot given to tiles that don't specify one (the center of the tile)
+pub const DEFAULT_PIVOT: Vec2 = Vec2::new(0.5, 0.5);
+
 /// An enum defining the tile's type
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 pub enum TileType {
 	/// A standard tile
 	Standard(usize),
 	/// A frame-based animated tile
 	Animated(AnimatedTileData),
+	/// A tile with multiple named animations (e.g. `"idle"` and `"walk"`)
+	MultiAnimated(BTreeMap<String, AnimatedTileData>),
 	/// A collection of tiles to randomly sample
 	#[cfg(feature = "variants")]
 	Variant(Vec<VariantTileData>),
 	/// A collection of auto tiles
 	#[cfg(feature = "auto-tile")]
 	Auto(Vec<AutoTileData>),
+	/// An empty tile that doesn't occupy any space in the atlas
+	Empty,
+}
+
+impl TileType {
+	/// Replaces every atlas index used by this tile with the result of the given function
+	fn map_indices(&mut self, map: &dyn Fn(usize) -> usize) {
+		match self {
+			Self::Standard(index) => *index = map(*index),
+			Self::Animated(anim) => anim.map_indices(map),
+			Self::MultiAnimated(tracks) => {
+				for anim in tracks.values_mut() {
+					anim.map_indices(map);
+				}
+			},
+			#[cfg(feature = "variants")]
+			Self::Variant(variants) => {
+				for variant in variants {
+					variant.map_indices(map);
+				}
+			},
+			#[cfg(feature = "auto-tile")]
+			Self::Auto(autos) => {
+				for auto in autos {
+					auto.map_indices(map);
+				}
+			},
+			Self::Empty => {},
+		}
+	}
 }
 
 /// Top-level structure defining a tile
@@ -37,6 +90,24 @@ pub enum TileType {
 pub struct TileHandle {
 	pub name: String,
 	pub tile: TileHandleType,
+	/// The transform to apply to every texture of this tile
+	pub transform: Option<TileTransform>,
+	/// The color to treat as transparent in every texture of this tile
+	pub color_key: Option<[u8; 4]>,
+	/// The collision shape of this tile
+	pub collision: TileCollision,
+	/// The tags attached to this tile
+	pub tags: Vec<String>,
+	/// The render ordering offset of this tile
+	pub z_offset: f32,
+	/// The render layer of this tile
+	pub layer: i32,
+	/// The normalized origin of this tile
+	pub pivot: Vec2,
+	/// The additional names this tile can be referenced by
+	pub aliases: Vec<String>,
+	/// The sampler this tile needs its atlas page to use (if any)
+	pub sampler: Option<SamplerPreset>,
 }
 
 /// An enum defining the tile's type
@@ -44,10 +115,16 @@ pub struct TileHandle {
 pub enum TileHandleType {
 	Standard(Handle<Image>),
 	Animated(AnimatedTileHandle),
+	/// A tile with multiple named animations
+	MultiAnimated(BTreeMap<String, AnimatedTileHandle>),
+	/// A region of an existing atlas texture
+	AtlasRegion(Handle<Image>, Rect),
 	#[cfg(feature = "variants")]
 	Variant(Vec<VariantTileHandle>),
 	#[cfg(feature = "auto-tile")]
 	Auto(Vec<AutoTileHandle>),
+	/// An empty tile, which has no textures
+	Empty,
 }
 
 /// Top-level tile definition structure
@@ -57,6 +134,78 @@ pub struct TileDef {
 	pub name: String,
 	/// The actual tile data
 	pub tile: TileDefType,
+	/// The transform to apply to every texture of this tile
+	///
+	/// Default: `None`
+	#[serde(default)]
+	pub transform: Option<TileTransform>,
+	/// The color to treat as transparent in every texture of this tile
+	///
+	/// Pixels exactly matching this RGBA color are replaced with fully transparent pixels
+	/// before being added to the atlas. This is useful for legacy art that uses a magic
+	/// background color (e.g. pure magenta) instead of an alpha channel.
+	///
+	/// Default: `None`
+	#[serde(default)]
+	pub color_key: Option<[u8; 4]>,
+	/// The collision shape of this tile
+	///
+	/// This isn't used by the tileset itself, but is made available for physics integrations
+	/// through [`TileData::collision`].
+	///
+	/// Default: `None`
+	#[serde(default)]
+	pub collision: TileCollision,
+	/// Free-form tags used to query tiles (e.g. `["water", "impassable"]`)
+	///
+	/// Like collision, these are purely metadata and don't affect how the tile is built.
+	///
+	/// Default: `[]`
+	#[serde(default)]
+	pub tags: Vec<String>,
+	/// An offset hinting how this tile should be ordered when rendered (e.g. so a tall tree is
+	/// drawn above the player)
+	///
+	/// Like collision and tags, this is purely metadata for a tilemap integration to apply.
+	///
+	/// Default: `0.0`
+	#[serde(default)]
+	pub z_offset: f32,
+	/// The render layer this tile should be drawn on
+	///
+	/// Default: `0`
+	#[serde(default)]
+	pub layer: i32,
+	/// The origin of this tile, used to align tiles larger than a grid cell (e.g. props and
+	/// characters) when placed
+	///
+	/// This is normalized to the size of the tile, where `(0.0, 0.0)` is its bottom-left corner and
+	/// `(1.0, 1.0)` is its top-right corner. For example, `(0.5, 0.0)` anchors a tall tree at the
+	/// bottom-center of its trunk. Like z-offsets, this is purely metadata for a tilemap or sprite
+	/// integration to apply as an anchor.
+	///
+	/// Default: `(0.5, 0.5)` (the center of the tile)
+	#[serde(default = "default_pivot")]
+	pub pivot: Vec2,
+	/// Additional names that resolve to this tile (e.g. its old names after a rename)
+	///
+	/// Aliases share the tile's group ID and atlas indices, so they don't use any extra atlas
+	/// space. An alias never takes precedence over another tile's name.
+	///
+	/// Default: `[]`
+	#[serde(default)]
+	pub aliases: Vec<String>,
+	/// The sampler this tile needs, if it differs from the rest of the tileset (e.g. a soft
+	/// gradient in an otherwise pixel-art tileset)
+	///
+	/// Since an atlas texture only has a single sampler, tiles with a sampler hint are packed
+	/// into a separate atlas page (one per distinct hint), unless the hint matches the tileset's
+	/// own sampler. See the tileset's `page` methods for accessing them. Tile names must be
+	/// unique across every page, since tiles can be found by name from any of them.
+	///
+	/// Default: `None` (the tileset's sampler)
+	#[serde(default)]
+	pub sampler: Option<SamplerPreset>,
 }
 
 /// An enum defining the tile's type
@@ -66,6 +215,24 @@ pub enum TileDefType {
 	Standard(String),
 	/// Defines a tile with a frame-based animation
 	Animated(AnimatedTileDef),
+	/// Defines a tile with multiple named animations, or "tracks" (e.g. `"idle"` and `"walk"`)
+	///
+	/// Each track is packed like a regular animated tile. Tracks are ordered by name, and the
+	/// first one is used wherever a single animation is expected (such as the tile's index).
+	MultiAnimated(BTreeMap<String, AnimatedTileDef>),
+	/// Defines a tile using a region of an existing atlas texture
+	///
+	/// This is useful for projects that pack their own atlases with an external pipeline.
+	/// The region isn't copied: the region tiles of each atlas texture are gathered into their
+	/// own atlas page, which uses that texture as its atlas texture. This also means the region
+	/// is used exactly as it is (it can't be transformed, color keyed, or otherwise processed).
+	/// The region must be made up of whole pixels within the atlas texture.
+	AtlasRegion {
+		/// The path to the atlas texture
+		atlas: String,
+		/// The region of the atlas texture to use (in pixels)
+		rect: Rect,
+	},
 	/// Defines a set of tiles to randomly sample
 	#[cfg(feature = "variants")]
 	Variant(Vec<VariantTileDef>),
@@ -75,6 +242,11 @@ pub enum TileDefType {
 	/// > descending rule restriction (i.e. the first item being the most restrictive)
 	#[cfg(feature = "auto-tile")]
 	Auto(Vec<AutoTileDef>),
+	/// Defines an empty (or "air") tile
+	///
+	/// This registers the tile's name and ID without adding anything to the atlas, so map code
+	/// can reference an empty tile just like any other.
+	Empty,
 }
 
 impl TileData {
@@ -98,7 +270,94 @@ impl TileData {
 	/// );
 	/// ```
 	pub fn new(name: String, tile: TileType) -> Self {
-		Self { name, tile }
+		Self {
+			name,
+			tile,
+			collision: TileCollision::None,
+			tags: Vec::new(),
+			z_offset: 0.0,
+			layer: 0,
+			pivot: DEFAULT_PIVOT,
+			aliases: Vec::new(),
+		}
+	}
+
+	/// Sets the collision shape of this tile
+	///
+	/// # Arguments
+	///
+	/// * `collision`: The collision shape
+	///
+	/// returns: TileData
+	///
+	pub fn with_collision(mut self, collision: TileCollision) -> Self {
+		self.collision = collision;
+		self
+	}
+
+	/// Sets the tags attached to this tile
+	///
+	/// # Arguments
+	///
+	/// * `tags`: The tags
+	///
+	/// returns: TileData
+	///
+	pub fn with_tags(mut self, tags: Vec<String>) -> Self {
+		self.tags = tags;
+		self
+	}
+
+	/// Sets the render ordering offset of this tile
+	///
+	/// # Arguments
+	///
+	/// * `z_offset`: The offset
+	///
+	/// returns: TileData
+	///
+	pub fn with_z_offset(mut self, z_offset: f32) -> Self {
+		self.z_offset = z_offset;
+		self
+	}
+
+	/// Sets the render layer of this tile
+	///
+	/// # Arguments
+	///
+	/// * `layer`: The layer
+	///
+	/// returns: TileData
+	///
+	pub fn with_layer(mut self, layer: i32) -> Self {
+		self.layer = layer;
+		self
+	}
+
+	/// Sets the normalized origin of this tile
+	///
+	/// # Arguments
+	///
+	/// * `pivot`: The pivot (see [`TileDef::pivot`])
+	///
+	/// returns: TileData
+	///
+	pub fn with_pivot(mut self, pivot: Vec2) -> Self {
+		self.pivot = pivot;
+		self
+	}
+
+	/// Sets the additional names this tile can be referenced by
+	///
+	/// # Arguments
+	///
+	/// * `aliases`: The aliases
+	///
+	/// returns: TileData
+	///
+	pub fn with_aliases(mut self, aliases: Vec<String>) -> Self {
+		self.aliases = aliases;
+		self
 	}
 
 	/// Gets the name of this tile
@@ -111,6 +370,56 @@ impl TileData {
 		&self.tile
 	}
 
+	/// Gets the collision shape of this tile
+	pub fn collision(&self) -> &TileCollision {
+		&self.collision
+	}
+
+	/// Gets the tags attached to this tile
+	pub fn tags(&self) -> &[String] {
+		&self.tags
+	}
+
+	/// Gets the render ordering offset of this tile
+	pub fn z_offset(&self) -> f32 {
+		self.z_offset
+	}
+
+	/// Gets the render layer of this tile
+	pub fn layer(&self) -> i32 {
+		self.layer
+	}
+
+	/// Gets the normalized origin of this tile
+	pub fn pivot(&self) -> Vec2 {
+		self.pivot
+	}
+
+	/// Gets the additional names this tile can be referenced by
+	pub fn aliases(&self) -> &[String] {
+		&self.aliases
+	}
+
+	/// Replaces every atlas index used by this tile with the result of the given function
+	///
+	/// This is useful for keeping the tile valid after textures are removed from its atlas,
+	/// shifting the indices of every texture after them.
+	///
+	/// # Arguments
+	///
+	/// * `map`: The function mapping each old index to its new index
+	///
+	/// returns: ()
+	///
+	pub fn map_indices<F: Fn(usize) -> usize>(&mut self, map: F) {
+		self.tile.map_indices(&map);
+	}
+
+	/// Checks if this tile has the given tag
+	pub fn has_tag(&self, tag: &str) -> bool {
+		self.tags.iter().any(|t| t == tag)
+	}
+
 	/// Checks if the underlying tile is a [`TileType::Standard`] tile
 	pub fn is_standard(&self) -> bool {
 		matches!(self.tile, TileType::Standard(..))
@@ -121,6 +430,16 @@ impl TileData {
 		matches!(self.tile, TileType::Animated(..))
 	}
 
+	/// Checks if the underlying tile is a [`TileType::MultiAnimated`] tile
+	pub fn is_multi_animated(&self) -> bool {
+		matches!(self.tile, TileType::MultiAnimated(..))
+	}
+
+	/// Checks if the underlying tile is a [`TileType::Empty`] tile
+	pub fn is_empty(&self) -> bool {
+		matches!(self.tile, TileType::Empty)
+	}
+
 	/// Checks if the underlying tile is a [`TileType::Variant`] tile
 	#[cfg(feature = "variants")]
 	pub fn is_variant(&self) -> bool {
@@ -147,6 +466,9 @@ impl TileType {
 		match self {
 			Self::Standard(idx) => idx == index,
 			Self::Animated(anim) => anim.start() <= *index && *index <= anim.end(),
+			Self::MultiAnimated(tracks) => tracks
+				.values()
+				.any(|anim| anim.start() <= *index && *index <= anim.end()),
 			#[cfg(feature = "variants")]
 			Self::Variant(variants) => variants.iter().any(|v| v.tile().contains_index(index)),
 			#[cfg(feature = "auto-tile")]
@@ -154,8 +476,41 @@ impl TileType {
 				.iter()
 				.flat_map(|a| a.variants())
 				.any(|v| v.tile().contains_index(index)),
+			Self::Empty => false,
 		}
 	}
+
+	/// Gets the animation that contains the given index
+	///
+	/// For variant and auto tiles, this is the animation of whichever variant contains the index.
+	///
+	/// # Arguments
+	///
+	/// * `index`: The index of any frame within the animation
+	///
+	/// returns: Option<&AnimatedTileData>
+	///
+	pub fn animation_at(&self, index: &usize) -> Option<&AnimatedTileData> {
+		let animations: Vec<&AnimatedTileData> = match self {
+			Self::Animated(anim) => vec![anim],
+			Self::MultiAnimated(tracks) => tracks.values().collect(),
+			#[cfg(feature = "variants")]
+			Self::Variant(variants) => variants
+				.iter()
+				.filter_map(|v| v.tile().animation())
+				.collect(),
+			#[cfg(feature = "auto-tile")]
+			Self::Auto(autos) => autos
+				.iter()
+				.flat_map(|a| a.variants())
+				.filter_map(|v| v.tile().animation())
+				.collect(),
+			_ => Vec::new(),
+		};
+		animations
+			.into_iter()
+			.find(|anim| anim.start() <= *index && *index <= anim.end())
+	}
 }
 
 impl TileHandle {
@@ -163,6 +518,15 @@ impl TileHandle {
 		Self {
 			name: name.into(),
 			tile: TileHandleType::Standard(handle),
+			transform: None,
+			color_key: None,
+			collision: TileCollision::None,
+			tags: Vec::new(),
+			z_offset: 0.0,
+			layer: 0,
+			pivot: DEFAULT_PIVOT,
+			aliases: Vec::new(),
+			sampler: None,
 		}
 	}
 
@@ -170,6 +534,25 @@ impl TileHandle {
 		Self {
 			name: name.into(),
 			tile: TileHandleType::Animated(handle),
+			transform: None,
+			color_key: None,
+			collision: TileCollision::None,
+			tags: Vec::new(),
+			z_offset: 0.0,
+			layer: 0,
+			pivot: DEFAULT_PIVOT,
+			aliases: Vec::new(),
+			sampler: None,
+		}
+	}
+
+	pub fn new_multi_animated<TName: Into<String>>(
+		name: TName,
+		tracks: BTreeMap<String, AnimatedTileHandle>,
+	) -> Self {
+		Self {
+			tile: TileHandleType::MultiAnimated(tracks),
+			..Self::new_empty(name)
 		}
 	}
 
@@ -178,6 +561,35 @@ impl TileHandle {
 		Self {
 			name: name.into(),
 			tile: TileHandleType::Variant(handles.clone()),
+			transform: None,
+			color_key: None,
+			collision: TileCollision::None,
+			tags: Vec::new(),
+			z_offset: 0.0,
+			layer: 0,
+			pivot: DEFAULT_PIVOT,
+			aliases: Vec::new(),
+			sampler: None,
+		}
+	}
+
+	/// Creates a handle for an empty (or "air") tile with the given name
+	///
+	/// Empty tiles have no textures, so they don't take up any space in the atlas. Their index
+	/// is always `TileIndex::Empty`.
+	pub fn new_empty<TName: Into<String>>(name: TName) -> Self {
+		Self {
+			name: name.into(),
+			tile: TileHandleType::Empty,
+			transform: None,
+			color_key: None,
+			collision: TileCollision::None,
+			tags: Vec::new(),
+			z_offset: 0.0,
+			layer: 0,
+			pivot: DEFAULT_PIVOT,
+			aliases: Vec::new(),
+			sampler: None,
 		}
 	}
 
@@ -186,31 +598,114 @@ impl TileHandle {
 		Self {
 			name: name.into(),
 			tile: TileHandleType::Auto(handles.clone()),
+			transform: None,
+			color_key: None,
+			collision: TileCollision::None,
+			tags: Vec::new(),
+			z_offset: 0.0,
+			layer: 0,
+			pivot: DEFAULT_PIVOT,
+			aliases: Vec::new(),
+			sampler: None,
 		}
 	}
 
+	/// Clones this handle, making every image handle contained within this tile weak
+	pub fn clone_weak(&self) -> Self {
+		Self {
+			name: self.name.clone(),
+			tile: match &self.tile {
+				TileHandleType::Standard(handle) => TileHandleType::Standard(handle.clone_weak()),
+				TileHandleType::Animated(anim) => TileHandleType::Animated(anim.clone_weak()),
+				TileHandleType::MultiAnimated(tracks) => TileHandleType::MultiAnimated(
+					tracks
+						.iter()
+						.map(|(track, anim)| (track.clone(), anim.clone_weak()))
+						.collect(),
+				),
+				TileHandleType::AtlasRegion(handle, rect) => {
+					TileHandleType::AtlasRegion(handle.clone_weak(), *rect)
+				},
+				#[cfg(feature = "variants")]
+				TileHandleType::Variant(variants) => TileHandleType::Variant(
+					variants.iter().map(VariantTileHandle::clone_weak).collect(),
+				),
+				#[cfg(feature = "auto-tile")]
+				TileHandleType::Auto(autos) => {
+					TileHandleType::Auto(autos.iter().map(AutoTileHandle::clone_weak).collect())
+				},
+				TileHandleType::Empty => TileHandleType::Empty,
+			},
+			transform: self.transform,
+			color_key: self.color_key,
+			collision: self.collision.clone(),
+			tags: self.tags.clone(),
+			z_offset: self.z_offset,
+			layer: self.layer,
+			pivot: self.pivot,
+			aliases: self.aliases.clone(),
+			sampler: self.sampler,
+		}
+	}
+
+	/// Checks if _every_ image handle contained within this tile has been loaded
+	///
+	/// This includes every frame of an animated tile, every variant of a variant tile,
+	/// and every variant of every rule of an auto tile.
 	pub fn is_loaded(&self, asset_server: &AssetServer) -> bool {
 		self.get_load_state(asset_server) == LoadState::Loaded
 	}
 
+	/// Gets the combined load state of every image handle contained within this tile
 	pub fn get_load_state(&self, asset_server: &AssetServer) -> LoadState {
-		asset_server.get_group_load_state(self.iter_handles().map(|handle| handle.id()))
+		self.get_load_state_by(|handle| asset_server.get_load_state(handle))
+	}
+
+	/// Gets the combined load state of every image handle contained within this tile, using
+	/// the given function to get the load state of an individual handle
+	///
+	/// The combined state is only [`LoadState::Loaded`] if every handle is loaded. Otherwise, the
+	/// first failed, unloaded, or not-loaded state is returned, falling back to [`LoadState::Loading`]
+	/// if some handles are still loading.
+	pub fn get_load_state_by<F: Fn(&Handle<Image>) -> LoadState>(&self, get_state: F) -> LoadState {
+		let mut load_state = LoadState::Loaded;
+		for handle in self.iter_handles() {
+			match get_state(handle) {
+				LoadState::Loaded => continue,
+				LoadState::Loading => load_state = LoadState::Loading,
+				state => return state,
+			}
+		}
+		load_state
 	}
 
 	pub fn iter_handles(&self) -> Box<dyn Iterator<Item = &Handle<Image>> + '_> {
 		match &self.tile {
 			TileHandleType::Standard(handle) => Box::new(std::iter::once(handle)),
 			TileHandleType::Animated(anim) => Box::new(anim.frames.iter()),
+			TileHandleType::MultiAnimated(tracks) => {
+				Box::new(tracks.values().flat_map(|anim| anim.frames.iter()))
+			},
+			TileHandleType::AtlasRegion(handle, ..) => Box::new(std::iter::once(handle)),
 			#[cfg(feature = "variants")]
 			TileHandleType::Variant(variants) => Box::new(iter_variant_handles(variants.iter())),
 			#[cfg(feature = "auto-tile")]
 			TileHandleType::Auto(autos) => Box::new(iter_variant_handles(
 				autos.iter().flat_map(|auto| auto.variants.iter()),
 			)),
+			TileHandleType::Empty => Box::new(std::iter::empty()),
 		}
 	}
 }
 
+/// Gets the default tile pivot
+///
+/// Used for deserialization
+#[inline]
+fn default_pivot() -> Vec2 {
+	DEFAULT_PIVOT
+}
+
 #[cfg(feature = "variants")]
 fn iter_variant_handles<'a>(
 	variants: impl Iterator<Item = &'a VariantTileHandle>,
@@ -228,10 +723,26 @@ fn iter_variant_handles<'a>(
 
 #[cfg(test)]
 mod tests {
-	use bevy_asset::Handle;
+	use bevy_asset::{Handle, HandleId, LoadState};
+	use bevy_render::texture::Image;
 
 	use crate::prelude::*;
 
+	fn new_handle() -> Handle<Image> {
+		Handle::weak(HandleId::random::<Image>())
+	}
+
+	/// Mocks the load state such that only the given handle is still loading
+	fn mock_loading(loading: &Handle<Image>) -> impl Fn(&Handle<Image>) -> LoadState + '_ {
+		move |handle| {
+			if handle == loading {
+				LoadState::Loading
+			} else {
+				LoadState::Loaded
+			}
+		}
+	}
+
 	#[test]
 	fn should_iter_standard() {
 		let standard = TileHandle::new_standard("Standard", Handle::default());
@@ -249,6 +760,8 @@ mod tests {
 			AnimatedTileHandle {
 				speed: 1.0,
 				frames: vec![Handle::default(); 3],
+				regions: Vec::new(),
+				..Default::default()
 			},
 		);
 		let mut anim_iter = anim.iter_handles();
@@ -260,6 +773,22 @@ mod tests {
 		assert!(anim_iter.next().is_none());
 	}
 
+	#[test]
+	fn should_iter_multi_animated() {
+		let track = |count: usize| AnimatedTileHandle {
+			speed: 1.0,
+			frames: vec![Handle::default(); count],
+			regions: Vec::new(),
+			..Default::default()
+		};
+		let anim = TileHandle::new_multi_animated(
+			"MultiAnimated",
+			[(String::from("idle"), track(2)), (String::from("walk"), track(3))].into(),
+		);
+		// Idle (2) + Walk (3)
+		assert_eq!(5, anim.iter_handles().count());
+	}
+
 	#[cfg(feature = "variants")]
 	#[test]
 	fn should_iter_variant() {
@@ -268,13 +797,17 @@ mod tests {
 			vec![
 				VariantTileHandle {
 					weight: 1.0,
+					transform: None,
 					tile: SimpleTileHandle::Standard(Handle::default()),
 				},
 				VariantTileHandle {
 					weight: 1.0,
+					transform: None,
 					tile: SimpleTileHandle::Animated(AnimatedTileHandle {
 						speed: 1.0,
 						frames: vec![Handle::default(); 3],
+						regions: Vec::new(),
+						..Default::default()
 					}),
 				},
 			],
@@ -301,13 +834,17 @@ mod tests {
 					variants: vec![
 						VariantTileHandle {
 							weight: 1.0,
+							transform: None,
 							tile: SimpleTileHandle::Standard(Handle::default()),
 						},
 						VariantTileHandle {
 							weight: 1.0,
+							transform: None,
 							tile: SimpleTileHandle::Animated(AnimatedTileHandle {
 								speed: 1.0,
 								frames: vec![Handle::default(); 3],
+								regions: Vec::new(),
+								..Default::default()
 							}),
 						},
 					],
@@ -317,13 +854,17 @@ mod tests {
 					variants: vec![
 						VariantTileHandle {
 							weight: 1.0,
+							transform: None,
 							tile: SimpleTileHandle::Standard(Handle::default()),
 						},
 						VariantTileHandle {
 							weight: 1.0,
+							transform: None,
 							tile: SimpleTileHandle::Animated(AnimatedTileHandle {
 								speed: 1.0,
 								frames: vec![Handle::default(); 3],
+								regions: Vec::new(),
+								..Default::default()
 							}),
 						},
 					],
@@ -349,4 +890,121 @@ mod tests {
 		// End
 		assert!(auto_iter.next().is_none());
 	}
+
+	#[test]
+	fn should_check_standard_load_state() {
+		let handle = new_handle();
+		let standard = TileHandle::new_standard("Standard", handle.clone());
+		assert_eq!(
+			LoadState::Loading,
+			standard.get_load_state_by(mock_loading(&handle))
+		);
+		assert_eq!(
+			LoadState::Loaded,
+			standard.get_load_state_by(mock_loading(&new_handle()))
+		);
+	}
+
+	#[test]
+	fn should_check_animated_load_state() {
+		let frames = vec![new_handle(), new_handle(), new_handle()];
+		let anim = TileHandle::new_animated(
+			"Animated",
+			AnimatedTileHandle {
+				speed: 1.0,
+				frames: frames.clone(),
+				regions: Vec::new(),
+				..Default::default()
+			},
+		);
+		// Only the last frame is still loading
+		assert_eq!(
+			LoadState::Loading,
+			anim.get_load_state_by(mock_loading(&frames[2]))
+		);
+		assert_eq!(
+			LoadState::Loaded,
+			anim.get_load_state_by(mock_loading(&new_handle()))
+		);
+	}
+
+	#[cfg(feature = "variants")]
+	#[test]
+	fn should_check_variant_load_state() {
+		let frame = new_handle();
+		let variant = TileHandle::new_variant(
+			"Variant",
+			vec![
+				VariantTileHandle {
+					weight: 1.0,
+					transform: None,
+					tile: SimpleTileHandle::Standard(new_handle()),
+				},
+				VariantTileHandle {
+					weight: 1.0,
+					transform: None,
+					tile: SimpleTileHandle::Animated(AnimatedTileHandle {
+						speed: 1.0,
+						frames: vec![new_handle(), frame.clone()],
+						regions: Vec::new(),
+						..Default::default()
+					}),
+				},
+			],
+		);
+		assert_eq!(
+			LoadState::Loading,
+			variant.get_load_state_by(mock_loading(&frame))
+		);
+		assert_eq!(
+			LoadState::Loaded,
+			variant.get_load_state_by(mock_loading(&new_handle()))
+		);
+	}
+
+	#[cfg(feature = "auto-tile")]
+	#[test]
+	fn should_check_auto_load_state() {
+		let frame = new_handle();
+		let auto = TileHandle::new_auto(
+			"Auto",
+			vec![
+				AutoTileHandle {
+					rule: AutoTileRule::default(),
+					variants: vec![VariantTileHandle {
+						weight: 1.0,
+						transform: None,
+						tile: SimpleTileHandle::Standard(new_handle()),
+					}],
+				},
+				AutoTileHandle {
+					rule: AutoTileRule::default(),
+					variants: vec![VariantTileHandle {
+						weight: 1.0,
+						transform: None,
+						tile: SimpleTileHandle::Animated(AnimatedTileHandle {
+							speed: 1.0,
+							frames: vec![new_handle(), frame.clone()],
+							regions: Vec::new(),
+							..Default::default()
+						}),
+					}],
+				},
+			],
+		);
+		assert_eq!(
+			LoadState::Loading,
+			auto.get_load_state_by(mock_loading(&frame))
+		);
+		assert_eq!(
+			LoadState::Failed,
+			auto.get_load_state_by(|handle| {
+				if handle == &frame {
+					LoadState::Failed
+				} else {
+					LoadState::Loaded
+				}
+			})
+		);
+	}
 }
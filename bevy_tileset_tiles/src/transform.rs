@@ -0,0 +1,46 @@
+use serde::{Deserialize, Serialize};
+
+/// A transformation applied to a tile's texture before it's added to the atlas
+///
+/// This allows the same art to be reused for rotated or flipped tiles without
+/// needing to author a separate image for each.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Deserialize, Serialize)]
+pub enum TileTransform {
+	/// Rotate the texture 90° clockwise
+	Rotate90,
+	/// Rotate the texture 180°
+	Rotate180,
+	/// Rotate the texture 270° clockwise (90° counter-clockwise)
+	Rotate270,
+	/// Flip the texture horizontally
+	FlipX,
+	/// Flip the texture vertically
+	FlipY,
+}
+
+impl TileTransform {
+	/// Gets the source pixel coordinates that map to the given destination pixel
+	///
+	/// # Arguments
+	///
+	/// * `x`: The destination x coordinate
+	/// * `y`: The destination y coordinate
+	/// * `width`: The width of the _source_ texture
+	/// * `height`: The height of the _source_ texture
+	///
+	/// returns: (u32, u32)
+	pub fn source_coords(&self, x: u32, y: u32, width: u32, height: u32) -> (u32, u32) {
+		match self {
+			Self::Rotate90 => (y, height - 1 - x),
+			Self::Rotate180 => (width - 1 - x, height - 1 - y),
+			Self::Rotate270 => (width - 1 - y, x),
+			Self::FlipX => (width - 1 - x, y),
+			Self::FlipY => (x, height - 1 - y),
+		}
+	}
+
+	/// Returns true if this transform swaps the width and height of the texture
+	pub fn swaps_dimensions(&self) -> bool {
+		matches!(self, Self::Rotate90 | Self::Rotate270)
+	}
+}
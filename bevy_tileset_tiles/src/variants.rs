@@ -1,4 +1,4 @@
-use crate::prelude::{AnimatedTileData, AnimatedTileDef, AnimatedTileHandle};
+use crate::prelude::{AnimatedTileData, AnimatedTileDef, AnimatedTileHandle, TileTransform};
 use bevy_asset::Handle;
 use bevy_render::texture::Image;
 use serde::{Deserialize, Serialize};
@@ -7,7 +7,7 @@ use serde::{Deserialize, Serialize};
 ///
 /// A _variant_ essentially wraps a [simple](SimpleTileType) tile and gives it
 /// a weight. This weight is used to define how likely it should be picked at random
-#[derive(Debug, Copy, Clone, Serialize)]
+#[derive(Debug, Copy, Clone, PartialEq, Deserialize, Serialize)]
 pub struct VariantTileData {
 	/// The weight of this variant (used for random sampling)
 	weight: f32,
@@ -19,7 +19,7 @@ pub struct VariantTileData {
 ///
 /// These are "simple" types in that their inner types are not _too_ complex
 /// or heavily nested
-#[derive(Debug, Copy, Clone, Serialize)]
+#[derive(Debug, Copy, Clone, PartialEq, Deserialize, Serialize)]
 pub enum SimpleTileType {
 	Standard(usize),
 	Animated(AnimatedTileData),
@@ -32,6 +32,8 @@ pub struct VariantTileHandle {
 	pub weight: f32,
 	/// The underlying tile handle
 	pub tile: SimpleTileHandle,
+	/// The transform to apply to every texture of this variant
+	pub transform: Option<TileTransform>,
 }
 
 /// An enum defining "simple" tile types
@@ -54,6 +56,13 @@ pub struct VariantTileDef {
 	pub weight: f32,
 	/// The underlying tile
 	pub tile: SimpleTileDefType,
+	/// The transform to apply to every texture of this variant
+	///
+	/// This is applied _after_ the transform of the containing tile (if any).
+	///
+	/// Default: `None`
+	#[serde(default)]
+	pub transform: Option<TileTransform>,
 }
 
 /// An enum defining "simple" tile types
@@ -66,6 +75,88 @@ pub enum SimpleTileDefType {
 	Animated(AnimatedTileDef),
 }
 
+impl VariantTileDef {
+	/// Create a new [`VariantTileDef`] with the default weight
+	///
+	/// # Arguments
+	///
+	/// * `tile`: The underlying tile (either a texture path or an [`AnimatedTileDef`])
+	///
+	/// returns: VariantTileDef
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use bevy_tileset_tiles::prelude::*;
+	/// let variant = VariantTileDef::new("grass-flowers.png").with_weight(0.25);
+	/// ```
+	pub fn new<TTile: Into<SimpleTileDefType>>(tile: TTile) -> Self {
+		Self {
+			weight: default_weight(),
+			tile: tile.into(),
+			transform: None,
+		}
+	}
+
+	/// Sets the weight of this variant
+	///
+	/// # Arguments
+	///
+	/// * `weight`: The weight (used for random sampling)
+	///
+	/// returns: VariantTileDef
+	///
+	pub fn with_weight(mut self, weight: f32) -> Self {
+		self.weight = weight;
+		self
+	}
+
+	/// Sets the transform to apply to every texture of this variant
+	///
+	/// # Arguments
+	///
+	/// * `transform`: The transform
+	///
+	/// returns: VariantTileDef
+	///
+	pub fn with_transform(mut self, transform: TileTransform) -> Self {
+		self.transform = Some(transform);
+		self
+	}
+}
+
+impl From<String> for SimpleTileDefType {
+	fn from(path: String) -> Self {
+		Self::Standard(path)
+	}
+}
+
+impl From<&str> for SimpleTileDefType {
+	fn from(path: &str) -> Self {
+		Self::Standard(path.to_string())
+	}
+}
+
+impl From<AnimatedTileDef> for SimpleTileDefType {
+	fn from(anim: AnimatedTileDef) -> Self {
+		Self::Animated(anim)
+	}
+}
+
+impl VariantTileHandle {
+	/// Clones this handle, making every image handle weak
+	pub fn clone_weak(&self) -> Self {
+		Self {
+			weight: self.weight,
+			tile: match &self.tile {
+				SimpleTileHandle::Standard(handle) => SimpleTileHandle::Standard(handle.clone_weak()),
+				SimpleTileHandle::Animated(anim) => SimpleTileHandle::Animated(anim.clone_weak()),
+			},
+			transform: self.transform,
+		}
+	}
+}
+
 impl VariantTileData {
 	pub fn new(weight: f32, tile: SimpleTileType) -> Self {
 		Self { weight, tile }
@@ -80,6 +171,14 @@ impl VariantTileData {
 	pub fn tile(&self) -> &SimpleTileType {
 		&self.tile
 	}
+
+	/// Replaces every atlas index used by this variant with the result of the given function
+	pub(crate) fn map_indices(&mut self, map: &dyn Fn(usize) -> usize) {
+		match &mut self.tile {
+			SimpleTileType::Standard(index) => *index = map(*index),
+			SimpleTileType::Animated(anim) => anim.map_indices(map),
+		}
+	}
 }
 
 impl SimpleTileType {
@@ -97,6 +196,14 @@ impl SimpleTileType {
 			Self::Animated(anim) => anim.start() <= *index && *index <= anim.end(),
 		}
 	}
+
+	/// Gets the animation of this tile (if it's animated)
+	pub fn animation(&self) -> Option<&AnimatedTileData> {
+		match self {
+			Self::Standard(..) => None,
+			Self::Animated(anim) => Some(anim),
+		}
+	}
 }
 
 /// Gets the default variant weight
@@ -105,3 +212,19 @@ impl SimpleTileType {
 fn default_weight() -> f32 {
 	1.0
 }
+
+#[cfg(test)]
+mod tests {
+	use crate::prelude::{AnimatedTileDef, SimpleTileDefType, VariantTileDef};
+
+	#[test]
+	fn should_build_variant_def() {
+		let variant = VariantTileDef::new("grass.png");
+		assert_eq!(1.0, variant.weight);
+		assert!(matches!(&variant.tile, SimpleTileDefType::Standard(path) if path == "grass.png"));
+
+		let variant = VariantTileDef::new(AnimatedTileDef::new(["a.png"])).with_weight(0.5);
+		assert_eq!(0.5, variant.weight);
+		assert!(matches!(variant.tile, SimpleTileDefType::Animated(..)));
+	}
+}
@@ -0,0 +1,107 @@
+//! This example showcases how to pack a dynamic tileset on a background task
+//!
+//! Packing a tileset at runtime happens on whichever thread calls `TilesetBuilder::build`.
+//! For large tilesets, doing this in a system can stall the app for several frames. Instead,
+//! `TilesetBuilder::build_task` packs it on the `AsyncComputeTaskPool`, giving back a `Task`
+//! that we can poll every frame until it's done.
+//!
+//! Since `Assets<Image>` can't be sent to another thread, the tile textures are copied into an
+//! `OwnedTextureStore` which is moved onto the task (and handed back once it finishes).
+
+use bevy::prelude::*;
+use bevy::tasks::{block_on, futures_lite::future, Task};
+use bevy_tileset::prelude::*;
+
+fn main() {
+	App::new()
+		// === Required === //
+		.add_plugins((DefaultPlugins, TilesetPlugin::default()))
+		// /== Required === //
+		.init_resource::<MyTileset>()
+		.add_systems(Startup, load_tiles)
+		.add_systems(Update, (start_packing, poll_packing))
+		.run();
+}
+
+#[derive(Resource, Default)]
+struct MyTileset {
+	/// This stores the handles to our tiles so they don't get unloaded
+	tiles: Vec<TileHandle>,
+	/// The task packing our tileset (while it's in progress)
+	task: Option<Task<Result<PackedTileset, TilesetError>>>,
+	/// Whether the tileset has started packing
+	is_packing: bool,
+}
+
+/// Starts loading the tile textures
+fn load_tiles(mut my_tileset: ResMut<MyTileset>, asset_server: Res<AssetServer>) {
+	for name in ["dirt", "grass", "empty"] {
+		let handle = asset_server.load(format!("tiles/{}.png", name));
+		my_tileset.tiles.push(TileHandle::new_standard(name, handle));
+	}
+}
+
+/// Spawns the packing task once every tile texture is loaded
+fn start_packing(
+	mut my_tileset: ResMut<MyTileset>,
+	asset_server: Res<AssetServer>,
+	textures: Res<Assets<Image>>,
+) {
+	if my_tileset.is_packing {
+		return;
+	}
+
+	// We MUST ensure that every handle is loaded
+	if !my_tileset
+		.tiles
+		.iter()
+		.all(|tile| tile.is_loaded(&asset_server))
+	{
+		return;
+	}
+
+	// Copy the textures so they can be moved onto the task
+	let store = OwnedTextureStore::copy_tiles_from(&textures, &my_tileset.tiles);
+
+	let mut builder = TilesetBuilder::default();
+	for (group_id, tile) in my_tileset.tiles.iter().enumerate() {
+		builder
+			.add_tile(tile.clone(), group_id as TileGroupId, &store)
+			.unwrap();
+	}
+
+	my_tileset.task = Some(builder.build_task("My Background Tileset", 123, store));
+	my_tileset.is_packing = true;
+}
+
+/// Checks on the packing task each frame, showing the tileset once it's done
+fn poll_packing(
+	mut commands: Commands,
+	mut my_tileset: ResMut<MyTileset>,
+	mut textures: ResMut<Assets<Image>>,
+	mut atlases: ResMut<Assets<TextureAtlas>>,
+	mut tilesets: ResMut<Assets<Tileset>>,
+) {
+	let Some(task) = my_tileset.task.as_mut() else {
+		return;
+	};
+
+	// This returns immediately if the task isn't done yet
+	let Some(result) = block_on(future::poll_once(task)) else {
+		return;
+	};
+	my_tileset.task = None;
+
+	// Move the textures (including the new atlas texture) back into the `Image` assets
+	let tileset = result.unwrap().into_asset(&mut textures, &mut atlases);
+	let texture = tileset.texture().clone();
+	println!("Packed {} tiles", tileset.tile_count());
+	tilesets.add(tileset);
+
+	// === Display Tileset === //
+	commands.spawn(Camera2dBundle::default());
+	commands.spawn(SpriteBundle {
+		texture,
+		..Default::default()
+	});
+}
@@ -0,0 +1,119 @@
+//! This example showcases how to load tile textures from a custom source
+//!
+//! By default, tile textures are loaded via the `AssetServer`. However, any type implementing
+//! `TextureLoader` can be used to generate the `TileHandle` collection. Here, we load textures
+//! from an in-memory map of images, without ever touching the `AssetServer`.
+//!
+//! Since the `TilesetBuilder` also needs somewhere to find (and store) its textures, the same type
+//! implements `TextureStore` as well.
+
+use std::collections::HashMap;
+
+use bevy::asset::{AssetPath, HandleId};
+use bevy::prelude::*;
+use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+use bevy_tileset::prelude::*;
+
+/// A texture loader backed by a simple in-memory map of images
+#[derive(Default)]
+struct MemoryTextures {
+	/// The images mapped by the ID of their handle
+	///
+	/// These are keyed by handle ID rather than by path since `TextureStore::get` is only given a
+	/// handle, and images added by the builder (such as packed atlases) don't have a path at all.
+	/// Loaded images still get a stable key, as their IDs are derived from their path.
+	images: HashMap<HandleId, Image>,
+}
+
+impl MemoryTextures {
+	/// Insert an image so that it can be loaded at the given path
+	fn insert(&mut self, path: &str, image: Image) {
+		self.images.insert(HandleId::from(AssetPath::from(path)), image);
+	}
+}
+
+impl TextureLoader for MemoryTextures {
+	fn load_texture<'a, P: Into<AssetPath<'a>>>(&self, path: P) -> Handle<Image> {
+		// The handle is derived from the path so that it matches the one given in `insert`
+		Handle::weak(HandleId::from(path.into()))
+	}
+}
+
+impl TextureStore for MemoryTextures {
+	fn add(&mut self, asset: Image) -> Handle<Image> {
+		let id = HandleId::random::<Image>();
+		self.images.insert(id, asset);
+		Handle::weak(id)
+	}
+
+	fn get<H: Into<HandleId>>(&self, handle: H) -> Option<&Image> {
+		self.images.get(&handle.into())
+	}
+}
+
+fn main() {
+	let mut textures = MemoryTextures::default();
+	textures.insert("tiles/red.png", solid_image([255, 0, 0, 255]));
+	textures.insert("tiles/blue.png", solid_image([0, 0, 255, 255]));
+
+	let tiles = vec![
+		TileDef {
+			name: String::from("Red"),
+			tile: TileDefType::Standard(String::from("tiles/red.png")),
+			transform: None,
+			color_key: None,
+			collision: TileCollision::None,
+			tags: Vec::new(),
+			z_offset: 0.0,
+			layer: 0,
+			pivot: DEFAULT_PIVOT,
+			aliases: Vec::new(),
+			sampler: None,
+		},
+		TileDef {
+			name: String::from("Blue"),
+			tile: TileDefType::Standard(String::from("tiles/blue.png")),
+			transform: None,
+			color_key: None,
+			collision: TileCollision::None,
+			tags: Vec::new(),
+			z_offset: 0.0,
+			layer: 0,
+			pivot: DEFAULT_PIVOT,
+			aliases: Vec::new(),
+			sampler: None,
+		},
+	];
+
+	// Generate the handles using our custom loader
+	let handles = load_tile_handles(tiles, &textures);
+
+	// Build the tileset using our custom store
+	let mut builder = TilesetBuilder::default();
+	for (group_id, tile) in handles.into_iter().enumerate() {
+		builder
+			.add_tile(tile, group_id as TileGroupId, &textures)
+			.unwrap();
+	}
+	let raw_tileset = builder
+		.build("My In-Memory Tileset", 0, &mut textures)
+		.unwrap();
+
+	println!("Red: {:?}", raw_tileset.get_tile_index("Red"));
+	println!("Blue: {:?}", raw_tileset.get_tile_index("Blue"));
+	println!("Atlas size: {:?}", raw_tileset.size());
+}
+
+/// Creates a 16x16 image filled with the given color
+fn solid_image(color: [u8; 4]) -> Image {
+	Image::new_fill(
+		Extent3d {
+			width: 16,
+			height: 16,
+			depth_or_array_layers: 1,
+		},
+		TextureDimension::D2,
+		&color,
+		TextureFormat::Rgba8UnormSrgb,
+	)
+}
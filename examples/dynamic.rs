@@ -159,6 +159,9 @@ fn show_tileset(
 			TileIndex::Animated(start, end, speed) => {
 				// Do something  ✨ animated ✨
 			},
+			TileIndex::Empty => {
+				// Nothing to display
+			},
 		}
 	}
 
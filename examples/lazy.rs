@@ -0,0 +1,84 @@
+//! This example showcases how to load a tileset lazily, only decoding the tiles that are used
+//!
+//! A tileset definition with `lazy: true` is loaded as a `LazyTilesetAsset`, which reads every
+//! tile definition up front, but only decodes a tile's textures (and packs them into a new atlas
+//! page) once that tile is requested. This keeps memory low for huge tilesets where only a few
+//! tiles are ever shown, at the cost of a hitch whenever new tiles are requested.
+//!
+//! Press `Space` to request the next tile.
+
+use bevy::prelude::*;
+use bevy_tileset::prelude::*;
+
+/// The tiles requested by this example (in order)
+const TILES: &[&str] = &["Dirt", "Grass", "Glass", "Wall"];
+
+fn main() {
+	App::new()
+		// === Required === //
+		.add_plugins((DefaultPlugins, TilesetPlugin::default()))
+		// /== Required === //
+		.add_systems(Startup, load_tileset)
+		.add_systems(Update, request_tiles)
+		.run();
+}
+
+#[derive(Resource)]
+struct MyTileset {
+	/// The handle to the lazily loaded tileset
+	handle: Handle<LazyTilesetAsset>,
+	/// The number of tiles requested so far
+	requested: usize,
+}
+
+/// Loads the tile definitions of our tileset (without decoding any of its textures)
+fn load_tileset(mut commands: Commands, asset_server: Res<AssetServer>) {
+	commands.insert_resource(MyTileset {
+		handle: asset_server.load("tilesets/my_lazy_tileset.ron"),
+		requested: 0,
+	});
+	commands.spawn(Camera2dBundle::default());
+}
+
+/// Requests the next tile when `Space` is pressed, showing the atlas page it was packed into
+fn request_tiles(
+	mut commands: Commands,
+	mut my_tileset: ResMut<MyTileset>,
+	keys: Res<Input<KeyCode>>,
+	mut lazy_tilesets: ResMut<Assets<LazyTilesetAsset>>,
+	mut textures: ResMut<Assets<Image>>,
+	sprites: Query<Entity, With<Sprite>>,
+) {
+	let is_first = my_tileset.requested == 0;
+	if !is_first && !keys.just_pressed(KeyCode::Space) {
+		return;
+	}
+	let Some(lazy) = lazy_tilesets.get_mut(&my_tileset.handle) else {
+		// The tile definitions are still loading
+		return;
+	};
+	let Some(name) = TILES.get(my_tileset.requested) else {
+		return;
+	};
+	my_tileset.requested += 1;
+
+	// Only the new tile's textures are decoded here
+	let raw = lazy.request(&[*name], &mut *textures).unwrap();
+	let page = raw.tile_page(name).unwrap();
+	let texture = raw.page(page).unwrap().atlas().texture.clone();
+	println!(
+		"Requested {:?} into page {} ({} textures decoded)",
+		name,
+		page,
+		lazy.decoded_count()
+	);
+
+	// === Display Page === //
+	for entity in &sprites {
+		commands.entity(entity).despawn();
+	}
+	commands.spawn(SpriteBundle {
+		texture,
+		..Default::default()
+	});
+}
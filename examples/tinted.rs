@@ -0,0 +1,72 @@
+//! This example showcases how to post-process tile textures before they're packed
+//!
+//! The `TilesetBuilder` can be given a function that's applied to every tile's texture before
+//! it's added to the atlas. This makes it easy to apply custom effects (tints, outlines, etc.)
+//! at build time. Here, we tint every tile red.
+
+use bevy::prelude::*;
+use bevy_tileset::prelude::*;
+
+fn main() {
+	App::new()
+		// === Required === //
+		.add_plugins((DefaultPlugins, TilesetPlugin::default()))
+		// /== Required === //
+		.init_resource::<MyTiles>()
+		.add_systems(Startup, load_tiles)
+		.add_systems(Update, build_tileset)
+		.run();
+}
+
+#[derive(Resource, Default)]
+struct MyTiles {
+	/// This stores the handles to our tiles so they don't get unloaded
+	tiles: Vec<TileHandle>,
+	is_built: bool,
+}
+
+/// Starts loading the tile textures
+fn load_tiles(mut my_tiles: ResMut<MyTiles>, asset_server: Res<AssetServer>) {
+	my_tiles.tiles = vec![
+		TileHandle::new_standard("Grass", asset_server.load("tiles/grass.png")),
+		TileHandle::new_standard("Dirt", asset_server.load("tiles/dirt.png")),
+	];
+}
+
+/// Builds (and displays) the tinted tileset once every texture is loaded
+fn build_tileset(
+	mut commands: Commands,
+	mut my_tiles: ResMut<MyTiles>,
+	asset_server: Res<AssetServer>,
+	mut textures: ResMut<Assets<Image>>,
+) {
+	if my_tiles.is_built || !my_tiles.tiles.iter().all(|tile| tile.is_loaded(&asset_server)) {
+		return;
+	}
+
+	let mut builder = TilesetBuilder::default();
+	builder.tile_processor(|_name, mut image| {
+		// Halve the green and blue channels of every pixel to tint it red
+		// (this assumes the textures use an 8-bit RGBA format)
+		for pixel in image.data.chunks_exact_mut(4) {
+			pixel[1] /= 2;
+			pixel[2] /= 2;
+		}
+		image
+	});
+	for (group_id, tile) in my_tiles.tiles.iter().enumerate() {
+		builder
+			.add_tile(tile.clone(), group_id as TileGroupId, &textures)
+			.unwrap();
+	}
+	let raw_tileset = builder.build("My Tinted Tileset", 0, &mut textures).unwrap();
+
+	// === Display Tileset === //
+	commands.spawn(Camera2dBundle::default());
+	commands.spawn(SpriteBundle {
+		texture: raw_tileset.texture().clone(),
+		..Default::default()
+	});
+
+	my_tiles.is_built = true;
+}